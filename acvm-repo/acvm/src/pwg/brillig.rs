@@ -34,6 +34,16 @@ pub struct BrilligSolver<'b, F, B: BlackBoxFunctionSolver<F>> {
     pub function_id: BrilligFunctionId,
 }
 
+impl<'b, F: Clone, B: BlackBoxFunctionSolver<F>> Clone for BrilligSolver<'b, F, B> {
+    fn clone(&self) -> Self {
+        BrilligSolver {
+            vm: self.vm.clone(),
+            acir_index: self.acir_index,
+            function_id: self.function_id,
+        }
+    }
+}
+
 impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
     /// Assigns the zero value to all outputs of the given [`Brillig`] bytecode.
     pub(super) fn zero_out_brillig_outputs(
@@ -167,7 +167,7 @@ impl<F> From<InvalidInputBitSize> for OpcodeResolutionError<F> {
 
 pub type ProfilingSamples = Vec<ProfilingSample>;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct ProfilingSample {
     pub call_stack: Vec<OpcodeLocation>,
     pub brillig_function_id: Option<BrilligFunctionId>,
@@ -209,6 +209,27 @@ pub struct ACVM<'a, F, B: BlackBoxFunctionSolver<F>> {
     profiling_samples: ProfilingSamples,
 }
 
+impl<'a, F: Clone, B: BlackBoxFunctionSolver<F>> Clone for ACVM<'a, F, B> {
+    fn clone(&self) -> Self {
+        ACVM {
+            status: self.status.clone(),
+            backend: self.backend,
+            block_solvers: self.block_solvers.clone(),
+            bigint_solver: self.bigint_solver.clone(),
+            opcodes: self.opcodes,
+            instruction_pointer: self.instruction_pointer,
+            witness_map: self.witness_map.clone(),
+            brillig_solver: self.brillig_solver.clone(),
+            acir_call_counter: self.acir_call_counter,
+            acir_call_results: self.acir_call_results.clone(),
+            unconstrained_functions: self.unconstrained_functions,
+            assertion_payloads: self.assertion_payloads,
+            profiling_active: self.profiling_active,
+            profiling_samples: self.profiling_samples.clone(),
+        }
+    }
+}
+
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
     pub fn new(
         backend: &'a B,
@@ -161,12 +161,13 @@ pub(super) fn compute_function_abi(
 }
 
 /// Attempts to retrieve the name of this parameter. Returns None
-/// if this parameter is a tuple or struct pattern.
+/// if this parameter is a tuple, array or struct pattern.
 fn get_param_name<'a>(pattern: &HirPattern, interner: &'a NodeInterner) -> Option<&'a str> {
     match pattern {
         HirPattern::Identifier(ident) => Some(interner.definition_name(ident.id)),
         HirPattern::Mutable(pattern, _) => get_param_name(pattern, interner),
         HirPattern::Tuple(_, _) => None,
+        HirPattern::Array(_, _, _) => None,
         HirPattern::Struct(_, _, _) => None,
     }
 }
@@ -174,7 +175,7 @@ fn get_param_name<'a>(pattern: &HirPattern, interner: &'a NodeInterner) -> Optio
 fn into_abi_params(context: &Context, params: Vec<Param>) -> Vec<AbiParameter> {
     vecmap(params, |(pattern, typ, vis)| {
         let param_name = get_param_name(&pattern, &context.def_interner)
-            .expect("Abi for tuple and struct parameters is unimplemented")
+            .expect("Abi for tuple, array and struct parameters is unimplemented")
             .to_owned();
         let as_abi = abi_type_from_hir_type(context, &typ);
         AbiParameter { name: param_name, typ: as_abi, visibility: to_abi_visibility(vis) }
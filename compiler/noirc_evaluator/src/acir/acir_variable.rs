@@ -593,7 +593,7 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
             }
             NumericType::Signed { bit_size } => {
                 let (quotient_var, _remainder_var) =
-                    self.signed_division_var(lhs, rhs, bit_size)?;
+                    self.signed_division_var(lhs, rhs, bit_size, predicate)?;
                 Ok(quotient_var)
             }
         }
@@ -763,7 +763,7 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
     pub(crate) fn not_var(&mut self, x: AcirVar, typ: AcirType) -> Result<AcirVar, RuntimeError> {
         let bit_size = typ.bit_size::<F>();
         // Subtracting from max flips the bits
-        let max = self.add_constant((1_u128 << bit_size) - 1);
+        let max = self.add_constant(F::from(2_u128).pow(&F::from(bit_size as u128)) - F::one());
         self.sub_var(max, x)
     }
 
@@ -790,7 +790,11 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
 
             // If `lhs` and `rhs` are known constants then we can calculate the result at compile time.
             // `rhs` must be non-zero.
-            (Some(lhs_const), Some(rhs_const), _) if !rhs_const.is_zero() => {
+            (Some(lhs_const), Some(rhs_const), _)
+                if !rhs_const.is_zero()
+                    && lhs_const.num_bits() <= 128
+                    && rhs_const.num_bits() <= 128 =>
+            {
                 let quotient = lhs_const.to_u128() / rhs_const.to_u128();
                 let remainder = lhs_const.to_u128() - quotient * rhs_const.to_u128();
 
@@ -799,6 +803,14 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
                 return Ok((quotient_var, remainder_var));
             }
 
+            // A `rhs` which doesn't fit in a u128 (e.g. the 2^128 used when truncating a u128)
+            // is larger than any `lhs` which does, so the division is trivial.
+            (Some(lhs_const), Some(rhs_const), _)
+                if lhs_const.num_bits() <= 128 && rhs_const.num_bits() > 128 =>
+            {
+                return Ok((zero, lhs));
+            }
+
             // If `rhs` is one then the division is a noop.
             (_, Some(rhs_const), _) if rhs_const.is_one() => {
                 return Ok((lhs, zero));
@@ -900,8 +912,10 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
             if rhs_expr.is_const() {
                 avoid_overflow = true;
             } else {
-                // we do not support unbounded division
-                unreachable!("overflow in unbounded division");
+                // q*b cannot be bounded by the bit sizes alone, so we bound it by splitting both
+                // operands into halves.
+                let bit_size = std::cmp::max(max_q_bits, max_rhs_bits);
+                self.bound_wide_product(quotient_var, rhs, bit_size, predicate, None)?;
             }
         }
 
@@ -1002,6 +1016,35 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
         Ok(())
     }
 
+    /// Generate constraints ensuring that `lhs * rhs` does not overflow the field,
+    /// where `lhs` and `rhs` are integers of at most `bit_size` bits.
+    ///
+    /// When `2 * bit_size` exceeds the field size, the product of the operands can wrap around
+    /// the field modulus. We split both operands into an upper and a lower half:
+    /// `lhs = lhs_hi * 2^{half} + lhs_lo` and `rhs = rhs_hi * 2^{half} + rhs_lo`
+    /// and constrain `lhs_hi * rhs_hi == 0`, which bounds the product to `3 * half + 1` bits.
+    /// Note that this holds for any product which fits in `bit_size` bits.
+    pub(super) fn bound_wide_product(
+        &mut self,
+        lhs: AcirVar,
+        rhs: AcirVar,
+        bit_size: u32,
+        predicate: AcirVar,
+        message: Option<String>,
+    ) -> Result<(), RuntimeError> {
+        let half_bit_size = (bit_size + 1) / 2;
+        let half_width = self.add_constant(F::from(2_u128).pow(&F::from(half_bit_size as u128)));
+
+        let (lhs_hi, _) = self.euclidean_division_var(lhs, half_width, bit_size, predicate)?;
+        let (rhs_hi, _) = self.euclidean_division_var(rhs, half_width, bit_size, predicate)?;
+
+        let hi_product = self.mul_var(lhs_hi, rhs_hi)?;
+        let hi_product = self.mul_var(hi_product, predicate)?;
+        let zero = self.add_constant(F::zero());
+        let payload = message.map(|message| self.generate_assertion_message_payload(message));
+        self.assert_eq_var(hi_product, zero, payload)
+    }
+
     // Returns the 2-complement of lhs, using the provided sign bit in 'leading'
     // if leading is zero, it returns lhs
     // if leading is one, it returns 2^bit_size-lhs
@@ -1029,6 +1072,7 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
         lhs: AcirVar,
         rhs: AcirVar,
         bit_size: u32,
+        predicate: AcirVar,
     ) -> Result<(AcirVar, AcirVar), RuntimeError> {
         // We derive the signed division from the unsigned euclidean division.
         // note that this is not euclidean division!
@@ -1044,13 +1088,14 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
         let max_power_of_two =
             self.add_constant(F::from(2_u128).pow(&F::from(bit_size as u128 - 1)));
         let zero = self.add_constant(F::zero());
-        let one = self.add_constant(F::one());
 
         // Get the sign bit of rhs by computing rhs / max_power_of_two
-        let (rhs_leading, _) = self.euclidean_division_var(rhs, max_power_of_two, bit_size, one)?;
+        let (rhs_leading, _) =
+            self.euclidean_division_var(rhs, max_power_of_two, bit_size, predicate)?;
 
         // Get the sign bit of lhs by computing lhs / max_power_of_two
-        let (lhs_leading, _) = self.euclidean_division_var(lhs, max_power_of_two, bit_size, one)?;
+        let (lhs_leading, _) =
+            self.euclidean_division_var(lhs, max_power_of_two, bit_size, predicate)?;
 
         // Signed to unsigned:
         let unsigned_lhs = self.two_complement(lhs, lhs_leading, bit_size)?;
@@ -1058,7 +1103,7 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
 
         // Performs the division using the unsigned values of lhs and rhs
         let (q1, r1) =
-            self.euclidean_division_var(unsigned_lhs, unsigned_rhs, bit_size - 1, one)?;
+            self.euclidean_division_var(unsigned_lhs, unsigned_rhs, bit_size - 1, predicate)?;
 
         // Unsigned to signed: derive q and r from q1,r1 and the signs of lhs and rhs
         // Quotient sign is lhs sign * rhs sign, whose resulting sign bit is the XOR of the sign bits
@@ -1091,6 +1136,19 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
         Ok(remainder)
     }
 
+    /// Returns a variable which is constrained to be `lhs mod rhs` for signed integers,
+    /// where the remainder has the same sign as `lhs`
+    pub(crate) fn signed_modulo_var(
+        &mut self,
+        lhs: AcirVar,
+        rhs: AcirVar,
+        bit_size: u32,
+        predicate: AcirVar,
+    ) -> Result<AcirVar, RuntimeError> {
+        let (_, remainder) = self.signed_division_var(lhs, rhs, bit_size, predicate)?;
+        Ok(remainder)
+    }
+
     /// Constrains the `AcirVar` variable to be of type `NumericType`.
     pub(crate) fn range_constrain_var(
         &mut self,
@@ -1157,7 +1215,7 @@ impl<F: AcirField, B: BlackBoxFunctionSolver<F>> AcirContext<F, B> {
         bit_count: u32,
     ) -> Result<AcirVar, RuntimeError> {
         let pow_last = self.add_constant(F::from(1_u128 << (bit_count - 1)));
-        let pow = self.add_constant(F::from(1_u128 << (bit_count)));
+        let pow = self.add_constant(F::from(2_u128).pow(&F::from(bit_count as u128)));
 
         // We check whether the inputs have same sign or not by computing the XOR of their bit sign
 
@@ -1925,10 +1925,11 @@ impl<'a> Context<'a> {
         match &binary_type {
             Type::Numeric(NumericType::Unsigned { bit_size })
             | Type::Numeric(NumericType::Signed { bit_size }) => {
-                // Conservative max bit size that is small enough such that two operands can be
-                // multiplied and still fit within the field modulus. This is necessary for the
-                // truncation technique: result % 2^bit_size to be valid.
-                let max_integer_bit_size = FieldElement::max_num_bits() / 2;
+                // Conservative max bit size that is small enough such that the sum of two operands
+                // still fits within the field modulus. This is necessary for the truncation
+                // technique: result % 2^bit_size to be valid.
+                // Products of operands wider than half the field are checked using `bound_wide_product`.
+                let max_integer_bit_size = 128;
                 if *bit_size > max_integer_bit_size {
                     return Err(RuntimeError::UnsupportedIntegerSize {
                         num_bits: *bit_size,
@@ -1965,12 +1966,17 @@ impl<'a> Context<'a> {
             BinaryOp::Xor => self.acir_context.xor_var(lhs, rhs, binary_type),
             BinaryOp::And => self.acir_context.and_var(lhs, rhs, binary_type),
             BinaryOp::Or => self.acir_context.or_var(lhs, rhs, binary_type),
-            BinaryOp::Mod => self.acir_context.modulo_var(
-                lhs,
-                rhs,
-                bit_count,
-                self.current_side_effects_enabled_var,
-            ),
+            BinaryOp::Mod => match binary_type {
+                AcirType::NumericType(NumericType::Signed { .. }) => self
+                    .acir_context
+                    .signed_modulo_var(lhs, rhs, bit_count, self.current_side_effects_enabled_var),
+                _ => self.acir_context.modulo_var(
+                    lhs,
+                    rhs,
+                    bit_count,
+                    self.current_side_effects_enabled_var,
+                ),
+            },
             BinaryOp::Shl | BinaryOp::Shr => unreachable!(
                 "ICE - bit shift operators do not exist in ACIR and should have been replaced"
             ),
@@ -2027,7 +2033,21 @@ impl<'a> Context<'a> {
                     // or `lhs` and `rhs` have both been casted up from smaller types and so cannot overflow.
                     return Ok(());
                 }
-                "attempt to multiply with overflow".to_string()
+                let msg = "attempt to multiply with overflow".to_string();
+                if max_lhs_bits + max_rhs_bits >= FieldElement::max_num_bits() {
+                    // The product may have wrapped around the field, in which case the range check
+                    // on the result would not detect the overflow.
+                    let lhs = self.convert_numeric_value(lhs, dfg)?;
+                    let rhs = self.convert_numeric_value(rhs, dfg)?;
+                    self.acir_context.bound_wide_product(
+                        lhs,
+                        rhs,
+                        bit_size,
+                        self.current_side_effects_enabled_var,
+                        Some(msg.clone()),
+                    )?;
+                }
+                msg
             }
             _ => return Ok(()),
         };
@@ -2103,7 +2123,9 @@ impl<'a> Context<'a> {
                 ) {
                     // Subtractions must first have the integer modulus added before truncation can be
                     // applied. This is done in order to prevent underflow.
-                    let integer_modulus = self.acir_context.add_constant(2_u128.pow(bit_size));
+                    let integer_modulus = self.acir_context.add_constant(
+                        FieldElement::from(2_u128).pow(&FieldElement::from(bit_size)),
+                    );
                     var = self.acir_context.add_var(var, integer_modulus)?;
                 }
             }
@@ -231,7 +231,7 @@ impl Binary {
                         (Some(bitmask), None) | (None, Some(bitmask)) => {
                             // This substitution requires the bitmask to retain all of the lower bits.
                             // The bitmask must then be one less than a power of 2.
-                            let bitmask_plus_one = bitmask.to_u128() + 1;
+                            let bitmask_plus_one = bitmask.to_u128().wrapping_add(1);
                             if bitmask_plus_one.is_power_of_two() {
                                 let value = if lhs.is_some() { self.rhs } else { self.lhs };
                                 let num_bits = bitmask_plus_one.ilog2();
@@ -322,7 +322,7 @@ fn eval_constant_binary_op(
             }
             let result = function(lhs, rhs)?;
             // Check for overflow
-            if result >= 1 << *bit_size {
+            if *bit_size < 128 && result >= 1 << *bit_size {
                 return None;
             }
             result.into()
@@ -342,10 +342,12 @@ fn eval_constant_binary_op(
             }
 
             let result = function(lhs, rhs)?;
-            // Check for overflow
-            let two_pow_bit_size_minus_one = 1i128 << (*bit_size - 1);
-            if result >= two_pow_bit_size_minus_one || result < -two_pow_bit_size_minus_one {
-                return None;
+            // Check for overflow. 128-bit overflow is already caught by the checked i128 functions.
+            if *bit_size < 128 {
+                let two_pow_bit_size_minus_one = 1i128 << (*bit_size - 1);
+                if result >= two_pow_bit_size_minus_one || result < -two_pow_bit_size_minus_one {
+                    return None;
+                }
             }
             convert_signed_integer_to_field_element(result, *bit_size)
         }
@@ -365,15 +367,9 @@ fn eval_constant_binary_op(
 fn try_convert_field_element_to_signed_integer(field: FieldElement, bit_size: u32) -> Option<i128> {
     let unsigned_int = truncate(field.try_into_u128()?, bit_size);
 
-    let max_positive_value = 1 << (bit_size - 1);
-    let is_positive = unsigned_int < max_positive_value;
-
-    let signed_int = if is_positive {
-        unsigned_int as i128
-    } else {
-        let x = (1u128 << bit_size) - unsigned_int;
-        -(x as i128)
-    };
+    // Move the sign bit into the top bit of the i128 and shift back down to sign-extend it.
+    let shift = 128 - bit_size;
+    let signed_int = ((unsigned_int << shift) as i128) >> shift;
 
     Some(signed_int)
 }
@@ -382,13 +378,17 @@ fn convert_signed_integer_to_field_element(int: i128, bit_size: u32) -> FieldEle
     if int >= 0 {
         FieldElement::from(int)
     } else {
-        // We add an offset of `bit_size` bits to shift the negative values into the range [2^(bitsize-1), 2^bitsize)
-        let offset_int = (1i128 << bit_size) + int;
+        // We take the two's complement representation of the integer in `bit_size` bits to
+        // shift the negative values into the range [2^(bitsize-1), 2^bitsize)
+        let offset_int = (int as u128) & (u128::MAX >> (128 - bit_size));
         FieldElement::from(offset_int)
     }
 }
 
 fn truncate(int: u128, bit_size: u32) -> u128 {
+    if bit_size >= 128 {
+        return int;
+    }
     let max = 1 << bit_size;
     int % max
 }
@@ -424,8 +424,12 @@ impl BinaryOp {
             BinaryOp::Xor => |x, y| Some(x ^ y),
             BinaryOp::Eq => |x, y| Some((x == y) as u128),
             BinaryOp::Lt => |x, y| Some((x < y) as u128),
-            BinaryOp::Shl => |x, y| Some(x << y),
-            BinaryOp::Shr => |x, y| Some(x >> y),
+            BinaryOp::Shl => |x, y| {
+                let result = x.checked_shl(y.try_into().ok()?)?;
+                // Don't evaluate shifts which lose bits off the top of a u128
+                (result >> y == x).then_some(result)
+            },
+            BinaryOp::Shr => |x, y| x.checked_shr(y.try_into().ok()?),
         }
     }
 
@@ -441,8 +445,12 @@ impl BinaryOp {
             BinaryOp::Xor => |x, y| Some(x ^ y),
             BinaryOp::Eq => |x, y| Some((x == y) as i128),
             BinaryOp::Lt => |x, y| Some((x < y) as i128),
-            BinaryOp::Shl => |x, y| Some(x << y),
-            BinaryOp::Shr => |x, y| Some(x >> y),
+            BinaryOp::Shl => |x, y| {
+                let result = x.checked_shl(y.try_into().ok()?)?;
+                // Don't evaluate shifts which lose bits off the top of an i128
+                (result >> y == x).then_some(result)
+            },
+            BinaryOp::Shr => |x, y| x.checked_shr(y.try_into().ok()?),
         }
     }
 }
@@ -457,8 +465,8 @@ mod test {
 
     proptest! {
         #[test]
-        fn signed_int_roundtrip(int: i128, bit_size in 1u32..=64) {
-            let int = int % (1i128 << (bit_size - 1));
+        fn signed_int_roundtrip(int: i128, bit_size in 1u32..=128) {
+            let int = if bit_size == 128 { int } else { int % (1i128 << (bit_size - 1)) };
 
             let int_as_field = convert_signed_integer_to_field_element(int, bit_size);
             let recovered_int = try_convert_field_element_to_signed_integer(int_as_field, bit_size).unwrap();
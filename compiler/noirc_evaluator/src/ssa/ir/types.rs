@@ -40,7 +40,7 @@ impl NumericType {
     ) -> Option<String> {
         match self {
             NumericType::Unsigned { bit_size } => {
-                let max = 2u128.pow(bit_size) - 1;
+                let max = u128::MAX >> (128 - bit_size);
                 if negative {
                     return Some(format!("0..={}", max));
                 }
@@ -51,8 +51,8 @@ impl NumericType {
                 }
             }
             NumericType::Signed { bit_size } => {
-                let min = 2u128.pow(bit_size - 1);
-                let max = 2u128.pow(bit_size - 1) - 1;
+                let min = 1u128 << (bit_size - 1);
+                let max = min - 1;
                 let target_max = if negative { min } else { max };
                 if field <= target_max.into() {
                     None
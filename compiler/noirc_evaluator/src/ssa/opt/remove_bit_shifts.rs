@@ -109,11 +109,9 @@ impl Context<'_> {
 
             let (rhs_bit_size_pow_2, overflows) = 2_u128.overflowing_pow(bit_shift_size);
             if overflows {
-                assert!(bit_size < 128, "ICE - shift left with big integers are not supported");
-                if bit_size < 128 {
-                    let zero = self.numeric_constant(FieldElement::zero(), typ);
-                    return InsertInstructionResult::SimplifiedTo(zero).first();
-                }
+                // Shifting by 128 bits or more shifts out every bit of any integer type
+                let zero = self.numeric_constant(FieldElement::zero(), typ);
+                return InsertInstructionResult::SimplifiedTo(zero).first();
             }
             let pow = self.numeric_constant(FieldElement::from(rhs_bit_size_pow_2), typ.clone());
 
@@ -135,6 +133,27 @@ impl Context<'_> {
 
         if max_bit <= bit_size {
             self.insert_binary(lhs, BinaryOp::Mul, pow)
+        } else if 2 * bit_size > FieldElement::max_num_bits() {
+            // `lhs * pow` could wrap around the field, so we shift both halves of `lhs` separately
+            // and only keep the bits of the high half which stay within the bit size.
+            let half_bit_size = bit_size / 2;
+            let max_bit = bit_size + half_bit_size;
+            let half_width = self.field_constant(FieldElement::from(1_u128 << half_bit_size));
+            let lhs_field = self.insert_cast(lhs, Type::field());
+            let pow_field = self.insert_cast(pow, Type::field());
+
+            let lo = self.insert_truncate(lhs_field, half_bit_size, bit_size);
+            let hi = self.insert_binary(lhs_field, BinaryOp::Sub, lo);
+            let hi = self.insert_binary(hi, BinaryOp::Div, half_width);
+
+            let hi_shifted = self.insert_binary(hi, BinaryOp::Mul, pow_field);
+            let hi_shifted = self.insert_truncate(hi_shifted, bit_size - half_bit_size, max_bit);
+            let hi_shifted = self.insert_binary(hi_shifted, BinaryOp::Mul, half_width);
+            let lo_shifted = self.insert_binary(lo, BinaryOp::Mul, pow_field);
+
+            let result = self.insert_binary(hi_shifted, BinaryOp::Add, lo_shifted);
+            let result = self.insert_truncate(result, bit_size, max_bit);
+            self.insert_cast(result, typ)
         } else {
             let lhs_field = self.insert_cast(lhs, Type::field());
             let pow_field = self.insert_cast(pow, Type::field());
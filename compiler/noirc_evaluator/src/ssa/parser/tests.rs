@@ -40,7 +40,9 @@ fn test_empty_brillig_function() {
 
 #[test]
 fn test_return_integer() {
-    for typ in ["u1", "u8", "u16", "u32", "u64", "i1", "i8", "i16", "i32", "i64", "Field"] {
+    for typ in
+        ["u1", "u8", "u16", "u32", "u64", "u128", "i1", "i8", "i16", "i32", "i64", "i128", "Field"]
+    {
         let src = format!(
             "
             acir(inline) fn main f0 {{
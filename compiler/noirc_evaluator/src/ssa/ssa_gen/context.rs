@@ -43,6 +43,10 @@ pub(super) struct FunctionContext<'a> {
     /// These are ordered such that an inner loop is at the end of the vector and
     /// outer loops are at the beginning. When a loop is finished, it is popped.
     loops: Vec<Loop>,
+
+    /// How `return` expressions are lowered in the current function.
+    /// This is only set if the function contains any.
+    pub(super) early_return: Option<EarlyReturn>,
 }
 
 /// Shared context for all functions during ssa codegen. This is the only
@@ -83,6 +87,19 @@ pub(super) struct Loop {
     pub(super) loop_end: BasicBlockId,
 }
 
+/// What's needed to lower `return` expressions in the function being compiled.
+#[derive(Clone)]
+pub(super) enum EarlyReturn {
+    /// Brillig functions jump to this exit block, passing it the returned values as arguments.
+    /// The exit block then returns its parameters. The end of the function body jumps there too.
+    Block(BasicBlockId),
+    /// ACIR functions can't jump out of the middle of their control flow. Instead, a `return`
+    /// stores the returned values in these mutable variables and sets the `returned` flag.
+    /// Any code that may run after a `return` is then only executed if the flag is unset,
+    /// and `flatten_cfg` later merges the values of both paths.
+    Predicated { returned: ValueId, values: Values },
+}
+
 /// The queue of functions remaining to compile
 type FunctionQueue = Vec<(FuncId, IrFunctionId)>;
 
@@ -110,7 +127,8 @@ impl<'a> FunctionContext<'a> {
         let mut builder = FunctionBuilder::new(function_name, function_id);
         builder.set_runtime(runtime);
         let definitions = HashMap::default();
-        let mut this = Self { definitions, builder, shared_context, loops: Vec::new() };
+        let mut this =
+            Self { definitions, builder, shared_context, loops: Vec::new(), early_return: None };
         this.add_parameters_to_scope(parameters);
         this
     }
@@ -179,6 +197,35 @@ impl<'a> FunctionContext<'a> {
         Value::Mutable(alloc, typ)
     }
 
+    /// Creates a value of the given type that stands in for one which is never used, such as the
+    /// value of a `return` expression.
+    pub(super) fn zeroed_value(&mut self, typ: &Type) -> ValueId {
+        match typ {
+            Type::Numeric(_) => self.builder.numeric_constant(0u128, typ.clone()),
+            Type::Array(element_types, length) => {
+                let mut elements = im::Vector::new();
+                for _ in 0..*length {
+                    for element_type in element_types.iter() {
+                        elements.push_back(self.zeroed_value(element_type));
+                    }
+                }
+                self.builder.insert_make_array(elements, typ.clone())
+            }
+            Type::Slice(_) => self.builder.insert_make_array(im::Vector::new(), typ.clone()),
+            Type::Reference(element_type) => {
+                let element = self.zeroed_value(element_type);
+                let reference = self.builder.insert_allocate(element_type.as_ref().clone());
+                self.builder.insert_store(reference, element);
+                reference
+            }
+            // Function types don't carry a signature, so any function will do
+            Type::Function => {
+                let function = self.builder.current_function.id();
+                self.builder.import_function(function)
+            }
+        }
+    }
+
     /// Maps the given type to a Tree of the result type.
     ///
     /// This can be used to (for example) flatten a tuple type, creating
@@ -291,8 +338,8 @@ impl<'a> FunctionContext<'a> {
                 match numeric_type {
                     NumericType::NativeField => -value,
                     NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size } => {
-                        let base = 1_u128 << bit_size;
-                        FieldElement::from(base) - value
+                        let base = FieldElement::from(2_u128).pow(&FieldElement::from(bit_size));
+                        base - value
                     }
                 }
             } else {
@@ -311,8 +358,8 @@ impl<'a> FunctionContext<'a> {
         assert_eq!(self.builder.type_of_value(sign), Type::bool());
 
         // We compute the absolute value of lhs
-        let bit_width =
-            self.builder.numeric_constant(FieldElement::from(2_i128.pow(bit_size)), Type::field());
+        let bit_width = FieldElement::from(2_u128).pow(&FieldElement::from(bit_size));
+        let bit_width = self.builder.numeric_constant(bit_width, Type::field());
         let sign_not = self.builder.insert_not(sign);
 
         // We use unsafe casts here, this is fine as we're casting to a `field` type.
@@ -358,6 +405,11 @@ impl<'a> FunctionContext<'a> {
                         self.check_signed_overflow(result, lhs, rhs, operator, bit_size, location);
                         self.insert_safe_cast(result, result_type, location)
                     }
+                    BinaryOpKind::Multiply if 2 * bit_size >= FieldElement::max_num_bits() => {
+                        // The product would not fit in a field element, so we cannot compute it in
+                        // an integer type twice the bit size. Instead we multiply the absolute values.
+                        self.insert_wide_signed_mul(lhs, rhs, bit_size, location)
+                    }
                     BinaryOpKind::Multiply => {
                         // Result is computed modulo the bit size
                         let mut result =
@@ -453,7 +505,7 @@ impl<'a> FunctionContext<'a> {
     ) {
         let is_sub = operator == BinaryOpKind::Subtract;
         let half_width = self.builder.numeric_constant(
-            FieldElement::from(2_i128.pow(bit_size - 1)),
+            FieldElement::from(1_u128 << (bit_size - 1)),
             Type::unsigned(bit_size),
         );
         // We compute the sign of the operands. The overflow checks for signed integers depends on these signs
@@ -518,6 +570,63 @@ impl<'a> FunctionContext<'a> {
         }
     }
 
+    /// Multiply two signed integers whose product, computed in an integer type of twice their
+    /// bit size, could wrap around the field (i.e. `i128`).
+    ///
+    /// We multiply the absolute values of the operands as unsigned integers, which checks that the
+    /// product does not overflow the bit size, check that the signed product fits in a signed
+    /// integer of bit_size-bits, and then apply the sign of the result using the rule of signs.
+    fn insert_wide_signed_mul(
+        &mut self,
+        lhs: ValueId,
+        rhs: ValueId,
+        bit_size: u32,
+        location: Location,
+    ) -> ValueId {
+        let unsigned_type = Type::unsigned(bit_size);
+        let half_width = self
+            .builder
+            .numeric_constant(FieldElement::from(1_u128 << (bit_size - 1)), unsigned_type.clone());
+
+        // We compute the sign of the operands
+        let lhs_as_unsigned = self.insert_safe_cast(lhs, unsigned_type.clone(), location);
+        let rhs_as_unsigned = self.insert_safe_cast(rhs, unsigned_type.clone(), location);
+        let lhs_sign = self.builder.insert_binary(lhs_as_unsigned, BinaryOp::Lt, half_width);
+        let rhs_sign = self.builder.insert_binary(rhs_as_unsigned, BinaryOp::Lt, half_width);
+        let same_sign = self.builder.insert_binary(lhs_sign, BinaryOp::Eq, rhs_sign);
+
+        // The absolute values are at most 2^(bit_size-1) so they fit in the unsigned type.
+        let lhs_abs = self.absolute_value_helper(lhs, lhs_sign, bit_size);
+        let rhs_abs = self.absolute_value_helper(rhs, rhs_sign, bit_size);
+        let lhs_abs = self.builder.insert_cast(lhs_abs, unsigned_type.clone());
+        let rhs_abs = self.builder.insert_cast(rhs_abs, unsigned_type.clone());
+
+        // Unsigned multiplication is checked for overflow of the bit size
+        let product =
+            self.builder.set_location(location).insert_binary(lhs_abs, BinaryOp::Mul, rhs_abs);
+
+        // Then we check the signed product fits in a signed integer of bit_size-bits
+        let not_same = self.builder.insert_not(same_sign);
+        let not_same_sign_unsigned =
+            self.insert_safe_cast(not_same, unsigned_type.clone(), location);
+        let positive_maximum_with_offset =
+            self.builder.insert_binary(half_width, BinaryOp::Add, not_same_sign_unsigned);
+        let product_overflow_check =
+            self.builder.insert_binary(product, BinaryOp::Lt, positive_maximum_with_offset);
+        let one = self.builder.numeric_constant(FieldElement::one(), Type::bool());
+        self.builder.set_location(location).insert_constrain(
+            product_overflow_check,
+            one,
+            Some("attempt to multiply with overflow".to_string().into()),
+        );
+
+        // Finally the result is the product when the signs are the same, or its two complement otherwise
+        let product_field = self.builder.insert_cast(product, Type::field());
+        let signed_product = self.absolute_value_helper(product_field, same_sign, bit_size);
+        let result = self.builder.insert_truncate(signed_product, bit_size, bit_size + 1);
+        self.builder.insert_cast(result, Type::signed(bit_size))
+    }
+
     /// Insert a binary instruction at the end of the current block.
     /// Converts the form of the binary instruction as necessary
     /// (e.g. swapping arguments, inserting a not) to represent it in the IR.
@@ -886,7 +995,7 @@ impl<'a> FunctionContext<'a> {
 
     /// Given an lhs containing only references, create a store instruction to store each value of
     /// rhs into its corresponding value in lhs.
-    fn assign(&mut self, lhs: Values, rhs: Values) {
+    pub(super) fn assign(&mut self, lhs: Values, rhs: Values) {
         match (lhs, rhs) {
             (Tree::Branch(lhs_branches), Tree::Branch(rhs_branches)) => {
                 assert_eq!(lhs_branches.len(), rhs_branches.len());
@@ -4,7 +4,7 @@ mod value;
 
 pub(crate) use program::Ssa;
 
-use context::SharedContext;
+use context::{EarlyReturn, SharedContext};
 use iter_extended::{try_vecmap, vecmap};
 use noirc_errors::Location;
 use noirc_frontend::ast::{UnaryOp, Visibility};
@@ -68,7 +68,7 @@ pub(crate) fn generate_ssa(
     // Generate the call_data bus from the relevant parameters. We create it *before* processing the function body
     let call_data = function_context.builder.call_data_bus(is_databus);
 
-    function_context.codegen_function_body(&main.body)?;
+    function_context.codegen_function_body(&main.body, &main.return_type)?;
 
     let mut return_data = DataBusBuilder::new();
     if let Some(return_location) = return_location {
@@ -115,7 +115,7 @@ pub(crate) fn generate_ssa(
     while let Some((src_function_id, dest_id)) = context.pop_next_function_in_queue() {
         let function = &context.program[src_function_id];
         function_context.new_function(dest_id, function, force_brillig_runtime);
-        function_context.codegen_function_body(&function.body)?;
+        function_context.codegen_function_body(&function.body, &function.return_type)?;
     }
 
     Ok(function_context.builder.finish())
@@ -124,16 +124,69 @@ pub(crate) fn generate_ssa(
 impl<'a> FunctionContext<'a> {
     /// Codegen a function's body and set its return value to that of its last parameter.
     /// For functions returning nothing, this will be an empty list.
-    fn codegen_function_body(&mut self, body: &Expression) -> Result<(), RuntimeError> {
+    fn codegen_function_body(
+        &mut self,
+        body: &Expression,
+        return_type: &ast::Type,
+    ) -> Result<(), RuntimeError> {
         let entry_block = self.increment_parameter_rcs();
+        self.early_return = contains_return(body).then(|| self.prepare_early_return(return_type));
+
         let return_value = self.codegen_expression(body)?;
-        let results = return_value.into_value_list(self);
-        self.end_scope(entry_block, &results);
+
+        let results = match self.early_return.clone() {
+            None => {
+                let results = return_value.into_value_list(self);
+                self.end_scope(entry_block, &results);
+                results
+            }
+            Some(EarlyReturn::Block(exit_block)) => {
+                let results = return_value.into_value_list(self);
+                self.end_scope(entry_block, &results);
+                self.builder.terminate_with_jmp(exit_block, results);
+                self.builder.switch_to_block(exit_block);
+                self.builder.current_function.dfg.block_parameters(exit_block).to_vec()
+            }
+            Some(EarlyReturn::Predicated { returned, values }) => {
+                let return_value = self.codegen_unless_returned(
+                    returned,
+                    |_| Ok(return_value),
+                    |this, _| Ok(values.into_value_list(this)),
+                )?;
+                let results = return_value.into_value_list(self);
+                self.end_scope(entry_block, &results);
+                results
+            }
+        };
 
         self.builder.terminate_with_return(results);
         Ok(())
     }
 
+    /// Sets up what's needed to lower the `return` expressions of a function returning the given type.
+    fn prepare_early_return(&mut self, return_type: &ast::Type) -> EarlyReturn {
+        if self.builder.current_function.runtime().is_brillig() {
+            let exit_block = self.builder.insert_block();
+            for typ in Self::convert_type(return_type).flatten() {
+                self.builder.add_block_parameter(exit_block, typ);
+            }
+            EarlyReturn::Block(exit_block)
+        } else {
+            // The slots need to be initialized here, in the entry block, so that `flatten_cfg`
+            // can merge the values stored into them within each branch.
+            let false_value = self.builder.numeric_constant(false, Type::bool());
+            let returned = match self.new_mutable_variable(false_value) {
+                value::Value::Mutable(returned, _) => returned,
+                value::Value::Normal(_) => unreachable!("Expected a mutable variable"),
+            };
+            let values = Self::map_type(return_type, |typ| {
+                let value = self.zeroed_value(&typ);
+                self.new_mutable_variable(value)
+            });
+            EarlyReturn::Predicated { returned, values }
+        }
+    }
+
     fn codegen_expression(&mut self, expr: &Expression) -> Result<Values, RuntimeError> {
         match expr {
             Expression::Ident(ident) => Ok(self.codegen_ident(ident)),
@@ -158,6 +211,7 @@ impl<'a> FunctionContext<'a> {
             Expression::Semi(semi) => self.codegen_semi(semi),
             Expression::Break => Ok(self.codegen_break()),
             Expression::Continue => Ok(self.codegen_continue()),
+            Expression::Return(value, typ) => self.codegen_return(value, typ),
         }
     }
 
@@ -296,8 +350,20 @@ impl<'a> FunctionContext<'a> {
 
     fn codegen_block(&mut self, block: &[Expression]) -> Result<Values, RuntimeError> {
         let mut result = Self::unit_value();
-        for expr in block {
+        for (i, expr) in block.iter().enumerate() {
             result = self.codegen_expression(expr)?;
+
+            // In ACIR, the rest of the block must only run if the expression didn't return
+            if let Some(EarlyReturn::Predicated { returned, .. }) = self.early_return {
+                let rest = &block[i + 1..];
+                if !rest.is_empty() && contains_return(expr) {
+                    return self.codegen_unless_returned(
+                        returned,
+                        |this| this.codegen_block(rest),
+                        |this, types| Ok(vecmap(types, |typ| this.zeroed_value(typ))),
+                    );
+                }
+            }
         }
         Ok(result)
     }
@@ -517,7 +583,19 @@ impl<'a> FunctionContext<'a> {
         // Compile the loop body
         self.builder.switch_to_block(loop_body);
         self.define(for_expr.index_variable, loop_index.into());
-        self.codegen_expression(&for_expr.block)?;
+        match self.early_return {
+            // ACIR loops are unrolled, so any iteration after a `return` must be skipped
+            Some(EarlyReturn::Predicated { returned, .. }) if contains_return(&for_expr.block) => {
+                self.codegen_unless_returned(
+                    returned,
+                    |this| this.codegen_expression(&for_expr.block),
+                    |_, _| Ok(Vec::new()),
+                )?;
+            }
+            _ => {
+                self.codegen_expression(&for_expr.block)?;
+            }
+        }
         let new_loop_index = self.make_offset(loop_index, 1);
         self.builder.terminate_with_jmp(loop_entry, vec![new_loop_index]);
 
@@ -757,4 +835,135 @@ impl<'a> FunctionContext<'a> {
         self.builder.terminate_with_jmp(loop_.loop_entry, vec![new_loop_index]);
         Self::unit_value()
     }
+
+    /// Codegens a `return` expression.
+    ///
+    /// In Brillig this jumps to the function's exit block, and any code following the `return`
+    /// is placed in a new, unreachable block. In ACIR the returned value is stored instead, and
+    /// the code following the `return` is skipped by `codegen_unless_returned`.
+    ///
+    /// Since a `return` never evaluates to anything, the value of the expression itself is a
+    /// placeholder of the given type.
+    fn codegen_return(
+        &mut self,
+        value: &Expression,
+        typ: &ast::Type,
+    ) -> Result<Values, RuntimeError> {
+        let value = self.codegen_expression(value)?;
+
+        match self.early_return.clone() {
+            Some(EarlyReturn::Block(exit_block)) => {
+                let values = value.into_value_list(self);
+                let entry_block = self.builder.current_function.entry_block();
+                self.end_scope(entry_block, &values);
+                self.builder.terminate_with_jmp(exit_block, values);
+
+                let unreachable_block = self.builder.insert_block();
+                self.builder.switch_to_block(unreachable_block);
+            }
+            Some(EarlyReturn::Predicated { returned, values }) => {
+                self.assign(values, value);
+                let true_value = self.builder.numeric_constant(true, Type::bool());
+                self.builder.insert_store(returned, true_value);
+            }
+            None => unreachable!("ICE: `return` found in a function not prepared for it"),
+        }
+
+        Ok(Self::map_type(typ, |typ| self.zeroed_value(&typ).into()))
+    }
+
+    /// Codegens `then` only if no `return` was executed yet, in which case the result of
+    /// `otherwise` is used instead. `otherwise` receives the flattened types of `then`'s result.
+    ///
+    /// ```text
+    ///   v0 = load returned
+    ///   v1 = not v0
+    ///   brif v1, then: then_block, else: else_block
+    /// then_block():
+    ///   v2 = ... codegen then ...
+    ///   br end_block(v2)
+    /// else_block():
+    ///   v3 = ... codegen otherwise ...
+    ///   br end_block(v3)
+    /// end_block(v4: ?):
+    ///   ... This is the current insert point after codegen_unless_returned finishes ...
+    /// ```
+    fn codegen_unless_returned(
+        &mut self,
+        returned: ValueId,
+        then: impl FnOnce(&mut Self) -> Result<Values, RuntimeError>,
+        otherwise: impl FnOnce(&mut Self, &[Type]) -> Result<Vec<ValueId>, RuntimeError>,
+    ) -> Result<Values, RuntimeError> {
+        let returned = self.builder.insert_load(returned, Type::bool());
+        let condition = self.builder.insert_not(returned);
+
+        let then_block = self.builder.insert_block();
+        let else_block = self.builder.insert_block();
+        let end_block = self.builder.insert_block();
+        self.builder.terminate_with_jmpif(condition, then_block, else_block);
+
+        self.builder.switch_to_block(then_block);
+        let then_value = then(self)?;
+        let then_values = then_value.clone().into_value_list(self);
+        let types = vecmap(&then_values, |value| self.builder.type_of_value(*value));
+        self.builder.terminate_with_jmp(end_block, then_values);
+
+        self.builder.switch_to_block(else_block);
+        let else_values = otherwise(self, &types)?;
+        self.builder.terminate_with_jmp(end_block, else_values);
+
+        self.builder.switch_to_block(end_block);
+        let mut results =
+            vecmap(types, |typ| self.builder.add_block_parameter(end_block, typ)).into_iter();
+        Ok(then_value.map(|_| {
+            results.next().expect("ICE: expected a block parameter for each value").into()
+        }))
+    }
+}
+
+/// Returns whether the given expression contains a `return`, not counting those in other
+/// functions it may refer to.
+fn contains_return(expr: &Expression) -> bool {
+    match expr {
+        Expression::Return(..) => true,
+        Expression::Ident(_) | Expression::Break | Expression::Continue => false,
+        Expression::Literal(literal) => match literal {
+            ast::Literal::Array(array) | ast::Literal::Slice(array) => {
+                array.contents.iter().any(contains_return)
+            }
+            ast::Literal::FmtStr(_, _, fields) => contains_return(fields),
+            ast::Literal::Integer(..)
+            | ast::Literal::Bool(_)
+            | ast::Literal::Unit
+            | ast::Literal::Str(_) => false,
+        },
+        Expression::Block(exprs) | Expression::Tuple(exprs) => exprs.iter().any(contains_return),
+        Expression::Unary(unary) => contains_return(&unary.rhs),
+        Expression::Binary(binary) => contains_return(&binary.lhs) || contains_return(&binary.rhs),
+        Expression::Index(index) => {
+            contains_return(&index.collection) || contains_return(&index.index)
+        }
+        Expression::Cast(cast) => contains_return(&cast.lhs),
+        Expression::For(for_expr) => {
+            contains_return(&for_expr.start_range)
+                || contains_return(&for_expr.end_range)
+                || contains_return(&for_expr.block)
+        }
+        Expression::If(if_expr) => {
+            contains_return(&if_expr.condition)
+                || contains_return(&if_expr.consequence)
+                || if_expr.alternative.as_ref().is_some_and(|alt| contains_return(alt))
+        }
+        Expression::ExtractTupleField(tuple, _) => contains_return(tuple),
+        Expression::Call(call) => {
+            contains_return(&call.func) || call.arguments.iter().any(contains_return)
+        }
+        Expression::Let(let_expr) => contains_return(&let_expr.expression),
+        Expression::Constrain(expr, _, payload) => {
+            contains_return(expr)
+                || payload.as_ref().is_some_and(|payload| contains_return(&payload.0))
+        }
+        Expression::Assign(assign) => contains_return(&assign.expression),
+        Expression::Semi(expr) => contains_return(expr),
+    }
 }
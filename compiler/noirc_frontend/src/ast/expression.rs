@@ -510,6 +510,10 @@ impl FunctionDefinition {
             false
         }
     }
+
+    pub fn is_bench(&self) -> bool {
+        matches!(self.attributes.function(), Some(FunctionAttribute::Bench))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
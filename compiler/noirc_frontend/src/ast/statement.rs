@@ -7,9 +7,9 @@ use iter_extended::vecmap;
 use noirc_errors::{Span, Spanned};
 
 use super::{
-    BinaryOpKind, BlockExpression, ConstructorExpression, Expression, ExpressionKind,
-    GenericTypeArgs, IndexExpression, InfixExpression, ItemVisibility, MemberAccessExpression,
-    MethodCallExpression, UnresolvedType,
+    ArrayLiteral, BinaryOpKind, BlockExpression, ConstructorExpression, Expression, ExpressionKind,
+    GenericTypeArgs, IndexExpression, InfixExpression, ItemVisibility, Literal,
+    MemberAccessExpression, MethodCallExpression, UnresolvedType,
 };
 use crate::ast::UnresolvedTypeData;
 use crate::elaborator::types::SELF_TYPE_NAME;
@@ -48,6 +48,7 @@ pub enum StatementKind {
     For(ForLoopStatement),
     Break,
     Continue,
+    Return(Option<Expression>),
     /// This statement should be executed at compile-time
     Comptime(Box<Statement>),
     // This is an expression with a trailing semi-colon
@@ -107,6 +108,14 @@ impl StatementKind {
             // A semicolon on a for loop is optional and does nothing
             StatementKind::For(_) => self,
 
+            // Like an expression, a `return` at the end of a block doesn't need a semicolon
+            StatementKind::Return(_) => {
+                if semi.is_none() && !last_statement_in_block {
+                    emit_error(missing_semicolon);
+                }
+                self
+            }
+
             // No semicolon needed for a resolved statement
             StatementKind::Interned(_) => self,
 
@@ -634,6 +643,9 @@ pub enum Pattern {
     Identifier(Ident),
     Mutable(Box<Pattern>, Span, /*is_synthesized*/ bool),
     Tuple(Vec<Pattern>, Span),
+    /// `[a, b, c]`, optionally with a single `..` rest pattern skipping
+    /// any elements at the given index, e.g. `[first, .., last]`.
+    Array(Vec<Pattern>, /*rest_index*/ Option<usize>, Span),
     Struct(Path, Vec<(Ident, Pattern)>, Span),
     Interned(InternedPattern, Span),
 }
@@ -648,6 +660,7 @@ impl Pattern {
             Pattern::Identifier(ident) => ident.span(),
             Pattern::Mutable(_, span, _)
             | Pattern::Tuple(_, span)
+            | Pattern::Array(_, _, span)
             | Pattern::Struct(_, _, span)
             | Pattern::Interned(_, span) => *span,
         }
@@ -674,6 +687,15 @@ impl Pattern {
                 }
                 Some(Expression { kind: ExpressionKind::Tuple(expressions), span: *span })
             }
+            Pattern::Array(_, Some(_), _) => None,
+            Pattern::Array(patterns, None, span) => {
+                let mut expressions = Vec::new();
+                for pattern in patterns {
+                    expressions.push(pattern.try_as_expression(interner)?);
+                }
+                let literal = Literal::Array(ArrayLiteral::Standard(expressions));
+                Some(Expression { kind: ExpressionKind::Literal(literal), span: *span })
+            }
             Pattern::Struct(path, patterns, span) => {
                 let mut fields = Vec::new();
                 for (field, pattern) in patterns {
@@ -962,6 +984,8 @@ impl Display for StatementKind {
             StatementKind::For(for_loop) => for_loop.fmt(f),
             StatementKind::Break => write!(f, "break"),
             StatementKind::Continue => write!(f, "continue"),
+            StatementKind::Return(Some(value)) => write!(f, "return {value}"),
+            StatementKind::Return(None) => write!(f, "return"),
             StatementKind::Comptime(statement) => write!(f, "comptime {}", statement.kind),
             StatementKind::Semi(semi) => write!(f, "{semi};"),
             StatementKind::Interned(_) => write!(f, "(resolved);"),
@@ -1041,6 +1065,13 @@ impl Display for Pattern {
                 let fields = vecmap(fields, ToString::to_string);
                 write!(f, "({})", fields.join(", "))
             }
+            Pattern::Array(elements, rest_index, _) => {
+                let mut elements = vecmap(elements, ToString::to_string);
+                if let Some(rest_index) = rest_index {
+                    elements.insert(*rest_index, "..".to_string());
+                }
+                write!(f, "[{}]", elements.join(", "))
+            }
             Pattern::Struct(typename, fields, _) => {
                 let fields = vecmap(fields, |(name, pattern)| format!("{name}: {pattern}"));
                 write!(f, "{} {{ {} }}", typename, fields.join(", "))
@@ -41,14 +41,17 @@ pub enum TraitItem {
         return_type: FunctionReturnType,
         where_clause: Vec<UnresolvedTraitConstraint>,
         body: Option<BlockExpression>,
+        attributes: Vec<SecondaryAttribute>,
     },
     Constant {
         name: Ident,
         typ: UnresolvedType,
         default_value: Option<Expression>,
+        attributes: Vec<SecondaryAttribute>,
     },
     Type {
         name: Ident,
+        attributes: Vec<SecondaryAttribute>,
     },
 }
 
@@ -112,8 +115,8 @@ pub struct TraitImplItem {
 #[derive(Clone, Debug)]
 pub enum TraitImplItemKind {
     Function(NoirFunction),
-    Constant(Ident, UnresolvedType, Expression),
-    Type { name: Ident, alias: UnresolvedType },
+    Constant(Ident, UnresolvedType, Expression, Vec<SecondaryAttribute>),
+    Type { name: Ident, alias: UnresolvedType, attributes: Vec<SecondaryAttribute> },
 }
 
 impl Display for TypeImpl {
@@ -177,6 +180,7 @@ impl Display for TraitItem {
                 is_unconstrained,
                 visibility,
                 is_comptime,
+                attributes: _,
             } => {
                 let generics = vecmap(generics, |generic| generic.to_string());
                 let parameters = vecmap(parameters, |(name, typ)| format!("{name}: {typ}"));
@@ -205,7 +209,7 @@ impl Display for TraitItem {
                     write!(f, ";")
                 }
             }
-            TraitItem::Constant { name, typ, default_value } => {
+            TraitItem::Constant { name, typ, default_value, attributes: _ } => {
                 write!(f, "let {name}: {typ}")?;
 
                 if let Some(default_value) = default_value {
@@ -214,7 +218,7 @@ impl Display for TraitItem {
                     write!(f, ";")
                 }
             }
-            TraitItem::Type { name } => write!(f, "type {name};"),
+            TraitItem::Type { name, attributes: _ } => write!(f, "type {name};"),
         }
     }
 }
@@ -278,8 +282,10 @@ impl Display for TraitImplItemKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TraitImplItemKind::Function(function) => function.fmt(f),
-            TraitImplItemKind::Type { name, alias } => write!(f, "type {name} = {alias};"),
-            TraitImplItemKind::Constant(name, typ, value) => {
+            TraitImplItemKind::Type { name, alias, attributes: _ } => {
+                write!(f, "type {name} = {alias};")
+            }
+            TraitImplItemKind::Constant(name, typ, value, _attributes) => {
                 write!(f, "let {name}: {typ} = {value};")
             }
         }
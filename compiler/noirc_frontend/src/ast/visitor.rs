@@ -304,6 +304,10 @@ pub trait Visitor {
 
     fn visit_continue(&mut self) {}
 
+    fn visit_return(&mut self, _: Option<&Expression>, _: Span) -> bool {
+        true
+    }
+
     fn visit_interned_statement(&mut self, _: InternedStatementKind) {}
 
     fn visit_error_statement(&mut self) {}
@@ -460,6 +464,10 @@ pub trait Visitor {
         true
     }
 
+    fn visit_array_pattern(&mut self, _: &[Pattern], _: Option<usize>, _: Span) -> bool {
+        true
+    }
+
     fn visit_struct_pattern(&mut self, _: &Path, _: &[(Ident, Pattern)], _: Span) -> bool {
         true
     }
@@ -618,13 +626,13 @@ impl TraitImplItemKind {
                     noir_function.accept(span, visitor);
                 }
             }
-            TraitImplItemKind::Constant(name, unresolved_type, expression) => {
+            TraitImplItemKind::Constant(name, unresolved_type, expression, _attributes) => {
                 if visitor.visit_trait_impl_item_constant(name, unresolved_type, expression, span) {
                     unresolved_type.accept(visitor);
                     expression.accept(visitor);
                 }
             }
-            TraitImplItemKind::Type { name, alias } => {
+            TraitImplItemKind::Type { name, alias, attributes: _ } => {
                 if visitor.visit_trait_impl_item_type(name, alias, span) {
                     alias.accept(visitor);
                 }
@@ -694,6 +702,7 @@ impl TraitItem {
                 is_unconstrained: _,
                 visibility: _,
                 is_comptime: _,
+                attributes: _,
             } => {
                 if visitor.visit_trait_item_function(
                     name,
@@ -718,7 +727,7 @@ impl TraitItem {
                     }
                 }
             }
-            TraitItem::Constant { name, typ, default_value } => {
+            TraitItem::Constant { name, typ, default_value, attributes: _ } => {
                 if visitor.visit_trait_item_constant(name, typ, default_value) {
                     typ.accept(visitor);
 
@@ -727,7 +736,7 @@ impl TraitItem {
                     }
                 }
             }
-            TraitItem::Type { name } => visitor.visit_trait_item_type(name),
+            TraitItem::Type { name, attributes: _ } => visitor.visit_trait_item_type(name),
         }
     }
 }
@@ -1114,6 +1123,13 @@ impl Statement {
             }
             StatementKind::Break => visitor.visit_break(),
             StatementKind::Continue => visitor.visit_continue(),
+            StatementKind::Return(value) => {
+                if visitor.visit_return(value.as_ref(), self.span) {
+                    if let Some(value) = value {
+                        value.accept(visitor);
+                    }
+                }
+            }
             StatementKind::Interned(id) => visitor.visit_interned_statement(*id),
             StatementKind::Error => visitor.visit_error_statement(),
         }
@@ -1420,6 +1436,13 @@ impl Pattern {
                     }
                 }
             }
+            Pattern::Array(patterns, rest_index, span) => {
+                if visitor.visit_array_pattern(patterns, *rest_index, *span) {
+                    for pattern in patterns {
+                        pattern.accept(visitor);
+                    }
+                }
+            }
             Pattern::Struct(path, fields, span) => {
                 if visitor.visit_struct_pattern(path, fields, *span) {
                     path.accept(visitor);
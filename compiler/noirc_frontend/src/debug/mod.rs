@@ -39,6 +39,10 @@ pub struct DebugInstrumenter {
 
     // last seen variable names and their IDs grouped by scope
     scope: Vec<HashMap<String, SourceVarId>>,
+
+    // the function being instrumented and the index of its outermost scope,
+    // unless we're within a lambda, which a `return` would only exit from
+    current_fn: Option<(DebugFnId, usize)>,
 }
 
 impl Default for DebugInstrumenter {
@@ -51,6 +55,7 @@ impl Default for DebugInstrumenter {
             next_var_id: 0,
             next_field_name_id: 1,
             next_fn_id: 0,
+            current_fn: None,
         }
     }
 }
@@ -100,6 +105,7 @@ impl DebugInstrumenter {
         let fn_id = self.insert_function(func_name, func_args);
         let enter_stmt = build_debug_call_stmt("enter", fn_id, func.span);
         self.scope.push(HashMap::default());
+        self.current_fn = Some((fn_id, self.scope.len() - 1));
 
         let set_fn_params: Vec<_> = func
             .parameters
@@ -117,8 +123,10 @@ impl DebugInstrumenter {
 
         let func_body = &mut func.body.statements;
         let mut statements = take(func_body);
+        let ends_with_return = ends_with_return(&statements);
 
         self.walk_scope(&mut statements, func.span);
+        self.current_fn = None;
 
         // walk_scope ensures that the last statement is the return value of the function
         let last_stmt = statements.pop().expect("at least one statement after walk_scope");
@@ -128,15 +136,26 @@ impl DebugInstrumenter {
         func_body.push(enter_stmt);
         func_body.extend(set_fn_params);
         func_body.extend(statements);
-        func_body.push(exit_stmt);
+        // a trailing return statement already exits the function
+        if !ends_with_return {
+            func_body.push(exit_stmt);
+        }
         func_body.push(last_stmt);
     }
 
     // Modify a vector of statements in-place, adding instrumentation for sets and drops.
     // This function will consume a scope level.
     fn walk_scope(&mut self, statements: &mut Vec<ast::Statement>, span: Span) {
+        let ends_with_return = ends_with_return(statements);
         statements.iter_mut().for_each(|stmt| self.walk_statement(stmt));
 
+        if ends_with_return {
+            // the return statement already drops the scope variables, and keeping it last
+            // lets the scope take any type, like it does without instrumentation
+            self.scope.pop();
+            return;
+        }
+
         // extract and save the return value from the scope if there is one
         let ret_stmt = statements.pop();
         let has_ret_expr = match ret_stmt {
@@ -354,6 +373,63 @@ impl DebugInstrumenter {
         }
     }
 
+    fn walk_return_statement(
+        &mut self,
+        value: &mut Option<ast::Expression>,
+        span: Span,
+    ) -> ast::Statement {
+        // return X becomes:
+        // {
+        //   let __debug_expr = X;
+        //   __debug_var_drop(...);  // for every variable in scope within the function
+        //   __debug_fn_exit(3);
+        //   return __debug_expr
+        // }
+        //
+        // This is an expression statement, so that a scope ending with it still
+        // takes the type of the return statement, as it does without instrumentation.
+
+        if let Some(value) = value {
+            self.walk_expr(value);
+        }
+        let Some((fn_id, fn_scope)) = self.current_fn else {
+            return ast::Statement { kind: ast::StatementKind::Return(value.take()), span };
+        };
+
+        let value = value.take().unwrap_or(ast::Expression {
+            kind: ast::ExpressionKind::Literal(ast::Literal::Unit),
+            span,
+        });
+        let mut statements = vec![ast::Statement {
+            kind: ast::StatementKind::new_let(
+                ast::Pattern::Identifier(ident("__debug_expr", value.span)),
+                ast::UnresolvedTypeData::Unspecified.with_span(Default::default()),
+                value,
+                vec![],
+            ),
+            span,
+        }];
+        statements.extend(
+            self.scope[fn_scope..]
+                .iter()
+                .flat_map(|vars| vars.values())
+                .map(|var_id| build_drop_var_stmt(*var_id, span)),
+        );
+        statements.push(build_debug_call_stmt("exit", fn_id, span));
+        statements.push(ast::Statement {
+            kind: ast::StatementKind::Return(Some(id_expr(&ident("__debug_expr", span)))),
+            span,
+        });
+
+        ast::Statement {
+            kind: ast::StatementKind::Expression(ast::Expression {
+                kind: ast::ExpressionKind::Block(ast::BlockExpression { statements }),
+                span,
+            }),
+            span,
+        }
+    }
+
     fn walk_expr(&mut self, expr: &mut ast::Expression) {
         match &mut expr.kind {
             ast::ExpressionKind::Block(ast::BlockExpression { ref mut statements, .. }) => {
@@ -409,7 +485,9 @@ impl DebugInstrumenter {
                 });
             }
             ast::ExpressionKind::Lambda(lambda) => {
+                let current_fn = self.current_fn.take();
                 self.walk_expr(&mut lambda.body);
+                self.current_fn = current_fn;
             }
             ast::ExpressionKind::Parenthesized(expr) => {
                 self.walk_expr(expr);
@@ -458,6 +536,9 @@ impl DebugInstrumenter {
             ast::StatementKind::For(ref mut for_stmt) => {
                 self.walk_for(for_stmt);
             }
+            ast::StatementKind::Return(value) => {
+                *stmt = self.walk_return_statement(value, stmt.span);
+            }
             _ => {} // Constrain, Error
         }
     }
@@ -675,6 +756,9 @@ fn pattern_vars(pattern: &ast::Pattern) -> Vec<(ast::Ident, bool)> {
             ast::Pattern::Tuple(patterns, _) => {
                 stack.extend(patterns.iter().map(|pattern| (pattern, false)));
             }
+            ast::Pattern::Array(patterns, _, _) => {
+                stack.extend(patterns.iter().map(|pattern| (pattern, is_mut)));
+            }
             ast::Pattern::Struct(_, pids, _) => {
                 stack.extend(pids.iter().map(|(_, pattern)| (pattern, is_mut)));
                 vars.extend(pids.iter().map(|(id, _)| (id.clone(), false)));
@@ -693,6 +777,13 @@ fn pattern_to_string(pattern: &ast::Pattern) -> String {
             "({})",
             elements.iter().map(pattern_to_string).collect::<Vec<String>>().join(", ")
         ),
+        ast::Pattern::Array(elements, rest_index, _) => {
+            let mut elements: Vec<String> = elements.iter().map(pattern_to_string).collect();
+            if let Some(rest_index) = rest_index {
+                elements.insert(*rest_index, "..".to_string());
+            }
+            format!("[{}]", elements.join(", "))
+        }
         ast::Pattern::Struct(name, fields, _) => {
             format!(
                 "{} {{ {} }}",
@@ -710,6 +801,10 @@ fn pattern_to_string(pattern: &ast::Pattern) -> String {
     }
 }
 
+fn ends_with_return(statements: &[ast::Statement]) -> bool {
+    matches!(statements.last(), Some(ast::Statement { kind: ast::StatementKind::Return(_), .. }))
+}
+
 fn ident(s: &str, span: Span) -> ast::Ident {
     ast::Ident(Spanned::from(span, s.to_string()))
 }
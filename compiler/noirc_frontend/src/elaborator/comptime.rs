@@ -19,7 +19,9 @@ use crate::{
         resolution::errors::ResolverError,
     },
     hir_def::expr::{HirExpression, HirIdent},
-    node_interner::{DefinitionKind, DependencyId, FuncId, NodeInterner, StructId, TraitId},
+    node_interner::{
+        ComptimeExpansion, DefinitionKind, DependencyId, FuncId, NodeInterner, StructId, TraitId,
+    },
     parser::{Item, ItemKind},
     token::{MetaAttribute, SecondaryAttribute},
     Type, TypeBindings, UnificationError,
@@ -255,6 +257,11 @@ impl<'context> Elaborator<'context> {
                 .into_top_level_items(location, self.interner)
                 .map_err(|error| error.into_compilation_error_pair())?;
 
+            let item_location = Location::new(span, attribute_context.file);
+            self.record_comptime_expansion(item_location, format!("#[{attribute}]"), |_| {
+                vecmap(&items, |item| item.kind.to_string()).join("\n\n")
+            });
+
             self.add_items(items, generated_items, location);
         }
 
@@ -497,6 +504,20 @@ impl<'context> Elaborator<'context> {
         }
     }
 
+    /// Remembers the code a comptime evaluation produced so the LSP can show it.
+    pub(super) fn record_comptime_expansion<T: Display, F: FnOnce(&mut NodeInterner) -> T>(
+        &mut self,
+        location: Location,
+        title: impl Into<String>,
+        expansion_f: F,
+    ) {
+        if self.interner.is_in_lsp_mode() {
+            let expansion = expansion_f(self.interner).to_string();
+            let title = title.into();
+            self.interner.push_comptime_expansion(ComptimeExpansion { location, title, expansion });
+        }
+    }
+
     /// Run all the attributes on each item. The ordering is unspecified to users but currently
     /// we run trait attributes first to (e.g.) register derive handlers before derive is
     /// called on structs.
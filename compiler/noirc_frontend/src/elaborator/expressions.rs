@@ -826,7 +826,10 @@ impl<'context> Elaborator<'context> {
         self.push_scope();
         let scope_index = self.scopes.current_scope_index();
 
-        self.lambda_stack.push(LambdaContext { captures: Vec::new(), scope_index });
+        let return_type = self.resolve_inferred_type(lambda.return_type);
+        let lambda_context =
+            LambdaContext { captures: Vec::new(), scope_index, return_type: return_type.clone() };
+        self.lambda_stack.push(lambda_context);
 
         let mut arg_types = Vec::with_capacity(lambda.parameters.len());
         let parameters = vecmap(lambda.parameters, |(pattern, typ)| {
@@ -836,7 +839,6 @@ impl<'context> Elaborator<'context> {
             (self.elaborate_pattern(pattern, typ.clone(), parameter, true), typ)
         });
 
-        let return_type = self.resolve_inferred_type(lambda.return_type);
         let body_span = lambda.body.span;
         let (body, body_type) = self.elaborate_expression(lambda.body);
 
@@ -884,6 +886,9 @@ impl<'context> Elaborator<'context> {
         self.debug_comptime(location, |interner| {
             interner.expression(&id).to_display_ast(interner, location.span).kind
         });
+        self.record_comptime_expansion(location, "comptime block", |interner| {
+            interner.expression(&id).to_display_ast(interner, location.span).kind
+        });
 
         (id, typ)
     }
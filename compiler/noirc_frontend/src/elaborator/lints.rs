@@ -204,7 +204,7 @@ pub(crate) fn overflowing_int(
         HirExpression::Literal(HirLiteral::Integer(value, negative)) => match annotated_type {
             Type::Integer(Signedness::Unsigned, bit_count) => {
                 let bit_count: u32 = (*bit_count).into();
-                let max = 2u128.pow(bit_count) - 1;
+                let max = u128::MAX >> (128 - bit_count);
                 if value > max.into() || negative {
                     errors.push(TypeCheckError::OverflowingAssignment {
                         expr: if negative { -value } else { value },
@@ -216,8 +216,8 @@ pub(crate) fn overflowing_int(
             }
             Type::Integer(Signedness::Signed, bit_count) => {
                 let bit_count: u32 = (*bit_count).into();
-                let min = 2u128.pow(bit_count - 1);
-                let max = 2u128.pow(bit_count - 1) - 1;
+                let min = 1u128 << (bit_count - 1);
+                let max = min - 1;
                 if (negative && value > min.into()) || (!negative && value > max.into()) {
                     errors.push(TypeCheckError::OverflowingAssignment {
                         expr: if negative { -value } else { value },
@@ -260,7 +260,10 @@ pub(crate) fn unbounded_recursion<'a>(
     func_span: Span,
     body_id: ExprId,
 ) -> Option<ResolverError> {
-    if !can_return_without_recursing(interner, func_id, body_id) {
+    // An early `return` is assumed to be a way out of the recursion
+    if !can_return_without_recursing(interner, func_id, body_id)
+        && !contains_return(interner, body_id)
+    {
         Some(ResolverError::UnconditionalRecursion {
             name: func_name().to_string(),
             span: func_span,
@@ -280,6 +283,7 @@ fn can_return_without_recursing(interner: &NodeInterner, func_id: FuncId, expr_i
             HirStatement::Assign(s) => check(s.expression),
             HirStatement::Expression(e) => check(e),
             HirStatement::Semi(e) => check(e),
+            HirStatement::Return(e, _) => e.map_or(true, check),
             // Rust doesn't seem to check the for loop body (it's bounds might mean it's never called).
             HirStatement::For(e) => check(e.start_range) && check(e.end_range),
             HirStatement::Constrain(_)
@@ -325,3 +329,31 @@ fn can_return_without_recursing(interner: &NodeInterner, func_id: FuncId, expr_i
         | HirExpression::Error => true,
     }
 }
+
+/// Check if an expression contains a `return` statement, outside of lambdas.
+fn contains_return(interner: &NodeInterner, expr_id: ExprId) -> bool {
+    let check = |e| contains_return(interner, e);
+
+    let check_block = |block: HirBlockExpression| {
+        block.statements.iter().any(|stmt_id| match interner.statement(stmt_id) {
+            HirStatement::Return(..) => true,
+            HirStatement::Let(s) => check(s.expression),
+            HirStatement::Assign(s) => check(s.expression),
+            HirStatement::Expression(e) | HirStatement::Semi(e) => check(e),
+            HirStatement::For(e) => check(e.block),
+            HirStatement::Constrain(_)
+            | HirStatement::Comptime(_)
+            | HirStatement::Break
+            | HirStatement::Continue
+            | HirStatement::Error => false,
+        })
+    };
+
+    match interner.expression(&expr_id) {
+        HirExpression::Block(b) | HirExpression::Unsafe(b) => check_block(b),
+        HirExpression::If(e) => {
+            check(e.condition) || check(e.consequence) || e.alternative.is_some_and(check)
+        }
+        _ => false,
+    }
+}
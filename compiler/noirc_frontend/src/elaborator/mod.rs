@@ -77,6 +77,8 @@ pub struct LambdaContext {
     /// the index in the scope tree
     /// (sometimes being filled by ScopeTree's find method)
     pub scope_index: usize,
+    /// The type `return` statements in the lambda's body are checked against
+    pub return_type: Type,
 }
 
 pub struct Elaborator<'context> {
@@ -1345,6 +1347,11 @@ impl<'context> Elaborator<'context> {
                 return;
             }
 
+            if !is_trait_impl {
+                self.interner
+                    .add_struct_impl_location(struct_ref.id, Location::new(span, self.file));
+            }
+
             // Grab the module defined by the struct type. Note that impls are a case
             // where the module the methods are added to is not the same as the module
             // they are resolved in.
@@ -1702,6 +1709,9 @@ impl<'context> Elaborator<'context> {
                 .expect("The global should be defined since evaluate_let did not error");
 
             self.debug_comptime(location, |interner| value.display(interner).to_string());
+            self.record_comptime_expansion(location, "comptime global", |interner| {
+                value.display(interner).to_string()
+            });
 
             self.interner.get_global_mut(global_id).value = Some(value);
         }
@@ -1798,6 +1808,22 @@ impl<'context> Elaborator<'context> {
         }
     }
 
+    /// The type `return` statements are checked against: the return type of the innermost lambda
+    /// or, outside of lambdas, of the current function.
+    /// `None` if there is nothing to return from, eg. within a `comptime` block or a global.
+    fn current_return_type(&self) -> Option<Type> {
+        if let Some(lambda) = self.lambda_stack.last() {
+            return Some(lambda.return_type.clone());
+        }
+
+        match self.current_item {
+            Some(DependencyId::Function(id)) if !self.in_comptime_context => {
+                Some(self.interner.function_meta(&id).return_type().clone())
+            }
+            _ => None,
+        }
+    }
+
     /// True if we're currently within a constrained function.
     /// Defaults to `true` if the current function is unknown.
     fn in_constrained_function(&self) -> bool {
@@ -144,6 +144,27 @@ impl<'context> Elaborator<'context> {
                 let location = Location::new(span, self.file);
                 HirPattern::Tuple(fields, location)
             }
+            Pattern::Array(elements, rest_index, span) => {
+                let element_type = self.elaborate_array_pattern_type(
+                    elements.len(),
+                    rest_index,
+                    expected_type,
+                    span,
+                );
+
+                let elements = vecmap(elements, |element| {
+                    self.elaborate_pattern_mut(
+                        element,
+                        element_type.clone(),
+                        definition.clone(),
+                        mutable,
+                        new_definitions,
+                        warn_if_unused,
+                    )
+                });
+                let location = Location::new(span, self.file);
+                HirPattern::Array(elements, rest_index, location)
+            }
             Pattern::Struct(name, fields, span) => self.elaborate_struct_pattern(
                 name,
                 fields,
@@ -167,6 +188,62 @@ impl<'context> Elaborator<'context> {
         }
     }
 
+    /// Checks that an array pattern with `pattern_length` elements can bind to `expected_type`,
+    /// returning the type of each element. Without a `..` the array length must be exactly
+    /// `pattern_length`. With a `..` the length must be known and at least `pattern_length`.
+    fn elaborate_array_pattern_type(
+        &mut self,
+        pattern_length: usize,
+        rest_index: Option<usize>,
+        expected_type: Type,
+        span: Span,
+    ) -> Type {
+        // Unlike arrays, slices don't have a length known at compile-time to check the pattern against
+        if let Type::Slice(element_type) = expected_type.follow_bindings() {
+            self.push_err(TypeCheckError::ArrayPatternOnSlice { typ: expected_type, span });
+            return *element_type;
+        }
+
+        if rest_index.is_none() {
+            let element_type = self.interner.next_type_variable();
+            let length = Type::Constant(pattern_length.into(), Kind::u32());
+            let array = Type::Array(Box::new(length), Box::new(element_type.clone()));
+
+            self.unify(&array, &expected_type, || TypeCheckError::TypeMismatchWithSource {
+                expected: expected_type.clone(),
+                actual: array.clone(),
+                span,
+                source: Source::Assignment,
+            });
+            return element_type;
+        }
+
+        match expected_type.follow_bindings() {
+            Type::Array(length, element_type) => {
+                // A length that isn't known yet, like a numeric generic, is checked during
+                // monomorphization instead
+                if let Ok(length) = length.evaluate_to_u32(span) {
+                    if (length as usize) < pattern_length {
+                        self.push_err(TypeCheckError::ArrayPatternTooLong {
+                            typ: expected_type,
+                            pattern_length,
+                            span,
+                        });
+                    }
+                }
+                *element_type
+            }
+            Type::Error => Type::Error,
+            _ => {
+                self.push_err(TypeCheckError::RestPatternUnknownLength {
+                    typ: expected_type,
+                    span,
+                });
+                Type::Error
+            }
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn elaborate_struct_pattern(
         &mut self,
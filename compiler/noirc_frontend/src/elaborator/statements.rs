@@ -35,6 +35,7 @@ impl<'context> Elaborator<'context> {
             StatementKind::For(for_stmt) => self.elaborate_for(for_stmt),
             StatementKind::Break => self.elaborate_jump(true, statement.span),
             StatementKind::Continue => self.elaborate_jump(false, statement.span),
+            StatementKind::Return(value) => self.elaborate_return(value, statement.span),
             StatementKind::Comptime(statement) => self.elaborate_comptime_statement(*statement),
             StatementKind::Expression(expr) => {
                 let (expr, typ) = self.elaborate_expression(expr);
@@ -279,6 +280,51 @@ impl<'context> Elaborator<'context> {
         (expr, self.interner.next_type_variable())
     }
 
+    fn elaborate_return(&mut self, value: Option<Expression>, span: Span) -> (HirStatement, Type) {
+        let (value, value_type, value_span) = match value {
+            Some(value) => {
+                let value_span = value.span;
+                let (value, value_type) = self.elaborate_expression(value);
+                (Some(value), value_type, value_span)
+            }
+            None => (None, Type::Unit, span),
+        };
+
+        let make_error = |expected_typ: &Type| TypeCheckError::TypeMismatch {
+            expected_typ: expected_typ.to_string(),
+            expr_typ: value_type.to_string(),
+            expr_span: value_span,
+        };
+
+        match self.current_return_type() {
+            Some(Type::TraitAsType(trait_id, name, generics)) => {
+                let implementation = self.interner.lookup_trait_implementation(
+                    &value_type,
+                    trait_id,
+                    &generics.ordered,
+                    &generics.named,
+                );
+                if implementation.is_err() {
+                    let expected = Type::TraitAsType(trait_id, name, generics);
+                    self.push_err(make_error(&expected));
+                }
+            }
+            Some(return_type) => match value {
+                Some(value) => {
+                    self.unify_with_coercions(&value_type, &return_type, value, value_span, || {
+                        make_error(&return_type)
+                    });
+                }
+                None => self.unify(&value_type, &return_type, || make_error(&return_type)),
+            },
+            None => self.push_err(ResolverError::ReturnOutsideFunction { span }),
+        }
+
+        // A `return` never produces a value, so it can be used where any type is expected
+        let typ = self.interner.next_type_variable();
+        (HirStatement::Return(value, typ.clone()), typ)
+    }
+
     fn get_lvalue_name_and_span(&self, lvalue: &HirLValue) -> (String, Span) {
         match lvalue {
             HirLValue::Ident(name, _) => {
@@ -539,6 +585,9 @@ impl<'context> Elaborator<'context> {
 
         let location = self.interner.id_location(hir_statement);
         self.debug_comptime(location, |interner| expr.to_display_ast(interner).kind);
+        self.record_comptime_expansion(location, "comptime statement", |interner| {
+            expr.to_display_ast(interner).kind
+        });
 
         (HirStatement::Expression(expr), typ)
     }
@@ -98,6 +98,7 @@ impl<'context> Elaborator<'context> {
                 is_unconstrained,
                 visibility: _,
                 is_comptime: _,
+                attributes: _,
             } = &item.item
             {
                 self.recover_generics(|this| {
@@ -4,7 +4,7 @@
 // This version is also simpler due to not having macro_defs or proc_macros
 // XXX: Edition may be reintroduced or some sort of versioning
 
-use std::{fmt::Display, str::FromStr};
+use std::{collections::BTreeSet, fmt::Display, str::FromStr};
 
 use fm::FileId;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -122,6 +122,8 @@ pub const CHARACTER_BLACK_LIST: [char; 1] = ['-'];
 pub struct CrateData {
     pub root_file_id: FileId,
     pub dependencies: Vec<Dependency>,
+    /// Features enabled for this crate, checked by `#[cfg(feature = "...")]` attributes
+    pub enabled_features: BTreeSet<String>,
 }
 
 /// A dependency is a crate name and a crate_id
@@ -164,7 +166,7 @@ impl CrateGraph {
             }
         }
 
-        let data = CrateData { root_file_id: file_id, dependencies: Vec::new() };
+        let data = CrateData::new(file_id);
         let crate_id = CrateId::Root(self.arena.len());
         let prev = self.arena.insert(crate_id, data);
         assert!(prev.is_none());
@@ -195,7 +197,7 @@ impl CrateGraph {
                 panic!("ICE: A dummy CrateId should not exist in the CrateGraph")
             }
             None => {
-                let data = CrateData { root_file_id: file_id, dependencies: Vec::new() };
+                let data = CrateData::new(file_id);
                 let crate_id = CrateId::Crate(self.arena.len());
                 let prev = self.arena.insert(crate_id, data);
                 assert!(prev.is_none());
@@ -215,7 +217,7 @@ impl CrateGraph {
             }
         }
 
-        let data = CrateData { root_file_id: file_id, dependencies: Vec::new() };
+        let data = CrateData::new(file_id);
         let crate_id = CrateId::Stdlib(self.arena.len());
         let prev = self.arena.insert(crate_id, data);
         assert!(prev.is_none());
@@ -237,7 +239,7 @@ impl CrateGraph {
             }
         }
 
-        let data = CrateData { root_file_id: file_id, dependencies: Vec::new() };
+        let data = CrateData::new(file_id);
         let crate_id = CrateId::RootAndStdlib(self.arena.len());
         let prev = self.arena.insert(crate_id, data);
         assert!(prev.is_none());
@@ -305,11 +307,27 @@ impl CrateGraph {
         false
     }
 
+    /// Enables the given features for a crate.
+    ///
+    /// A crate is only added to the graph once even if several crates depend on it,
+    /// so the enabled features are the union of the features requested by every dependent.
+    pub fn enable_features(
+        &mut self,
+        crate_id: CrateId,
+        features: impl IntoIterator<Item = String>,
+    ) {
+        self.arena.get_mut(&crate_id).unwrap().enabled_features.extend(features);
+    }
+
     pub fn number_of_crates(&self) -> usize {
         self.arena.len()
     }
 }
 impl CrateData {
+    fn new(root_file_id: FileId) -> Self {
+        Self { root_file_id, dependencies: Vec::new(), enabled_features: BTreeSet::new() }
+    }
+
     fn add_dep(&mut self, name: CrateName, crate_id: CrateId) {
         self.dependencies.push(Dependency { crate_id, name });
     }
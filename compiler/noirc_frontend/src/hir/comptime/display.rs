@@ -341,11 +341,13 @@ impl<'value, 'interner> Display for ValuePrinter<'value, 'interner> {
             Value::I16(value) => write!(f, "{value}"),
             Value::I32(value) => write!(f, "{value}"),
             Value::I64(value) => write!(f, "{value}"),
+            Value::I128(value) => write!(f, "{value}"),
             Value::U1(value) => write!(f, "{value}"),
             Value::U8(value) => write!(f, "{value}"),
             Value::U16(value) => write!(f, "{value}"),
             Value::U32(value) => write!(f, "{value}"),
             Value::U64(value) => write!(f, "{value}"),
+            Value::U128(value) => write!(f, "{value}"),
             Value::String(value) => write!(f, "{value}"),
             Value::CtString(value) => write!(f, "{value}"),
             Value::FormatString(value, _) => write!(f, "{value}"),
@@ -742,6 +744,9 @@ fn remove_interned_in_statement_kind(
             let statement = interner.get_statement_kind(id).clone();
             remove_interned_in_statement_kind(interner, statement)
         }
+        StatementKind::Return(value) => {
+            StatementKind::Return(value.map(|value| remove_interned_in_expression(interner, value)))
+        }
         StatementKind::Break | StatementKind::Continue | StatementKind::Error => statement,
     }
 }
@@ -878,6 +883,11 @@ fn remove_interned_in_pattern(interner: &NodeInterner, pattern: Pattern) -> Patt
             vecmap(patterns, |pattern| remove_interned_in_pattern(interner, pattern)),
             span,
         ),
+        Pattern::Array(patterns, rest_index, span) => Pattern::Array(
+            vecmap(patterns, |pattern| remove_interned_in_pattern(interner, pattern)),
+            rest_index,
+            span,
+        ),
         Pattern::Struct(path, patterns, span) => {
             let patterns = vecmap(patterns, |(name, pattern)| {
                 (name, remove_interned_in_pattern(interner, pattern))
@@ -10,6 +10,8 @@ use crate::{
     parser::ParserError,
     Type,
 };
+
+use super::value::Value;
 use acvm::{acir::AcirField, BlackBoxResolutionError, FieldElement};
 use fm::FileId;
 use noirc_errors::{CustomDiagnostic, Location};
@@ -177,6 +179,9 @@ pub enum InterpreterError {
     ContinueNotInLoop {
         location: Location,
     },
+    ReturnNotInFunction {
+        location: Location,
+    },
     BlackBoxError(BlackBoxResolutionError, Location),
     FailedToResolveTraitBound {
         trait_bound: TraitBound,
@@ -237,9 +242,10 @@ pub enum InterpreterError {
     },
 
     // These cases are not errors, they are just used to prevent us from running more code
-    // until the loop can be resumed properly. These cases will never be displayed to users.
+    // until the loop or function can be resumed properly. These cases will never be displayed to users.
     Break,
     Continue,
+    Return(Value),
 }
 
 #[allow(unused)]
@@ -297,6 +303,7 @@ impl InterpreterError {
             | InterpreterError::BlackBoxError(_, location)
             | InterpreterError::BreakNotInLoop { location, .. }
             | InterpreterError::ContinueNotInLoop { location, .. }
+            | InterpreterError::ReturnNotInFunction { location, .. }
             | InterpreterError::TraitDefinitionMustBeAPath { location }
             | InterpreterError::FailedToResolveTraitDefinition { location }
             | InterpreterError::FailedToResolveTraitBound { location, .. }
@@ -317,8 +324,8 @@ impl InterpreterError {
             InterpreterError::NoMatchingImplFound { error, file } => {
                 Location::new(error.span, *file)
             }
-            InterpreterError::Break | InterpreterError::Continue => {
-                panic!("Tried to get the location of Break/Continue error!")
+            InterpreterError::Break | InterpreterError::Continue | InterpreterError::Return(_) => {
+                panic!("Tried to get the location of Break/Continue/Return error!")
             }
         }
     }
@@ -548,6 +555,10 @@ impl<'a> From<&'a InterpreterError> for CustomDiagnostic {
                 let msg = "There is no loop to continue!".into();
                 CustomDiagnostic::simple_error(msg, String::new(), location.span)
             }
+            InterpreterError::ReturnNotInFunction { location } => {
+                let msg = "There is no function to return from!".into();
+                CustomDiagnostic::simple_error(msg, String::new(), location.span)
+            }
             InterpreterError::NoImpl { location } => {
                 let msg = "No impl found due to prior type error".into();
                 CustomDiagnostic::simple_error(msg, String::new(), location.span)
@@ -568,6 +579,7 @@ impl<'a> From<&'a InterpreterError> for CustomDiagnostic {
             InterpreterError::NoMatchingImplFound { error, .. } => error.into(),
             InterpreterError::Break => unreachable!("Uncaught InterpreterError::Break"),
             InterpreterError::Continue => unreachable!("Uncaught InterpreterError::Continue"),
+            InterpreterError::Return(_) => unreachable!("Uncaught InterpreterError::Return"),
             InterpreterError::TraitDefinitionMustBeAPath { location } => {
                 let msg = "Trait definition arguments must be a variable or path".to_string();
                 CustomDiagnostic::simple_error(msg, String::new(), location.span)
@@ -61,6 +61,9 @@ impl HirStatement {
             }),
             HirStatement::Break => StatementKind::Break,
             HirStatement::Continue => StatementKind::Continue,
+            HirStatement::Return(value, _) => {
+                StatementKind::Return(value.map(|value| value.to_display_ast(interner)))
+            }
             HirStatement::Expression(expr) => {
                 StatementKind::Expression(expr.to_display_ast(interner))
             }
@@ -240,6 +243,10 @@ impl HirPattern {
                 let patterns = vecmap(patterns, |pattern| pattern.to_display_ast(interner));
                 Pattern::Tuple(patterns, location.span)
             }
+            HirPattern::Array(patterns, rest_index, location) => {
+                let patterns = vecmap(patterns, |pattern| pattern.to_display_ast(interner));
+                Pattern::Array(patterns, *rest_index, location.span)
+            }
             HirPattern::Struct(typ, patterns, location) => {
                 let patterns = vecmap(patterns, |(name, pattern)| {
                     (name.clone(), pattern.to_display_ast(interner))
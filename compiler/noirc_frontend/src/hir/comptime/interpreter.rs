@@ -55,6 +55,10 @@ pub struct Interpreter<'local, 'interner> {
 
     in_loop: bool,
 
+    /// Whether a `return` would return from a function or closure being called,
+    /// as opposed to e.g. a `comptime` block.
+    in_function: bool,
+
     current_function: Option<FuncId>,
 
     /// Maps each bound generic to each binding it has in the current callstack.
@@ -73,7 +77,8 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
     ) -> Self {
         let bound_generics = Vec::new();
         let in_loop = false;
-        Self { elaborator, crate_id, current_function, bound_generics, in_loop }
+        let in_function = false;
+        Self { elaborator, crate_id, current_function, bound_generics, in_loop, in_function }
     }
 
     pub(crate) fn call_function(
@@ -164,11 +169,19 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
         }
 
         let function_body = self.get_function_body(function, location)?;
-        let result = self.evaluate(function_body)?;
+        let result = self.evaluate(function_body).or_else(Self::catch_return)?;
         self.exit_function(previous_state);
         Ok(result)
     }
 
+    /// Turns a `return` that unwound a function or closure body into the value it returns.
+    fn catch_return(error: InterpreterError) -> IResult<Value> {
+        match error {
+            InterpreterError::Return(value) => Ok(value),
+            other => Err(other),
+        }
+    }
+
     /// Try to retrieve a function's body.
     /// If the function has not yet been resolved this will attempt to lazily resolve it.
     /// Afterwards, if the function's body is still not known or the function is still
@@ -294,7 +307,7 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
             self.define(param.ident.id, arg);
         }
 
-        let result = self.evaluate(closure.body)?;
+        let result = self.evaluate(closure.body).or_else(Self::catch_return)?;
 
         self.exit_function(previous_state);
         Ok(result)
@@ -303,22 +316,27 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
     /// Enters a function, pushing a new scope and resetting any required state.
     /// Returns the previous values of the internal state, to be reset when
     /// `exit_function` is called.
-    pub(super) fn enter_function(&mut self) -> (bool, Vec<HashMap<DefinitionId, Value>>) {
+    pub(super) fn enter_function(&mut self) -> (bool, bool, Vec<HashMap<DefinitionId, Value>>) {
         // Drain every scope except the global scope
         let mut scope = Vec::new();
         if self.elaborator.interner.comptime_scopes.len() > 1 {
             scope = self.elaborator.interner.comptime_scopes.drain(1..).collect();
         }
         self.push_scope();
-        (std::mem::take(&mut self.in_loop), scope)
+        let in_function = std::mem::replace(&mut self.in_function, true);
+        (std::mem::take(&mut self.in_loop), in_function, scope)
     }
 
-    pub(super) fn exit_function(&mut self, mut state: (bool, Vec<HashMap<DefinitionId, Value>>)) {
+    pub(super) fn exit_function(
+        &mut self,
+        mut state: (bool, bool, Vec<HashMap<DefinitionId, Value>>),
+    ) {
         self.in_loop = state.0;
+        self.in_function = state.1;
 
         // Keep only the global scope
         self.elaborator.interner.comptime_scopes.truncate(1);
-        self.elaborator.interner.comptime_scopes.append(&mut state.1);
+        self.elaborator.interner.comptime_scopes.append(&mut state.2);
     }
 
     pub(super) fn push_scope(&mut self) {
@@ -411,6 +429,29 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                     }
                 }
             }
+            HirPattern::Array(patterns, rest_index, _) => match argument {
+                Value::Array(elements, _)
+                    if elements.len() >= patterns.len()
+                        && (rest_index.is_some() || elements.len() == patterns.len()) =>
+                {
+                    // Any elements after a `..` are matched against the end of the array
+                    let rest_index = rest_index.unwrap_or(patterns.len());
+                    let skipped = elements.len() - patterns.len();
+
+                    for (i, pattern) in patterns.iter().enumerate() {
+                        let index = if i < rest_index { i } else { i + skipped };
+                        let element = elements[index].clone();
+                        let element_type = element.get_type().into_owned();
+                        self.define_pattern(pattern, &element_type, element, location)?;
+                    }
+                    Ok(())
+                }
+                value => Err(InterpreterError::TypeMismatch {
+                    expected: typ.clone(),
+                    actual: value.get_type().into_owned(),
+                    location,
+                }),
+            },
             HirPattern::Struct(struct_type, pattern_fields, _) => {
                 self.push_scope();
 
@@ -724,6 +765,13 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                     let value = if is_negative { 0u64.wrapping_sub(value) } else { value };
                     Ok(Value::U64(value))
                 }
+                (Signedness::Unsigned, IntegerBitSize::HundredTwentyEight) => {
+                    let value: u128 = value.try_into_u128().ok_or(
+                        InterpreterError::IntegerOutOfRangeForType { value, typ, location },
+                    )?;
+                    let value = if is_negative { 0u128.wrapping_sub(value) } else { value };
+                    Ok(Value::U128(value))
+                }
                 (Signedness::Signed, IntegerBitSize::One) => {
                     return Err(InterpreterError::TypeUnsupported { typ, location });
                 }
@@ -759,6 +807,24 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                     let value = if is_negative { -value } else { value };
                     Ok(Value::I64(value))
                 }
+                (Signedness::Signed, IntegerBitSize::HundredTwentyEight) => {
+                    // The magnitude of `i128::MIN` doesn't fit in an i128, so negate it as a u128
+                    let value: i128 = value
+                        .try_into_u128()
+                        .and_then(|magnitude| {
+                            if is_negative {
+                                0i128.checked_sub_unsigned(magnitude)
+                            } else {
+                                magnitude.try_into().ok()
+                            }
+                        })
+                        .ok_or(InterpreterError::IntegerOutOfRangeForType {
+                            value,
+                            typ,
+                            location,
+                        })?;
+                    Ok(Value::I128(value))
+                }
             }
         } else if let Type::TypeVariable(variable) = &typ {
             if variable.is_integer_or_field() {
@@ -859,10 +925,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 Value::I16(value) => Ok(Value::I16(-value)),
                 Value::I32(value) => Ok(Value::I32(-value)),
                 Value::I64(value) => Ok(Value::I64(-value)),
+                Value::I128(value) => Ok(Value::I128(-value)),
                 Value::U8(value) => Ok(Value::U8(0 - value)),
                 Value::U16(value) => Ok(Value::U16(0 - value)),
                 Value::U32(value) => Ok(Value::U32(0 - value)),
                 Value::U64(value) => Ok(Value::U64(0 - value)),
+                Value::U128(value) => Ok(Value::U128(0 - value)),
                 value => {
                     let location = self.elaborator.interner.expr_location(&id);
                     let operator = "minus";
@@ -876,10 +944,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 Value::I16(value) => Ok(Value::I16(!value)),
                 Value::I32(value) => Ok(Value::I32(!value)),
                 Value::I64(value) => Ok(Value::I64(!value)),
+                Value::I128(value) => Ok(Value::I128(!value)),
                 Value::U8(value) => Ok(Value::U8(!value)),
                 Value::U16(value) => Ok(Value::U16(!value)),
                 Value::U32(value) => Ok(Value::U32(!value)),
                 Value::U64(value) => Ok(Value::U64(!value)),
+                Value::U128(value) => Ok(Value::U128(!value)),
                 value => {
                     let location = self.elaborator.interner.expr_location(&id);
                     let typ = value.get_type().into_owned();
@@ -940,6 +1010,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I64(lhs), Value::I64(rhs)) => {
                     Ok(Value::I64(lhs.checked_add(rhs).ok_or(error("+"))?))
                 }
+                (Value::I128(lhs), Value::I128(rhs)) => {
+                    Ok(Value::I128(lhs.checked_add(rhs).ok_or(error("+"))?))
+                }
                 (Value::U8(lhs), Value::U8(rhs)) => {
                     Ok(Value::U8(lhs.checked_add(rhs).ok_or(error("+"))?))
                 }
@@ -952,6 +1025,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::U64(lhs), Value::U64(rhs)) => {
                     Ok(Value::U64(lhs.checked_add(rhs).ok_or(error("+"))?))
                 }
+                (Value::U128(lhs), Value::U128(rhs)) => {
+                    Ok(Value::U128(lhs.checked_add(rhs).ok_or(error("+"))?))
+                }
                 (lhs, rhs) => Err(error("+")),
             },
             BinaryOpKind::Subtract => match (lhs_value.clone(), rhs_value.clone()) {
@@ -968,6 +1044,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I64(lhs), Value::I64(rhs)) => {
                     Ok(Value::I64(lhs.checked_sub(rhs).ok_or(error("-"))?))
                 }
+                (Value::I128(lhs), Value::I128(rhs)) => {
+                    Ok(Value::I128(lhs.checked_sub(rhs).ok_or(error("-"))?))
+                }
                 (Value::U8(lhs), Value::U8(rhs)) => {
                     Ok(Value::U8(lhs.checked_sub(rhs).ok_or(error("-"))?))
                 }
@@ -980,6 +1059,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::U64(lhs), Value::U64(rhs)) => {
                     Ok(Value::U64(lhs.checked_sub(rhs).ok_or(error("-"))?))
                 }
+                (Value::U128(lhs), Value::U128(rhs)) => {
+                    Ok(Value::U128(lhs.checked_sub(rhs).ok_or(error("-"))?))
+                }
                 (lhs, rhs) => Err(error("-")),
             },
             BinaryOpKind::Multiply => match (lhs_value.clone(), rhs_value.clone()) {
@@ -996,6 +1078,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I64(lhs), Value::I64(rhs)) => {
                     Ok(Value::I64(lhs.checked_mul(rhs).ok_or(error("*"))?))
                 }
+                (Value::I128(lhs), Value::I128(rhs)) => {
+                    Ok(Value::I128(lhs.checked_mul(rhs).ok_or(error("*"))?))
+                }
                 (Value::U8(lhs), Value::U8(rhs)) => {
                     Ok(Value::U8(lhs.checked_mul(rhs).ok_or(error("*"))?))
                 }
@@ -1008,6 +1093,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::U64(lhs), Value::U64(rhs)) => {
                     Ok(Value::U64(lhs.checked_mul(rhs).ok_or(error("*"))?))
                 }
+                (Value::U128(lhs), Value::U128(rhs)) => {
+                    Ok(Value::U128(lhs.checked_mul(rhs).ok_or(error("*"))?))
+                }
                 (lhs, rhs) => Err(error("*")),
             },
             BinaryOpKind::Divide => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1024,6 +1112,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I64(lhs), Value::I64(rhs)) => {
                     Ok(Value::I64(lhs.checked_div(rhs).ok_or(error("/"))?))
                 }
+                (Value::I128(lhs), Value::I128(rhs)) => {
+                    Ok(Value::I128(lhs.checked_div(rhs).ok_or(error("/"))?))
+                }
                 (Value::U8(lhs), Value::U8(rhs)) => {
                     Ok(Value::U8(lhs.checked_div(rhs).ok_or(error("/"))?))
                 }
@@ -1036,6 +1127,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::U64(lhs), Value::U64(rhs)) => {
                     Ok(Value::U64(lhs.checked_div(rhs).ok_or(error("/"))?))
                 }
+                (Value::U128(lhs), Value::U128(rhs)) => {
+                    Ok(Value::U128(lhs.checked_div(rhs).ok_or(error("/"))?))
+                }
                 (lhs, rhs) => Err(error("/")),
             },
             BinaryOpKind::Equal => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1044,10 +1138,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I16(lhs), Value::I16(rhs)) => Ok(Value::Bool(lhs == rhs)),
                 (Value::I32(lhs), Value::I32(rhs)) => Ok(Value::Bool(lhs == rhs)),
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::Bool(lhs == rhs)),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::Bool(lhs == rhs)),
                 (Value::U8(lhs), Value::U8(rhs)) => Ok(Value::Bool(lhs == rhs)),
                 (Value::U16(lhs), Value::U16(rhs)) => Ok(Value::Bool(lhs == rhs)),
                 (Value::U32(lhs), Value::U32(rhs)) => Ok(Value::Bool(lhs == rhs)),
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::Bool(lhs == rhs)),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::Bool(lhs == rhs)),
                 (Value::Bool(lhs), Value::Bool(rhs)) => Ok(Value::Bool(lhs == rhs)),
                 (lhs, rhs) => Err(error("==")),
             },
@@ -1057,10 +1153,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I16(lhs), Value::I16(rhs)) => Ok(Value::Bool(lhs != rhs)),
                 (Value::I32(lhs), Value::I32(rhs)) => Ok(Value::Bool(lhs != rhs)),
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::Bool(lhs != rhs)),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::Bool(lhs != rhs)),
                 (Value::U8(lhs), Value::U8(rhs)) => Ok(Value::Bool(lhs != rhs)),
                 (Value::U16(lhs), Value::U16(rhs)) => Ok(Value::Bool(lhs != rhs)),
                 (Value::U32(lhs), Value::U32(rhs)) => Ok(Value::Bool(lhs != rhs)),
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::Bool(lhs != rhs)),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::Bool(lhs != rhs)),
                 (Value::Bool(lhs), Value::Bool(rhs)) => Ok(Value::Bool(lhs != rhs)),
                 (lhs, rhs) => Err(error("!=")),
             },
@@ -1070,10 +1168,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I16(lhs), Value::I16(rhs)) => Ok(Value::Bool(lhs < rhs)),
                 (Value::I32(lhs), Value::I32(rhs)) => Ok(Value::Bool(lhs < rhs)),
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::Bool(lhs < rhs)),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::Bool(lhs < rhs)),
                 (Value::U8(lhs), Value::U8(rhs)) => Ok(Value::Bool(lhs < rhs)),
                 (Value::U16(lhs), Value::U16(rhs)) => Ok(Value::Bool(lhs < rhs)),
                 (Value::U32(lhs), Value::U32(rhs)) => Ok(Value::Bool(lhs < rhs)),
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::Bool(lhs < rhs)),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::Bool(lhs < rhs)),
                 (lhs, rhs) => Err(error("<")),
             },
             BinaryOpKind::LessEqual => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1082,10 +1182,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I16(lhs), Value::I16(rhs)) => Ok(Value::Bool(lhs <= rhs)),
                 (Value::I32(lhs), Value::I32(rhs)) => Ok(Value::Bool(lhs <= rhs)),
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::Bool(lhs <= rhs)),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::Bool(lhs <= rhs)),
                 (Value::U8(lhs), Value::U8(rhs)) => Ok(Value::Bool(lhs <= rhs)),
                 (Value::U16(lhs), Value::U16(rhs)) => Ok(Value::Bool(lhs <= rhs)),
                 (Value::U32(lhs), Value::U32(rhs)) => Ok(Value::Bool(lhs <= rhs)),
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::Bool(lhs <= rhs)),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::Bool(lhs <= rhs)),
                 (lhs, rhs) => Err(error("<=")),
             },
             BinaryOpKind::Greater => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1094,10 +1196,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I16(lhs), Value::I16(rhs)) => Ok(Value::Bool(lhs > rhs)),
                 (Value::I32(lhs), Value::I32(rhs)) => Ok(Value::Bool(lhs > rhs)),
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::Bool(lhs > rhs)),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::Bool(lhs > rhs)),
                 (Value::U8(lhs), Value::U8(rhs)) => Ok(Value::Bool(lhs > rhs)),
                 (Value::U16(lhs), Value::U16(rhs)) => Ok(Value::Bool(lhs > rhs)),
                 (Value::U32(lhs), Value::U32(rhs)) => Ok(Value::Bool(lhs > rhs)),
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::Bool(lhs > rhs)),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::Bool(lhs > rhs)),
                 (lhs, rhs) => Err(error(">")),
             },
             BinaryOpKind::GreaterEqual => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1106,10 +1210,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I16(lhs), Value::I16(rhs)) => Ok(Value::Bool(lhs >= rhs)),
                 (Value::I32(lhs), Value::I32(rhs)) => Ok(Value::Bool(lhs >= rhs)),
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::Bool(lhs >= rhs)),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::Bool(lhs >= rhs)),
                 (Value::U8(lhs), Value::U8(rhs)) => Ok(Value::Bool(lhs >= rhs)),
                 (Value::U16(lhs), Value::U16(rhs)) => Ok(Value::Bool(lhs >= rhs)),
                 (Value::U32(lhs), Value::U32(rhs)) => Ok(Value::Bool(lhs >= rhs)),
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::Bool(lhs >= rhs)),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::Bool(lhs >= rhs)),
                 (lhs, rhs) => Err(error(">=")),
             },
             BinaryOpKind::And => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1118,10 +1224,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I16(lhs), Value::I16(rhs)) => Ok(Value::I16(lhs & rhs)),
                 (Value::I32(lhs), Value::I32(rhs)) => Ok(Value::I32(lhs & rhs)),
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::I64(lhs & rhs)),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::I128(lhs & rhs)),
                 (Value::U8(lhs), Value::U8(rhs)) => Ok(Value::U8(lhs & rhs)),
                 (Value::U16(lhs), Value::U16(rhs)) => Ok(Value::U16(lhs & rhs)),
                 (Value::U32(lhs), Value::U32(rhs)) => Ok(Value::U32(lhs & rhs)),
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::U64(lhs & rhs)),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::U128(lhs & rhs)),
                 (lhs, rhs) => Err(error("&")),
             },
             BinaryOpKind::Or => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1130,10 +1238,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I16(lhs), Value::I16(rhs)) => Ok(Value::I16(lhs | rhs)),
                 (Value::I32(lhs), Value::I32(rhs)) => Ok(Value::I32(lhs | rhs)),
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::I64(lhs | rhs)),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::I128(lhs | rhs)),
                 (Value::U8(lhs), Value::U8(rhs)) => Ok(Value::U8(lhs | rhs)),
                 (Value::U16(lhs), Value::U16(rhs)) => Ok(Value::U16(lhs | rhs)),
                 (Value::U32(lhs), Value::U32(rhs)) => Ok(Value::U32(lhs | rhs)),
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::U64(lhs | rhs)),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::U128(lhs | rhs)),
                 (lhs, rhs) => Err(error("|")),
             },
             BinaryOpKind::Xor => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1142,10 +1252,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I16(lhs), Value::I16(rhs)) => Ok(Value::I16(lhs ^ rhs)),
                 (Value::I32(lhs), Value::I32(rhs)) => Ok(Value::I32(lhs ^ rhs)),
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::I64(lhs ^ rhs)),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::I128(lhs ^ rhs)),
                 (Value::U8(lhs), Value::U8(rhs)) => Ok(Value::U8(lhs ^ rhs)),
                 (Value::U16(lhs), Value::U16(rhs)) => Ok(Value::U16(lhs ^ rhs)),
                 (Value::U32(lhs), Value::U32(rhs)) => Ok(Value::U32(lhs ^ rhs)),
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::U64(lhs ^ rhs)),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::U128(lhs ^ rhs)),
                 (lhs, rhs) => Err(error("^")),
             },
             BinaryOpKind::ShiftRight => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1161,6 +1273,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::I64(
                     lhs.checked_shr(rhs.try_into().map_err(|_| error(">>"))?).ok_or(error(">>"))?,
                 )),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::I128(
+                    lhs.checked_shr(rhs.try_into().map_err(|_| error(">>"))?).ok_or(error(">>"))?,
+                )),
                 (Value::U8(lhs), Value::U8(rhs)) => {
                     Ok(Value::U8(lhs.checked_shr(rhs.into()).ok_or(error(">>"))?))
                 }
@@ -1173,6 +1288,15 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::U64(
                     lhs.checked_shr(rhs.try_into().map_err(|_| error(">>"))?).ok_or(error(">>"))?,
                 )),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::U128(
+                    lhs.checked_shr(rhs.try_into().map_err(|_| error(">>"))?).ok_or(error(">>"))?,
+                )),
+                (Value::I128(lhs), Value::U8(rhs)) => {
+                    Ok(Value::I128(lhs.checked_shr(rhs.into()).ok_or(error(">>"))?))
+                }
+                (Value::U128(lhs), Value::U8(rhs)) => {
+                    Ok(Value::U128(lhs.checked_shr(rhs.into()).ok_or(error(">>"))?))
+                }
                 (lhs, rhs) => Err(error(">>")),
             },
             BinaryOpKind::ShiftLeft => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1188,6 +1312,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I64(lhs), Value::I64(rhs)) => Ok(Value::I64(
                     lhs.checked_shl(rhs.try_into().map_err(|_| error("<<"))?).ok_or(error("<<"))?,
                 )),
+                (Value::I128(lhs), Value::I128(rhs)) => Ok(Value::I128(
+                    lhs.checked_shl(rhs.try_into().map_err(|_| error("<<"))?).ok_or(error("<<"))?,
+                )),
                 (Value::U8(lhs), Value::U8(rhs)) => {
                     Ok(Value::U8(lhs.checked_shl(rhs.into()).ok_or(error("<<"))?))
                 }
@@ -1200,6 +1327,15 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::U64(lhs), Value::U64(rhs)) => Ok(Value::U64(
                     lhs.checked_shl(rhs.try_into().map_err(|_| error("<<"))?).ok_or(error("<<"))?,
                 )),
+                (Value::U128(lhs), Value::U128(rhs)) => Ok(Value::U128(
+                    lhs.checked_shl(rhs.try_into().map_err(|_| error("<<"))?).ok_or(error("<<"))?,
+                )),
+                (Value::I128(lhs), Value::U8(rhs)) => {
+                    Ok(Value::I128(lhs.checked_shl(rhs.into()).ok_or(error("<<"))?))
+                }
+                (Value::U128(lhs), Value::U8(rhs)) => {
+                    Ok(Value::U128(lhs.checked_shl(rhs.into()).ok_or(error("<<"))?))
+                }
                 (lhs, rhs) => Err(error("<<")),
             },
             BinaryOpKind::Modulo => match (lhs_value.clone(), rhs_value.clone()) {
@@ -1215,6 +1351,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::I64(lhs), Value::I64(rhs)) => {
                     Ok(Value::I64(lhs.checked_rem(rhs).ok_or(error("%"))?))
                 }
+                (Value::I128(lhs), Value::I128(rhs)) => {
+                    Ok(Value::I128(lhs.checked_rem(rhs).ok_or(error("%"))?))
+                }
                 (Value::U8(lhs), Value::U8(rhs)) => {
                     Ok(Value::U8(lhs.checked_rem(rhs).ok_or(error("%"))?))
                 }
@@ -1227,6 +1366,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Value::U64(lhs), Value::U64(rhs)) => {
                     Ok(Value::U64(lhs.checked_rem(rhs).ok_or(error("%"))?))
                 }
+                (Value::U128(lhs), Value::U128(rhs)) => {
+                    Ok(Value::U128(lhs.checked_rem(rhs).ok_or(error("%"))?))
+                }
                 (lhs, rhs) => Err(error("%")),
             },
         }
@@ -1347,10 +1489,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
             Value::I16(value) => value as usize,
             Value::I32(value) => value as usize,
             Value::I64(value) => value as usize,
+            Value::I128(value) => value as usize,
             Value::U8(value) => value as usize,
             Value::U16(value) => value as usize,
             Value::U32(value) => value as usize,
             Value::U64(value) => value as usize,
+            Value::U128(value) => value as usize,
             value => {
                 let typ = value.get_type().into_owned();
                 return Err(InterpreterError::NonIntegerUsedAsIndex { typ, location });
@@ -1510,14 +1654,11 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
     ) -> IResult<Value> {
         macro_rules! signed_int_to_field {
             ($x:expr) => {{
-                // Need to convert the signed integer to an i128 before
-                // we negate it to preserve the MIN value.
-                let mut value = $x as i128;
+                // Need to take the unsigned absolute value of the signed integer
+                // to preserve the MIN value.
+                let value = $x as i128;
                 let is_negative = value < 0;
-                if is_negative {
-                    value = -value;
-                }
-                ((value as u128).into(), is_negative)
+                (value.unsigned_abs().into(), is_negative)
             }};
         }
 
@@ -1527,10 +1668,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
             Value::U16(value) => ((value as u128).into(), false),
             Value::U32(value) => ((value as u128).into(), false),
             Value::U64(value) => ((value as u128).into(), false),
+            Value::U128(value) => (value.into(), false),
             Value::I8(value) => signed_int_to_field!(value),
             Value::I16(value) => signed_int_to_field!(value),
             Value::I32(value) => signed_int_to_field!(value),
             Value::I64(value) => signed_int_to_field!(value),
+            Value::I128(value) => signed_int_to_field!(value),
             Value::Bool(value) => {
                 (if value { FieldElement::one() } else { FieldElement::zero() }, false)
             }
@@ -1574,6 +1717,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Signedness::Unsigned, IntegerBitSize::SixtyFour) => {
                     cast_to_int!(lhs, to_u128, u64, U64)
                 }
+                (Signedness::Unsigned, IntegerBitSize::HundredTwentyEight) => {
+                    cast_to_int!(lhs, to_u128, u128, U128)
+                }
                 (Signedness::Signed, IntegerBitSize::One) => {
                     let location = interner.expr_location(&id);
                     Err(InterpreterError::TypeUnsupported { typ: cast.r#type.clone(), location })
@@ -1588,6 +1734,9 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 (Signedness::Signed, IntegerBitSize::SixtyFour) => {
                     cast_to_int!(lhs, to_i128, i64, I64)
                 }
+                (Signedness::Signed, IntegerBitSize::HundredTwentyEight) => {
+                    cast_to_int!(lhs, to_i128, i128, I128)
+                }
             },
             Type::Bool => Ok(Value::Bool(!lhs.is_zero() || lhs_is_negative)),
             typ => {
@@ -1656,6 +1805,7 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
             HirStatement::For(for_) => self.evaluate_for(for_),
             HirStatement::Break => self.evaluate_break(statement),
             HirStatement::Continue => self.evaluate_continue(statement),
+            HirStatement::Return(value, _) => self.evaluate_return(value, statement),
             HirStatement::Expression(expression) => self.evaluate(expression),
             HirStatement::Comptime(statement) => self.evaluate_comptime(statement),
             HirStatement::Semi(expression) => {
@@ -1811,10 +1961,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 Value::I16(value) => Ok((value as i128, |i| Value::I16(i as i16))),
                 Value::I32(value) => Ok((value as i128, |i| Value::I32(i as i32))),
                 Value::I64(value) => Ok((value as i128, |i| Value::I64(i as i64))),
+                Value::I128(value) => Ok((value, Value::I128)),
                 Value::U8(value) => Ok((value as i128, |i| Value::U8(i as u8))),
                 Value::U16(value) => Ok((value as i128, |i| Value::U16(i as u16))),
                 Value::U32(value) => Ok((value as i128, |i| Value::U32(i as u32))),
                 Value::U64(value) => Ok((value as i128, |i| Value::U64(i as u64))),
+                Value::U128(value) => Ok((value as i128, |i| Value::U128(i as u128))),
                 value => {
                     let location = this.elaborator.interner.expr_location(&expr);
                     let typ = value.get_type().into_owned();
@@ -1863,6 +2015,18 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
         }
     }
 
+    fn evaluate_return(&mut self, value: Option<ExprId>, id: StmtId) -> IResult<Value> {
+        if !self.in_function {
+            let location = self.elaborator.interner.statement_location(id);
+            return Err(InterpreterError::ReturnNotInFunction { location });
+        }
+        let value = match value {
+            Some(value) => self.evaluate(value)?,
+            None => Value::Unit,
+        };
+        Err(InterpreterError::Return(value))
+    }
+
     pub(super) fn evaluate_comptime(&mut self, statement: StmtId) -> IResult<Value> {
         self.evaluate_statement(statement)
     }
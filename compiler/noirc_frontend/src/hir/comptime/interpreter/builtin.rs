@@ -1291,11 +1291,13 @@ fn zeroed(return_type: Type, span: Span) -> IResult<Value> {
             (Signedness::Unsigned, IntegerBitSize::Sixteen) => Ok(Value::U16(0)),
             (Signedness::Unsigned, IntegerBitSize::ThirtyTwo) => Ok(Value::U32(0)),
             (Signedness::Unsigned, IntegerBitSize::SixtyFour) => Ok(Value::U64(0)),
+            (Signedness::Unsigned, IntegerBitSize::HundredTwentyEight) => Ok(Value::U128(0)),
             (Signedness::Signed, IntegerBitSize::One) => Ok(Value::I8(0)),
             (Signedness::Signed, IntegerBitSize::Eight) => Ok(Value::I8(0)),
             (Signedness::Signed, IntegerBitSize::Sixteen) => Ok(Value::I16(0)),
             (Signedness::Signed, IntegerBitSize::ThirtyTwo) => Ok(Value::I32(0)),
             (Signedness::Signed, IntegerBitSize::SixtyFour) => Ok(Value::I64(0)),
+            (Signedness::Signed, IntegerBitSize::HundredTwentyEight) => Ok(Value::I128(0)),
         },
         Type::Bool => Ok(Value::Bool(false)),
         Type::String(length_type) => {
@@ -343,6 +343,26 @@ fn gather_hir_pattern_tokens(
             }
             tokens.push(Token::RightParen);
         }
+        HirPattern::Array(patterns, rest_index, _) => {
+            tokens.push(Token::LeftBracket);
+            for (index, pattern) in patterns.iter().enumerate() {
+                if index != 0 {
+                    tokens.push(Token::Comma);
+                }
+                if *rest_index == Some(index) {
+                    tokens.push(Token::DoubleDot);
+                    tokens.push(Token::Comma);
+                }
+                gather_hir_pattern_tokens(interner, pattern, tokens);
+            }
+            if *rest_index == Some(patterns.len()) {
+                if !patterns.is_empty() {
+                    tokens.push(Token::Comma);
+                }
+                tokens.push(Token::DoubleDot);
+            }
+            tokens.push(Token::RightBracket);
+        }
         HirPattern::Struct(typ, fields, _) => {
             let Type::Struct(struct_type, _) = typ.follow_bindings() else {
                 panic!("Expected type to be a struct");
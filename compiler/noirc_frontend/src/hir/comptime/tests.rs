@@ -106,6 +106,26 @@ fn mutation_works() {
     assert_eq!(result, Value::I8(4));
 }
 
+#[test]
+fn u128_arithmetic_works() {
+    let program = "comptime fn main() -> pub u128 {
+        let x: u128 = 340282366920938463463374607431768211455;
+        x - (x >> 64) * 18446744073709551616
+    }";
+    let result = interpret(program);
+    assert_eq!(result, Value::U128(u64::MAX as u128));
+}
+
+#[test]
+fn i128_min_works() {
+    let program = "comptime fn main() -> pub i128 {
+        let x: i128 = -170141183460469231731687303715884105728;
+        x + 1
+    }";
+    let result = interpret(program);
+    assert_eq!(result, Value::I128(i128::MIN + 1));
+}
+
 #[test]
 fn mutating_references() {
     let program = "comptime fn main() -> pub i32 {
@@ -151,6 +171,22 @@ fn mutating_arrays() {
     assert_eq!(result, Value::U8(22));
 }
 
+#[test]
+fn array_patterns() {
+    let program = "comptime fn main() -> pub u8 {
+        let [a, .., b] = [1, 2, 3, 4];
+        let [c, mut d] = [5, 6];
+        d += 1;
+        a + b + c + d + sum([8, 9])
+    }
+
+    comptime fn sum([x, y]: [u8; 2]) -> u8 {
+        x + y
+    }";
+    let result = interpret(program);
+    assert_eq!(result, Value::U8(34));
+}
+
 #[test]
 fn mutate_in_new_scope() {
     let program = "comptime fn main() -> pub u8 {
@@ -38,11 +38,13 @@ pub enum Value {
     I16(i16),
     I32(i32),
     I64(i64),
+    I128(i128),
     U1(bool),
     U8(u8),
     U16(u16),
     U32(u32),
     U64(u64),
+    U128(u128),
     String(Rc<String>),
     FormatString(Rc<String>, Type),
     CtString(Rc<String>),
@@ -114,11 +116,15 @@ impl Value {
             Value::I16(_) => Type::Integer(Signedness::Signed, IntegerBitSize::Sixteen),
             Value::I32(_) => Type::Integer(Signedness::Signed, IntegerBitSize::ThirtyTwo),
             Value::I64(_) => Type::Integer(Signedness::Signed, IntegerBitSize::SixtyFour),
+            Value::I128(_) => Type::Integer(Signedness::Signed, IntegerBitSize::HundredTwentyEight),
             Value::U1(_) => Type::Integer(Signedness::Unsigned, IntegerBitSize::One),
             Value::U8(_) => Type::Integer(Signedness::Unsigned, IntegerBitSize::Eight),
             Value::U16(_) => Type::Integer(Signedness::Unsigned, IntegerBitSize::Sixteen),
             Value::U32(_) => Type::Integer(Signedness::Unsigned, IntegerBitSize::ThirtyTwo),
             Value::U64(_) => Type::Integer(Signedness::Unsigned, IntegerBitSize::SixtyFour),
+            Value::U128(_) => {
+                Type::Integer(Signedness::Unsigned, IntegerBitSize::HundredTwentyEight)
+            }
             Value::String(value) => {
                 let length = Type::Constant(value.len().into(), Kind::u32());
                 Type::String(Box::new(length))
@@ -189,6 +195,11 @@ impl Value {
                 let value = (value as u128).into();
                 ExpressionKind::Literal(Literal::Integer(value, negative))
             }
+            Value::I128(value) => {
+                let negative = value < 0;
+                let value = value.unsigned_abs().into();
+                ExpressionKind::Literal(Literal::Integer(value, negative))
+            }
             Value::U1(value) => {
                 ExpressionKind::Literal(Literal::Integer((value as u128).into(), false))
             }
@@ -204,6 +215,7 @@ impl Value {
             Value::U64(value) => {
                 ExpressionKind::Literal(Literal::Integer((value as u128).into(), false))
             }
+            Value::U128(value) => ExpressionKind::Literal(Literal::Integer(value.into(), false)),
             Value::String(value) | Value::CtString(value) => {
                 ExpressionKind::Literal(Literal::Str(unwrap_rc(value)))
             }
@@ -336,6 +348,11 @@ impl Value {
                 let value = (value as u128).into();
                 HirExpression::Literal(HirLiteral::Integer(value, negative))
             }
+            Value::I128(value) => {
+                let negative = value < 0;
+                let value = value.unsigned_abs().into();
+                HirExpression::Literal(HirLiteral::Integer(value, negative))
+            }
             Value::U1(value) => {
                 HirExpression::Literal(HirLiteral::Integer((value as u128).into(), false))
             }
@@ -351,6 +368,7 @@ impl Value {
             Value::U64(value) => {
                 HirExpression::Literal(HirLiteral::Integer((value as u128).into(), false))
             }
+            Value::U128(value) => HirExpression::Literal(HirLiteral::Integer(value.into(), false)),
             Value::String(value) | Value::CtString(value) => {
                 HirExpression::Literal(HirLiteral::Str(unwrap_rc(value)))
             }
@@ -468,6 +486,7 @@ impl Value {
             Value::U16(value) => Token::Int((value as u128).into()),
             Value::U32(value) => Token::Int((value as u128).into()),
             Value::U64(value) => Token::Int((value as u128).into()),
+            Value::U128(value) => Token::Int(value.into()),
             Value::I8(value) => {
                 if value < 0 {
                     return Ok(vec![Token::Minus, Token::Int((-value as u128).into())]);
@@ -496,6 +515,13 @@ impl Value {
                     Token::Int((value as u128).into())
                 }
             }
+            Value::I128(value) => {
+                if value < 0 {
+                    return Ok(vec![Token::Minus, Token::Int(value.unsigned_abs().into())]);
+                } else {
+                    Token::Int((value as u128).into())
+                }
+            }
             Value::Field(value) => Token::Int(value),
             other => Token::UnquoteMarker(other.into_hir_expression(interner, location)?),
         };
@@ -511,10 +537,12 @@ impl Value {
             Self::I16(value) => (*value >= 0).then_some(*value as u128),
             Self::I32(value) => (*value >= 0).then_some(*value as u128),
             Self::I64(value) => (*value >= 0).then_some(*value as u128),
+            Self::I128(value) => (*value >= 0).then_some(*value as u128),
             Self::U8(value) => Some(*value as u128),
             Self::U16(value) => Some(*value as u128),
             Self::U32(value) => Some(*value as u128),
             Self::U64(value) => Some(*value as u128),
+            Self::U128(value) => Some(*value),
             _ => None,
         }
     }
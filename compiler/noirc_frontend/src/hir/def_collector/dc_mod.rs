@@ -1,4 +1,5 @@
 use core::str;
+use std::collections::BTreeSet;
 use std::path::Path;
 use std::rc::Rc;
 use std::vec;
@@ -52,12 +53,14 @@ struct ModCollector<'a> {
 /// This performs the entirety of the definition collection phase of the name resolution pass.
 pub fn collect_defs(
     def_collector: &mut DefCollector,
-    ast: SortedModule,
+    mut ast: SortedModule,
     file_id: FileId,
     module_id: LocalModuleId,
     crate_id: CrateId,
     context: &mut Context,
 ) -> Vec<(CompilationError, FileId)> {
+    remove_cfg_disabled_items(&mut ast, &context.crate_graph[crate_id].enabled_features);
+
     let mut collector = ModCollector { def_collector, file_id, module_id };
     let mut errors: Vec<(CompilationError, FileId)> = vec![];
 
@@ -113,6 +116,44 @@ pub fn collect_defs(
     errors
 }
 
+/// Drops every item in the module annotated with a `#[cfg(...)]` attribute whose
+/// predicate doesn't hold, so that it's as if the item was never written.
+fn remove_cfg_disabled_items(ast: &mut SortedModule, enabled_features: &BTreeSet<String>) {
+    let is_enabled = |attributes: &[SecondaryAttribute]| {
+        attributes.iter().all(|attribute| attribute.is_cfg_enabled(enabled_features))
+    };
+
+    ast.functions.retain(|function| is_enabled(function.item.secondary_attributes()));
+    ast.types.retain(|typ| is_enabled(&typ.item.attributes));
+    ast.traits.retain(|r#trait| is_enabled(&r#trait.item.attributes));
+    ast.globals.retain(|(global, _)| is_enabled(&global.item.attributes));
+    ast.module_decls.retain(|decl| is_enabled(&decl.item.outer_attributes));
+    ast.submodules.retain(|submodule| {
+        is_enabled(&submodule.item.outer_attributes)
+            && is_enabled(&submodule.item.contents.inner_attributes)
+    });
+
+    for r#trait in &mut ast.traits {
+        r#trait.item.items.retain(|item| match &item.item {
+            TraitItem::Function { attributes, .. }
+            | TraitItem::Constant { attributes, .. }
+            | TraitItem::Type { attributes, .. } => is_enabled(attributes),
+        });
+    }
+
+    for r#impl in &mut ast.impls {
+        r#impl.methods.retain(|(method, _)| is_enabled(method.item.secondary_attributes()));
+    }
+
+    for trait_impl in &mut ast.trait_impls {
+        trait_impl.items.retain(|item| match &item.item.kind {
+            TraitImplItemKind::Function(function) => is_enabled(function.secondary_attributes()),
+            TraitImplItemKind::Constant(_, _, _, attributes)
+            | TraitImplItemKind::Type { attributes, .. } => is_enabled(attributes),
+        });
+    }
+}
+
 impl<'a> ModCollector<'a> {
     fn collect_attributes(
         &mut self,
@@ -216,6 +257,13 @@ impl<'a> ModCollector<'a> {
                     errors.push((error.into(), self.file_id));
                 }
 
+                if noir_function.def.attributes.is_bench_function() {
+                    let error = DefCollectorErrorKind::BenchOnAssociatedFunction {
+                        span: noir_function.name_ident().span(),
+                    };
+                    errors.push((error.into(), self.file_id));
+                }
+
                 let location = Location::new(noir_function.def.span, self.file_id);
                 context.def_interner.push_function(*func_id, &noir_function.def, module, location);
             }
@@ -473,6 +521,7 @@ impl<'a> ModCollector<'a> {
                         is_unconstrained,
                         visibility: _,
                         is_comptime,
+                        attributes: _,
                     } => {
                         let func_id = context.def_interner.push_empty_fn();
                         method_ids.insert(name.to_string(), func_id);
@@ -492,6 +541,10 @@ impl<'a> ModCollector<'a> {
                         context
                             .def_interner
                             .push_function_definition(func_id, modifiers, trait_id.0, location);
+                        context.def_interner.add_definition_location(
+                            ReferenceId::Function(func_id),
+                            Some(trait_id.0),
+                        );
 
                         if !trait_item.doc_comments.is_empty() {
                             context.def_interner.set_doc_comments(
@@ -532,7 +585,7 @@ impl<'a> ModCollector<'a> {
                             }
                         }
                     }
-                    TraitItem::Constant { name, typ, default_value: _ } => {
+                    TraitItem::Constant { name, typ, default_value: _, attributes: _ } => {
                         let global_id = context.def_interner.push_empty_global(
                             name.clone(),
                             trait_id.0.local_id,
@@ -567,7 +620,7 @@ impl<'a> ModCollector<'a> {
                             });
                         }
                     }
-                    TraitItem::Type { name } => {
+                    TraitItem::Type { name, attributes: _ } => {
                         if let Err((first_def, second_def)) = self.def_collector.def_map.modules
                             [trait_id.0.local_id.0]
                             .declare_type_alias(
@@ -938,7 +991,8 @@ pub fn collect_function(
 
     let module_data = &mut def_map.modules[module.local_id.0];
 
-    let is_test = function.def.attributes.is_test_function();
+    let is_test_or_bench =
+        function.def.attributes.is_test_function() || function.def.attributes.is_bench_function();
     let is_entry_point_function = if module_data.is_contract {
         function.attributes().is_contract_entry_point()
     } else {
@@ -950,11 +1004,11 @@ pub fn collect_function(
     let visibility = function.def.visibility;
     let location = Location::new(function.span(), file);
     interner.push_function(func_id, &function.def, module, location);
-    if interner.is_in_lsp_mode() && !function.def.is_test() {
+    if interner.is_in_lsp_mode() && !function.def.is_test() && !function.def.is_bench() {
         interner.register_function(func_id, &function.def);
     }
 
-    if !is_test && !is_entry_point_function {
+    if !is_test_or_bench && !is_entry_point_function {
         let item = UnusedItem::Function(func_id);
         usage_tracker.add_unused_item(module, name.clone(), item, visibility);
     }
@@ -1088,6 +1142,14 @@ pub fn collect_impl(
             continue;
         }
 
+        if method.def.attributes.is_bench_function() {
+            let error = DefCollectorErrorKind::BenchOnAssociatedFunction {
+                span: method.name_ident().span(),
+            };
+            errors.push((error.into(), file_id));
+            continue;
+        }
+
         let func_id = interner.push_empty_fn();
         method.def.where_clause.extend(r#impl.where_clause.clone());
         let location = Location::new(method.span(), file_id);
@@ -1215,10 +1277,10 @@ pub(crate) fn collect_trait_impl_items(
                 interner.set_doc_comments(ReferenceId::Function(func_id), item.doc_comments);
                 unresolved_functions.push_fn(local_id, func_id, impl_method);
             }
-            TraitImplItemKind::Constant(name, typ, expr) => {
+            TraitImplItemKind::Constant(name, typ, expr, _attributes) => {
                 associated_constants.push((name, typ, expr));
             }
-            TraitImplItemKind::Type { name, alias } => {
+            TraitImplItemKind::Type { name, alias, attributes: _ } => {
                 associated_types.push((name, alias));
             }
         }
@@ -84,6 +84,8 @@ pub enum DefCollectorErrorKind {
     UnsupportedNumericGenericType(#[from] UnsupportedNumericGenericType),
     #[error("The `#[test]` attribute may only be used on a non-associated function")]
     TestOnAssociatedFunction { span: Span },
+    #[error("The `#[bench]` attribute may only be used on a non-associated function")]
+    BenchOnAssociatedFunction { span: Span },
 }
 
 impl DefCollectorErrorKind {
@@ -298,6 +300,11 @@ impl<'a> From<&'a DefCollectorErrorKind> for Diagnostic {
                 String::new(),
                 *span,
             ),
+            DefCollectorErrorKind::BenchOnAssociatedFunction { span } => Diagnostic::simple_error(
+                "The `#[bench]` attribute is disallowed on `impl` methods".into(),
+                String::new(),
+                *span,
+            ),
 
         }
     }
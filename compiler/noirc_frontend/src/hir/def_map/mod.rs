@@ -4,7 +4,7 @@ use crate::hir::Context;
 use crate::node_interner::{FuncId, GlobalId, NodeInterner, StructId};
 use crate::parse_program;
 use crate::parser::{ParsedModule, ParserError};
-use crate::token::{FunctionAttribute, SecondaryAttribute, TestScope};
+use crate::token::{FunctionAttribute, FuzzConfig, SecondaryAttribute, TestScope};
 use fm::{FileId, FileManager};
 use noirc_arena::{Arena, Index};
 use noirc_errors::Location;
@@ -170,9 +170,14 @@ impl CrateDefMap {
                 if let Some(func_id) = id.as_function() {
                     let attributes = interner.function_attributes(&func_id);
                     match attributes.function() {
-                        Some(FunctionAttribute::Test(scope)) => {
+                        Some(FunctionAttribute::Test(scope, fuzz_config)) => {
                             let location = interner.function_meta(&func_id).name.location;
-                            Some(TestFunction::new(func_id, scope.clone(), location))
+                            Some(TestFunction::new(
+                                func_id,
+                                scope.clone(),
+                                fuzz_config.clone(),
+                                location,
+                            ))
                         }
                         _ => None,
                     }
@@ -183,6 +188,20 @@ impl CrateDefMap {
         })
     }
 
+    /// Go through all modules in this crate, and find all functions in
+    /// each module with the #[bench] attribute
+    pub fn get_all_bench_functions<'a>(
+        &'a self,
+        interner: &'a NodeInterner,
+    ) -> impl Iterator<Item = FuncId> + 'a {
+        self.modules.iter().flat_map(|(_, module)| {
+            module.value_definitions().filter_map(|id| {
+                let func_id = id.as_function()?;
+                interner.function_attributes(&func_id).is_bench_function().then_some(func_id)
+            })
+        })
+    }
+
     /// Go through all modules in this crate, and find all functions in
     /// each module with the #[export] attribute
     pub fn get_all_exported_functions<'a>(
@@ -343,12 +362,13 @@ impl std::ops::IndexMut<LocalModuleId> for CrateDefMap {
 pub struct TestFunction {
     id: FuncId,
     scope: TestScope,
+    fuzz_config: FuzzConfig,
     location: Location,
 }
 
 impl TestFunction {
-    fn new(id: FuncId, scope: TestScope, location: Location) -> Self {
-        TestFunction { id, scope, location }
+    fn new(id: FuncId, scope: TestScope, fuzz_config: FuzzConfig, location: Location) -> Self {
+        TestFunction { id, scope, fuzz_config, location }
     }
 
     /// Returns the function id of the test function
@@ -378,4 +398,10 @@ impl TestFunction {
             TestScope::ShouldFailWith { reason } => reason.as_deref(),
         }
     }
+
+    /// Returns the fuzzer options given in the `#[test]` attribute,
+    /// used if the test function takes arguments.
+    pub fn fuzz_config(&self) -> &FuzzConfig {
+        &self.fuzz_config
+    }
 }
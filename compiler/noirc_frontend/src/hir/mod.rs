@@ -226,22 +226,42 @@ impl Context<'_, '_> {
         crate_id: &CrateId,
         pattern: FunctionNameMatch,
     ) -> Vec<(String, TestFunction)> {
-        let interner = &self.def_interner;
         let def_map = self.def_map(crate_id).expect("The local crate should be analyzed already");
+        let test_functions = def_map.get_all_test_functions(&self.def_interner);
+        self.get_functions_matching(crate_id, pattern, test_functions, TestFunction::get_id)
+    }
 
-        def_map
-            .get_all_test_functions(interner)
-            .filter_map(|test_function| {
+    /// Returns a list of all functions in the current crate marked with #[bench]
+    /// whose names match the given pattern.
+    pub fn get_all_bench_functions_in_crate_matching(
+        &self,
+        crate_id: &CrateId,
+        pattern: FunctionNameMatch,
+    ) -> Vec<(String, FuncId)> {
+        let def_map = self.def_map(crate_id).expect("The local crate should be analyzed already");
+        let bench_functions = def_map.get_all_bench_functions(&self.def_interner);
+        self.get_functions_matching(crate_id, pattern, bench_functions, |func_id| *func_id)
+    }
+
+    /// Pairs each of the given functions with its fully qualified name, keeping only
+    /// those whose name matches the given pattern.
+    fn get_functions_matching<T>(
+        &self,
+        crate_id: &CrateId,
+        pattern: FunctionNameMatch,
+        functions: impl Iterator<Item = T>,
+        get_id: impl Fn(&T) -> FuncId,
+    ) -> Vec<(String, T)> {
+        functions
+            .filter_map(|function| {
                 let fully_qualified_name =
-                    self.fully_qualified_function_name(crate_id, &test_function.get_id());
-                match &pattern {
-                    FunctionNameMatch::Anything => Some((fully_qualified_name, test_function)),
-                    FunctionNameMatch::Exact(pattern) => (&fully_qualified_name == pattern)
-                        .then_some((fully_qualified_name, test_function)),
-                    FunctionNameMatch::Contains(pattern) => fully_qualified_name
-                        .contains(pattern)
-                        .then_some((fully_qualified_name, test_function)),
-                }
+                    self.fully_qualified_function_name(crate_id, &get_id(&function));
+                let matches = match &pattern {
+                    FunctionNameMatch::Anything => true,
+                    FunctionNameMatch::Exact(pattern) => &fully_qualified_name == pattern,
+                    FunctionNameMatch::Contains(pattern) => fully_qualified_name.contains(pattern),
+                };
+                matches.then_some((fully_qualified_name, function))
             })
             .collect()
     }
@@ -99,6 +99,8 @@ pub enum ResolverError {
     JumpInConstrainedFn { is_break: bool, span: Span },
     #[error("break/continue are only allowed within loops")]
     JumpOutsideLoop { is_break: bool, span: Span },
+    #[error("return is only allowed within function bodies")]
+    ReturnOutsideFunction { span: Span },
     #[error("Only `comptime` globals can be mutable")]
     MutableGlobal { span: Span },
     #[error("Globals must have a specified type")]
@@ -426,6 +428,13 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                     *span,
                 )
             },
+            ResolverError::ReturnOutsideFunction { span } => {
+                Diagnostic::simple_error(
+                    "return is only allowed within function bodies".into(),
+                    "`comptime` blocks and globals can't return from a function".into(),
+                    *span,
+                )
+            },
             ResolverError::MutableGlobal { span } => {
                 Diagnostic::simple_error(
                     "Only `comptime` globals may be mutable".into(),
@@ -93,6 +93,12 @@ pub enum TypeCheckError {
     UnsupportedCast { span: Span },
     #[error("Index {index} is out of bounds for this tuple {lhs_type} of length {length}")]
     TupleIndexOutOfBounds { index: usize, lhs_type: Type, length: usize, span: Span },
+    #[error("Array pattern with {pattern_length} elements cannot bind to an array of type {typ}")]
+    ArrayPatternTooLong { typ: Type, pattern_length: usize, span: Span },
+    #[error("`..` in an array pattern requires an array of known length, but found {typ}")]
+    RestPatternUnknownLength { typ: Type, span: Span },
+    #[error("Array patterns cannot be used on slices, found {typ}")]
+    ArrayPatternOnSlice { typ: Type, span: Span },
     #[error("Variable `{name}` must be mutable to be assigned to")]
     VariableMustBeMutable { name: String, span: Span },
     #[error("Cannot mutate immutable variable `{name}`")]
@@ -348,6 +354,8 @@ impl<'a> From<&'a TypeCheckError> for Diagnostic {
             | TypeCheckError::AccessUnknownMember { span, .. }
             | TypeCheckError::UnsupportedCast { span }
             | TypeCheckError::TupleIndexOutOfBounds { span, .. }
+            | TypeCheckError::ArrayPatternTooLong { span, .. }
+            | TypeCheckError::RestPatternUnknownLength { span, .. }
             | TypeCheckError::VariableMustBeMutable { span, .. }
             | TypeCheckError::CannotMutateImmutableVariable { span, .. }
             | TypeCheckError::UnresolvedMethodCall { span, .. }
@@ -374,6 +382,15 @@ impl<'a> From<&'a TypeCheckError> for Diagnostic {
             | TypeCheckError::InvalidShiftSize { span } => {
                 Diagnostic::simple_error(error.to_string(), String::new(), *span)
             }
+            TypeCheckError::ArrayPatternOnSlice { span, .. } => {
+                let mut error = Diagnostic::simple_error(
+                    error.to_string(),
+                    "the length of a slice is only known at runtime".to_string(),
+                    *span,
+                );
+                error.add_note("Try indexing the slice instead, after checking its length".to_string());
+                error
+            }
             TypeCheckError::PublicReturnType { typ, span } => Diagnostic::simple_error(
                 "Functions cannot declare a public return type".to_string(),
                 format!("return type is {typ}"),
@@ -18,6 +18,9 @@ pub enum HirStatement {
     For(HirForStatement),
     Break,
     Continue,
+    /// A `return` with the returned value, if any, and the type of the statement itself.
+    /// This type can be unified with anything since a `return` never produces a value.
+    Return(Option<ExprId>, Type),
     Expression(ExprId),
     Semi(ExprId),
     Comptime(StmtId),
@@ -69,6 +72,9 @@ pub enum HirPattern {
     Identifier(HirIdent),
     Mutable(Box<HirPattern>, Location),
     Tuple(Vec<HirPattern>, Location),
+    /// An array pattern. If `rest_index` is set, the elements from that index
+    /// onwards bind to the end of the array, skipping any elements in between.
+    Array(Vec<HirPattern>, /*rest_index*/ Option<usize>, Location),
     Struct(Type, Vec<(Ident, HirPattern)>, Location),
 }
 
@@ -78,6 +84,7 @@ impl HirPattern {
             HirPattern::Identifier(_) => 0,
             HirPattern::Mutable(pattern, _) => pattern.field_count(),
             HirPattern::Tuple(fields, _) => fields.len(),
+            HirPattern::Array(elements, _, _) => elements.len(),
             HirPattern::Struct(_, fields, _) => fields.len(),
         }
     }
@@ -101,6 +108,7 @@ impl HirPattern {
             HirPattern::Identifier(ident) => ident.location.span,
             HirPattern::Mutable(_, location)
             | HirPattern::Tuple(_, location)
+            | HirPattern::Array(_, _, location)
             | HirPattern::Struct(_, _, location) => location.span,
         }
     }
@@ -110,6 +118,7 @@ impl HirPattern {
             HirPattern::Identifier(ident) => ident.location,
             HirPattern::Mutable(_, location)
             | HirPattern::Tuple(_, location)
+            | HirPattern::Array(_, _, location)
             | HirPattern::Struct(_, _, location) => *location,
         }
     }
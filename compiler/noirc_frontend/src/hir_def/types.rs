@@ -1094,7 +1094,7 @@ impl Type {
     /// This function should match the same check done in `create_value_from_type` in acir_gen.
     /// If this function does not catch a case where a type should be valid, it will later lead to a
     /// panic in that function instead of a user-facing compiler error message.
-    pub(crate) fn is_valid_for_program_input(&self) -> bool {
+    pub fn is_valid_for_program_input(&self) -> bool {
         match self {
             // Type::Error is allowed as usual since it indicates an error was already issued and
             // we don't need to issue further errors about this likely unresolved type
@@ -2510,7 +2510,7 @@ impl Type {
                 if sign == &Signedness::Signed {
                     max_bit_size -= 1;
                 }
-                Some(((1u128 << max_bit_size) - 1).into())
+                Some((u128::MAX >> (128 - max_bit_size)).into())
             }
             Type::Bool => Some(FieldElement::one()),
             Type::TypeVariable(var) => {
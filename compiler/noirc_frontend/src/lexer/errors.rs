@@ -22,6 +22,8 @@ pub enum LexerErrorKind {
     MalformedFuncAttribute { span: Span, found: String },
     #[error("Malformed test attribute")]
     MalformedTestAttribute { span: Span },
+    #[error("Malformed cfg attribute")]
+    MalformedCfgAttribute { span: Span },
     #[error("{:?} is not a valid inner attribute", found)]
     InvalidInnerAttribute { span: Span, found: String },
     #[error("Logical and used instead of bitwise and")]
@@ -64,6 +66,7 @@ impl LexerErrorKind {
             LexerErrorKind::IntegerLiteralTooLarge { span, .. } => *span,
             LexerErrorKind::MalformedFuncAttribute { span, .. } => *span,
             LexerErrorKind::MalformedTestAttribute { span, .. } => *span,
+            LexerErrorKind::MalformedCfgAttribute { span, .. } => *span,
             LexerErrorKind::InvalidInnerAttribute { span, .. } => *span,
             LexerErrorKind::LogicalAnd { span } => *span,
             LexerErrorKind::UnterminatedBlockComment { span } => *span,
@@ -114,7 +117,12 @@ impl LexerErrorKind {
             ),
             LexerErrorKind::MalformedTestAttribute { span } => (
                 "Malformed test attribute".to_string(),
-                "The test attribute can be written in one of these forms: `#[test]`, `#[test(should_fail)]` or `#[test(should_fail_with = \"message\")]`".to_string(),
+                "The test attribute can be written in one of these forms: `#[test]`, `#[test(should_fail)]` or `#[test(should_fail_with = \"message\")]`, optionally followed by the fuzzer options `cases = <int>`, `seed = <int>`, `max_shrink_iters = <int>` and `coverage_guided`".to_string(),
+                *span,
+            ),
+            LexerErrorKind::MalformedCfgAttribute { span } => (
+                "Malformed cfg attribute".to_string(),
+                "The cfg attribute must be written as `#[cfg(feature = \"name\")]`".to_string(),
                 *span,
             ),
             LexerErrorKind::InvalidInnerAttribute { span, found } => (
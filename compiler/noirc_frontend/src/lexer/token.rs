@@ -1,6 +1,9 @@
 use acvm::FieldElement;
 use noirc_errors::{Position, Span, Spanned};
-use std::fmt::{self, Display};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display},
+};
 
 use crate::{
     ast::{Expression, Path},
@@ -639,13 +642,70 @@ impl fmt::Display for TestScope {
         match self {
             TestScope::None => write!(f, ""),
             TestScope::ShouldFailWith { reason } => match reason {
-                Some(failure_reason) => write!(f, "(should_fail_with = {failure_reason:?})"),
-                None => write!(f, "(should_fail)"),
+                Some(failure_reason) => write!(f, "should_fail_with = {failure_reason:?}"),
+                None => write!(f, "should_fail"),
             },
         }
     }
 }
 
+/// Configuration of the fuzzer used to run test functions which take arguments,
+/// e.g. `#[test(cases = 1000, seed = 42, max_shrink_iters = 100, coverage_guided)]`.
+/// Any option which isn't set falls back to the fuzzer's default.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Default, PartialOrd, Ord)]
+pub struct FuzzConfig {
+    /// The number of inputs the test is run with
+    pub cases: Option<u32>,
+    /// The seed of the random number generator, to make the generated inputs reproducible
+    pub seed: Option<u64>,
+    /// The maximum number of iterations spent shrinking a failing input
+    pub max_shrink_iters: Option<u32>,
+    /// Whether inputs reaching new branches are kept and mutated to generate further inputs
+    pub coverage_guided: bool,
+}
+
+impl fmt::Display for FuzzConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut options = Vec::new();
+        if let Some(cases) = self.cases {
+            options.push(format!("cases = {cases}"));
+        }
+        if let Some(seed) = self.seed {
+            options.push(format!("seed = {seed}"));
+        }
+        if let Some(max_shrink_iters) = self.max_shrink_iters {
+            options.push(format!("max_shrink_iters = {max_shrink_iters}"));
+        }
+        if self.coverage_guided {
+            options.push("coverage_guided".to_string());
+        }
+        write!(f, "{}", options.join(", "))
+    }
+}
+
+/// The predicate of a `#[cfg(...)]` attribute, deciding whether the annotated item is compiled
+#[derive(PartialEq, Eq, Hash, Debug, Clone, PartialOrd, Ord)]
+pub enum CfgAttribute {
+    /// `#[cfg(feature = "name")]`: the item is only compiled if the crate has the feature enabled
+    Feature { name: String },
+}
+
+impl CfgAttribute {
+    pub fn is_enabled(&self, enabled_features: &BTreeSet<String>) -> bool {
+        match self {
+            CfgAttribute::Feature { name } => enabled_features.contains(name),
+        }
+    }
+}
+
+impl fmt::Display for CfgAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CfgAttribute::Feature { name } => write!(f, "feature = {name:?}"),
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 // Attributes are special language markers in the target language
 // An example of one is `#[SHA256]` . Currently only Foreign attributes are supported
@@ -682,14 +742,18 @@ impl Attributes {
     }
 
     pub fn is_test_function(&self) -> bool {
-        matches!(self.function(), Some(FunctionAttribute::Test(_)))
+        matches!(self.function(), Some(FunctionAttribute::Test(..)))
+    }
+
+    pub fn is_bench_function(&self) -> bool {
+        matches!(self.function(), Some(FunctionAttribute::Bench))
     }
 
     /// True if these attributes mean the given function is an entry point function if it was
     /// defined within a contract. Note that this does not check if the function is actually part
     /// of a contract.
     pub fn is_contract_entry_point(&self) -> bool {
-        !self.has_contract_library_method() && !self.is_test_function()
+        !self.has_contract_library_method() && !self.is_test_function() && !self.is_bench_function()
     }
 
     /// Returns note if a deprecated secondary attribute is found
@@ -751,7 +815,8 @@ pub enum FunctionAttribute {
     Foreign(String),
     Builtin(String),
     Oracle(String),
-    Test(TestScope),
+    Test(TestScope, FuzzConfig),
+    Bench,
     Fold,
     NoPredicates,
     InlineAlways,
@@ -814,7 +879,8 @@ impl FunctionAttribute {
             FunctionAttribute::Foreign(_) => "foreign",
             FunctionAttribute::Builtin(_) => "builtin",
             FunctionAttribute::Oracle(_) => "oracle",
-            FunctionAttribute::Test(_) => "test",
+            FunctionAttribute::Test(..) => "test",
+            FunctionAttribute::Bench => "bench",
             FunctionAttribute::Fold => "fold",
             FunctionAttribute::NoPredicates => "no_predicates",
             FunctionAttribute::InlineAlways => "inline_always",
@@ -825,7 +891,18 @@ impl FunctionAttribute {
 impl fmt::Display for FunctionAttribute {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            FunctionAttribute::Test(scope) => write!(f, "#[test{scope}]"),
+            FunctionAttribute::Test(scope, fuzz_config) => {
+                let arguments: Vec<String> = [scope.to_string(), fuzz_config.to_string()]
+                    .into_iter()
+                    .filter(|argument| !argument.is_empty())
+                    .collect();
+                if arguments.is_empty() {
+                    write!(f, "#[test]")
+                } else {
+                    write!(f, "#[test({})]", arguments.join(", "))
+                }
+            }
+            FunctionAttribute::Bench => write!(f, "#[bench]"),
             FunctionAttribute::Foreign(ref k) => write!(f, "#[foreign({k})]"),
             FunctionAttribute::Builtin(ref k) => write!(f, "#[builtin({k})]"),
             FunctionAttribute::Oracle(ref k) => write!(f, "#[oracle({k})]"),
@@ -867,6 +944,9 @@ pub enum SecondaryAttribute {
 
     /// Allow chosen warnings to happen so they are silenced.
     Allow(String),
+
+    /// Only compile the annotated item if the predicate holds: #[cfg(feature = "foo")]
+    Cfg(CfgAttribute),
 }
 
 impl SecondaryAttribute {
@@ -884,6 +964,7 @@ impl SecondaryAttribute {
             SecondaryAttribute::Varargs => Some("varargs".to_string()),
             SecondaryAttribute::UseCallersScope => Some("use_callers_scope".to_string()),
             SecondaryAttribute::Allow(_) => Some("allow".to_string()),
+            SecondaryAttribute::Cfg(_) => Some("cfg".to_string()),
         }
     }
 
@@ -898,6 +979,15 @@ impl SecondaryAttribute {
         matches!(self, SecondaryAttribute::Abi(_))
     }
 
+    /// Returns false if this is a `cfg` attribute whose predicate doesn't hold
+    /// given the features enabled for the current crate.
+    pub(crate) fn is_cfg_enabled(&self, enabled_features: &BTreeSet<String>) -> bool {
+        match self {
+            SecondaryAttribute::Cfg(cfg) => cfg.is_enabled(enabled_features),
+            _ => true,
+        }
+    }
+
     pub(crate) fn contents(&self) -> String {
         match self {
             SecondaryAttribute::Deprecated(None) => "deprecated".to_string(),
@@ -913,6 +1003,7 @@ impl SecondaryAttribute {
             SecondaryAttribute::Varargs => "varargs".to_string(),
             SecondaryAttribute::UseCallersScope => "use_callers_scope".to_string(),
             SecondaryAttribute::Allow(ref k) => format!("allow({k})"),
+            SecondaryAttribute::Cfg(ref cfg) => format!("cfg({cfg})"),
         }
     }
 }
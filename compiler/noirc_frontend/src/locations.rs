@@ -7,8 +7,10 @@ use crate::{
     ast::{FunctionDefinition, ItemVisibility},
     hir::def_map::{ModuleDefId, ModuleId},
     node_interner::{
-        DefinitionId, FuncId, GlobalId, NodeInterner, ReferenceId, StructId, TraitId, TypeAliasId,
+        DefinitionId, FuncId, GlobalId, Node, NodeInterner, ReferenceId, StructId, TraitId,
+        TypeAliasId,
     },
+    Type,
 };
 use petgraph::prelude::NodeIndex as PetGraphIndex;
 
@@ -32,6 +34,16 @@ impl LocationIndices {
         let range_map = self.map_file_to_range.get(&location.file)?;
         Some(*range_map.get(&location.span.start())?)
     }
+
+    pub(crate) fn get_nodes_in_location(
+        &self,
+        location: Location,
+    ) -> impl Iterator<Item = PetGraphIndex> + '_ {
+        let range = location.span.start()..location.span.end();
+        self.map_file_to_range.get(&location.file).into_iter().flat_map(move |range_map| {
+            range_map.overlapping(range.clone()).map(|(_, index)| *index)
+        })
+    }
 }
 
 pub struct ReferencesTracker<'a> {
@@ -204,6 +216,20 @@ impl NodeInterner {
         }
     }
 
+    pub(crate) fn add_struct_impl_location(&mut self, id: StructId, location: Location) {
+        if !self.lsp_mode {
+            return;
+        }
+
+        self.struct_impl_locations.entry(id).or_default().push(location);
+    }
+
+    // Returns the location of the self type of every `impl` block (excluding trait impls)
+    // of the given struct.
+    pub fn struct_impl_locations(&self, id: StructId) -> &[Location] {
+        self.struct_impl_locations.get(&id).map_or(&[], Vec::as_slice)
+    }
+
     #[tracing::instrument(skip(self), ret)]
     pub(crate) fn get_or_insert_reference(&mut self, id: ReferenceId) -> PetGraphIndex {
         if let Some(index) = self.reference_graph_indices.get(&id) {
@@ -268,6 +294,51 @@ impl NodeInterner {
         }
     }
 
+    // Returns all references found inside the given location, together with what they reference.
+    pub fn find_references_in_location(&self, location: Location) -> Vec<(Location, ReferenceId)> {
+        self.location_indices
+            .get_nodes_in_location(location)
+            .filter_map(|node_index| {
+                let ReferenceId::Reference(reference_location, _) =
+                    self.reference_graph[node_index]
+                else {
+                    return None;
+                };
+                let referenced_index = self.referenced_index(node_index)?;
+                Some((reference_location, self.reference_graph[referenced_index]))
+            })
+            .collect()
+    }
+
+    // Returns the function whose body contains the given location, if any.
+    // For nested functions, like lambdas, the function they are declared in is returned.
+    pub fn find_function_containing(&self, location: Location) -> Option<FuncId> {
+        self.func_meta
+            .iter()
+            .filter(|(_, func_meta)| func_meta.location.file == location.file)
+            .filter_map(|(func_id, _)| {
+                let body = self.function(func_id).try_as_expr()?;
+                let body_span = self.try_expr_span(&body)?;
+                body_span.contains(&location.span).then_some((*func_id, body_span))
+            })
+            .min_by_key(|(_, body_span)| body_span.end() - body_span.start())
+            .map(|(func_id, _)| func_id)
+    }
+
+    // Returns the type of the expression found exactly at the given location, if any.
+    // When several expressions share that location (for example an implicit dereference added
+    // around a variable) the type of the outermost one, which is the last one created, is returned.
+    pub fn find_expression_type_at_location(&self, location: Location) -> Option<Type> {
+        self.id_to_location
+            .iter()
+            .filter(|(index, expression_location)| {
+                **expression_location == location
+                    && matches!(self.nodes.get(**index), Some(Node::Expression(_)))
+            })
+            .max_by_key(|(index, _)| **index)
+            .map(|(index, _)| self.id_type(*index))
+    }
+
     // Given a referenced node index, find all references to it and return their locations, optionally together
     // with the reference node's location if `include_referenced` is true.
     // If `include_self_type_name` is true, references where "Self" is written are returned,
@@ -46,6 +46,10 @@ pub enum Expression {
     Semi(Box<Expression>),
     Break,
     Continue,
+    /// Returns the given value from the current function.
+    /// The type is the one of the `return` expression itself, which never produces a value:
+    /// it's only needed to generate placeholder values of that type.
+    Return(Box<Expression>, Type),
 }
 
 /// A definition is either a local (variable), function, or is a built-in
@@ -16,6 +16,7 @@ pub enum MonomorphizationError {
     ComptimeTypeInRuntimeCode { typ: String, location: Location },
     CheckedTransmuteFailed { actual: Type, expected: Type, location: Location },
     CheckedCastFailed { actual: Type, expected: Type, location: Location },
+    ArrayPatternTooLong { length: u32, pattern_length: usize, location: Location },
 }
 
 impl MonomorphizationError {
@@ -28,6 +29,7 @@ impl MonomorphizationError {
             | MonomorphizationError::ComptimeTypeInRuntimeCode { location, .. }
             | MonomorphizationError::CheckedTransmuteFailed { location, .. }
             | MonomorphizationError::CheckedCastFailed { location, .. }
+            | MonomorphizationError::ArrayPatternTooLong { location, .. }
             | MonomorphizationError::NoDefaultType { location, .. } => *location,
             MonomorphizationError::InterpreterError(error) => error.get_location(),
         }
@@ -58,6 +60,12 @@ impl MonomorphizationError {
             MonomorphizationError::CheckedCastFailed { actual, expected, .. } => {
                 format!("Arithmetic generics simplification failed: `{actual:?}` != `{expected:?}`")
             }
+            MonomorphizationError::ArrayPatternTooLong { length, pattern_length, .. } => {
+                format!(
+                    "Array pattern with {} elements cannot bind to an array of length {}",
+                    pattern_length, length
+                )
+            }
             MonomorphizationError::NoDefaultType { location } => {
                 let message = "Type annotation needed".into();
                 let secondary = "Could not determine type of generic argument".into();
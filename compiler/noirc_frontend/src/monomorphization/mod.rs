@@ -328,8 +328,9 @@ impl<'interner> Monomorphizer<'interner> {
         let attributes = self.interner.function_attributes(&f);
         let inline_type = InlineType::from(attributes);
 
-        let parameters = self.parameters(&meta.parameters)?;
+        let (parameters, parameter_unpacks) = self.parameters(&meta.parameters)?;
         let body = self.expr(body_expr_id)?;
+        let body = prepend_parameter_unpacks(parameter_unpacks, body);
         let function = ast::Function {
             id,
             name,
@@ -352,22 +353,28 @@ impl<'interner> Monomorphizer<'interner> {
 
     /// Monomorphize each parameter, expanding tuple/struct patterns into multiple parameters
     /// and binding any generic types found.
+    ///
+    /// Array patterns can't be expanded this way since arrays are passed as a single value.
+    /// For those, the returned `Let` expressions unpack the array parameter and should be
+    /// placed at the start of the function body (see `prepend_parameter_unpacks`).
     fn parameters(
         &mut self,
         params: &Parameters,
-    ) -> Result<Vec<(ast::LocalId, bool, String, ast::Type)>, MonomorphizationError> {
+    ) -> Result<(ast::Parameters, Vec<ast::Expression>), MonomorphizationError> {
         let mut new_params = Vec::with_capacity(params.len());
+        let mut unpacks = Vec::new();
         for (parameter, typ, _) in &params.0 {
-            self.parameter(parameter, typ, &mut new_params)?;
+            self.parameter(parameter, typ, &mut new_params, &mut unpacks)?;
         }
-        Ok(new_params)
+        Ok((new_params, unpacks))
     }
 
     fn parameter(
         &mut self,
         param: &HirPattern,
         typ: &HirType,
-        new_params: &mut Vec<(ast::LocalId, bool, String, ast::Type)>,
+        new_params: &mut ast::Parameters,
+        unpacks: &mut Vec<ast::Expression>,
     ) -> Result<(), MonomorphizationError> {
         match param {
             HirPattern::Identifier(ident) => {
@@ -378,14 +385,29 @@ impl<'interner> Monomorphizer<'interner> {
                 new_params.push((new_id, definition.mutable, name, typ));
                 self.define_local(ident.id, new_id);
             }
-            HirPattern::Mutable(pattern, _) => self.parameter(pattern, typ, new_params)?,
+            HirPattern::Mutable(pattern, _) => self.parameter(pattern, typ, new_params, unpacks)?,
             HirPattern::Tuple(fields, _) => {
                 let tuple_field_types = unwrap_tuple_type(typ);
 
                 for (field, typ) in fields.iter().zip(tuple_field_types) {
-                    self.parameter(field, &typ, new_params)?;
+                    self.parameter(field, &typ, new_params, unpacks)?;
                 }
             }
+            HirPattern::Array(_, _, location) => {
+                let new_id = self.next_local_id();
+                let name = "_".to_string();
+                let ast_type = Self::convert_type(typ, *location)?;
+                new_params.push((new_id, false, name.clone(), ast_type.clone()));
+
+                let value = ast::Expression::Ident(ast::Ident {
+                    location: Some(*location),
+                    mutable: false,
+                    definition: Definition::Local(new_id),
+                    name,
+                    typ: ast_type,
+                });
+                unpacks.push(self.unpack_pattern(param.clone(), value, typ)?);
+            }
             HirPattern::Struct(_, fields, location) => {
                 let struct_field_types = unwrap_struct_type(typ, *location)?;
                 assert_eq!(struct_field_types.len(), fields.len());
@@ -400,7 +422,7 @@ impl<'interner> Monomorphizer<'interner> {
                         unreachable!("Expected a field named '{field_name}' in the struct pattern")
                     });
 
-                    self.parameter(field, &field_type, new_params)?;
+                    self.parameter(field, &field_type, new_params, unpacks)?;
                 }
             }
         }
@@ -646,6 +668,18 @@ impl<'interner> Monomorphizer<'interner> {
             }
             HirStatement::Break => Ok(ast::Expression::Break),
             HirStatement::Continue => Ok(ast::Expression::Continue),
+            HirStatement::Return(value, typ) => {
+                let value = match value {
+                    Some(value) => self.expr(value)?,
+                    None => ast::Expression::Literal(ast::Literal::Unit),
+                };
+                // The type of a `return` is left unbound unless it's used as the value of a block
+                let typ = match typ.follow_bindings() {
+                    HirType::TypeVariable(_) => ast::Type::Unit,
+                    typ => Self::convert_type(&typ, self.interner.statement_location(id))?,
+                };
+                Ok(ast::Expression::Return(Box::new(value), typ))
+            }
             HirStatement::Error => unreachable!(),
 
             // All `comptime` statements & expressions should be removed before runtime.
@@ -746,6 +780,9 @@ impl<'interner> Monomorphizer<'interner> {
                 let fields = unwrap_tuple_type(typ);
                 self.unpack_tuple_pattern(value, patterns.into_iter().zip(fields))
             }
+            HirPattern::Array(patterns, rest_index, location) => {
+                self.unpack_array_pattern(value, patterns, rest_index, typ, location)
+            }
             HirPattern::Struct(_, patterns, location) => {
                 let fields = unwrap_struct_type(typ, location)?;
                 assert_eq!(patterns.len(), fields.len());
@@ -797,6 +834,72 @@ impl<'interner> Monomorphizer<'interner> {
         Ok(ast::Expression::Block(definitions))
     }
 
+    fn unpack_array_pattern(
+        &mut self,
+        value: ast::Expression,
+        patterns: Vec<HirPattern>,
+        rest_index: Option<usize>,
+        typ: &HirType,
+        location: Location,
+    ) -> Result<ast::Expression, MonomorphizationError> {
+        let (length, element_type) = unwrap_array_type(typ, location)?;
+        let array_type = Self::convert_type(typ, location)?;
+        let ast_element_type = Self::convert_type(&element_type, location)?;
+        let fresh_id = self.next_local_id();
+
+        let mut definitions = vec![ast::Expression::Let(ast::Let {
+            id: fresh_id,
+            mutable: false,
+            name: "_".into(),
+            expression: Box::new(value),
+        })];
+
+        // The elaborator couldn't check this if the length of the array is generic
+        if rest_index.is_some() && (length as usize) < patterns.len() {
+            let pattern_length = patterns.len();
+            return Err(MonomorphizationError::ArrayPatternTooLong {
+                length,
+                pattern_length,
+                location,
+            });
+        }
+
+        // Any elements after a `..` are matched against the end of the array
+        let rest_index = rest_index.unwrap_or(patterns.len());
+        let skipped = (length as usize).saturating_sub(patterns.len());
+
+        for (i, element_pattern) in patterns.into_iter().enumerate() {
+            let location = element_pattern.location();
+            let index = if i < rest_index { i } else { i + skipped };
+
+            let collection = ast::Expression::Ident(ast::Ident {
+                location: Some(location),
+                mutable: false,
+                definition: Definition::Local(fresh_id),
+                name: "_".into(),
+                typ: array_type.clone(),
+            });
+            let index_type = ast::Type::Integer(Signedness::Unsigned, IntegerBitSize::ThirtyTwo);
+            let index = ast::Expression::Literal(ast::Literal::Integer(
+                (index as u128).into(),
+                false,
+                index_type,
+                location,
+            ));
+
+            let new_rhs = ast::Expression::Index(ast::Index {
+                collection: Box::new(collection),
+                index: Box::new(index),
+                element_type: ast_element_type.clone(),
+                location,
+            });
+            let new_expr = self.unpack_pattern(element_pattern, new_rhs, &element_type)?;
+            definitions.push(new_expr);
+        }
+
+        Ok(ast::Expression::Block(definitions))
+    }
+
     /// Find a captured variable in the innermost closure, and construct an expression
     fn lookup_captured_expr(&mut self, id: node_interner::DefinitionId) -> Option<ast::Expression> {
         let ctx = self.lambda_envs_stack.last()?;
@@ -1636,8 +1739,9 @@ impl<'interner> Monomorphizer<'interner> {
         let parameters =
             vecmap(lambda.parameters, |(pattern, typ)| (pattern, typ, Visibility::Private)).into();
 
-        let parameters = self.parameters(&parameters)?;
+        let (parameters, parameter_unpacks) = self.parameters(&parameters)?;
         let body = self.expr(lambda.body)?;
+        let body = prepend_parameter_unpacks(parameter_unpacks, body);
 
         let id = self.next_function_id();
         let return_type = ret_type.clone();
@@ -1702,7 +1806,7 @@ impl<'interner> Monomorphizer<'interner> {
         let parameters =
             vecmap(lambda.parameters, |(pattern, typ)| (pattern, typ, Visibility::Private)).into();
 
-        let mut converted_parameters = self.parameters(&parameters)?;
+        let (mut converted_parameters, parameter_unpacks) = self.parameters(&parameters)?;
 
         let id = self.next_function_id();
         let name = lambda_name.to_owned();
@@ -1757,6 +1861,7 @@ impl<'interner> Monomorphizer<'interner> {
         self.lambda_envs_stack
             .push(LambdaContext { env_ident: env_ident.clone(), captures: lambda.captures });
         let body = self.expr(lambda.body)?;
+        let body = prepend_parameter_unpacks(parameter_unpacks, body);
         self.lambda_envs_stack.pop();
 
         let lambda_fn_typ: ast::Type = ast::Type::Function(
@@ -2016,6 +2121,35 @@ fn unwrap_tuple_type(typ: &HirType) -> Vec<HirType> {
     }
 }
 
+fn unwrap_array_type(
+    typ: &HirType,
+    location: Location,
+) -> Result<(u32, HirType), MonomorphizationError> {
+    match typ.follow_bindings() {
+        HirType::Array(length, element) => match length.evaluate_to_u32(location.span) {
+            Ok(length) => Ok((length, *element)),
+            Err(err) => {
+                let length = *length;
+                Err(MonomorphizationError::UnknownArrayLength { location, err, length })
+            }
+        },
+        other => unreachable!("unwrap_array_type: expected array, found {:?}", other),
+    }
+}
+
+/// Places the `Let` expressions unpacking any array parameter patterns before the function body.
+fn prepend_parameter_unpacks(
+    mut unpacks: Vec<ast::Expression>,
+    body: ast::Expression,
+) -> ast::Expression {
+    if unpacks.is_empty() {
+        body
+    } else {
+        unpacks.push(body);
+        ast::Expression::Block(unpacks)
+    }
+}
+
 fn unwrap_struct_type(
     typ: &HirType,
     location: Location,
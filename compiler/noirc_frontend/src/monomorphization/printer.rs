@@ -75,6 +75,10 @@ impl AstPrinter {
             }
             Expression::Break => write!(f, "break"),
             Expression::Continue => write!(f, "continue"),
+            Expression::Return(value, _) => {
+                write!(f, "return ")?;
+                self.print_expr(value, f)
+            }
         }
     }
 
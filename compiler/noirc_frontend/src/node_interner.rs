@@ -254,6 +254,9 @@ pub struct NodeInterner {
     // (ReferenceId::Reference and ReferenceId::Local aren't included here)
     pub(crate) reference_modules: HashMap<ReferenceId, ModuleId>,
 
+    // The location of the self type of every `impl` block (excluding trait impls) of each struct
+    pub(crate) struct_impl_locations: HashMap<StructId, Vec<Location>>,
+
     // All names (and their definitions) that can be offered for auto_import.
     // The third value in the tuple is the module where the definition is (only for pub use).
     // These include top-level functions, global variables and types, but excludes
@@ -271,6 +274,9 @@ pub struct NodeInterner {
 
     /// Captures the documentation comments for each module, struct, trait, function, etc.
     pub(crate) doc_comments: HashMap<ReferenceId, Vec<String>>,
+
+    /// The code produced by each comptime evaluation, only tracked in LSP mode.
+    pub(crate) comptime_expansions: Vec<ComptimeExpansion>,
 }
 
 /// A dependency in the dependency graph may be a type or a definition.
@@ -600,6 +606,17 @@ impl DefinitionKind {
     }
 }
 
+/// The code produced by evaluating something at comptime: the items generated by an attribute,
+/// or the value a comptime block, statement or global evaluates to.
+#[derive(Debug, Clone)]
+pub struct ComptimeExpansion {
+    /// The location of the attributed item, or of the evaluated code
+    pub location: Location,
+    /// What was evaluated, for example `#[derive(Eq)]` or `comptime block`
+    pub title: String,
+    pub expansion: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct GlobalInfo {
     pub id: GlobalId,
@@ -673,10 +690,12 @@ impl Default for NodeInterner {
             reference_graph: petgraph::graph::DiGraph::new(),
             reference_graph_indices: HashMap::default(),
             reference_modules: HashMap::default(),
+            struct_impl_locations: HashMap::default(),
             auto_import_names: HashMap::default(),
             comptime_scopes: vec![HashMap::default()],
             trait_impl_associated_types: HashMap::default(),
             doc_comments: HashMap::default(),
+            comptime_expansions: Vec::new(),
         }
     }
 }
@@ -1062,6 +1081,11 @@ impl NodeInterner {
         self.func_meta.get(func_id)
     }
 
+    /// Returns the ids of all functions that have metadata, in no particular order
+    pub fn function_ids(&self) -> impl Iterator<Item = FuncId> + '_ {
+        self.func_meta.keys().copied()
+    }
+
     pub fn function_ident(&self, func_id: &FuncId) -> crate::ast::Ident {
         let name = self.function_name(func_id).to_owned();
         let span = self.function_meta(func_id).name.location.span;
@@ -1433,6 +1457,17 @@ impl NodeInterner {
         self.trait_implementations[&id].clone()
     }
 
+    /// Returns all trait implementations in the order they were declared in.
+    pub fn get_all_trait_implementations(&self) -> Vec<(TraitImplId, Shared<TraitImpl>)> {
+        let mut trait_impls: Vec<_> = self
+            .trait_implementations
+            .iter()
+            .map(|(id, trait_impl)| (*id, trait_impl.clone()))
+            .collect();
+        trait_impls.sort_by_key(|(id, _)| id.0);
+        trait_impls
+    }
+
     /// If the given function belongs to a trait impl, return its trait method id.
     /// Otherwise, return None.
     pub fn get_trait_method_id(&self, function: FuncId) -> Option<TraitMethodId> {
@@ -2244,6 +2279,14 @@ impl NodeInterner {
         self.lsp_mode
     }
 
+    pub fn push_comptime_expansion(&mut self, expansion: ComptimeExpansion) {
+        self.comptime_expansions.push(expansion);
+    }
+
+    pub fn comptime_expansions(&self) -> &[ComptimeExpansion] {
+        &self.comptime_expansions
+    }
+
     pub fn set_associated_types_for_impl(
         &mut self,
         impl_id: TraitImplId,
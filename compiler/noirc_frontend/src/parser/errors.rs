@@ -41,6 +41,8 @@ pub enum ParserErrorReason {
     RefMutCanOnlyBeUsedWithSelf,
     #[error("Invalid pattern")]
     InvalidPattern,
+    #[error("`..` can only be used once per array pattern")]
+    MultipleRestPatterns,
     #[error("Documentation comment does not document anything")]
     DocCommentDoesNotDocumentAnything,
 
@@ -63,8 +65,6 @@ pub enum ParserErrorReason {
     ConstrainDeprecated,
     #[error("Invalid type expression: '{0}'. Only unsigned integer constants up to `u32`, globals, generics, +, -, *, /, and % may be used in this context.")]
     InvalidTypeExpression(Expression),
-    #[error("Early 'return' is unsupported")]
-    EarlyReturn,
     #[error("Patterns aren't allowed in a trait's function declarations")]
     PatternInTraitFunctionParameter,
     #[error("Patterns aren't allowed in a trait impl's associated constants")]
@@ -1,10 +1,13 @@
+use acvm::AcirField;
 use noirc_errors::Span;
 
 use crate::ast::{Expression, ExpressionKind, Ident, Literal, Path};
 use crate::lexer::errors::LexerErrorKind;
 use crate::parser::labels::ParsingRuleLabel;
 use crate::parser::ParserErrorReason;
-use crate::token::{Attribute, FunctionAttribute, MetaAttribute, TestScope, Token};
+use crate::token::{
+    Attribute, CfgAttribute, FunctionAttribute, FuzzConfig, MetaAttribute, TestScope, Token,
+};
 use crate::token::{CustomAttribute, SecondaryAttribute};
 
 use super::parse_many::without_separator;
@@ -44,7 +47,8 @@ impl<'a> Parser<'a> {
     /// Attribute = '#[' (FunctionAttribute | SecondaryAttribute) ']'
     ///
     /// FunctionAttribute
-    ///     = 'builtin' '(' AttributeValue ')'
+    ///     = 'bench'
+    ///     | 'builtin' '(' AttributeValue ')'
     ///     | 'fold'
     ///     | 'foreign' '(' AttributeValue ')'
     ///     | 'inline_always'
@@ -52,12 +56,12 @@ impl<'a> Parser<'a> {
     ///     | 'oracle' '(' AttributeValue ')'
     ///     | 'recursive'
     ///     | 'test'
-    ///     | 'test' '(' 'should_fail' ')'
-    ///     | 'test' '(' 'should_fail_with' '=' string ')'
+    ///     | 'test' '(' TestArgument (',' TestArgument)* ')'
     ///
     /// SecondaryAttribute
     ///     = 'abi' '(' AttributeValue ')'
     ///     | 'allow' '(' AttributeValue ')'
+    ///     | 'cfg' '(' 'feature' '=' string ')'
     ///     | 'deprecated'
     ///     | 'deprecated' '(' string ')'
     ///     | 'contract_library_method'
@@ -67,6 +71,14 @@ impl<'a> Parser<'a> {
     ///     | 'varargs'
     ///     | MetaAttribute
     ///
+    /// TestArgument
+    ///     = 'should_fail'
+    ///     | 'should_fail_with' '=' string
+    ///     | 'cases' '=' int
+    ///     | 'seed' '=' int
+    ///     | 'max_shrink_iters' '=' int
+    ///     | 'coverage_guided'
+    ///
     /// MetaAttribute
     ///     = Path Arguments?
     ///
@@ -141,9 +153,11 @@ impl<'a> Parser<'a> {
         } else if let Some(path) = self.parse_path_no_turbofish() {
             if let Some(ident) = path.as_ident() {
                 if ident.0.contents == "test" {
-                    // The test attribute is the only secondary attribute that has `a = b` in its syntax
-                    // (`should_fail_with = "..."``) so we parse it differently.
+                    // The test and cfg attributes are the only attributes that have `a = b` in their syntax
+                    // (`should_fail_with = "..."` and `feature = "..."`) so we parse them differently.
                     self.parse_test_attribute(start_span)
+                } else if ident.0.contents == "cfg" {
+                    self.parse_cfg_attribute(start_span)
                 } else {
                     // Every other attribute has the form `name(arg1, arg2, .., argN)`
                     self.parse_ident_attribute_other_than_test(ident, start_span)
@@ -182,6 +196,10 @@ impl<'a> Parser<'a> {
             "allow" => self.parse_single_name_attribute(ident, arguments, start_span, |name| {
                 Attribute::Secondary(SecondaryAttribute::Allow(name))
             }),
+            "bench" => {
+                let attr = Attribute::Function(FunctionAttribute::Bench);
+                self.parse_no_args_attribute(ident, arguments, attr)
+            }
             "builtin" => self.parse_single_name_attribute(ident, arguments, start_span, |name| {
                 Attribute::Function(FunctionAttribute::Builtin(name))
             }),
@@ -266,41 +284,91 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_test_attribute(&mut self, start_span: Span) -> Attribute {
-        let scope = if self.eat_left_paren() {
-            let scope = if let Some(ident) = self.eat_ident() {
+        let mut scope = TestScope::None;
+        let mut fuzz_config = FuzzConfig::default();
+        let mut malformed = false;
+
+        if self.eat_left_paren() {
+            loop {
+                let Some(ident) = self.eat_ident() else {
+                    malformed = true;
+                    break;
+                };
+
                 match ident.0.contents.as_str() {
-                    "should_fail" => Some(TestScope::ShouldFailWith { reason: None }),
+                    "should_fail" => scope = TestScope::ShouldFailWith { reason: None },
                     "should_fail_with" => {
                         self.eat_or_error(Token::Assign);
-                        if let Some(reason) = self.eat_str() {
-                            Some(TestScope::ShouldFailWith { reason: Some(reason) })
-                        } else {
-                            Some(TestScope::ShouldFailWith { reason: None })
-                        }
+                        scope = TestScope::ShouldFailWith { reason: self.eat_str() };
+                    }
+                    "cases" => {
+                        fuzz_config.cases = self.parse_test_attribute_int_option();
+                        malformed |= fuzz_config.cases.is_none();
+                    }
+                    "seed" => {
+                        fuzz_config.seed = self.parse_test_attribute_int_option();
+                        malformed |= fuzz_config.seed.is_none();
+                    }
+                    "max_shrink_iters" => {
+                        fuzz_config.max_shrink_iters = self.parse_test_attribute_int_option();
+                        malformed |= fuzz_config.max_shrink_iters.is_none();
                     }
-                    _ => None,
+                    "coverage_guided" => fuzz_config.coverage_guided = true,
+                    _ => malformed = true,
                 }
-            } else {
-                None
+
+                if malformed || !self.eat_comma() {
+                    break;
+                }
+            }
+            self.eat_or_error(Token::RightParen);
+        }
+
+        self.skip_until_right_bracket();
+
+        if malformed {
+            self.errors.push(
+                LexerErrorKind::MalformedTestAttribute { span: self.span_since(start_span) }.into(),
+            );
+        }
+
+        Attribute::Function(FunctionAttribute::Test(scope, fuzz_config))
+    }
+
+    /// Parses the `= int` part of a fuzzer option such as `cases = 100`
+    fn parse_test_attribute_int_option<T: TryFrom<u128>>(&mut self) -> Option<T> {
+        self.eat_or_error(Token::Assign);
+        let value = self.eat_int()?;
+        value.try_into_u128().and_then(|value| T::try_from(value).ok())
+    }
+
+    fn parse_cfg_attribute(&mut self, start_span: Span) -> Attribute {
+        let cfg = if self.eat_left_paren() {
+            let cfg = match self.eat_ident() {
+                Some(ident) if ident.0.contents == "feature" => {
+                    self.eat_or_error(Token::Assign);
+                    self.eat_str().map(|name| CfgAttribute::Feature { name })
+                }
+                _ => None,
             };
             self.eat_or_error(Token::RightParen);
-            scope
+            cfg
         } else {
-            Some(TestScope::None)
+            None
         };
 
         self.skip_until_right_bracket();
 
-        let scope = if let Some(scope) = scope {
-            scope
+        let cfg = if let Some(cfg) = cfg {
+            cfg
         } else {
             self.errors.push(
-                LexerErrorKind::MalformedTestAttribute { span: self.span_since(start_span) }.into(),
+                LexerErrorKind::MalformedCfgAttribute { span: self.span_since(start_span) }.into(),
             );
-            TestScope::None
+            CfgAttribute::Feature { name: String::new() }
         };
 
-        Attribute::Function(FunctionAttribute::Test(scope))
+        Attribute::Secondary(SecondaryAttribute::Cfg(cfg))
     }
 
     fn parse_single_name_attribute<F>(
@@ -389,7 +457,9 @@ mod tests {
 
     use crate::{
         parser::{parser::tests::expect_no_errors, Parser},
-        token::{Attribute, FunctionAttribute, SecondaryAttribute, TestScope},
+        token::{
+            Attribute, CfgAttribute, FunctionAttribute, FuzzConfig, SecondaryAttribute, TestScope,
+        },
     };
 
     fn parse_inner_secondary_attribute_no_errors(src: &str, expected: SecondaryAttribute) {
@@ -500,6 +570,13 @@ mod tests {
         parse_attribute_no_errors(src, expected);
     }
 
+    #[test]
+    fn parses_attribute_bench() {
+        let src = "#[bench]";
+        let expected = Attribute::Function(FunctionAttribute::Bench);
+        parse_attribute_no_errors(src, expected);
+    }
+
     #[test]
     fn parses_attribute_fold() {
         let src = "#[fold]";
@@ -542,31 +619,86 @@ mod tests {
         parse_attribute_no_errors(src, expected);
     }
 
+    #[test]
+    fn parses_attribute_cfg_feature() {
+        let src = "#[cfg(feature = \"debug_checks\")]";
+        let expected = Attribute::Secondary(SecondaryAttribute::Cfg(CfgAttribute::Feature {
+            name: "debug_checks".to_string(),
+        }));
+        parse_attribute_no_errors(src, expected);
+    }
+
+    #[test]
+    fn parses_attribute_cfg_without_feature_with_error() {
+        let src = "#[cfg(debug_checks)]";
+        let mut parser = Parser::for_str(src);
+        let (attribute, _span) = parser.parse_attribute().unwrap();
+        assert!(matches!(attribute, Attribute::Secondary(SecondaryAttribute::Cfg(..))));
+        assert!(!parser.errors.is_empty());
+    }
+
     #[test]
     fn parses_attribute_test_no_scope() {
         let src = "#[test]";
-        let expected = Attribute::Function(FunctionAttribute::Test(TestScope::None));
+        let expected =
+            Attribute::Function(FunctionAttribute::Test(TestScope::None, FuzzConfig::default()));
         parse_attribute_no_errors(src, expected);
     }
 
     #[test]
     fn parses_attribute_test_should_fail() {
         let src = "#[test(should_fail)]";
-        let expected = Attribute::Function(FunctionAttribute::Test(TestScope::ShouldFailWith {
-            reason: None,
-        }));
+        let expected = Attribute::Function(FunctionAttribute::Test(
+            TestScope::ShouldFailWith { reason: None },
+            FuzzConfig::default(),
+        ));
         parse_attribute_no_errors(src, expected);
     }
 
     #[test]
     fn parses_attribute_test_should_fail_with() {
         let src = "#[test(should_fail_with = \"reason\")]";
-        let expected = Attribute::Function(FunctionAttribute::Test(TestScope::ShouldFailWith {
-            reason: Some("reason".to_string()),
-        }));
+        let expected = Attribute::Function(FunctionAttribute::Test(
+            TestScope::ShouldFailWith { reason: Some("reason".to_string()) },
+            FuzzConfig::default(),
+        ));
+        parse_attribute_no_errors(src, expected);
+    }
+
+    #[test]
+    fn parses_attribute_test_with_fuzz_config() {
+        let src = "#[test(should_fail, cases = 1000, seed = 42, max_shrink_iters = 10)]";
+        let expected = Attribute::Function(FunctionAttribute::Test(
+            TestScope::ShouldFailWith { reason: None },
+            FuzzConfig {
+                cases: Some(1000),
+                seed: Some(42),
+                max_shrink_iters: Some(10),
+                coverage_guided: false,
+            },
+        ));
+        parse_attribute_no_errors(src, expected);
+    }
+
+    #[test]
+    fn parses_attribute_test_coverage_guided() {
+        let src = "#[test(coverage_guided)]";
+        let expected = Attribute::Function(FunctionAttribute::Test(
+            TestScope::None,
+            FuzzConfig { coverage_guided: true, ..FuzzConfig::default() },
+        ));
         parse_attribute_no_errors(src, expected);
     }
 
+    #[test]
+    fn parses_attribute_test_with_unknown_option_with_error() {
+        let src = "#[test(iterations = 10)]";
+        let mut parser = Parser::for_str(src);
+        let (attribute, _span) = parser.parse_attribute().unwrap();
+        assert!(matches!(attribute, Attribute::Function(FunctionAttribute::Test(..))));
+        assert!(!parser.errors.is_empty());
+    }
+
     #[test]
     fn parses_meta_attribute_single_identifier_no_arguments() {
         let src = "#[foo]";
@@ -630,7 +762,7 @@ mod tests {
         assert_eq!(attributes.len(), 2);
 
         let (attr, _) = attributes.remove(0);
-        assert!(matches!(attr, Attribute::Function(FunctionAttribute::Test(TestScope::None))));
+        assert!(matches!(attr, Attribute::Function(FunctionAttribute::Test(TestScope::None, _))));
 
         let (attr, _) = attributes.remove(0);
         assert!(matches!(attr, Attribute::Secondary(SecondaryAttribute::Deprecated(None))));
@@ -7,7 +7,7 @@ use crate::{
         UnresolvedGeneric, UnresolvedType, UnresolvedTypeData,
     },
     parser::{labels::ParsingRuleLabel, ParserErrorReason},
-    token::{Keyword, Token},
+    token::{Attribute, Keyword, SecondaryAttribute, Token},
 };
 
 use super::{parse_many::without_separator, Parser};
@@ -155,34 +155,37 @@ impl<'a> Parser<'a> {
     }
 
     /// TraitImplItem
-    ///     = TraitImplType
-    ///     | TraitImplConstant
-    ///     | TraitImplFunction
+    ///     = Attributes
+    ///       ( TraitImplType
+    ///       | TraitImplConstant
+    ///       | TraitImplFunction
+    ///       )
     fn parse_trait_impl_item_kind(&mut self) -> Option<TraitImplItemKind> {
-        if let Some(kind) = self.parse_trait_impl_type() {
-            return Some(kind);
+        let attributes = self.parse_attributes();
+
+        if self.eat_keyword(Keyword::Type) {
+            let attributes = self.validate_secondary_attributes(attributes);
+            return Some(self.parse_trait_impl_type(attributes));
         }
 
-        if let Some(kind) = self.parse_trait_impl_constant() {
-            return Some(kind);
+        if self.eat_keyword(Keyword::Let) {
+            let attributes = self.validate_secondary_attributes(attributes);
+            return Some(self.parse_trait_impl_constant(attributes));
         }
 
-        self.parse_trait_impl_function()
+        self.parse_trait_impl_function(attributes)
     }
 
     /// TraitImplType = 'type' identifier ( ':' Type )? ';'
-    fn parse_trait_impl_type(&mut self) -> Option<TraitImplItemKind> {
-        if !self.eat_keyword(Keyword::Type) {
-            return None;
-        }
-
+    fn parse_trait_impl_type(&mut self, attributes: Vec<SecondaryAttribute>) -> TraitImplItemKind {
         let Some(name) = self.eat_ident() else {
             self.expected_identifier();
             self.eat_semicolons();
-            return Some(TraitImplItemKind::Type {
+            return TraitImplItemKind::Type {
                 name: Ident::default(),
                 alias: UnresolvedType { typ: UnresolvedTypeData::Error, span: Span::default() },
-            });
+                attributes,
+            };
         };
 
         let alias = if self.eat_assign() {
@@ -193,15 +196,14 @@ impl<'a> Parser<'a> {
 
         self.eat_semicolons();
 
-        Some(TraitImplItemKind::Type { name, alias })
+        TraitImplItemKind::Type { name, alias, attributes }
     }
 
     /// TraitImplConstant = 'let' identifier OptionalTypeAnnotation ';'
-    fn parse_trait_impl_constant(&mut self) -> Option<TraitImplItemKind> {
-        if !self.eat_keyword(Keyword::Let) {
-            return None;
-        }
-
+    fn parse_trait_impl_constant(
+        &mut self,
+        attributes: Vec<SecondaryAttribute>,
+    ) -> TraitImplItemKind {
         let name = match self.eat_ident() {
             Some(name) => name,
             None => {
@@ -221,13 +223,14 @@ impl<'a> Parser<'a> {
 
         self.eat_semicolons();
 
-        Some(TraitImplItemKind::Constant(name, typ, expr))
+        TraitImplItemKind::Constant(name, typ, expr, attributes)
     }
 
-    /// TraitImplFunction = Attributes Modifiers Function
-    fn parse_trait_impl_function(&mut self) -> Option<TraitImplItemKind> {
-        let attributes = self.parse_attributes();
-
+    /// TraitImplFunction = Modifiers Function
+    fn parse_trait_impl_function(
+        &mut self,
+        attributes: Vec<(Attribute, Span)>,
+    ) -> Option<TraitImplItemKind> {
         let modifiers = self.parse_modifiers(
             false, // allow mut
         );
@@ -501,7 +504,7 @@ mod tests {
         assert_eq!(trait_impl.items.len(), 1);
 
         let item = trait_impl.items.remove(0).item;
-        let TraitImplItemKind::Type { name, alias } = item.kind else {
+        let TraitImplItemKind::Type { name, alias, .. } = item.kind else {
             panic!("Expected type");
         };
         assert_eq!(name.to_string(), "Foo");
@@ -516,7 +519,7 @@ mod tests {
         assert_eq!(trait_impl.items.len(), 1);
 
         let item = trait_impl.items.remove(0).item;
-        let TraitImplItemKind::Constant(name, typ, expr) = item.kind else {
+        let TraitImplItemKind::Constant(name, typ, expr, _) = item.kind else {
             panic!("Expected constant");
         };
         assert_eq!(name.to_string(), "x");
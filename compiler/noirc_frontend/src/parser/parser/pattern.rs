@@ -7,7 +7,10 @@ use crate::{
 };
 
 use super::{
-    parse_many::{separated_by_comma_until_right_brace, separated_by_comma_until_right_paren},
+    parse_many::{
+        separated_by_comma, separated_by_comma_until_right_brace,
+        separated_by_comma_until_right_paren,
+    },
     Parser,
 };
 
@@ -16,6 +19,11 @@ pub(crate) enum PatternOrSelf {
     SelfPattern(SelfPattern),
 }
 
+enum ArrayPatternElement {
+    Pattern(Pattern),
+    Rest(Span),
+}
+
 /// SelfPattern is guaranteed to be `self`, `&self` or `&mut self` without a colon following it.
 pub(crate) struct SelfPattern {
     pub(crate) reference: bool,
@@ -112,6 +120,7 @@ impl<'a> Parser<'a> {
     /// PatternNoMut
     ///     = InternedPattern
     ///     | TuplePattern
+    ///     | ArrayPattern
     ///     | StructPattern
     ///     | IdentifierPattern
     ///
@@ -127,6 +136,10 @@ impl<'a> Parser<'a> {
             return Some(pattern);
         }
 
+        if let Some(pattern) = self.parse_array_pattern() {
+            return Some(pattern);
+        }
+
         let Some(mut path) = self.parse_path() else {
             if self.at_built_in_type() {
                 self.push_error(
@@ -194,6 +207,55 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// ArrayPattern = '[' ArrayPatternElements? ']'
+    ///
+    /// ArrayPatternElements = ArrayPatternElement ( ',' ArrayPatternElement )* ','?
+    ///
+    /// ArrayPatternElement = Pattern | '..'
+    fn parse_array_pattern(&mut self) -> Option<Pattern> {
+        let start_span = self.current_token_span;
+
+        if !self.eat_left_bracket() {
+            return None;
+        }
+
+        let elements = self.parse_many(
+            "array elements",
+            separated_by_comma().until(Token::RightBracket),
+            Self::parse_array_pattern_element,
+        );
+
+        let mut patterns = Vec::with_capacity(elements.len());
+        let mut rest_index = None;
+        for element in elements {
+            match element {
+                ArrayPatternElement::Pattern(pattern) => patterns.push(pattern),
+                ArrayPatternElement::Rest(span) => {
+                    if rest_index.is_some() {
+                        self.push_error(ParserErrorReason::MultipleRestPatterns, span);
+                    } else {
+                        rest_index = Some(patterns.len());
+                    }
+                }
+            }
+        }
+
+        Some(Pattern::Array(patterns, rest_index, self.span_since(start_span)))
+    }
+
+    fn parse_array_pattern_element(&mut self) -> Option<ArrayPatternElement> {
+        if self.eat(Token::DoubleDot) {
+            return Some(ArrayPatternElement::Rest(self.previous_token_span));
+        }
+
+        if let Some(pattern) = self.parse_pattern() {
+            Some(ArrayPatternElement::Pattern(pattern))
+        } else {
+            self.expected_label(ParsingRuleLabel::Pattern);
+            None
+        }
+    }
+
     /// StructPattern = Path '{' StructPatternFields? '}'
     ///
     /// StructPatternFields = StructPatternField ( ',' StructPatternField )? ','?
@@ -316,6 +378,58 @@ mod tests {
         assert_eq!(patterns.len(), 1);
     }
 
+    #[test]
+    fn parses_array_pattern() {
+        let src = "[foo, mut bar]";
+        let pattern = parse_pattern_no_errors(src);
+        let Pattern::Array(patterns, rest_index, _) = pattern else {
+            panic!("Expected an array pattern")
+        };
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(rest_index, None);
+        assert_eq!(patterns[0].to_string(), "foo");
+        assert_eq!(patterns[1].to_string(), "mut bar");
+    }
+
+    #[test]
+    fn parses_array_pattern_with_rest() {
+        let src = "[first, .., last]";
+        let pattern = parse_pattern_no_errors(src);
+        assert_eq!(pattern.to_string(), "[first, .., last]");
+        let Pattern::Array(patterns, rest_index, _) = pattern else {
+            panic!("Expected an array pattern")
+        };
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(rest_index, Some(1));
+    }
+
+    #[test]
+    fn parses_nested_array_pattern() {
+        let src = "[(a, b), [c, ..]]";
+        let pattern = parse_pattern_no_errors(src);
+        assert_eq!(pattern.to_string(), "[(a, b), [c, ..]]");
+    }
+
+    #[test]
+    fn errors_on_multiple_rest_patterns_in_array_pattern() {
+        let src = "
+        [a, .., b, ..]
+                   ^^
+        ";
+        let (src, span) = get_source_with_error_span(src);
+        let mut parser = Parser::for_str(&src);
+        let pattern = parser.parse_pattern_or_error();
+
+        let reason = get_single_error_reason(&parser.errors, span);
+        assert!(matches!(reason, ParserErrorReason::MultipleRestPatterns));
+
+        let Pattern::Array(patterns, rest_index, _) = pattern else {
+            panic!("Expected an array pattern")
+        };
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(rest_index, Some(1));
+    }
+
     #[test]
     fn parses_struct_pattern_no_fields() {
         let src = "foo::Bar {}";
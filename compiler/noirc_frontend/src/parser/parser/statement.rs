@@ -106,9 +106,8 @@ impl<'a> Parser<'a> {
         }
 
         if self.eat_keyword(Keyword::Return) {
-            self.parse_expression();
-            self.push_error(ParserErrorReason::EarlyReturn, self.span_since(start_span));
-            return Some(StatementKind::Error);
+            let value = self.parse_expression();
+            return Some(StatementKind::Return(value));
         }
 
         if self.at_keyword(Keyword::Let) {
@@ -677,18 +676,20 @@ mod tests {
     }
 
     #[test]
-    fn errors_on_return_statement() {
-        // This shouldn't be parsed as a call
-        let src = "
-        return 1
-        ^^^^^^^^
-        ";
-        let (src, span) = get_source_with_error_span(src);
-        let mut parser = Parser::for_str(&src);
-        let statement = parser.parse_statement_or_error();
-        assert!(matches!(statement.kind, StatementKind::Error));
-        let reason = get_single_error_reason(&parser.errors, span);
-        assert!(matches!(reason, ParserErrorReason::EarlyReturn));
+    fn parses_return_statement() {
+        let src = "return 1";
+        let statement = parse_statement_no_errors(src);
+        let StatementKind::Return(Some(value)) = statement.kind else {
+            panic!("Expected return with a value");
+        };
+        assert_eq!(value.to_string(), "1");
+    }
+
+    #[test]
+    fn parses_return_statement_without_value() {
+        let src = "return";
+        let statement = parse_statement_no_errors(src);
+        assert!(matches!(statement.kind, StatementKind::Return(None)));
     }
 
     #[test]
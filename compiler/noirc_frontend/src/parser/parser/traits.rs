@@ -152,31 +152,28 @@ impl<'a> Parser<'a> {
     }
 
     /// TraitItem
-    ///     = TraitType
-    ///     | TraitConstant
-    ///     | TraitFunction
+    ///     = Attributes
+    ///       ( TraitType
+    ///       | TraitConstant
+    ///       | TraitFunction
+    ///       )
     fn parse_trait_item(&mut self) -> Option<TraitItem> {
-        if let Some(item) = self.parse_trait_type() {
-            return Some(item);
-        }
+        let attributes = self.parse_attributes();
+        let attributes = self.validate_secondary_attributes(attributes);
 
-        if let Some(item) = self.parse_trait_constant() {
-            return Some(item);
+        if self.eat_keyword(Keyword::Type) {
+            return Some(self.parse_trait_type(attributes));
         }
 
-        if let Some(item) = self.parse_trait_function() {
-            return Some(item);
+        if self.eat_keyword(Keyword::Let) {
+            return Some(self.parse_trait_constant(attributes));
         }
 
-        None
+        self.parse_trait_function(attributes)
     }
 
     /// TraitType = 'type' identifier ';'
-    fn parse_trait_type(&mut self) -> Option<TraitItem> {
-        if !self.eat_keyword(Keyword::Type) {
-            return None;
-        }
-
+    fn parse_trait_type(&mut self, attributes: Vec<SecondaryAttribute>) -> TraitItem {
         let name = match self.eat_ident() {
             Some(name) => name,
             None => {
@@ -187,15 +184,11 @@ impl<'a> Parser<'a> {
 
         self.eat_semicolons();
 
-        Some(TraitItem::Type { name })
+        TraitItem::Type { name, attributes }
     }
 
     /// TraitConstant = 'let' identifier ':' Type ( '=' Expression ) ';'
-    fn parse_trait_constant(&mut self) -> Option<TraitItem> {
-        if !self.eat_keyword(Keyword::Let) {
-            return None;
-        }
-
+    fn parse_trait_constant(&mut self, attributes: Vec<SecondaryAttribute>) -> TraitItem {
         let name = match self.eat_ident() {
             Some(name) => name,
             None => {
@@ -216,11 +209,11 @@ impl<'a> Parser<'a> {
 
         self.eat_semicolons();
 
-        Some(TraitItem::Constant { name, typ, default_value })
+        TraitItem::Constant { name, typ, default_value, attributes }
     }
 
     /// TraitFunction = Modifiers Function
-    fn parse_trait_function(&mut self) -> Option<TraitItem> {
+    fn parse_trait_function(&mut self, attributes: Vec<SecondaryAttribute>) -> Option<TraitItem> {
         let modifiers = self.parse_modifiers(
             false, // allow mut
         );
@@ -258,6 +251,7 @@ impl<'a> Parser<'a> {
             return_type: function.return_type,
             where_clause: function.where_clause,
             body: function.body,
+            attributes,
         })
     }
 }
@@ -461,7 +455,7 @@ mod tests {
         assert_eq!(noir_trait.items.len(), 1);
 
         let item = noir_trait.items.remove(0).item;
-        let TraitItem::Type { name } = item else {
+        let TraitItem::Type { name, .. } = item else {
             panic!("Expected type");
         };
         assert_eq!(name.to_string(), "Elem");
@@ -475,7 +469,7 @@ mod tests {
         assert_eq!(noir_trait.items.len(), 1);
 
         let item = noir_trait.items.remove(0).item;
-        let TraitItem::Constant { name, typ, default_value } = item else {
+        let TraitItem::Constant { name, typ, default_value, .. } = item else {
             panic!("Expected constant");
         };
         assert_eq!(name.to_string(), "x");
@@ -2,6 +2,7 @@
 
 mod aliases;
 mod arithmetic_generics;
+mod array_patterns;
 mod bound_checks;
 mod imports;
 mod metaprogramming;
@@ -903,6 +904,7 @@ fn find_lambda_captures(stmts: &[StmtId], interner: &NodeInterner, result: &mut
             HirStatement::Error => panic!("Invalid HirStatement!"),
             HirStatement::Break => panic!("Unexpected break"),
             HirStatement::Continue => panic!("Unexpected continue"),
+            HirStatement::Return(..) => panic!("Unexpected return"),
             HirStatement::Comptime(_) => panic!("Unexpected comptime"),
         };
         let expr = interner.expression(&expr_id);
@@ -3799,3 +3801,228 @@ fn disallows_test_attribute_on_trait_impl_method() {
         })
     ));
 }
+
+#[test]
+fn disallows_bench_attribute_on_impl_method() {
+    let src = r#"
+    pub struct Foo {}
+    impl Foo {
+        #[bench]
+        fn foo() {}
+    }
+
+    fn main() {}
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::DefinitionError(DefCollectorErrorKind::BenchOnAssociatedFunction {
+            span: _
+        })
+    ));
+}
+
+#[test]
+fn disallows_bench_attribute_on_trait_impl_method() {
+    let src = r#"
+    pub trait Trait {
+        fn foo() {}
+    }
+
+    pub struct Foo {}
+    impl Trait for Foo {
+        #[bench]
+        fn foo() {}
+    }
+
+    fn main() {}
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::DefinitionError(DefCollectorErrorKind::BenchOnAssociatedFunction {
+            span: _
+        })
+    ));
+}
+
+#[test]
+fn does_not_collect_items_with_disabled_cfg_feature() {
+    let src = r#"
+    #[cfg(feature = "debug_checks")]
+    fn check(x: Field) {
+        assert(x != 0);
+    }
+
+    fn check(_x: Field) {}
+
+    #[cfg(feature = "debug_checks")]
+    pub struct Foo {}
+
+    fn main() {
+        check(1);
+    }
+    "#;
+    assert_no_errors(src);
+}
+
+#[test]
+fn does_not_collect_trait_items_with_disabled_cfg_feature() {
+    let src = r#"
+    pub trait Checker {
+        #[cfg(feature = "debug_checks")]
+        fn check(self);
+
+        #[cfg(feature = "debug_checks")]
+        let LIMIT: u32;
+
+        #[cfg(feature = "debug_checks")]
+        type Item;
+
+        fn run(self);
+    }
+
+    pub trait Limited {
+        fn limit(self) -> u32;
+    }
+
+    pub struct Foo {}
+
+    impl Checker for Foo {
+        fn run(self) {}
+    }
+
+    impl Limited for Foo {
+        #[cfg(feature = "debug_checks")]
+        let LIMIT: u32 = 10;
+
+        #[cfg(feature = "debug_checks")]
+        type Item = Field;
+
+        fn limit(self) -> u32 {
+            0
+        }
+    }
+
+    fn main() {
+        let foo = Foo {};
+        foo.run();
+        assert_eq(foo.limit(), 0);
+    }
+    "#;
+    assert_no_errors(src);
+}
+
+#[test]
+fn errors_if_using_item_with_disabled_cfg_feature() {
+    let src = r#"
+    #[cfg(feature = "debug_checks")]
+    fn check(_x: Field) {}
+
+    fn main() {
+        check(1);
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    let CompilationError::ResolverError(ResolverError::VariableNotDeclared { name, .. }) =
+        &errors[0].0
+    else {
+        panic!("Expected a 'variable not declared' error, got {:?}", errors[0].0);
+    };
+    assert_eq!(name, "check");
+}
+
+#[test]
+fn allows_early_return() {
+    let src = r#"
+    fn main(x: u32) -> pub u32 {
+        let double_or_zero = |y: u32| {
+            if y > 10 {
+                return 0;
+            }
+            y * 2
+        };
+        if x == 0 {
+            return 1;
+        }
+        double_or_zero(x)
+    }
+
+    pub unconstrained fn first_zero(array: [Field; 3]) -> u32 {
+        for i in 0..3 {
+            if array[i] == 0 {
+                return i;
+            }
+        }
+        3
+    }
+    "#;
+    assert_no_errors(src);
+}
+
+#[test]
+fn errors_on_return_with_wrong_type() {
+    let src = r#"
+    fn main(x: u32) -> pub u32 {
+        if x == 0 {
+            return true;
+        }
+        x
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    let CompilationError::TypeError(TypeCheckError::TypeMismatch {
+        expected_typ, expr_typ, ..
+    }) = &errors[0].0
+    else {
+        panic!("Expected a type mismatch error, got {:?}", errors[0].0);
+    };
+    assert_eq!(expected_typ, "u32");
+    assert_eq!(expr_typ, "bool");
+}
+
+#[test]
+fn errors_on_return_in_comptime_block() {
+    let src = r#"
+    fn main() {
+        comptime {
+            return;
+        }
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert!(!errors.is_empty());
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::ReturnOutsideFunction { .. })
+    ));
+}
+
+#[test]
+fn interprets_early_return_in_comptime_function() {
+    let src = r#"
+    comptime fn clamp(x: u32, max: u32) -> u32 {
+        if x > max {
+            return max;
+        }
+        x
+    }
+
+    fn main() {
+        comptime {
+            assert_eq(clamp(3, 2), 2);
+            assert_eq(clamp(1, 2), 1);
+        }
+    }
+    "#;
+    assert_no_errors(src);
+}
@@ -0,0 +1,147 @@
+use crate::{
+    hir::{def_collector::dc_crate::CompilationError, type_check::TypeCheckError},
+    monomorphization::errors::MonomorphizationError,
+};
+
+use super::{
+    assert_no_errors, get_monomorphization_error, get_program_errors, monomorphize_program,
+};
+
+#[test]
+fn array_patterns_in_let_parameters_and_lambdas() {
+    let src = r#"
+    fn main() {
+        let [a, b, c] = [1, 2, 3];
+        let [first, .., last] = [a, b, c];
+        let [.., mut z] = [first, last];
+        z += 1;
+        assert_eq(sum([a, b]) + c, 6);
+        let add = |[x, y]: [Field; 2]| x + y;
+        assert_eq(add([first, z]), 5);
+    }
+
+    fn sum([x, y]: [Field; 2]) -> Field {
+        x + y
+    }
+    "#;
+    assert_no_errors(src);
+    assert!(monomorphize_program(src).is_ok());
+}
+
+#[test]
+fn nested_array_patterns() {
+    let src = r#"
+    fn main() {
+        let [(a, [b, c]), ..] = [(1, [2, 3]), (4, [5, 6])];
+        assert_eq(a + b + c, 6);
+    }
+    "#;
+    assert_no_errors(src);
+    assert!(monomorphize_program(src).is_ok());
+}
+
+#[test]
+fn errors_if_array_pattern_length_does_not_match() {
+    let src = r#"
+    fn main() {
+        let [_a, _b] = [1, 2, 3];
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::TypeError(TypeCheckError::TypeMismatchWithSource { .. }),
+    ));
+}
+
+#[test]
+fn errors_if_array_pattern_with_rest_is_longer_than_array() {
+    let src = r#"
+    fn main() {
+        let [_a, .., _b, _c] = [1, 2];
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    let CompilationError::TypeError(TypeCheckError::ArrayPatternTooLong { pattern_length, .. }) =
+        &errors[0].0
+    else {
+        panic!("Expected an ArrayPatternTooLong error, got {:?}", errors[0].0);
+    };
+    assert_eq!(*pattern_length, 3);
+}
+
+#[test]
+fn errors_on_array_pattern_for_slice() {
+    let src = r#"
+    fn main() {
+        let [_a, ..] = &[1, 2];
+        let [_b, _c] = &[1, 2];
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 2);
+    for (error, _) in errors {
+        assert!(matches!(
+            error,
+            CompilationError::TypeError(TypeCheckError::ArrayPatternOnSlice { .. }),
+        ));
+    }
+}
+
+#[test]
+fn errors_on_array_pattern_for_slice_parameter() {
+    let src = r#"
+    fn main() {
+        foo(&[1, 2]);
+    }
+
+    fn foo([_a, _b]: [Field]) {}
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::TypeError(TypeCheckError::ArrayPatternOnSlice { .. }),
+    ));
+}
+
+#[test]
+fn rest_pattern_on_array_with_generic_length() {
+    let src = r#"
+    fn main() {
+        assert_eq(head([1, 2]), 1);
+        assert_eq(head([3]), 3);
+    }
+
+    fn head<let N: u32>(array: [Field; N]) -> Field {
+        let [x, ..] = array;
+        x
+    }
+    "#;
+    assert_no_errors(src);
+    assert!(monomorphize_program(src).is_ok());
+}
+
+#[test]
+fn errors_if_array_pattern_with_rest_is_longer_than_generic_array() {
+    let src = r#"
+    fn main() {
+        let _ = first_two([1]);
+    }
+
+    fn first_two<let N: u32>(array: [Field; N]) -> Field {
+        let [x, y, ..] = array;
+        x + y
+    }
+    "#;
+    assert_no_errors(src);
+
+    let error = get_monomorphization_error(src);
+    assert!(matches!(
+        error,
+        Some(MonomorphizationError::ArrayPatternTooLong { length: 1, pattern_length: 2, .. }),
+    ));
+}
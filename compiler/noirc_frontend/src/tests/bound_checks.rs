@@ -77,3 +77,41 @@ fn underflowing_i8() {
         panic!("Expected OverflowingAssignment error, got {:?}", errors[0].0);
     }
 }
+
+#[test]
+fn overflowing_u128() {
+    let src = r#"
+        fn main() {
+            let _: u128 = 340282366920938463463374607431768211456;
+        }"#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    if let CompilationError::TypeError(error) = &errors[0].0 {
+        assert_eq!(
+            error.to_string(),
+            "The value `340282366920938463463374607431768211456` cannot fit into `u128` which has range `0..=340282366920938463463374607431768211455`"
+        );
+    } else {
+        panic!("Expected OverflowingAssignment error, got {:?}", errors[0].0);
+    }
+}
+
+#[test]
+fn underflowing_i128() {
+    let src = r#"
+        fn main() {
+            let _: i128 = -170141183460469231731687303715884105729;
+        }"#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    if let CompilationError::TypeError(error) = &errors[0].0 {
+        assert_eq!(
+            error.to_string(),
+            "The value `-170141183460469231731687303715884105729` cannot fit into `i128` which has range `-170141183460469231731687303715884105728..=170141183460469231731687303715884105727`"
+        );
+    } else {
+        panic!("Expected OverflowingAssignment error, got {:?}", errors[0].0);
+    }
+}
@@ -160,7 +160,7 @@ fn to_string<F: AcirField>(value: &PrintableValue<F>, typ: &PrintableType) -> Op
             output.push_str(&format_field_string(*f));
         }
         (PrintableValue::Field(f), PrintableType::UnsignedInteger { width }) => {
-            let uint_cast = f.to_u128() & ((1 << width) - 1); // Retain the lower 'width' bits
+            let uint_cast = f.to_u128() & (u128::MAX >> (128 - width)); // Retain the lower 'width' bits
             output.push_str(&uint_cast.to_string());
         }
         (PrintableValue::Field(f), PrintableType::SignedInteger { width }) => {
@@ -169,7 +169,7 @@ fn to_string<F: AcirField>(value: &PrintableValue<F>, typ: &PrintableType) -> Op
             // Extract sign relative to width of input
             if (uint >> (width - 1)) == 1 {
                 output.push('-');
-                uint = (uint ^ ((1 << width) - 1)) + 1; // Two's complement relative to width of input
+                uint = (uint ^ (u128::MAX >> (128 - width))) + 1; // Two's complement relative to width of input
             }
 
             output.push_str(&uint.to_string());
@@ -0,0 +1,148 @@
+use acvm::FieldElement;
+use noirc_artifacts::debug::StackFrame;
+
+use crate::expressions::{Expression, ExpressionError, LogMessage};
+
+/// Options of a breakpoint set at a debug location. A breakpoint without any of them stops
+/// execution every time the location is reached.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Breakpoint {
+    /// Only stop when this expression evaluates to `true`
+    pub(crate) condition: Option<Expression>,
+    /// Only stop when the number of hits (times the location was reached with the condition
+    /// holding) satisfies this
+    pub(crate) hit_condition: Option<HitCondition>,
+    /// Instead of stopping, log this message
+    pub(crate) log_message: Option<LogMessage>,
+}
+
+impl Breakpoint {
+    /// Builds a breakpoint from the (optional) text of its condition, hit condition and log
+    /// message, as they are entered by the user.
+    pub(crate) fn parse(
+        condition: Option<&str>,
+        hit_condition: Option<&str>,
+        log_message: Option<&str>,
+    ) -> Result<Self, ExpressionError> {
+        fn non_empty(text: Option<&str>) -> Option<&str> {
+            text.map(str::trim).filter(|text| !text.is_empty())
+        }
+
+        Ok(Breakpoint {
+            condition: non_empty(condition).map(str::parse).transpose()?,
+            hit_condition: non_empty(hit_condition).map(str::parse).transpose()?,
+            log_message: log_message.filter(|text| !text.is_empty()).map(str::parse).transpose()?,
+        })
+    }
+
+    /// Returns whether the condition of this breakpoint holds in `stack_frame`.
+    pub(crate) fn condition_holds(
+        &self,
+        stack_frame: Option<&StackFrame<FieldElement>>,
+    ) -> Result<bool, ExpressionError> {
+        match &self.condition {
+            Some(condition) => condition.evaluate_condition(stack_frame),
+            None => Ok(true),
+        }
+    }
+
+    /// Returns whether the breakpoint fires on its `hits`-th hit.
+    pub(crate) fn hit_condition_holds(&self, hits: usize) -> bool {
+        self.hit_condition.as_ref().map_or(true, |hit_condition| hit_condition.holds(hits))
+    }
+}
+
+/// When a breakpoint fires depending on how many times it was hit:
+/// `N` or `>= N` from the N-th hit on, `== N` only on the N-th hit, `> N` after the N-th hit,
+/// and `% N` every N hits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HitCondition {
+    AtLeast(usize),
+    Equal(usize),
+    Multiple(usize),
+}
+
+impl HitCondition {
+    fn holds(&self, hits: usize) -> bool {
+        match self {
+            HitCondition::AtLeast(count) => hits >= *count,
+            HitCondition::Equal(count) => hits == *count,
+            HitCondition::Multiple(count) => hits % count == 0,
+        }
+    }
+}
+
+impl std::str::FromStr for HitCondition {
+    type Err = ExpressionError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let invalid = || ExpressionError::Syntax(format!("Invalid hit condition `{text}`"));
+        let parse_count = |count: &str| count.trim().parse::<usize>().map_err(|_| invalid());
+
+        if let Some(count) = text.strip_prefix(">=") {
+            parse_count(count).map(HitCondition::AtLeast)
+        } else if let Some(count) = text.strip_prefix("==") {
+            parse_count(count).map(HitCondition::Equal)
+        } else if let Some(count) = text.strip_prefix('>') {
+            parse_count(count).map(|count| HitCondition::AtLeast(count + 1))
+        } else if let Some(count) = text.strip_prefix('%') {
+            match parse_count(count)? {
+                0 => Err(invalid()),
+                count => Ok(HitCondition::Multiple(count)),
+            }
+        } else {
+            parse_count(text).map(HitCondition::AtLeast)
+        }
+    }
+}
+
+/// A value whose writes stop execution
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Watchpoint {
+    /// A variable of a function, in any of its calls
+    Variable { function_name: String, name: String },
+    /// A cell of the Brillig VM memory, in any Brillig call
+    BrilligMemory(usize),
+}
+
+impl std::fmt::Display for Watchpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Watchpoint::Variable { function_name, name } => write!(f, "{name} in {function_name}"),
+            Watchpoint::BrilligMemory(address) => write!(f, "memory[{address}]"),
+        }
+    }
+}
+
+/// A write to a watched value that stopped execution, with the value before and after it.
+/// Values are `None` when they are not set, eg. before a variable is first assigned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WatchpointHit {
+    pub(crate) watchpoint: Watchpoint,
+    pub(crate) old_value: Option<String>,
+    pub(crate) new_value: Option<String>,
+}
+
+impl std::fmt::Display for WatchpointHit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let show = |value: &Option<String>| value.clone().unwrap_or_else(|| "<unset>".to_string());
+        write!(f, "{}: {} -> {}", self.watchpoint, show(&self.old_value), show(&self.new_value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HitCondition;
+
+    #[test]
+    fn parses_hit_conditions() {
+        assert_eq!("5".parse(), Ok(HitCondition::AtLeast(5)));
+        assert_eq!(">= 5".parse(), Ok(HitCondition::AtLeast(5)));
+        assert_eq!("> 5".parse(), Ok(HitCondition::AtLeast(6)));
+        assert_eq!("==3".parse(), Ok(HitCondition::Equal(3)));
+        assert_eq!("% 4".parse(), Ok(HitCondition::Multiple(4)));
+        assert!("% 0".parse::<HitCondition>().is_err());
+        assert!("often".parse::<HitCondition>().is_err());
+    }
+}
@@ -1,26 +1,33 @@
-use crate::foreign_calls::DebugForeignCallExecutor;
-use acvm::acir::brillig::BitSize;
+use crate::breakpoints::{Breakpoint, Watchpoint, WatchpointHit};
+use crate::expressions::{Expression, ExpressionError, Value};
+use crate::foreign_calls::{DebugForeignCall, DebugForeignCallExecutor};
+use acvm::acir::brillig::{
+    BitSize, ForeignCallParam, ForeignCallResult, MemoryAddress, Opcode as BrilligOpcode,
+};
 use acvm::acir::circuit::brillig::{BrilligBytecode, BrilligFunctionId};
-use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
+use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation, ResolvedOpcodeLocation};
 use acvm::acir::native_types::{Witness, WitnessMap, WitnessStack};
 use acvm::brillig_vm::MemoryValue;
 use acvm::pwg::{
     ACVMStatus, AcirCallWaitInfo, BrilligSolver, BrilligSolverStatus, ForeignCallWaitInfo,
-    OpcodeNotSolvable, StepResult, ACVM,
+    OpcodeNotSolvable, OpcodeResolutionError, StepResult, ACVM,
 };
-use acvm::{BlackBoxFunctionSolver, FieldElement};
+use acvm::{AcirField, BlackBoxFunctionSolver, FieldElement};
 
 use codespan_reporting::files::{Files, SimpleFile};
 use fm::FileId;
 use nargo::errors::{ExecutionError, Location};
 use nargo::NargoError;
-use noirc_artifacts::debug::{DebugArtifact, StackFrame};
+use noirc_artifacts::debug::{DebugArtifact, DebugVars, StackFrame};
 use noirc_driver::DebugFile;
+use noirc_errors::debug_info::DebugVarId;
+use noirc_printable_type::PrintableValueDisplay;
 
 use thiserror::Error;
 
 use std::collections::BTreeMap;
-use std::collections::{hash_set::Iter, HashSet};
+use std::collections::{hash_map::Iter, HashMap, HashSet};
+use std::ops::ControlFlow;
 
 /// A Noir program is composed by
 /// `n` ACIR circuits
@@ -224,6 +231,7 @@ pub(super) enum DebugCommandResult {
     Done,
     Ok,
     BreakpointReached(DebugLocation),
+    WatchpointTriggered(WatchpointHit),
     Error(NargoError<FieldElement>),
 }
 
@@ -232,6 +240,29 @@ pub struct ExecutionFrame<'a, B: BlackBoxFunctionSolver<FieldElement>> {
     acvm: ACVM<'a, FieldElement, B>,
 }
 
+impl<'a, B: BlackBoxFunctionSolver<FieldElement>> Clone for ExecutionFrame<'a, B> {
+    fn clone(&self) -> Self {
+        Self { circuit_id: self.circuit_id, acvm: self.acvm.clone() }
+    }
+}
+
+/// How many steps are executed between two snapshots of the execution state. Going back in time
+/// restores the closest snapshot before the target step, and replays at most this many steps.
+const SNAPSHOT_INTERVAL: usize = 1000;
+
+/// The state of the execution after a number of steps, from which it can be resumed
+struct Snapshot<'a, B: BlackBoxFunctionSolver<FieldElement>> {
+    step: usize,
+    acvm: ACVM<'a, FieldElement, B>,
+    current_circuit_id: u32,
+    brillig_solver: Option<BrilligSolver<'a, FieldElement, B>>,
+    witness_stack: WitnessStack<FieldElement>,
+    acvm_stack: Vec<ExecutionFrame<'a, B>>,
+    debug_vars: DebugVars<FieldElement>,
+    /// How many of the recorded foreign call results had been used at this step
+    foreign_call_index: usize,
+}
+
 pub(super) struct DebugContext<'a, B: BlackBoxFunctionSolver<FieldElement>> {
     acvm: ACVM<'a, FieldElement, B>,
     current_circuit_id: u32,
@@ -244,13 +275,30 @@ pub(super) struct DebugContext<'a, B: BlackBoxFunctionSolver<FieldElement>> {
     foreign_call_executor: Box<dyn DebugForeignCallExecutor + 'a>,
 
     debug_artifact: &'a DebugArtifact,
-    breakpoints: HashSet<DebugLocation>,
+    breakpoints: HashMap<DebugLocation, Breakpoint>,
+    /// How many times each breakpoint was hit, for breakpoints with a hit condition
+    breakpoint_hits: HashMap<DebugLocation, usize>,
+    /// Messages of the logpoints hit since they were last taken
+    log_messages: Vec<String>,
+    watchpoints: HashSet<Watchpoint>,
     source_to_locations: BTreeMap<FileId, Vec<(usize, DebugLocation)>>,
 
     circuits: &'a [Circuit<FieldElement>],
     unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
 
     acir_opcode_addresses: AddressMap,
+
+    /// Number of steps (see `step_into_opcode`) executed since the start of the program
+    step: usize,
+    /// Snapshots of the execution state sorted by step, the first one being the initial state
+    snapshots: Vec<Snapshot<'a, B>>,
+    /// Results of the foreign calls executed so far, other than the ones of the debugger
+    /// instrumentation. They are reused instead of executing the calls again when replaying.
+    foreign_call_results: Vec<ForeignCallResult<FieldElement>>,
+    /// How many of `foreign_call_results` have been used up to the current step
+    foreign_call_index: usize,
+    /// Whether steps are being executed again to go back in time, which ignores breakpoints
+    replaying: bool,
 }
 
 impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
@@ -266,7 +314,7 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         let current_circuit_id: u32 = 0;
         let initial_circuit = &circuits[current_circuit_id as usize];
         let acir_opcode_addresses = AddressMap::new(circuits, unconstrained_functions);
-        Self {
+        let mut context = Self {
             acvm: ACVM::new(
                 blackbox_solver,
                 &initial_circuit.opcodes,
@@ -281,12 +329,22 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
             backend: blackbox_solver,
             foreign_call_executor,
             debug_artifact,
-            breakpoints: HashSet::new(),
+            breakpoints: HashMap::new(),
+            breakpoint_hits: HashMap::new(),
+            log_messages: Vec::new(),
+            watchpoints: HashSet::new(),
             source_to_locations: source_to_opcodes,
             circuits,
             unconstrained_functions,
             acir_opcode_addresses,
-        }
+            step: 0,
+            snapshots: Vec::new(),
+            foreign_call_results: Vec::new(),
+            foreign_call_index: 0,
+            replaying: false,
+        };
+        context.snapshots.push(context.take_snapshot());
+        context
     }
 
     pub(super) fn get_opcodes(&self) -> &[Opcode<FieldElement>] {
@@ -306,7 +364,9 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         witness: Witness,
         value: FieldElement,
     ) -> Option<FieldElement> {
-        self.acvm.overwrite_witness(witness, value)
+        let previous_value = self.acvm.overwrite_witness(witness, value);
+        self.fork_history();
+        previous_value
     }
 
     pub(super) fn get_current_debug_location(&self) -> Option<DebugLocation> {
@@ -533,9 +593,12 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
                 self.brillig_solver = Some(solver);
                 self.handle_foreign_call(foreign_call)
             }
-            Err(err) => DebugCommandResult::Error(NargoError::ExecutionError(
-                ExecutionError::SolvingError(err, None),
-            )),
+            Err(err) => {
+                // Keep the solver, so that its memory and location can be inspected after the
+                // failure
+                self.brillig_solver = Some(solver);
+                DebugCommandResult::Error(self.execution_error(err))
+            }
         }
     }
 
@@ -543,7 +606,21 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         &mut self,
         foreign_call: ForeignCallWaitInfo<FieldElement>,
     ) -> DebugCommandResult {
-        let foreign_call_result = self.foreign_call_executor.execute(&foreign_call);
+        let watched_variable = self.watched_variable_assigned_by(&foreign_call);
+        let foreign_call_result = if DebugForeignCall::lookup(&foreign_call.function).is_some() {
+            // These only update the state of the debug variables, which is not recorded
+            self.foreign_call_executor.execute(&foreign_call)
+        } else if let Some(result) = self.foreign_call_results.get(self.foreign_call_index) {
+            self.foreign_call_index += 1;
+            Ok(result.clone())
+        } else {
+            let result = self.foreign_call_executor.execute(&foreign_call);
+            if let Ok(result) = &result {
+                self.foreign_call_results.push(result.clone());
+                self.foreign_call_index += 1;
+            }
+            result
+        };
         match foreign_call_result {
             Ok(foreign_call_result) => {
                 if let Some(mut solver) = self.brillig_solver.take() {
@@ -552,6 +629,14 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
                 } else {
                     self.acvm.resolve_pending_foreign_call(foreign_call_result);
                 }
+                if let Some((watchpoint, old_value)) = watched_variable {
+                    let new_value = self.render_variable(&watchpoint);
+                    return DebugCommandResult::WatchpointTriggered(WatchpointHit {
+                        watchpoint,
+                        old_value,
+                        new_value,
+                    });
+                }
                 // TODO: should we retry executing the opcode somehow in this
                 // case? Otherwise, executing a foreign call takes two debugging
                 // steps.
@@ -616,6 +701,35 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         DebugCommandResult::Ok
     }
 
+    /// Reports failed assertions with their payload, like `nargo execute` does, so that their
+    /// messages can be shown and matched against the expected failure of a test.
+    fn execution_error(
+        &self,
+        error: OpcodeResolutionError<FieldElement>,
+    ) -> NargoError<FieldElement> {
+        let call_stack = || {
+            self.get_call_stack()
+                .into_iter()
+                .map(|location| ResolvedOpcodeLocation {
+                    acir_function_index: location.circuit_id as usize,
+                    opcode_location: location.opcode_location,
+                })
+                .collect()
+        };
+        let error = match error {
+            OpcodeResolutionError::BrilligFunctionFailed {
+                payload: Some(payload),
+                function_id,
+                ..
+            } => ExecutionError::AssertionFailed(payload, call_stack(), Some(function_id)),
+            OpcodeResolutionError::UnsatisfiedConstrain { payload: Some(payload), .. } => {
+                ExecutionError::AssertionFailed(payload, call_stack(), None)
+            }
+            error => ExecutionError::SolvingError(error, None),
+        };
+        NargoError::ExecutionError(error)
+    }
+
     fn handle_acvm_status(&mut self, status: ACVMStatus<FieldElement>) -> DebugCommandResult {
         match status {
             ACVMStatus::Solved => {
@@ -634,15 +748,36 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
                     DebugCommandResult::Ok
                 }
             }
-            ACVMStatus::Failure(error) => DebugCommandResult::Error(NargoError::ExecutionError(
-                ExecutionError::SolvingError(error, None),
-            )),
+            ACVMStatus::Failure(error) => DebugCommandResult::Error(self.execution_error(error)),
             ACVMStatus::RequiresForeignCall(foreign_call) => self.handle_foreign_call(foreign_call),
             ACVMStatus::RequiresAcirCall(call_info) => self.handle_acir_call(call_info),
         }
     }
 
+    /// Executes a single ACIR or Brillig opcode, which is the unit of time used to go back in
+    /// time. Foreign calls that were already executed are not executed again, their recorded
+    /// results are used instead.
     pub(super) fn step_into_opcode(&mut self) -> DebugCommandResult {
+        let watched_memory = self.watched_memory_before_next_step();
+        let mut result = self.execute_step();
+        if matches!(result, DebugCommandResult::Ok | DebugCommandResult::BreakpointReached(..)) {
+            if let Some(hit) = self.written_memory_watchpoint(watched_memory) {
+                result = DebugCommandResult::WatchpointTriggered(hit);
+            }
+        }
+        self.step += 1;
+
+        let index = self.snapshots.partition_point(|snapshot| snapshot.step < self.step);
+        if index < self.snapshots.len() && self.snapshots[index].step == self.step {
+            // The state at this step may have been modified by the user (see `fork_history`)
+            self.restore_snapshot(index);
+        } else if self.step % SNAPSHOT_INTERVAL == 0 {
+            self.snapshots.push(self.take_snapshot());
+        }
+        result
+    }
+
+    fn execute_step(&mut self) -> DebugCommandResult {
         if self.brillig_solver.is_some() {
             return self.step_brillig_opcode();
         }
@@ -707,8 +842,7 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         if self.is_executing_brillig() {
             self.step_out_of_brillig_opcode()
         } else {
-            let status = self.acvm.solve_opcode();
-            self.handle_acvm_status(status)
+            self.step_into_opcode()
         }
     }
 
@@ -772,19 +906,27 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         self.brillig_solver.as_ref().map(|solver| solver.get_memory())
     }
 
+    /// Writes a cell of the Brillig memory. Returns the hit of the watchpoint on the cell if
+    /// there is one, as the write doesn't happen during a step.
     pub(super) fn write_brillig_memory(
         &mut self,
         ptr: usize,
         value: FieldElement,
         bit_size: BitSize,
-    ) {
-        if let Some(solver) = self.brillig_solver.as_mut() {
-            solver.write_memory_at(
-                ptr,
-                MemoryValue::new_checked(value, bit_size)
-                    .expect("Invalid value for the given bit size"),
-            );
-        }
+    ) -> Option<WatchpointHit> {
+        let solver = self.brillig_solver.as_mut()?;
+        let old_value = solver.get_memory().get(ptr).copied().unwrap_or_default();
+        let new_value = MemoryValue::new_checked(value, bit_size)
+            .expect("Invalid value for the given bit size");
+        solver.write_memory_at(ptr, new_value);
+        self.fork_history();
+
+        let watchpoint = Watchpoint::BrilligMemory(ptr);
+        self.watchpoints.contains(&watchpoint).then(|| WatchpointHit {
+            watchpoint,
+            old_value: Some(old_value.to_string()),
+            new_value: Some(new_value.to_string()),
+        })
     }
 
     pub(super) fn get_variables(&self) -> Vec<StackFrame<FieldElement>> {
@@ -795,14 +937,82 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         return self.foreign_call_executor.current_stack_frame();
     }
 
-    fn breakpoint_reached(&self) -> bool {
-        if let Some(location) = self.get_current_debug_location() {
-            self.breakpoints.contains(&location)
-        } else {
-            false
+    /// Evaluates `expression` over the variables of the stack frame at `frame_index` (as
+    /// returned by `get_variables`), or of the current stack frame if not given.
+    pub(super) fn evaluate_expression(
+        &self,
+        expression: &str,
+        frame_index: Option<usize>,
+    ) -> Result<Value, ExpressionError> {
+        let expression: Expression = expression.parse()?;
+        let stack_frame = match frame_index {
+            Some(frame_index) => self.get_variables().into_iter().nth(frame_index),
+            None => self.current_stack_frame(),
+        };
+        expression.evaluate(stack_frame.as_ref())
+    }
+
+    /// Returns whether execution should stop at the current location because of a breakpoint.
+    /// Breakpoints only stop when their condition and hit condition hold, and logpoints record
+    /// their message instead of stopping.
+    fn breakpoint_reached(&mut self) -> bool {
+        if self.replaying {
+            return false;
+        }
+        let Some(location) = self.get_current_debug_location() else {
+            return false;
+        };
+        let Some(breakpoint) = self.breakpoints.get(&location) else {
+            return false;
+        };
+        let stack_frame = self.foreign_call_executor.current_stack_frame();
+
+        match breakpoint.condition_holds(stack_frame.as_ref()) {
+            Ok(true) => (),
+            Ok(false) => return false,
+            Err(error) => {
+                // Stop so that the user notices the condition is wrong
+                self.log_messages.push(format!(
+                    "Could not evaluate the condition of the breakpoint at {location}: {error}"
+                ));
+                return true;
+            }
+        }
+
+        let hits = self.breakpoint_hits.entry(location).or_default();
+        *hits += 1;
+        if !breakpoint.hit_condition_holds(*hits) {
+            return false;
+        }
+
+        match &breakpoint.log_message {
+            Some(log_message) => {
+                self.log_messages.push(log_message.format(stack_frame.as_ref()));
+                false
+            }
+            None => true,
         }
     }
 
+    /// Returns whether going forward would stop at the current location because of a breakpoint,
+    /// without taking hit conditions into account.
+    fn breakpoint_stops_here(&self) -> bool {
+        let Some(breakpoint) =
+            self.get_current_debug_location().and_then(|location| self.breakpoints.get(&location))
+        else {
+            return false;
+        };
+        let stack_frame = self.foreign_call_executor.current_stack_frame();
+        breakpoint.log_message.is_none()
+            && breakpoint.condition_holds(stack_frame.as_ref()).unwrap_or(true)
+    }
+
+    /// Returns the messages logged by logpoints (and failed breakpoint conditions) since the
+    /// last time they were taken.
+    pub(super) fn take_log_messages(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.log_messages)
+    }
+
     pub(super) fn is_valid_debug_location(&self, location: &DebugLocation) -> bool {
         if location.circuit_id as usize >= self.circuits.len() {
             return false;
@@ -827,23 +1037,357 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
     }
 
     pub(super) fn is_breakpoint_set(&self, location: &DebugLocation) -> bool {
-        self.breakpoints.contains(location)
+        self.breakpoints.contains_key(location)
+    }
+
+    pub(super) fn get_breakpoint(&self, location: &DebugLocation) -> Option<&Breakpoint> {
+        self.breakpoints.get(location)
     }
 
     pub(super) fn add_breakpoint(&mut self, location: DebugLocation) -> bool {
-        self.breakpoints.insert(location)
+        if self.breakpoints.contains_key(&location) {
+            return false;
+        }
+        self.set_breakpoint(location, Breakpoint::default())
+    }
+
+    /// Sets a breakpoint at `location`, replacing the one already there (and resetting its hit
+    /// count). Returns true if there was no breakpoint at `location`.
+    pub(super) fn set_breakpoint(
+        &mut self,
+        location: DebugLocation,
+        breakpoint: Breakpoint,
+    ) -> bool {
+        self.breakpoint_hits.remove(&location);
+        self.breakpoints.insert(location, breakpoint).is_none()
     }
 
     pub(super) fn delete_breakpoint(&mut self, location: &DebugLocation) -> bool {
-        self.breakpoints.remove(location)
+        self.breakpoint_hits.remove(location);
+        self.breakpoints.remove(location).is_some()
     }
 
-    pub(super) fn iterate_breakpoints(&self) -> Iter<'_, DebugLocation> {
+    pub(super) fn iterate_breakpoints(&self) -> Iter<'_, DebugLocation, Breakpoint> {
         self.breakpoints.iter()
     }
 
     pub(super) fn clear_breakpoints(&mut self) {
         self.breakpoints.clear();
+        self.breakpoint_hits.clear();
+    }
+
+    /// Returns the watchpoint on the variable `name` of the current function, if there is one.
+    pub(super) fn variable_watchpoint(&self, name: &str) -> Option<Watchpoint> {
+        let stack_frame = self.current_stack_frame()?;
+        Some(Watchpoint::Variable {
+            function_name: stack_frame.function_name.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    /// Returns true if the watchpoint was not already set.
+    pub(super) fn add_watchpoint(&mut self, watchpoint: Watchpoint) -> bool {
+        self.watchpoints.insert(watchpoint)
+    }
+
+    pub(super) fn delete_watchpoint(&mut self, watchpoint: &Watchpoint) -> bool {
+        self.watchpoints.remove(watchpoint)
+    }
+
+    pub(super) fn iterate_watchpoints(&self) -> impl Iterator<Item = &Watchpoint> {
+        self.watchpoints.iter()
+    }
+
+    pub(super) fn clear_watchpoints(&mut self) {
+        self.watchpoints.clear();
+    }
+
+    /// If `foreign_call` assigns a watched variable of the current function, returns the
+    /// watchpoint together with the value of the variable before the assignment.
+    fn watched_variable_assigned_by(
+        &self,
+        foreign_call: &ForeignCallWaitInfo<FieldElement>,
+    ) -> Option<(Watchpoint, Option<String>)> {
+        if self.replaying || self.watchpoints.is_empty() {
+            return None;
+        }
+        match DebugForeignCall::lookup(&foreign_call.function)? {
+            DebugForeignCall::VarAssign
+            | DebugForeignCall::MemberAssign(_)
+            | DebugForeignCall::DerefAssign => (),
+            _ => return None,
+        }
+        let Some(ForeignCallParam::Single(var_id)) = foreign_call.inputs.first() else {
+            return None;
+        };
+        let var_id = DebugVarId(var_id.to_u128() as u32);
+        let debug_symbols =
+            self.debug_artifact.debug_symbols.get(self.current_circuit_id as usize)?;
+        let variable = debug_symbols.variables.get(&var_id)?;
+
+        let watchpoint = self.variable_watchpoint(&variable.name)?;
+        if !self.watchpoints.contains(&watchpoint) {
+            return None;
+        }
+        let old_value = self.render_variable(&watchpoint);
+        Some((watchpoint, old_value))
+    }
+
+    /// Renders the current value of the variable of a watchpoint, if it's set
+    fn render_variable(&self, watchpoint: &Watchpoint) -> Option<String> {
+        let Watchpoint::Variable { name, .. } = watchpoint else {
+            return None;
+        };
+        let stack_frame = self.current_stack_frame()?;
+        let (_, value, var_type) =
+            stack_frame.variables.iter().rev().find(|(var_name, ..)| var_name == name)?;
+        Some(PrintableValueDisplay::Plain((*value).clone(), (*var_type).clone()).to_string())
+    }
+
+    /// Returns the Brillig opcode executed by the next step along with the memory it runs on:
+    /// either the current Brillig opcode, or the first one of the Brillig function called by the
+    /// current ACIR opcode, which starts with an empty memory.
+    fn next_brillig_opcode(
+        &self,
+    ) -> Option<(&BrilligOpcode<FieldElement>, &[MemoryValue<FieldElement>])> {
+        if let Some(solver) = &self.brillig_solver {
+            let bytecode = &self.unconstrained_functions[solver.function_id.as_usize()].bytecode;
+            return Some((bytecode.get(solver.program_counter())?, solver.get_memory()));
+        }
+        let Opcode::BrilligCall { id, .. } =
+            self.get_opcodes().get(self.acvm.instruction_pointer())?
+        else {
+            return None;
+        };
+        Some((self.unconstrained_functions[id.as_usize()].bytecode.first()?, &[]))
+    }
+
+    /// Returns the watched Brillig memory cells along with their values before the next step,
+    /// and whether the opcode executed by the step writes to them. The destinations of the
+    /// opcode are needed so that writing the value a cell already has stops execution too, while
+    /// the values are compared after the step to catch the writes of foreign calls and black box
+    /// functions, which go through pointers.
+    /// Like in the Brillig VM, cells that were never written read as zero.
+    fn watched_memory_before_next_step(&self) -> Vec<(usize, MemoryValue<FieldElement>, bool)> {
+        if self.replaying || self.watchpoints.is_empty() {
+            return vec![];
+        }
+        let Some((opcode, memory)) = self.next_brillig_opcode() else {
+            return vec![];
+        };
+        let written = brillig_memory_writes(opcode, memory);
+        let mut addresses: Vec<usize> = self
+            .watchpoints
+            .iter()
+            .filter_map(|watchpoint| match watchpoint {
+                Watchpoint::BrilligMemory(address) => Some(*address),
+                Watchpoint::Variable { .. } => None,
+            })
+            .collect();
+        addresses.sort_unstable();
+        addresses
+            .into_iter()
+            .map(|address| {
+                let value = memory.get(address).copied().unwrap_or_default();
+                (address, value, written.contains(&address))
+            })
+            .collect()
+    }
+
+    /// Builds the hit of the first watched memory cell written by the last step, if the step
+    /// was executed.
+    fn written_memory_watchpoint(
+        &self,
+        watched_memory: Vec<(usize, MemoryValue<FieldElement>, bool)>,
+    ) -> Option<WatchpointHit> {
+        let memory = self.get_brillig_memory()?;
+        watched_memory.into_iter().find_map(|(address, old_value, written)| {
+            let new_value = memory.get(address).copied().unwrap_or_default();
+            (written || new_value != old_value).then(|| WatchpointHit {
+                watchpoint: Watchpoint::BrilligMemory(address),
+                old_value: Some(old_value.to_string()),
+                new_value: Some(new_value.to_string()),
+            })
+        })
+    }
+
+    /// Returns whether execution is at the start of the program, so it can't go back any further.
+    pub(super) fn is_at_start(&self) -> bool {
+        self.step == 0
+    }
+
+    /// Steps back to the state before the last executed opcode.
+    pub(super) fn step_back_into_opcode(&mut self) -> DebugCommandResult {
+        if self.step > 0 {
+            self.rewind_to(self.step - 1);
+        }
+        DebugCommandResult::Ok
+    }
+
+    /// Steps back to the start of the previous source location
+    pub(super) fn reverse_next_into(&mut self) -> DebugCommandResult {
+        self.reverse_next(|_| true, false)
+    }
+
+    /// Steps back to the start of the previous source location at the same (or less) call stack
+    /// depth (eg. don't dive into function calls)
+    pub(super) fn reverse_next_over(&mut self) -> DebugCommandResult {
+        let start_depth = self.get_source_call_stack().len();
+        self.reverse_next(|depth| depth <= start_depth, true)
+    }
+
+    /// Steps back to the start of the source location that called the current function
+    pub(super) fn reverse_next_out(&mut self) -> DebugCommandResult {
+        let start_depth = self.get_source_call_stack().len();
+        self.reverse_next(|depth| depth < start_depth, true)
+    }
+
+    /// Goes back to the start of the previous source location whose call stack depth is accepted
+    /// by `accepts_depth`, or to the first source location of the program if there is none.
+    /// When `over_calls` is set, steps in deeper stack frames are skipped while looking for the
+    /// start of that location.
+    fn reverse_next(
+        &mut self,
+        accepts_depth: impl Fn(usize) -> bool,
+        over_calls: bool,
+    ) -> DebugCommandResult {
+        let start_location = self.get_current_source_location();
+        let mut found: Option<(Vec<Location>, usize)> = None;
+        let mut target = None;
+        let mut first_located_step = None;
+
+        self.scan_history_backwards(
+            |context| {
+                (context.get_current_source_location(), context.get_source_call_stack().len())
+            },
+            |step, (location, depth)| {
+                let Some(location) = location else {
+                    return ControlFlow::Continue(());
+                };
+                first_located_step = Some(step);
+                match &found {
+                    None => {
+                        if Some(&location) != start_location.as_ref() && accepts_depth(depth) {
+                            found = Some((location, depth));
+                            target = Some(step);
+                        }
+                    }
+                    Some((found_location, found_depth)) => {
+                        if depth == *found_depth && location == *found_location {
+                            target = Some(step);
+                        } else if !(over_calls && depth > *found_depth) {
+                            return ControlFlow::Break(());
+                        }
+                    }
+                }
+                ControlFlow::Continue(())
+            },
+        );
+
+        self.rewind_to(target.or(first_located_step).unwrap_or(0));
+        DebugCommandResult::Ok
+    }
+
+    /// Goes back to the last location where going forward would have stopped because of a
+    /// breakpoint, or to the start of the program if there is none. Hit conditions are ignored,
+    /// and logpoints don't log anything.
+    pub(super) fn reverse_continue(&mut self) -> DebugCommandResult {
+        let mut target = 0;
+        self.scan_history_backwards(Self::breakpoint_stops_here, |step, stops| {
+            if stops {
+                target = step;
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        self.rewind_to(target);
+        match self.get_current_debug_location() {
+            Some(location) if self.breakpoint_stops_here() => {
+                DebugCommandResult::BreakpointReached(location)
+            }
+            _ => DebugCommandResult::Ok,
+        }
+    }
+
+    /// Visits the states of the execution before the current one, from the most recent to the
+    /// oldest, until `visit` breaks. `probe` extracts from each state what `visit` needs. This
+    /// leaves execution at an earlier step, so callers must rewind to the step they want.
+    fn scan_history_backwards<T>(
+        &mut self,
+        probe: impl Fn(&Self) -> T,
+        mut visit: impl FnMut(usize, T) -> ControlFlow<()>,
+    ) {
+        let replaying = std::mem::replace(&mut self.replaying, true);
+        let mut end = self.step;
+        while end > 0 {
+            // Replay the steps from the last snapshot before `end`
+            let index = self.snapshots.partition_point(|snapshot| snapshot.step < end) - 1;
+            self.restore_snapshot(index);
+            let mut states = vec![(self.step, probe(self))];
+            while self.step + 1 < end {
+                self.step_into_opcode();
+                states.push((self.step, probe(self)));
+            }
+
+            for (step, state) in states.into_iter().rev() {
+                if visit(step, state).is_break() {
+                    self.replaying = replaying;
+                    return;
+                }
+            }
+            end = self.snapshots[index].step;
+        }
+        self.replaying = replaying;
+    }
+
+    /// Brings execution back to the state it had after `step` steps, by restoring the last
+    /// snapshot before it and replaying the steps in between.
+    fn rewind_to(&mut self, step: usize) {
+        let index = self.snapshots.partition_point(|snapshot| snapshot.step <= step) - 1;
+        self.restore_snapshot(index);
+
+        let replaying = std::mem::replace(&mut self.replaying, true);
+        while self.step < step {
+            self.step_into_opcode();
+        }
+        self.replaying = replaying;
+    }
+
+    fn take_snapshot(&self) -> Snapshot<'a, B> {
+        Snapshot {
+            step: self.step,
+            acvm: self.acvm.clone(),
+            current_circuit_id: self.current_circuit_id,
+            brillig_solver: self.brillig_solver.clone(),
+            witness_stack: self.witness_stack.clone(),
+            acvm_stack: self.acvm_stack.clone(),
+            debug_vars: self.foreign_call_executor.save_debug_vars(),
+            foreign_call_index: self.foreign_call_index,
+        }
+    }
+
+    fn restore_snapshot(&mut self, index: usize) {
+        let snapshot = &self.snapshots[index];
+        self.step = snapshot.step;
+        self.acvm = snapshot.acvm.clone();
+        self.current_circuit_id = snapshot.current_circuit_id;
+        self.brillig_solver = snapshot.brillig_solver.clone();
+        self.witness_stack = snapshot.witness_stack.clone();
+        self.acvm_stack = snapshot.acvm_stack.clone();
+        self.foreign_call_executor.restore_debug_vars(snapshot.debug_vars.clone());
+        self.foreign_call_index = snapshot.foreign_call_index;
+    }
+
+    /// Called when the user modifies the execution state. What was recorded after the current
+    /// step no longer applies, and the modified state is saved so that it is restored when
+    /// this step is replayed.
+    fn fork_history(&mut self) {
+        let step = self.step;
+        self.foreign_call_results.truncate(self.foreign_call_index);
+        self.snapshots.retain(|snapshot| snapshot.step < step);
+        self.snapshots.push(self.take_snapshot());
     }
 
     pub(super) fn is_solved(&self) -> bool {
@@ -857,6 +1401,47 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
     }
 }
 
+/// Returns the memory cells a Brillig opcode writes to, resolving its addresses in `memory`.
+/// Foreign calls and black box functions aren't included, as they write their outputs through
+/// pointers once their results are known: their writes are found by comparing values instead.
+fn brillig_memory_writes(
+    opcode: &BrilligOpcode<FieldElement>,
+    memory: &[MemoryValue<FieldElement>],
+) -> Vec<usize> {
+    let read_address = |address: usize| {
+        let value = memory.get(address)?.extract_integer()?.0;
+        usize::try_from(value).ok()
+    };
+    let resolve = |address: MemoryAddress| match address {
+        MemoryAddress::Direct(address) => Some(address),
+        MemoryAddress::Relative(offset) => Some(read_address(0)? + offset),
+    };
+
+    match opcode {
+        BrilligOpcode::BinaryFieldOp { destination, .. }
+        | BrilligOpcode::BinaryIntOp { destination, .. }
+        | BrilligOpcode::Not { destination, .. }
+        | BrilligOpcode::Cast { destination, .. }
+        | BrilligOpcode::Const { destination, .. }
+        | BrilligOpcode::Mov { destination, .. }
+        | BrilligOpcode::ConditionalMov { destination, .. }
+        | BrilligOpcode::Load { destination, .. } => resolve(*destination).into_iter().collect(),
+        BrilligOpcode::IndirectConst { destination_pointer, .. }
+        | BrilligOpcode::Store { destination_pointer, .. } => {
+            resolve(*destination_pointer).and_then(read_address).into_iter().collect()
+        }
+        BrilligOpcode::CalldataCopy { destination_address, size_address, .. } => {
+            let destination = resolve(*destination_address);
+            let size = resolve(*size_address).and_then(read_address);
+            match (destination, size) {
+                (Some(destination), Some(size)) => (destination..destination + size).collect(),
+                _ => vec![],
+            }
+        }
+        _ => vec![],
+    }
+}
+
 fn is_debug_file_in_debug_crate(debug_file: &DebugFile) -> bool {
     debug_file.path.starts_with("__debug/")
 }
@@ -954,13 +1539,12 @@ mod tests {
     use crate::foreign_calls::DefaultDebugForeignCallExecutor;
     use acvm::{
         acir::{
-            brillig::{HeapVector, IntegerBitSize},
+            brillig::{BlackBoxOp, HeapArray, HeapVector, IntegerBitSize},
             circuit::{
                 brillig::{BrilligFunctionId, BrilligInputs, BrilligOutputs},
                 opcodes::{AcirFunctionId, BlockId, BlockType},
             },
             native_types::Expression,
-            AcirField,
         },
         blackbox_solver::StubbedBlackBoxSolver,
         brillig_vm::brillig::{
@@ -1025,8 +1609,13 @@ mod tests {
 
         let initial_witness = BTreeMap::from([(Witness(1), fe_1)]).into();
 
-        let foreign_call_executor =
-            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact));
+        let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            None,
+            None,
+            None,
+            debug_artifact,
+        ));
         let mut context = DebugContext::new(
             &StubbedBlackBoxSolver,
             circuits,
@@ -1190,8 +1779,13 @@ mod tests {
 
         let initial_witness = BTreeMap::from([(Witness(1), fe_1), (Witness(2), fe_1)]).into();
 
-        let foreign_call_executor =
-            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact));
+        let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            None,
+            None,
+            None,
+            debug_artifact,
+        ));
         let brillig_funcs = &vec![brillig_bytecode];
         let mut context = DebugContext::new(
             &StubbedBlackBoxSolver,
@@ -1233,6 +1827,403 @@ mod tests {
         assert_eq!(context.get_current_debug_location(), None);
     }
 
+    #[test]
+    fn test_conditional_breakpoints_hit_conditions_and_logpoints() {
+        let opcodes = vec![Opcode::AssertZero(Expression::default()); 5];
+        let circuit = Circuit { opcodes, ..Circuit::default() };
+        let circuits = &vec![circuit];
+
+        let debug_artifact = &DebugArtifact { debug_symbols: vec![], file_map: BTreeMap::new() };
+        let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            None,
+            None,
+            None,
+            debug_artifact,
+        ));
+        let brillig_funcs = &vec![];
+        let mut context = DebugContext::new(
+            &StubbedBlackBoxSolver,
+            circuits,
+            debug_artifact,
+            WitnessMap::default(),
+            foreign_call_executor,
+            brillig_funcs,
+        );
+
+        let location = |acir_index| DebugLocation {
+            circuit_id: 0,
+            opcode_location: OpcodeLocation::Acir(acir_index),
+            brillig_function_id: None,
+        };
+        let logpoint = Breakpoint::parse(None, None, Some("reached {-1 < 0}")).unwrap();
+        let never = Breakpoint::parse(Some("1 == 2"), None, None).unwrap();
+        let conditional = Breakpoint::parse(Some("true && 1 < 2"), None, None).unwrap();
+        let second_hit = Breakpoint::parse(None, Some("== 2"), None).unwrap();
+        assert!(context.set_breakpoint(location(1), logpoint));
+        assert!(context.set_breakpoint(location(2), never));
+        assert!(context.set_breakpoint(location(3), conditional));
+        assert!(context.set_breakpoint(location(4), second_hit));
+
+        // the logpoint and the breakpoint whose condition doesn't hold don't stop execution
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::BreakpointReached(at) if at == location(3)));
+        assert_eq!(context.take_log_messages(), vec!["reached true".to_string()]);
+        assert!(context.take_log_messages().is_empty());
+
+        // the last breakpoint is only hit once, so it never stops
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+    }
+
+    #[test]
+    fn test_step_back_and_reverse_continue() {
+        // Each opcode solves a witness: w_i = i
+        let opcodes = (1..=4)
+            .map(|i| {
+                Opcode::AssertZero(Expression {
+                    mul_terms: vec![],
+                    linear_combinations: vec![(FieldElement::one(), Witness(i))],
+                    q_c: -FieldElement::from(i as u128),
+                })
+            })
+            .collect();
+        let circuit = Circuit { current_witness_index: 4, opcodes, ..Circuit::default() };
+        let circuits = &vec![circuit];
+
+        let debug_artifact = &DebugArtifact { debug_symbols: vec![], file_map: BTreeMap::new() };
+        let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            None,
+            None,
+            None,
+            debug_artifact,
+        ));
+        let brillig_funcs = &vec![];
+        let mut context = DebugContext::new(
+            &StubbedBlackBoxSolver,
+            circuits,
+            debug_artifact,
+            WitnessMap::default(),
+            foreign_call_executor,
+            brillig_funcs,
+        );
+
+        let location = |acir_index| DebugLocation {
+            circuit_id: 0,
+            opcode_location: OpcodeLocation::Acir(acir_index),
+            brillig_function_id: None,
+        };
+
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+        assert_eq!(
+            context.get_witness_map().get_index(4).copied(),
+            Some(FieldElement::from(4u128))
+        );
+
+        // going back undoes the last opcode
+        context.step_back_into_opcode();
+        assert_eq!(context.get_current_debug_location(), Some(location(3)));
+        assert_eq!(
+            context.get_witness_map().get_index(3).copied(),
+            Some(FieldElement::from(3u128))
+        );
+        assert_eq!(context.get_witness_map().get_index(4).copied(), None);
+
+        // reverse continue stops at the previous breakpoint, and then at the start
+        assert!(context.add_breakpoint(location(1)));
+        let result = context.reverse_continue();
+        assert!(matches!(result, DebugCommandResult::BreakpointReached(at) if at == location(1)));
+        assert_eq!(context.get_witness_map().get_index(1).copied(), Some(FieldElement::one()));
+        assert_eq!(context.get_witness_map().get_index(2).copied(), None);
+
+        let result = context.reverse_continue();
+        assert!(matches!(result, DebugCommandResult::Ok));
+        assert!(context.is_at_start());
+        assert_eq!(context.get_current_debug_location(), Some(location(0)));
+        assert_eq!(context.get_witness_map().get_index(1).copied(), None);
+
+        // going forward again still stops at breakpoints
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::BreakpointReached(at) if at == location(1)));
+
+        // a modified witness is kept when going back to the step where it was modified
+        context.overwrite_witness(Witness(1), FieldElement::from(7u128));
+        context.step_into_opcode();
+        context.step_back_into_opcode();
+        assert_eq!(context.get_current_debug_location(), Some(location(1)));
+        assert_eq!(
+            context.get_witness_map().get_index(1).copied(),
+            Some(FieldElement::from(7u128))
+        );
+    }
+
+    #[test]
+    fn test_brillig_memory_watchpoints() {
+        let const_opcode = |address, bit_size, value: u128| BrilligOpcode::Const {
+            destination: MemoryAddress::direct(address),
+            bit_size,
+            value: FieldElement::from(value),
+        };
+        let u32_size = BitSize::Integer(IntegerBitSize::U32);
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                const_opcode(1, u32_size, 1),
+                const_opcode(2, u32_size, 0),
+                // writes the value the cell already has
+                BrilligOpcode::Mov {
+                    destination: MemoryAddress::direct(1),
+                    source: MemoryAddress::direct(1),
+                },
+                const_opcode(1, u32_size, 5),
+                BrilligOpcode::Stop {
+                    return_data: HeapVector {
+                        pointer: MemoryAddress::direct(2),
+                        size: MemoryAddress::direct(2),
+                    },
+                },
+            ],
+        };
+        let opcodes = vec![Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![],
+            outputs: vec![],
+            predicate: None,
+        }];
+        let brillig_funcs = &vec![brillig_bytecode];
+        let circuit = Circuit { current_witness_index: 0, opcodes, ..Circuit::default() };
+        let circuits = &vec![circuit];
+
+        let debug_artifact = &DebugArtifact { debug_symbols: vec![], file_map: BTreeMap::new() };
+        let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            None,
+            None,
+            None,
+            debug_artifact,
+        ));
+        let mut context = DebugContext::new(
+            &StubbedBlackBoxSolver,
+            circuits,
+            debug_artifact,
+            WitnessMap::default(),
+            foreign_call_executor,
+            brillig_funcs,
+        );
+
+        assert!(context.add_watchpoint(Watchpoint::BrilligMemory(1)));
+        assert!(context.add_watchpoint(Watchpoint::BrilligMemory(2)));
+        assert!(!context.add_watchpoint(Watchpoint::BrilligMemory(2)));
+
+        let hit = |address: usize, old_value: &str, new_value: &str| WatchpointHit {
+            watchpoint: Watchpoint::BrilligMemory(address),
+            old_value: Some(old_value.to_string()),
+            new_value: Some(new_value.to_string()),
+        };
+
+        let result = context.cont();
+        assert!(matches!(
+            result,
+            DebugCommandResult::WatchpointTriggered(ref watchpoint_hit)
+                if *watchpoint_hit == hit(1, "0: field", "1: u32")
+        ));
+
+        let result = context.cont();
+        assert!(matches!(
+            result,
+            DebugCommandResult::WatchpointTriggered(ref watchpoint_hit)
+                if *watchpoint_hit == hit(2, "0: field", "0: u32")
+        ));
+
+        // writes that don't change the watched cells stop execution too
+        let result = context.cont();
+        assert!(matches!(
+            result,
+            DebugCommandResult::WatchpointTriggered(ref watchpoint_hit)
+                if *watchpoint_hit == hit(1, "1: u32", "1: u32")
+        ));
+
+        let result = context.cont();
+        assert!(matches!(
+            result,
+            DebugCommandResult::WatchpointTriggered(ref watchpoint_hit)
+                if *watchpoint_hit == hit(1, "1: u32", "5: u32")
+        ));
+        assert_eq!(
+            context.get_current_debug_location(),
+            Some(DebugLocation {
+                circuit_id: 0,
+                opcode_location: OpcodeLocation::Brillig { acir_index: 0, brillig_index: 4 },
+                brillig_function_id: Some(BrilligFunctionId(0)),
+            })
+        );
+
+        assert!(context.delete_watchpoint(&Watchpoint::BrilligMemory(1)));
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+    }
+
+    #[test]
+    fn test_brillig_memory_watchpoints_on_black_box_and_user_writes() {
+        let u32_size = BitSize::Integer(IntegerBitSize::U32);
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(0),
+                    bit_size: BitSize::Field,
+                    value: FieldElement::from(258u128),
+                },
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(1),
+                    bit_size: u32_size,
+                    value: FieldElement::from(256u128),
+                },
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(2),
+                    bit_size: u32_size,
+                    value: FieldElement::from(10u128),
+                },
+                // writes the limbs of 258 to the cells 10 and 11 through the pointer in cell 2
+                BrilligOpcode::BlackBox(BlackBoxOp::ToRadix {
+                    input: MemoryAddress::direct(0),
+                    radix: MemoryAddress::direct(1),
+                    output: HeapArray { pointer: MemoryAddress::direct(2), size: 2 },
+                    output_bits: false,
+                }),
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(3),
+                    bit_size: u32_size,
+                    value: FieldElement::from(0u128),
+                },
+                BrilligOpcode::Stop {
+                    return_data: HeapVector {
+                        pointer: MemoryAddress::direct(3),
+                        size: MemoryAddress::direct(3),
+                    },
+                },
+            ],
+        };
+        let opcodes = vec![Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![],
+            outputs: vec![],
+            predicate: None,
+        }];
+        let brillig_funcs = &vec![brillig_bytecode];
+        let circuit = Circuit { current_witness_index: 0, opcodes, ..Circuit::default() };
+        let circuits = &vec![circuit];
+
+        let debug_artifact = &DebugArtifact { debug_symbols: vec![], file_map: BTreeMap::new() };
+        let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            None,
+            None,
+            None,
+            debug_artifact,
+        ));
+        let mut context = DebugContext::new(
+            &StubbedBlackBoxSolver,
+            circuits,
+            debug_artifact,
+            WitnessMap::default(),
+            foreign_call_executor,
+            brillig_funcs,
+        );
+
+        assert!(context.add_watchpoint(Watchpoint::BrilligMemory(11)));
+
+        let hit = |address: usize, old_value: &str, new_value: &str| WatchpointHit {
+            watchpoint: Watchpoint::BrilligMemory(address),
+            old_value: Some(old_value.to_string()),
+            new_value: Some(new_value.to_string()),
+        };
+
+        let result = context.cont();
+        assert!(matches!(
+            result,
+            DebugCommandResult::WatchpointTriggered(ref watchpoint_hit)
+                if *watchpoint_hit == hit(11, "0: field", "2: u8")
+        ));
+
+        // writes made from the debugger report the watchpoint on the cell
+        let u8_size = BitSize::Integer(IntegerBitSize::U8);
+        assert_eq!(
+            context.write_brillig_memory(11, FieldElement::from(7u128), u8_size),
+            Some(hit(11, "2: u8", "7: u8"))
+        );
+        assert_eq!(context.write_brillig_memory(10, FieldElement::from(7u128), u8_size), None);
+
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+    }
+
+    #[test]
+    fn test_brillig_state_is_kept_after_failure() {
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(0),
+                    bit_size: BitSize::Field,
+                    value: FieldElement::from(7u128),
+                },
+                // the revert data is empty
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(1),
+                    bit_size: BitSize::Integer(IntegerBitSize::U32),
+                    value: FieldElement::from(0u128),
+                },
+                BrilligOpcode::Trap {
+                    revert_data: HeapVector {
+                        pointer: MemoryAddress::direct(1),
+                        size: MemoryAddress::direct(1),
+                    },
+                },
+            ],
+        };
+        let opcodes = vec![Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![],
+            outputs: vec![],
+            predicate: None,
+        }];
+        let brillig_funcs = &vec![brillig_bytecode];
+        let circuit = Circuit { current_witness_index: 0, opcodes, ..Circuit::default() };
+        let circuits = &vec![circuit];
+
+        let debug_artifact = &DebugArtifact { debug_symbols: vec![], file_map: BTreeMap::new() };
+        let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            None,
+            None,
+            None,
+            debug_artifact,
+        ));
+        let mut context = DebugContext::new(
+            &StubbedBlackBoxSolver,
+            circuits,
+            debug_artifact,
+            WitnessMap::default(),
+            foreign_call_executor,
+            brillig_funcs,
+        );
+
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Error(..)));
+
+        // the failing opcode and the memory at the moment of the failure can be inspected
+        assert_eq!(
+            context.get_current_debug_location(),
+            Some(DebugLocation {
+                circuit_id: 0,
+                opcode_location: OpcodeLocation::Brillig { acir_index: 0, brillig_index: 2 },
+                brillig_function_id: Some(BrilligFunctionId(0)),
+            })
+        );
+        let memory = context.get_brillig_memory().expect("Brillig memory should be available");
+        assert_eq!(memory[0], MemoryValue::new_field(FieldElement::from(7u128)));
+    }
+
     #[test]
     fn test_address_debug_location_mapping() {
         let brillig_one =
@@ -1285,7 +2276,7 @@ mod tests {
             &circuits,
             &debug_artifact,
             WitnessMap::new(),
-            Box::new(DefaultDebugForeignCallExecutor::new(true)),
+            Box::new(DefaultDebugForeignCallExecutor::new(true, None, None, None)),
             brillig_funcs,
         );
 
@@ -1,29 +1,37 @@
 use std::collections::BTreeMap;
 use std::io::{Read, Write};
+use std::path::PathBuf;
 
 use acvm::acir::circuit::brillig::BrilligBytecode;
 use acvm::acir::circuit::Circuit;
 use acvm::acir::native_types::WitnessMap;
-use acvm::{BlackBoxFunctionSolver, FieldElement};
+use acvm::brillig_vm::MemoryValue;
+use acvm::{AcirField, BlackBoxFunctionSolver, FieldElement};
 
+use crate::breakpoints::{Breakpoint as DebugBreakpoint, Watchpoint, WatchpointHit};
 use crate::context::DebugContext;
 use crate::context::{DebugCommandResult, DebugLocation};
-use crate::foreign_calls::DefaultDebugForeignCallExecutor;
+use crate::foreign_calls::{DebugForeignCallExecutor, DefaultDebugForeignCallExecutor};
+use crate::DebugTest;
 
 use dap::errors::ServerError;
-use dap::events::StoppedEventBody;
+use dap::events::{OutputEventBody, StoppedEventBody};
 use dap::prelude::Event;
 use dap::requests::{Command, Request, SetBreakpointsArguments};
 use dap::responses::{
-    ContinueResponse, DisassembleResponse, ResponseBody, ScopesResponse, SetBreakpointsResponse,
+    ContinueResponse, DataBreakpointInfoResponse, DisassembleResponse, EvaluateResponse,
+    ResponseBody, ScopesResponse, SetBreakpointsResponse, SetDataBreakpointsResponse,
     SetExceptionBreakpointsResponse, SetInstructionBreakpointsResponse, StackTraceResponse,
     ThreadsResponse, VariablesResponse,
 };
 use dap::server::Server;
 use dap::types::{
-    Breakpoint, DisassembledInstruction, Scope, Source, StackFrame, SteppingGranularity,
-    StoppedEventReason, Thread, Variable,
+    Breakpoint, DisassembledInstruction, OutputEventCategory, Scope, Source, StackFrame,
+    SteppingGranularity, StoppedEventReason, Thread, Variable,
 };
+use nargo::ops::{test_status_program_compile_pass, TestStatus};
+use nargo::NargoError;
+use noirc_abi::Abi;
 use noirc_artifacts::debug::DebugArtifact;
 
 use fm::FileId;
@@ -35,15 +43,19 @@ pub struct DapSession<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElem
     server: Server<R, W>,
     context: DebugContext<'a, B>,
     debug_artifact: &'a DebugArtifact,
+    abi: &'a Abi,
+    test: Option<DebugTest>,
     running: bool,
     next_breakpoint_id: BreakpointId,
-    instruction_breakpoints: Vec<(DebugLocation, BreakpointId)>,
-    source_breakpoints: BTreeMap<FileId, Vec<(DebugLocation, BreakpointId)>>,
+    instruction_breakpoints: Vec<(DebugLocation, BreakpointId, DebugBreakpoint)>,
+    source_breakpoints: BTreeMap<FileId, Vec<(DebugLocation, BreakpointId, DebugBreakpoint)>>,
+    data_breakpoints: Vec<(Watchpoint, BreakpointId)>,
 }
 
 enum ScopeReferences {
     Locals = 1,
     WitnessMap = 2,
+    BrilligMemory = 3,
     InvalidScope = 0,
 }
 
@@ -52,36 +64,44 @@ impl From<i64> for ScopeReferences {
         match value {
             1 => Self::Locals,
             2 => Self::WitnessMap,
+            3 => Self::BrilligMemory,
             _ => Self::InvalidScope,
         }
     }
 }
 
 impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<'a, R, W, B> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         server: Server<R, W>,
         solver: &'a B,
         circuits: &'a [Circuit<FieldElement>],
         debug_artifact: &'a DebugArtifact,
+        abi: &'a Abi,
         initial_witness: WitnessMap<FieldElement>,
         unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
+        foreign_call_executor: Box<dyn DebugForeignCallExecutor + 'a>,
+        test: Option<DebugTest>,
     ) -> Self {
         let context = DebugContext::new(
             solver,
             circuits,
             debug_artifact,
             initial_witness,
-            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact)),
+            foreign_call_executor,
             unconstrained_functions,
         );
         Self {
             server,
             context,
             debug_artifact,
+            abi,
+            test,
             running: false,
             next_breakpoint_id: 1,
             instruction_breakpoints: vec![],
             source_breakpoints: BTreeMap::new(),
+            data_breakpoints: vec![],
         }
     }
 
@@ -99,19 +119,24 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         Ok(())
     }
 
-    pub fn run_loop(&mut self) -> Result<(), ServerError> {
+    pub fn run_loop(&mut self, debug_on_failure: bool) -> Result<(), ServerError> {
         self.running = self.context.get_current_debug_location().is_some();
 
-        if self.running && self.context.get_current_source_location().is_none() {
-            // TODO: remove this? This is to ensure that the tool has a proper
-            // source location to show when first starting the debugger, but
-            // maybe the default behavior should be to start executing until the
-            // first breakpoint set.
-            _ = self.context.next_into();
-        }
+        if self.running && debug_on_failure {
+            self.server.send_event(Event::Initialized)?;
+            self.run_to_failure()?;
+        } else {
+            if self.running && self.context.get_current_source_location().is_none() {
+                // TODO: remove this? This is to ensure that the tool has a proper
+                // source location to show when first starting the debugger, but
+                // maybe the default behavior should be to start executing until the
+                // first breakpoint set.
+                _ = self.context.next_into();
+            }
 
-        self.server.send_event(Event::Initialized)?;
-        self.send_stopped_event(StoppedEventReason::Entry)?;
+            self.server.send_event(Event::Initialized)?;
+            self.send_stopped_event(StoppedEventReason::Entry)?;
+        }
 
         while self.running {
             let req = match self.server.poll_request()? {
@@ -135,6 +160,12 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                 Command::SetInstructionBreakpoints(_) => {
                     self.handle_set_instruction_breakpoints(req)?;
                 }
+                Command::DataBreakpointInfo(_) => {
+                    self.handle_data_breakpoint_info(req)?;
+                }
+                Command::SetDataBreakpoints(_) => {
+                    self.handle_set_data_breakpoints(req)?;
+                }
                 Command::Threads => {
                     self.server.respond(req.success(ResponseBody::Threads(ThreadsResponse {
                         threads: vec![Thread { id: 0, name: "main".to_string() }],
@@ -173,12 +204,26 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                 Command::Continue(_) => {
                     self.handle_continue(req)?;
                 }
+                Command::StepBack(ref args) => {
+                    let granularity =
+                        args.granularity.as_ref().unwrap_or(&SteppingGranularity::Statement);
+                    match granularity {
+                        SteppingGranularity::Instruction => self.handle_step_back(req)?,
+                        _ => self.handle_reverse_next_over(req)?,
+                    }
+                }
+                Command::ReverseContinue(_) => {
+                    self.handle_reverse_continue(req)?;
+                }
                 Command::Scopes(_) => {
                     self.handle_scopes(req)?;
                 }
                 Command::Variables(ref _args) => {
                     self.handle_variables(req)?;
                 }
+                Command::Evaluate(_) => {
+                    self.handle_evaluate(req)?;
+                }
                 _ => {
                     eprintln!("ERROR: unhandled command: {:?}", req.command);
                 }
@@ -320,15 +365,46 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         self.handle_execution_result(result)
     }
 
+    /// Executes the program until it fails, so that the session starts at the failing opcode
+    fn run_to_failure(&mut self) -> Result<(), ServerError> {
+        let result = self.context.cont();
+        eprintln!("INFO: executed until failure with result {result:?}");
+        if !matches!(result, DebugCommandResult::Error(..)) {
+            self.send_output(String::from("Execution did not fail in the debugger\n"))?;
+        }
+        self.handle_execution_result(result)
+    }
+
+    fn handle_step_back(&mut self, req: Request) -> Result<(), ServerError> {
+        let result = self.context.step_back_into_opcode();
+        eprintln!("INFO: stepped back by instruction with result {result:?}");
+        self.server.respond(req.ack()?)?;
+        self.handle_execution_result(result)
+    }
+
+    fn handle_reverse_next_over(&mut self, req: Request) -> Result<(), ServerError> {
+        let result = self.context.reverse_next_over();
+        eprintln!("INFO: stepped back by statement with result {result:?}");
+        self.server.respond(req.ack()?)?;
+        self.handle_execution_result(result)
+    }
+
+    fn handle_reverse_continue(&mut self, req: Request) -> Result<(), ServerError> {
+        let result = self.context.reverse_continue();
+        eprintln!("INFO: reverse continue with result {result:?}");
+        self.server.respond(req.ack()?)?;
+        self.handle_execution_result(result)
+    }
+
     fn find_breakpoints_at_location(&self, debug_location: &DebugLocation) -> Vec<i64> {
         let mut result = vec![];
-        for (location, id) in &self.instruction_breakpoints {
+        for (location, id, _) in &self.instruction_breakpoints {
             if debug_location == location {
                 result.push(*id);
             }
         }
         for breakpoints in self.source_breakpoints.values() {
-            for (location, id) in breakpoints {
+            for (location, id, _) in breakpoints {
                 if debug_location == location {
                     result.push(*id);
                 }
@@ -337,9 +413,22 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         result
     }
 
+    fn find_data_breakpoints(&self, hit: &WatchpointHit) -> Vec<i64> {
+        self.data_breakpoints
+            .iter()
+            .filter(|(watchpoint, _)| *watchpoint == hit.watchpoint)
+            .map(|(_, id)| *id)
+            .collect()
+    }
+
     fn handle_execution_result(&mut self, result: DebugCommandResult) -> Result<(), ServerError> {
+        for message in self.context.take_log_messages() {
+            self.send_output(format!("{message}\n"))?;
+        }
+
         match result {
             DebugCommandResult::Done => {
+                self.send_test_outcome(Ok(()))?;
                 self.running = false;
             }
             DebugCommandResult::Ok => {
@@ -365,6 +454,19 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                     hit_breakpoint_ids: Some(breakpoint_ids),
                 }))?;
             }
+            DebugCommandResult::WatchpointTriggered(hit) => {
+                self.send_output(format!("Stopped at watchpoint on {hit}\n"))?;
+                let breakpoint_ids = self.find_data_breakpoints(&hit);
+                self.server.send_event(Event::Stopped(StoppedEventBody {
+                    reason: StoppedEventReason::Data,
+                    description: Some(hit.to_string()),
+                    thread_id: Some(0),
+                    preserve_focus_hint: Some(false),
+                    text: None,
+                    all_threads_stopped: Some(false),
+                    hit_breakpoint_ids: Some(breakpoint_ids),
+                }))?;
+            }
             DebugCommandResult::Error(err) => {
                 self.server.send_event(Event::Stopped(StoppedEventBody {
                     reason: StoppedEventReason::Exception,
@@ -375,11 +477,53 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                     all_threads_stopped: Some(false),
                     hit_breakpoint_ids: None,
                 }))?;
+                self.send_test_outcome(Err(err))?;
             }
         }
         Ok(())
     }
 
+    /// When debugging a test, tells the client whether it passed once its execution finishes.
+    fn send_test_outcome(
+        &mut self,
+        circuit_execution: Result<(), NargoError<FieldElement>>,
+    ) -> Result<(), ServerError> {
+        let Some(test) = &self.test else {
+            return Ok(());
+        };
+
+        let status = test_status_program_compile_pass(
+            &test.function,
+            self.abi,
+            &self.debug_artifact.debug_symbols,
+            circuit_execution,
+        );
+        let output = match status {
+            TestStatus::Pass => format!("Test {} passed\n", test.name),
+            TestStatus::Fail { message, .. } => {
+                format!("Test {} failed: {}\n", test.name, message.trim())
+            }
+            TestStatus::CompileError(diagnostic) => {
+                format!("Test {} failed to compile: {}\n", test.name, diagnostic.diagnostic.message)
+            }
+        };
+
+        self.send_output(output)
+    }
+
+    fn send_output(&mut self, output: String) -> Result<(), ServerError> {
+        self.server.send_event(Event::Output(OutputEventBody {
+            category: Some(OutputEventCategory::Console),
+            output,
+            group: None,
+            variables_reference: None,
+            source: None,
+            line: None,
+            column: None,
+            data: None,
+        }))
+    }
+
     fn get_next_breakpoint_id(&mut self) -> BreakpointId {
         let id = self.next_breakpoint_id;
         self.next_breakpoint_id += 1;
@@ -388,12 +532,12 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
 
     fn reinstall_breakpoints(&mut self) {
         self.context.clear_breakpoints();
-        for (location, _) in &self.instruction_breakpoints {
-            self.context.add_breakpoint(*location);
+        for (location, _, breakpoint) in &self.instruction_breakpoints {
+            self.context.set_breakpoint(*location, breakpoint.clone());
         }
         for breakpoints in self.source_breakpoints.values() {
-            for (location, _) in breakpoints {
-                self.context.add_breakpoint(*location);
+            for (location, _, breakpoint) in breakpoints {
+                self.context.set_breakpoint(*location, breakpoint.clone());
             }
         }
     }
@@ -404,11 +548,25 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         };
 
         // compute breakpoints to set and return
-        let mut breakpoints_to_set: Vec<(DebugLocation, i64)> = vec![];
+        let mut breakpoints_to_set: Vec<(DebugLocation, i64, DebugBreakpoint)> = vec![];
         let breakpoints: Vec<Breakpoint> = args
             .breakpoints
             .iter()
             .map(|breakpoint| {
+                let options = match DebugBreakpoint::parse(
+                    breakpoint.condition.as_deref(),
+                    breakpoint.hit_condition.as_deref(),
+                    None,
+                ) {
+                    Ok(options) => options,
+                    Err(error) => {
+                        return Breakpoint {
+                            verified: false,
+                            message: Some(error.to_string()),
+                            ..Breakpoint::default()
+                        };
+                    }
+                };
                 let offset = breakpoint.offset.unwrap_or(0);
                 let address = breakpoint.instruction_reference.parse::<i64>().unwrap_or(0) + offset;
                 let Ok(address): Result<usize, _> = address.try_into() else {
@@ -430,7 +588,7 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                     };
                 };
                 let id = self.get_next_breakpoint_id();
-                breakpoints_to_set.push((location, id));
+                breakpoints_to_set.push((location, id, options));
                 Breakpoint {
                     id: Some(id),
                     verified: true,
@@ -452,6 +610,75 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         Ok(())
     }
 
+    /// Tells the client whether a variable can be watched with a data breakpoint. Local
+    /// variables and the cells of the Brillig memory can be watched.
+    fn handle_data_breakpoint_info(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::DataBreakpointInfo(ref args) = req.command else {
+            unreachable!("handle_data_breakpoint_info called on a different request");
+        };
+        let scope: Option<ScopeReferences> = args.variables_reference.map(Into::into);
+        let watchpoint = match scope {
+            Some(ScopeReferences::Locals) => self.context.variable_watchpoint(&args.name),
+            Some(ScopeReferences::BrilligMemory) => {
+                parse_memory_cell_name(&args.name).map(Watchpoint::BrilligMemory)
+            }
+            _ => None,
+        };
+        let response = match watchpoint {
+            Some(watchpoint) => DataBreakpointInfoResponse {
+                data_id: Some(watchpoint_data_id(&watchpoint)),
+                description: format!("Stop when {watchpoint} is written"),
+                access_types: None,
+                can_persist: Some(false),
+            },
+            None => DataBreakpointInfoResponse {
+                data_id: None,
+                description: String::from(
+                    "Only local variables and Brillig memory cells can be watched",
+                ),
+                access_types: None,
+                can_persist: None,
+            },
+        };
+        self.server.respond(req.success(ResponseBody::DataBreakpointInfo(response)))?;
+        Ok(())
+    }
+
+    fn handle_set_data_breakpoints(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::SetDataBreakpoints(ref args) = req.command else {
+            unreachable!("handle_set_data_breakpoints called on a different request");
+        };
+
+        let mut data_breakpoints = vec![];
+        let breakpoints: Vec<Breakpoint> = args
+            .breakpoints
+            .iter()
+            .map(|breakpoint| {
+                let Some(watchpoint) = parse_watchpoint_data_id(&breakpoint.data_id) else {
+                    return Breakpoint {
+                        verified: false,
+                        message: Some(String::from("Invalid data breakpoint")),
+                        ..Breakpoint::default()
+                    };
+                };
+                let id = self.get_next_breakpoint_id();
+                data_breakpoints.push((watchpoint, id));
+                Breakpoint { id: Some(id), verified: true, ..Breakpoint::default() }
+            })
+            .collect();
+
+        self.context.clear_watchpoints();
+        for (watchpoint, _) in &data_breakpoints {
+            self.context.add_watchpoint(watchpoint.clone());
+        }
+        self.data_breakpoints = data_breakpoints;
+
+        self.server.respond(req.success(ResponseBody::SetDataBreakpoints(
+            SetDataBreakpointsResponse { breakpoints },
+        )))?;
+        Ok(())
+    }
+
     fn find_file_id(&self, source_path: &str) -> Option<FileId> {
         let file_map = &self.debug_artifact.file_map;
         let found = file_map.iter().find(|(_, debug_file)| match debug_file.path.to_str() {
@@ -472,11 +699,25 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         let Some(ref breakpoints) = &args.breakpoints else {
             return vec![];
         };
-        let mut breakpoints_to_set: Vec<(DebugLocation, i64)> = vec![];
+        let mut breakpoints_to_set: Vec<(DebugLocation, i64, DebugBreakpoint)> = vec![];
         let breakpoints = breakpoints
             .iter()
             .map(|breakpoint| {
                 let line = breakpoint.line;
+                let options = match DebugBreakpoint::parse(
+                    breakpoint.condition.as_deref(),
+                    breakpoint.hit_condition.as_deref(),
+                    breakpoint.log_message.as_deref(),
+                ) {
+                    Ok(options) => options,
+                    Err(error) => {
+                        return Breakpoint {
+                            verified: false,
+                            message: Some(error.to_string()),
+                            ..Breakpoint::default()
+                        };
+                    }
+                };
                 let Some(location) = self.context.find_opcode_for_source_location(&file_id, line)
                 else {
                     return Breakpoint {
@@ -500,7 +741,7 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                 let breakpoint_address = self.context.debug_location_to_address(&location);
                 let instruction_reference = format!("{}", breakpoint_address);
                 let breakpoint_id = self.get_next_breakpoint_id();
-                breakpoints_to_set.push((location, breakpoint_id));
+                breakpoints_to_set.push((location, breakpoint_id, options));
                 Breakpoint {
                     id: Some(breakpoint_id),
                     verified: true,
@@ -543,6 +784,11 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                     variables_reference: ScopeReferences::WitnessMap as i64,
                     ..Scope::default()
                 },
+                Scope {
+                    name: String::from("Brillig Memory"),
+                    variables_reference: ScopeReferences::BrilligMemory as i64,
+                    ..Scope::default()
+                },
             ],
         })))?;
         Ok(())
@@ -580,6 +826,25 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
             .collect()
     }
 
+    /// Lists the Brillig memory cells (when executing a Brillig block) which are not zero
+    fn build_brillig_memory(&self) -> Vec<Variable> {
+        let Some(memory) = self.context.get_brillig_memory() else {
+            return vec![];
+        };
+        memory
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| {
+                !matches!(value, MemoryValue::Field(field) if *field == FieldElement::zero())
+            })
+            .map(|(index, value)| Variable {
+                name: format!("[{index}]"),
+                value: value.to_string(),
+                ..Variable::default()
+            })
+            .collect()
+    }
+
     fn handle_variables(&mut self, req: Request) -> Result<(), ServerError> {
         let Command::Variables(ref args) = req.command else {
             unreachable!("handle_variables called on a different request");
@@ -588,6 +853,7 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         let variables: Vec<_> = match scope {
             ScopeReferences::Locals => self.build_local_variables(),
             ScopeReferences::WitnessMap => self.build_witness_map(),
+            ScopeReferences::BrilligMemory => self.build_brillig_memory(),
             _ => {
                 eprintln!(
                     "handle_variables with an unknown variables_reference {}",
@@ -600,23 +866,87 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
             .respond(req.success(ResponseBody::Variables(VariablesResponse { variables })))?;
         Ok(())
     }
+
+    /// Evaluates an expression over the variables of a stack frame. Clients send this request
+    /// for the debug console, for hovers, and to re-evaluate watch expressions at every stop.
+    fn handle_evaluate(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::Evaluate(ref args) = req.command else {
+            unreachable!("handle_evaluate called on a different request");
+        };
+        let frame_index = args.frame_id.and_then(|frame_id| usize::try_from(frame_id).ok());
+        let response = match self.context.evaluate_expression(&args.expression, frame_index) {
+            Ok(value) => req.success(ResponseBody::Evaluate(EvaluateResponse {
+                result: value.to_string(),
+                type_field: Some(format!("{:?}", value.typ)),
+                presentation_hint: None,
+                variables_reference: 0,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+            })),
+            Err(error) => req.error(&error.to_string()),
+        };
+        self.server.respond(response)?;
+        Ok(())
+    }
+}
+
+/// Identifies a watchpoint in the data breakpoints exchanged with the client
+fn watchpoint_data_id(watchpoint: &Watchpoint) -> String {
+    match watchpoint {
+        Watchpoint::Variable { function_name, name } => format!("var:{function_name}:{name}"),
+        Watchpoint::BrilligMemory(address) => format!("memory:{address}"),
+    }
+}
+
+fn parse_watchpoint_data_id(data_id: &str) -> Option<Watchpoint> {
+    if let Some(variable) = data_id.strip_prefix("var:") {
+        let (function_name, name) = variable.rsplit_once(':')?;
+        Some(Watchpoint::Variable {
+            function_name: function_name.to_string(),
+            name: name.to_string(),
+        })
+    } else {
+        data_id.strip_prefix("memory:")?.parse().ok().map(Watchpoint::BrilligMemory)
+    }
 }
 
+/// Parses the name of a Brillig memory cell, as listed by `build_brillig_memory`
+fn parse_memory_cell_name(name: &str) -> Option<usize> {
+    name.strip_prefix('[')?.strip_suffix(']')?.parse().ok()
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn run_session<R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>>(
     server: Server<R, W>,
     solver: &B,
     program: CompiledProgram,
     initial_witness: WitnessMap<FieldElement>,
+    test: Option<DebugTest>,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: Option<PathBuf>,
+    package_name: Option<String>,
+    debug_on_failure: bool,
 ) -> Result<(), ServerError> {
     let debug_artifact = DebugArtifact { debug_symbols: program.debug, file_map: program.file_map };
+    let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+        true,
+        foreign_call_resolver_url,
+        root_path,
+        package_name,
+        &debug_artifact,
+    ));
     let mut session = DapSession::new(
         server,
         solver,
         &program.program.functions,
         &debug_artifact,
+        &program.abi,
         initial_witness,
         &program.program.unconstrained_functions,
+        foreign_call_executor,
+        test,
     );
 
-    session.run_loop()
+    session.run_loop(debug_on_failure)
 }
@@ -0,0 +1,1021 @@
+//! A small expression language over the instrumented debug variables of a stack frame.
+//! It's used for breakpoint conditions (`i == 17`, `x.len > 3`), the `{...}` placeholders in
+//! logpoint messages, and to evaluate expressions and watches from the REPL and DAP clients.
+
+use std::cmp::Ordering;
+
+use acvm::{AcirField, FieldElement};
+use noirc_artifacts::debug::StackFrame;
+use noirc_printable_type::{PrintableType, PrintableValue, PrintableValueDisplay};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(crate) enum ExpressionError {
+    #[error("{0}")]
+    Syntax(String),
+    #[error("Unknown variable `{0}`")]
+    UnknownVariable(String),
+    #[error("{0}")]
+    Type(String),
+    #[error("Index {index} is out of bounds for length {length}")]
+    IndexOutOfBounds { index: u128, length: usize },
+    #[error("Unknown function `{0}`")]
+    UnknownFunction(String),
+    #[error("Attempt to divide by zero")]
+    DivisionByZero,
+    #[error("`{0}` overflowed")]
+    Overflow(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum UnaryOp {
+    Not,
+    Minus,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    And,
+    Or,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+}
+
+impl std::fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            BinaryOp::Equal => "==",
+            BinaryOp::NotEqual => "!=",
+            BinaryOp::Less => "<",
+            BinaryOp::LessEqual => "<=",
+            BinaryOp::Greater => ">",
+            BinaryOp::GreaterEqual => ">=",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+            BinaryOp::Add => "+",
+            BinaryOp::Subtract => "-",
+            BinaryOp::Multiply => "*",
+            BinaryOp::Divide => "/",
+            BinaryOp::Modulo => "%",
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Expression {
+    Integer(FieldElement),
+    Bool(bool),
+    String(String),
+    Variable(String),
+    MemberAccess(Box<Expression>, String),
+    Index(Box<Expression>, Box<Expression>),
+    Unary(UnaryOp, Box<Expression>),
+    Binary(Box<Expression>, BinaryOp, Box<Expression>),
+    /// A call to a builtin function, like `std::cmp::max(a, b)`
+    Call(String, Vec<Expression>),
+    /// A call to a builtin method, like `x.len()`
+    MethodCall(Box<Expression>, String, Vec<Expression>),
+}
+
+/// The result of evaluating an expression, together with its type so it can be displayed.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Value {
+    pub(crate) value: PrintableValue<FieldElement>,
+    pub(crate) typ: PrintableType,
+}
+
+impl Value {
+    fn bool(value: bool) -> Self {
+        Value { value: PrintableValue::Field(value.into()), typ: PrintableType::Boolean }
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        match (&self.value, &self.typ) {
+            (PrintableValue::Field(field), PrintableType::Boolean) => Some(field.is_one()),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = PrintableValueDisplay::Plain(self.value.clone(), self.typ.clone());
+        let mut output = String::new();
+        // Values whose type doesn't match their shape can't be displayed as Noir values
+        if std::fmt::write(&mut output, format_args!("{display}")).is_err() {
+            output = format!("{:?}", self.value);
+        }
+        f.write_str(&output)
+    }
+}
+
+impl std::str::FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, position: 0 };
+        let expression = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(expression),
+            Some(token) => Err(ExpressionError::Syntax(format!("Unexpected `{token}`"))),
+        }
+    }
+}
+
+impl Expression {
+    /// Evaluates the expression over the variables of `stack_frame`.
+    pub(crate) fn evaluate(
+        &self,
+        stack_frame: Option<&StackFrame<FieldElement>>,
+    ) -> Result<Value, ExpressionError> {
+        match self {
+            Expression::Integer(value) => {
+                Ok(Value { value: PrintableValue::Field(*value), typ: PrintableType::Field })
+            }
+            Expression::Bool(value) => Ok(Value::bool(*value)),
+            Expression::String(value) => Ok(Value {
+                value: PrintableValue::String(value.clone()),
+                typ: PrintableType::String { length: value.len() as u32 },
+            }),
+            Expression::Variable(name) => stack_frame
+                .and_then(|frame| {
+                    frame.variables.iter().find(|(variable_name, ..)| variable_name == name)
+                })
+                .map(|(_, value, typ)| Value { value: (*value).clone(), typ: (*typ).clone() })
+                .ok_or_else(|| ExpressionError::UnknownVariable(name.clone())),
+            Expression::MemberAccess(object, member) => {
+                member_access(object.evaluate(stack_frame)?, member)
+            }
+            Expression::Index(collection, index) => {
+                let collection = collection.evaluate(stack_frame)?;
+                let index = index.evaluate(stack_frame)?;
+                index_value(collection, index)
+            }
+            Expression::Unary(op, operand) => unary(*op, operand.evaluate(stack_frame)?),
+            Expression::Binary(lhs, op, rhs) => {
+                let lhs = lhs.evaluate(stack_frame)?;
+                // `&&` and `||` short-circuit, so `x.len > 0 && x[0] == 1` doesn't fail on empty `x`
+                match (op, lhs.as_bool()) {
+                    (BinaryOp::And, Some(false)) => return Ok(Value::bool(false)),
+                    (BinaryOp::Or, Some(true)) => return Ok(Value::bool(true)),
+                    _ => (),
+                }
+                binary(lhs, *op, rhs.evaluate(stack_frame)?)
+            }
+            Expression::Call(function, arguments) => {
+                let arguments = evaluate_all(arguments, stack_frame)?;
+                call(function, arguments)
+            }
+            Expression::MethodCall(object, method, arguments) => {
+                let object = object.evaluate(stack_frame)?;
+                let arguments = evaluate_all(arguments, stack_frame)?;
+                method_call(object, method, arguments)
+            }
+        }
+    }
+
+    /// Evaluates the expression as a condition, which must result in a boolean.
+    pub(crate) fn evaluate_condition(
+        &self,
+        stack_frame: Option<&StackFrame<FieldElement>>,
+    ) -> Result<bool, ExpressionError> {
+        let value = self.evaluate(stack_frame)?;
+        value.as_bool().ok_or_else(|| {
+            ExpressionError::Type(format!("Expected a boolean condition, got `{value}`"))
+        })
+    }
+}
+
+fn evaluate_all(
+    expressions: &[Expression],
+    stack_frame: Option<&StackFrame<FieldElement>>,
+) -> Result<Vec<Value>, ExpressionError> {
+    expressions.iter().map(|expression| expression.evaluate(stack_frame)).collect()
+}
+
+/// Calls one of the (pure) standard library functions the evaluator knows about.
+fn call(function: &str, mut arguments: Vec<Value>) -> Result<Value, ExpressionError> {
+    let name = function.strip_prefix("std::cmp::").unwrap_or(function);
+    match (name, arguments.len()) {
+        ("max" | "min", 2) => {
+            let rhs = arguments.pop().expect("Expected two arguments");
+            let lhs = arguments.pop().expect("Expected two arguments");
+            let lhs_is_less = binary(lhs.clone(), BinaryOp::Less, rhs.clone())?.as_bool();
+            Ok(if lhs_is_less == Some(name == "max") { rhs } else { lhs })
+        }
+        _ => Err(ExpressionError::UnknownFunction(format!("{function}/{}", arguments.len()))),
+    }
+}
+
+/// Calls one of the (pure) standard library methods the evaluator knows about.
+fn method_call(
+    object: Value,
+    method: &str,
+    mut arguments: Vec<Value>,
+) -> Result<Value, ExpressionError> {
+    match (method, arguments.len()) {
+        ("len", 0) => member_access(object, "len"),
+        ("is_empty", 0) => {
+            let length = member_access(object, "len")?;
+            binary(length, BinaryOp::Equal, length_value(0))
+        }
+        ("as_slice", 0) => match (object.value, object.typ) {
+            (PrintableValue::Vec { array_elements, .. }, PrintableType::Array { typ, .. }) => {
+                Ok(Value {
+                    value: PrintableValue::Vec { array_elements, is_slice: true },
+                    typ: PrintableType::Slice { typ },
+                })
+            }
+            (value, typ) => {
+                let object = Value { value, typ };
+                Err(ExpressionError::Type(format!("`{object}` is not an array")))
+            }
+        },
+        ("pow_32", 1) | ("lt", 1) => {
+            let argument = arguments.pop().expect("Expected one argument");
+            let (PrintableValue::Field(lhs), PrintableType::Field) = (&object.value, &object.typ)
+            else {
+                return Err(ExpressionError::Type(format!("`{object}` is not a Field")));
+            };
+            let PrintableValue::Field(rhs) = argument.value else {
+                return Err(ExpressionError::Type(format!("`{argument}` is not a Field")));
+            };
+            if method == "lt" {
+                Ok(Value::bool(*lhs < rhs))
+            } else {
+                let exponent = FieldElement::from(rhs.to_u128() as u32 as u128);
+                Ok(Value { value: PrintableValue::Field(lhs.pow(&exponent)), typ: object.typ })
+            }
+        }
+        _ => Err(ExpressionError::UnknownFunction(format!("{method}/{}", arguments.len()))),
+    }
+}
+
+fn member_access(object: Value, member: &str) -> Result<Value, ExpressionError> {
+    match (object.value, object.typ) {
+        (PrintableValue::Struct(mut values), PrintableType::Struct { name, fields }) => {
+            let typ = fields.into_iter().find(|(field_name, _)| field_name == member);
+            match (values.remove(member), typ) {
+                (Some(value), Some((_, typ))) => Ok(Value { value, typ }),
+                _ => Err(ExpressionError::Type(format!("Struct `{name}` has no field `{member}`"))),
+            }
+        }
+        (PrintableValue::Vec { mut array_elements, .. }, PrintableType::Tuple { mut types }) => {
+            let index = member
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index < types.len() && *index < array_elements.len())
+                .ok_or_else(|| ExpressionError::Type(format!("Tuple has no field `{member}`")))?;
+            Ok(Value { value: array_elements.swap_remove(index), typ: types.swap_remove(index) })
+        }
+        (PrintableValue::Vec { array_elements, .. }, _) if member == "len" => {
+            Ok(length_value(array_elements.len()))
+        }
+        (PrintableValue::String(string), _) if member == "len" => Ok(length_value(string.len())),
+        (value, typ) => {
+            let object = Value { value, typ };
+            Err(ExpressionError::Type(format!("`{object}` has no field `{member}`")))
+        }
+    }
+}
+
+fn length_value(length: usize) -> Value {
+    Value {
+        value: PrintableValue::Field(FieldElement::from(length as u128)),
+        typ: PrintableType::UnsignedInteger { width: 32 },
+    }
+}
+
+fn index_value(collection: Value, index: Value) -> Result<Value, ExpressionError> {
+    let index = match to_number(&index) {
+        Some(Number { negative: false, magnitude }) => magnitude.to_u128(),
+        _ => return Err(ExpressionError::Type(format!("Invalid index `{index}`"))),
+    };
+    match (collection.value, collection.typ) {
+        (PrintableValue::Vec { mut array_elements, .. }, PrintableType::Array { typ, .. })
+        | (PrintableValue::Vec { mut array_elements, .. }, PrintableType::Slice { typ }) => {
+            let length = array_elements.len();
+            if index >= length as u128 {
+                return Err(ExpressionError::IndexOutOfBounds { index, length });
+            }
+            Ok(Value { value: array_elements.swap_remove(index as usize), typ: *typ })
+        }
+        (value, typ) => {
+            let collection = Value { value, typ };
+            Err(ExpressionError::Type(format!("`{collection}` can't be indexed")))
+        }
+    }
+}
+
+fn unary(op: UnaryOp, operand: Value) -> Result<Value, ExpressionError> {
+    match op {
+        UnaryOp::Not => operand.as_bool().map(|value| Value::bool(!value)).ok_or_else(|| {
+            ExpressionError::Type(format!("`!` expects a boolean, got `{operand}`"))
+        }),
+        UnaryOp::Minus => match (&operand.value, &operand.typ) {
+            (PrintableValue::Field(field), PrintableType::Field) => {
+                Ok(Value { value: PrintableValue::Field(-*field), typ: PrintableType::Field })
+            }
+            (PrintableValue::Field(field), PrintableType::SignedInteger { width })
+                if *width <= 128 =>
+            {
+                let negated = field.to_u128().wrapping_neg() & (u128::MAX >> (128 - width));
+                Ok(Value {
+                    value: PrintableValue::Field(FieldElement::from(negated)),
+                    typ: operand.typ.clone(),
+                })
+            }
+            _ => Err(ExpressionError::Type(format!("`-` can't be applied to `{operand}`"))),
+        },
+    }
+}
+
+fn binary(lhs: Value, op: BinaryOp, rhs: Value) -> Result<Value, ExpressionError> {
+    match op {
+        BinaryOp::Add
+        | BinaryOp::Subtract
+        | BinaryOp::Multiply
+        | BinaryOp::Divide
+        | BinaryOp::Modulo => arithmetic(lhs, op, rhs),
+        BinaryOp::And | BinaryOp::Or => match (lhs.as_bool(), rhs.as_bool()) {
+            (Some(lhs), Some(rhs)) => {
+                Ok(Value::bool(if op == BinaryOp::And { lhs && rhs } else { lhs || rhs }))
+            }
+            _ => Err(ExpressionError::Type(format!(
+                "`{}` expects booleans, got `{lhs}` and `{rhs}`",
+                if op == BinaryOp::And { "&&" } else { "||" }
+            ))),
+        },
+        BinaryOp::Equal | BinaryOp::NotEqual => {
+            let equal = match (to_number(&lhs), to_number(&rhs)) {
+                (Some(lhs), Some(rhs)) => lhs == rhs,
+                _ => lhs.value == rhs.value,
+            };
+            Ok(Value::bool(equal == (op == BinaryOp::Equal)))
+        }
+        BinaryOp::Less | BinaryOp::LessEqual | BinaryOp::Greater | BinaryOp::GreaterEqual => {
+            let (Some(lhs_number), Some(rhs_number)) = (to_number(&lhs), to_number(&rhs)) else {
+                return Err(ExpressionError::Type(format!("Can't compare `{lhs}` and `{rhs}`")));
+            };
+            let ordering = lhs_number.cmp(&rhs_number);
+            Ok(Value::bool(match op {
+                BinaryOp::Less => ordering == Ordering::Less,
+                BinaryOp::LessEqual => ordering != Ordering::Greater,
+                BinaryOp::Greater => ordering == Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            }))
+        }
+    }
+}
+
+/// Applies an arithmetic operator. Fields use field arithmetic, while integers must not overflow
+/// their type. Integer literals (which are fields) take the type of the other operand.
+fn arithmetic(lhs: Value, op: BinaryOp, rhs: Value) -> Result<Value, ExpressionError> {
+    let typ = match (&lhs.typ, &rhs.typ) {
+        (PrintableType::Field, PrintableType::Field) => return field_arithmetic(lhs, op, rhs),
+        (PrintableType::Field, typ) | (typ, PrintableType::Field) => typ.clone(),
+        (lhs_type, rhs_type) if lhs_type == rhs_type => lhs_type.clone(),
+        _ => {
+            return Err(ExpressionError::Type(format!(
+                "Can't apply `{op}` to `{lhs}` and `{rhs}` as they have different types"
+            )))
+        }
+    };
+    let (PrintableType::UnsignedInteger { width } | PrintableType::SignedInteger { width }) = typ
+    else {
+        return Err(ExpressionError::Type(format!("Can't apply `{op}` to `{lhs}` and `{rhs}`")));
+    };
+
+    // Unsigned integers are computed as u128 and signed ones as i128, so that every value of
+    // the 128-bit types can be used
+    let max = u128::MAX >> (128 - width);
+    let overflow = || ExpressionError::Overflow(format!("{lhs} {op} {rhs}"));
+    let unsigned = if matches!(typ, PrintableType::SignedInteger { .. }) {
+        let (Some(lhs_integer), Some(rhs_integer)) =
+            (to_number(&lhs).and_then(to_i128), to_number(&rhs).and_then(to_i128))
+        else {
+            return Err(overflow());
+        };
+        if matches!(op, BinaryOp::Divide | BinaryOp::Modulo) && rhs_integer == 0 {
+            return Err(ExpressionError::DivisionByZero);
+        }
+        let result = match op {
+            BinaryOp::Add => lhs_integer.checked_add(rhs_integer),
+            BinaryOp::Subtract => lhs_integer.checked_sub(rhs_integer),
+            BinaryOp::Multiply => lhs_integer.checked_mul(rhs_integer),
+            BinaryOp::Divide => lhs_integer.checked_div(rhs_integer),
+            _ => lhs_integer.checked_rem(rhs_integer),
+        };
+        let signed_max = (max >> 1) as i128;
+        match result {
+            // Negative values are stored in two's complement
+            Some(result) if -signed_max - 1 <= result && result <= signed_max => {
+                (result as u128) & max
+            }
+            _ => return Err(overflow()),
+        }
+    } else {
+        let (Some(lhs_integer), Some(rhs_integer)) =
+            (to_number(&lhs).and_then(to_u128), to_number(&rhs).and_then(to_u128))
+        else {
+            return Err(overflow());
+        };
+        if matches!(op, BinaryOp::Divide | BinaryOp::Modulo) && rhs_integer == 0 {
+            return Err(ExpressionError::DivisionByZero);
+        }
+        let result = match op {
+            BinaryOp::Add => lhs_integer.checked_add(rhs_integer),
+            BinaryOp::Subtract => lhs_integer.checked_sub(rhs_integer),
+            BinaryOp::Multiply => lhs_integer.checked_mul(rhs_integer),
+            BinaryOp::Divide => lhs_integer.checked_div(rhs_integer),
+            _ => lhs_integer.checked_rem(rhs_integer),
+        };
+        match result {
+            Some(result) if result <= max => result,
+            _ => return Err(overflow()),
+        }
+    };
+    Ok(Value { value: PrintableValue::Field(FieldElement::from(unsigned)), typ })
+}
+
+fn field_arithmetic(lhs: Value, op: BinaryOp, rhs: Value) -> Result<Value, ExpressionError> {
+    let (PrintableValue::Field(lhs_field), PrintableValue::Field(rhs_field)) =
+        (&lhs.value, &rhs.value)
+    else {
+        return Err(ExpressionError::Type(format!("Can't apply `{op}` to `{lhs}` and `{rhs}`")));
+    };
+    let result = match op {
+        BinaryOp::Add => *lhs_field + *rhs_field,
+        BinaryOp::Subtract => *lhs_field - *rhs_field,
+        BinaryOp::Multiply => *lhs_field * *rhs_field,
+        BinaryOp::Divide if rhs_field.is_zero() => return Err(ExpressionError::DivisionByZero),
+        BinaryOp::Divide => *lhs_field / *rhs_field,
+        _ => return Err(ExpressionError::Type(format!("Can't apply `{op}` to fields"))),
+    };
+    Ok(Value { value: PrintableValue::Field(result), typ: PrintableType::Field })
+}
+
+fn to_i128(number: Number) -> Option<i128> {
+    let magnitude = number.magnitude.try_into_u128()?;
+    if number.negative {
+        0i128.checked_sub_unsigned(magnitude)
+    } else {
+        i128::try_from(magnitude).ok()
+    }
+}
+
+fn to_u128(number: Number) -> Option<u128> {
+    if number.negative {
+        None
+    } else {
+        number.magnitude.try_into_u128()
+    }
+}
+
+/// An integer or field value with its sign made explicit, so that values of different integer
+/// types (and untyped literals) can be compared with each other.
+#[derive(Debug, PartialEq, Eq)]
+struct Number {
+    negative: bool,
+    magnitude: FieldElement,
+}
+
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn to_number(value: &Value) -> Option<Number> {
+    let PrintableValue::Field(field) = &value.value else {
+        return None;
+    };
+    let field = *field;
+    match value.typ {
+        // Fields are treated as negative when that's the shorter representation, the same way
+        // the debugger displays them, so that `x == -1` works as expected
+        PrintableType::Field => {
+            let negated = -field;
+            if negated.num_bits() < field.num_bits() {
+                Some(Number { negative: true, magnitude: negated })
+            } else {
+                Some(Number { negative: false, magnitude: field })
+            }
+        }
+        PrintableType::UnsignedInteger { .. } => Some(Number { negative: false, magnitude: field }),
+        PrintableType::SignedInteger { width } if width <= 128 => {
+            let mask = u128::MAX >> (128 - width);
+            let unsigned = field.to_u128() & mask;
+            if unsigned >> (width - 1) == 1 {
+                let magnitude = unsigned.wrapping_neg() & mask;
+                Some(Number { negative: true, magnitude: FieldElement::from(magnitude) })
+            } else {
+                Some(Number { negative: false, magnitude: FieldElement::from(unsigned) })
+            }
+        }
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Integer(FieldElement),
+    Identifier(String),
+    String(String),
+    Symbol(&'static str),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Integer(value) => write!(f, "{value}"),
+            Token::Identifier(name) => write!(f, "{name}"),
+            Token::String(value) => write!(f, "{value:?}"),
+            Token::Symbol(symbol) => write!(f, "{symbol}"),
+        }
+    }
+}
+
+/// Symbols ordered so that longer ones are matched before their prefixes.
+const SYMBOLS: [&str; 21] = [
+    "==", "!=", "<=", ">=", "&&", "||", "::", "<", ">", "!", "-", "+", "*", "/", "%", ".", ",",
+    "(", ")", "[", "]",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut rest = source.trim_start();
+
+    while let Some(first) = rest.chars().next() {
+        let length = if first.is_ascii_digit() {
+            let length =
+                rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(rest.len());
+            let literal = rest[..length].replace('_', "");
+            let value = FieldElement::try_from_str(&literal).ok_or_else(|| {
+                ExpressionError::Syntax(format!("Invalid integer `{}`", &rest[..length]))
+            })?;
+            tokens.push(Token::Integer(value));
+            length
+        } else if first.is_alphabetic() || first == '_' {
+            let length =
+                rest.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(rest.len());
+            tokens.push(Token::Identifier(rest[..length].to_string()));
+            length
+        } else if first == '"' {
+            let Some(end) = rest[1..].find('"') else {
+                return Err(ExpressionError::Syntax("Unterminated string".to_string()));
+            };
+            tokens.push(Token::String(rest[1..=end].to_string()));
+            end + 2
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            tokens.push(Token::Symbol(symbol));
+            symbol.len()
+        } else {
+            return Err(ExpressionError::Syntax(format!("Unexpected character `{first}`")));
+        };
+        rest = rest[length..].trim_start();
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(found)) if *found == symbol) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), ExpressionError> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{symbol}`")))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> ExpressionError {
+        match self.peek() {
+            Some(token) => ExpressionError::Syntax(format!("Expected {expected}, found `{token}`")),
+            None => ExpressionError::Syntax(format!("Expected {expected}, found end of input")),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expression, ExpressionError> {
+        let mut lhs = self.parse_and()?;
+        while self.eat_symbol("||") {
+            let rhs = self.parse_and()?;
+            lhs = Expression::Binary(Box::new(lhs), BinaryOp::Or, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expression, ExpressionError> {
+        let mut lhs = self.parse_comparison()?;
+        while self.eat_symbol("&&") {
+            let rhs = self.parse_comparison()?;
+            lhs = Expression::Binary(Box::new(lhs), BinaryOp::And, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expression, ExpressionError> {
+        let lhs = self.parse_additive()?;
+        let op = match self.peek() {
+            Some(Token::Symbol("==")) => BinaryOp::Equal,
+            Some(Token::Symbol("!=")) => BinaryOp::NotEqual,
+            Some(Token::Symbol("<")) => BinaryOp::Less,
+            Some(Token::Symbol("<=")) => BinaryOp::LessEqual,
+            Some(Token::Symbol(">")) => BinaryOp::Greater,
+            Some(Token::Symbol(">=")) => BinaryOp::GreaterEqual,
+            _ => return Ok(lhs),
+        };
+        self.position += 1;
+        let rhs = self.parse_additive()?;
+        Ok(Expression::Binary(Box::new(lhs), op, Box::new(rhs)))
+    }
+
+    fn parse_additive(&mut self) -> Result<Expression, ExpressionError> {
+        let mut lhs = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Symbol("+")) => BinaryOp::Add,
+                Some(Token::Symbol("-")) => BinaryOp::Subtract,
+                _ => return Ok(lhs),
+            };
+            self.position += 1;
+            let rhs = self.parse_multiplicative()?;
+            lhs = Expression::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expression, ExpressionError> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Symbol("*")) => BinaryOp::Multiply,
+                Some(Token::Symbol("/")) => BinaryOp::Divide,
+                Some(Token::Symbol("%")) => BinaryOp::Modulo,
+                _ => return Ok(lhs),
+            };
+            self.position += 1;
+            let rhs = self.parse_unary()?;
+            lhs = Expression::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, ExpressionError> {
+        if self.eat_symbol("!") {
+            Ok(Expression::Unary(UnaryOp::Not, Box::new(self.parse_unary()?)))
+        } else if self.eat_symbol("-") {
+            Ok(Expression::Unary(UnaryOp::Minus, Box::new(self.parse_unary()?)))
+        } else {
+            self.parse_postfix()
+        }
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expression, ExpressionError> {
+        let mut expression = self.parse_primary()?;
+        loop {
+            if self.eat_symbol(".") {
+                let member = match self.next() {
+                    Some(Token::Identifier(name)) => name,
+                    Some(Token::Integer(index)) => index.to_string(),
+                    _ => {
+                        self.position -= 1;
+                        return Err(self.unexpected("a field name"));
+                    }
+                };
+                if self.eat_symbol("(") {
+                    let arguments = self.parse_arguments()?;
+                    expression = Expression::MethodCall(Box::new(expression), member, arguments);
+                } else {
+                    expression = Expression::MemberAccess(Box::new(expression), member);
+                }
+            } else if self.eat_symbol("[") {
+                let index = self.parse_or()?;
+                self.expect_symbol("]")?;
+                expression = Expression::Index(Box::new(expression), Box::new(index));
+            } else {
+                return Ok(expression);
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, ExpressionError> {
+        match self.next() {
+            Some(Token::Integer(value)) => Ok(Expression::Integer(value)),
+            Some(Token::String(value)) => Ok(Expression::String(value)),
+            Some(Token::Identifier(name)) => {
+                let mut path = name;
+                while self.eat_symbol("::") {
+                    match self.next() {
+                        Some(Token::Identifier(segment)) => path = format!("{path}::{segment}"),
+                        _ => {
+                            self.position -= 1;
+                            return Err(self.unexpected("an identifier"));
+                        }
+                    }
+                }
+                if self.eat_symbol("(") {
+                    return Ok(Expression::Call(path, self.parse_arguments()?));
+                }
+                Ok(match path.as_str() {
+                    "true" => Expression::Bool(true),
+                    "false" => Expression::Bool(false),
+                    _ if path.contains("::") => {
+                        return Err(ExpressionError::Syntax(format!("Unexpected path `{path}`")))
+                    }
+                    _ => Expression::Variable(path),
+                })
+            }
+            Some(Token::Symbol("(")) => {
+                let expression = self.parse_or()?;
+                self.expect_symbol(")")?;
+                Ok(expression)
+            }
+            _ => {
+                self.position -= 1;
+                Err(self.unexpected("an expression"))
+            }
+        }
+    }
+
+    /// Parses the arguments of a call, after its opening parenthesis.
+    fn parse_arguments(&mut self) -> Result<Vec<Expression>, ExpressionError> {
+        let mut arguments = Vec::new();
+        while !self.eat_symbol(")") {
+            arguments.push(self.parse_or()?);
+            if !self.eat_symbol(",") {
+                self.expect_symbol(")")?;
+                break;
+            }
+        }
+        Ok(arguments)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum MessagePart {
+    Text(String),
+    Expression(Expression),
+}
+
+/// A logpoint message, where each `{expression}` is replaced by the value of the expression.
+/// Use `{{` and `}}` to write literal braces.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LogMessage {
+    parts: Vec<MessagePart>,
+}
+
+impl std::str::FromStr for LogMessage {
+    type Err = ExpressionError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut expression = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => expression.push(c),
+                            None => {
+                                let error = "Unterminated `{` in message".to_string();
+                                return Err(ExpressionError::Syntax(error));
+                            }
+                        }
+                    }
+                    if !text.is_empty() {
+                        parts.push(MessagePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(MessagePart::Expression(expression.parse()?));
+                }
+                '}' => return Err(ExpressionError::Syntax("Unmatched `}` in message".to_string())),
+                _ => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(MessagePart::Text(text));
+        }
+
+        Ok(LogMessage { parts })
+    }
+}
+
+impl LogMessage {
+    /// Formats the message, evaluating its expressions over the variables of `stack_frame`.
+    /// Expressions that can't be evaluated are replaced by the error.
+    pub(crate) fn format(&self, stack_frame: Option<&StackFrame<FieldElement>>) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                MessagePart::Text(text) => text.clone(),
+                MessagePart::Expression(expression) => match expression.evaluate(stack_frame) {
+                    Ok(value) => value.to_string(),
+                    Err(error) => format!("<{error}>"),
+                },
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn struct_value(
+        name: &str,
+        fields: Vec<(&str, PrintableValue<FieldElement>, PrintableType)>,
+    ) -> (PrintableValue<FieldElement>, PrintableType) {
+        let values: BTreeMap<String, PrintableValue<FieldElement>> =
+            fields.iter().map(|(name, value, _)| (name.to_string(), value.clone())).collect();
+        let fields = fields.into_iter().map(|(name, _, typ)| (name.to_string(), typ)).collect();
+        (PrintableValue::Struct(values), PrintableType::Struct { name: name.to_string(), fields })
+    }
+
+    fn u32_value(value: u128) -> PrintableValue<FieldElement> {
+        PrintableValue::Field(FieldElement::from(value))
+    }
+
+    fn evaluate(source: &str) -> Result<String, ExpressionError> {
+        let u32_type = PrintableType::UnsignedInteger { width: 32 };
+        let i8_type = PrintableType::SignedInteger { width: 8 };
+        let array_type = PrintableType::Array { length: 3, typ: Box::new(u32_type.clone()) };
+        let array = PrintableValue::Vec {
+            array_elements: vec![u32_value(1), u32_value(2), u32_value(3)],
+            is_slice: false,
+        };
+        let (point, point_type) = struct_value(
+            "Point",
+            vec![("x", u32_value(4), u32_type.clone()), ("y", u32_value(5), u32_type.clone())],
+        );
+        // -3 as an i8, in two's complement
+        let negative = PrintableValue::Field(FieldElement::from(253u128));
+        let u128_type = PrintableType::UnsignedInteger { width: 128 };
+        let i128_type = PrintableType::SignedInteger { width: 128 };
+        let big = PrintableValue::Field(FieldElement::from((1u128 << 127) + 5));
+        // -2 as an i128, in two's complement
+        let negative_i128 = PrintableValue::Field(FieldElement::from(u128::MAX - 1));
+
+        let i = u32_value(17);
+        let frame = StackFrame {
+            function_name: "main",
+            function_params: vec![],
+            variables: vec![
+                ("i", &i, &u32_type),
+                ("x", &array, &array_type),
+                ("point", &point, &point_type),
+                ("n", &negative, &i8_type),
+                ("big", &big, &u128_type),
+                ("m", &negative_i128, &i128_type),
+            ],
+        };
+
+        let expression: Expression = source.parse()?;
+        expression.evaluate(Some(&frame)).map(|value| value.to_string())
+    }
+
+    #[test]
+    fn evaluates_comparisons() {
+        assert_eq!(evaluate("i == 17"), Ok("true".to_string()));
+        assert_eq!(evaluate("i != 17"), Ok("false".to_string()));
+        assert_eq!(evaluate("x.len > 3"), Ok("false".to_string()));
+        assert_eq!(evaluate("x.len() <= 3"), Ok("true".to_string()));
+        assert_eq!(evaluate("n < 0 && n == -3"), Ok("true".to_string()));
+        assert_eq!(evaluate("!(i >= 18) || false"), Ok("true".to_string()));
+    }
+
+    #[test]
+    fn evaluates_member_access_and_indexing() {
+        assert_eq!(evaluate("x[1]"), Ok("2".to_string()));
+        assert_eq!(evaluate("point.y"), Ok("5".to_string()));
+        assert_eq!(
+            evaluate("x[3]"),
+            Err(ExpressionError::IndexOutOfBounds { index: 3, length: 3 })
+        );
+        assert_eq!(
+            evaluate("point.z"),
+            Err(ExpressionError::Type("Struct `Point` has no field `z`".to_string()))
+        );
+    }
+
+    #[test]
+    fn evaluates_arithmetic() {
+        assert_eq!(evaluate("i + 1"), Ok("18".to_string()));
+        assert_eq!(evaluate("x[1] * 3 - point.x"), Ok("2".to_string()));
+        assert_eq!(evaluate("n * 2"), Ok("-6".to_string()));
+        assert_eq!(evaluate("i % 5 == 2"), Ok("true".to_string()));
+        assert_eq!(evaluate("(1 + 2) / 3"), Ok("0x01".to_string()));
+        assert!(matches!(evaluate("n - 126"), Err(ExpressionError::Overflow(_))));
+        assert!(matches!(evaluate("i - 18"), Err(ExpressionError::Overflow(_))));
+        assert_eq!(evaluate("i / 0"), Err(ExpressionError::DivisionByZero));
+        assert!(matches!(evaluate("i + n"), Err(ExpressionError::Type(_))));
+    }
+
+    #[test]
+    fn evaluates_128_bit_integers() {
+        assert_eq!(evaluate("big + 1"), Ok("170141183460469231731687303715884105734".to_string()));
+        assert_eq!(evaluate("big - 6 < big"), Ok("true".to_string()));
+        assert_eq!(evaluate("big > i"), Ok("true".to_string()));
+        assert_eq!(evaluate("big / 2"), Ok("85070591730234615865843651857942052866".to_string()));
+        assert!(matches!(evaluate("big * 2"), Err(ExpressionError::Overflow(_))));
+        assert_eq!(evaluate("m * 3"), Ok("-6".to_string()));
+        assert_eq!(evaluate("-m == 2"), Ok("true".to_string()));
+        assert_eq!(evaluate("m < 0"), Ok("true".to_string()));
+    }
+
+    #[test]
+    fn evaluates_calls() {
+        assert_eq!(evaluate("std::cmp::max(i, x[2])"), Ok("17".to_string()));
+        assert_eq!(evaluate("min(n, 0)"), Ok("-3".to_string()));
+        assert_eq!(evaluate("x.as_slice()"), Ok("&[1, 2, 3]".to_string()));
+        assert_eq!(evaluate("x.as_slice().len() == 3"), Ok("true".to_string()));
+        assert_eq!(evaluate("x.is_empty()"), Ok("false".to_string()));
+        assert_eq!(evaluate("foo(1)"), Err(ExpressionError::UnknownFunction("foo/1".to_string())));
+    }
+
+    #[test]
+    fn short_circuits_logical_operators() {
+        assert_eq!(evaluate("false && x[10] == 1"), Ok("false".to_string()));
+        assert_eq!(evaluate("true || unknown"), Ok("true".to_string()));
+        assert_eq!(
+            evaluate("unknown"),
+            Err(ExpressionError::UnknownVariable("unknown".to_string()))
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        assert!(matches!("i ==".parse::<Expression>(), Err(ExpressionError::Syntax(_))));
+        assert!(matches!("(i".parse::<Expression>(), Err(ExpressionError::Syntax(_))));
+        assert!(matches!("i == 1 2".parse::<Expression>(), Err(ExpressionError::Syntax(_))));
+    }
+
+    #[test]
+    fn formats_log_messages() {
+        let message: LogMessage = "i = {i}, {{x}} = {x}".parse().unwrap();
+        let i = u32_value(7);
+        let u32_type = PrintableType::UnsignedInteger { width: 32 };
+        let frame = StackFrame {
+            function_name: "main",
+            function_params: vec![],
+            variables: vec![("i", &i, &u32_type)],
+        };
+        assert_eq!(message.format(Some(&frame)), "i = 7, {x} = <Unknown variable `x`>");
+    }
+}
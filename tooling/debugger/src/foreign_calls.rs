@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use acvm::{
     acir::brillig::{ForeignCallParam, ForeignCallResult},
     pwg::ForeignCallWaitInfo,
@@ -39,23 +41,45 @@ impl DebugForeignCall {
 pub trait DebugForeignCallExecutor: ForeignCallExecutor<FieldElement> {
     fn get_variables(&self) -> Vec<StackFrame<FieldElement>>;
     fn current_stack_frame(&self) -> Option<StackFrame<FieldElement>>;
+    /// Returns a copy of the state of the debug variables, to be restored with
+    /// `restore_debug_vars` when going back in time.
+    fn save_debug_vars(&self) -> DebugVars<FieldElement>;
+    fn restore_debug_vars(&mut self, debug_vars: DebugVars<FieldElement>);
 }
 
+/// Executes the foreign calls of the debugging instrumentation, and chains every other call to
+/// a [`DefaultForeignCallExecutor`], which handles printing, mocks and the oracle resolver.
 pub struct DefaultDebugForeignCallExecutor {
     executor: DefaultForeignCallExecutor<FieldElement>,
     pub debug_vars: DebugVars<FieldElement>,
 }
 
 impl DefaultDebugForeignCallExecutor {
-    pub fn new(show_output: bool) -> Self {
+    pub fn new(
+        show_output: bool,
+        resolver_url: Option<&str>,
+        root_path: Option<PathBuf>,
+        package_name: Option<String>,
+    ) -> Self {
         Self {
-            executor: DefaultForeignCallExecutor::new(show_output, None, None, None),
+            executor: DefaultForeignCallExecutor::new(
+                show_output,
+                resolver_url,
+                root_path,
+                package_name,
+            ),
             debug_vars: DebugVars::default(),
         }
     }
 
-    pub fn from_artifact(show_output: bool, artifact: &DebugArtifact) -> Self {
-        let mut ex = Self::new(show_output);
+    pub fn from_artifact(
+        show_output: bool,
+        resolver_url: Option<&str>,
+        root_path: Option<PathBuf>,
+        package_name: Option<String>,
+        artifact: &DebugArtifact,
+    ) -> Self {
+        let mut ex = Self::new(show_output, resolver_url, root_path, package_name);
         ex.load_artifact(artifact);
         ex
     }
@@ -78,6 +102,14 @@ impl DebugForeignCallExecutor for DefaultDebugForeignCallExecutor {
     fn current_stack_frame(&self) -> Option<StackFrame<FieldElement>> {
         self.debug_vars.current_stack_frame()
     }
+
+    fn save_debug_vars(&self) -> DebugVars<FieldElement> {
+        self.debug_vars.clone()
+    }
+
+    fn restore_debug_vars(&mut self, debug_vars: DebugVars<FieldElement>) {
+        self.debug_vars = debug_vars;
+    }
 }
 
 fn debug_var_id(value: &FieldElement) -> DebugVarId {
@@ -167,3 +199,45 @@ impl ForeignCallExecutor<FieldElement> for DefaultDebugForeignCallExecutor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn foreign_call(
+        function: &str,
+        inputs: Vec<ForeignCallParam<FieldElement>>,
+    ) -> ForeignCallWaitInfo<FieldElement> {
+        ForeignCallWaitInfo { function: function.to_string(), inputs }
+    }
+
+    fn string_param(text: &str) -> ForeignCallParam<FieldElement> {
+        ForeignCallParam::Array(text.bytes().map(|byte| FieldElement::from(byte as u128)).collect())
+    }
+
+    #[test]
+    fn test_resolves_mocks_before_the_oracle_resolver() {
+        // Nothing listens on this port, so any call forwarded to the resolver fails
+        let mut executor =
+            DefaultDebugForeignCallExecutor::new(false, Some("http://127.0.0.1:1"), None, None);
+
+        let id = executor.execute(&foreign_call("create_mock", vec![string_param("foo")])).unwrap();
+        let id = id.values[0].clone();
+        let returns = vec![id, ForeignCallParam::Single(FieldElement::from(5_u128))];
+        executor.execute(&foreign_call("set_mock_returns", returns)).unwrap();
+
+        let result = executor.execute(&foreign_call("foo", vec![])).unwrap();
+        assert_eq!(result, FieldElement::from(5_u128).into());
+
+        assert!(executor.execute(&foreign_call("bar", vec![])).is_err());
+    }
+
+    #[test]
+    fn test_does_not_forward_debug_calls_to_the_oracle_resolver() {
+        let mut executor =
+            DefaultDebugForeignCallExecutor::new(false, Some("http://127.0.0.1:1"), None, None);
+
+        let result = executor.execute(&foreign_call("__debug_fn_exit", vec![]));
+        assert_eq!(result.unwrap(), ForeignCallResult::default());
+    }
+}
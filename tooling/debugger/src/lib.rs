@@ -1,11 +1,14 @@
+mod breakpoints;
 mod context;
 mod dap;
 pub mod errors;
+mod expressions;
 mod foreign_calls;
 mod repl;
 mod source_code_printer;
 
 use std::io::{Read, Write};
+use std::path::PathBuf;
 
 use ::dap::errors::ServerError;
 use ::dap::server::Server;
@@ -14,20 +17,83 @@ use acvm::{BlackBoxFunctionSolver, FieldElement};
 
 use nargo::NargoError;
 use noirc_driver::CompiledProgram;
+use noirc_frontend::hir::def_map::TestFunction;
 
+/// A `#[test]` function being debugged, so that once its execution finishes we can report
+/// whether it passed, taking `should_fail` and `should_fail_with` into account.
+pub struct DebugTest {
+    /// The fully qualified name of the test function.
+    pub name: String,
+    pub function: TestFunction,
+}
+
+/// Foreign calls other than the ones of the debugging instrumentation are resolved like
+/// `nargo execute` does: mocks are handled, and unknown oracles are sent to the JSON RPC
+/// `foreign_call_resolver_url` along with `root_path` and `package_name`.
 pub fn run_repl_session<B: BlackBoxFunctionSolver<FieldElement>>(
     solver: &B,
     program: CompiledProgram,
     initial_witness: WitnessMap<FieldElement>,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: Option<PathBuf>,
+    package_name: Option<String>,
+) -> Result<Option<WitnessStack<FieldElement>>, NargoError<FieldElement>> {
+    repl::run(
+        solver,
+        program,
+        initial_witness,
+        foreign_call_resolver_url,
+        root_path,
+        package_name,
+        false,
+    )
+}
+
+/// Executes the program until it fails and starts the REPL at the failing opcode, to inspect
+/// the state of the execution at the moment of the failure. The REPL doesn't start if the
+/// program doesn't fail.
+pub fn run_repl_session_at_failure<B: BlackBoxFunctionSolver<FieldElement>>(
+    solver: &B,
+    program: CompiledProgram,
+    initial_witness: WitnessMap<FieldElement>,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: Option<PathBuf>,
+    package_name: Option<String>,
 ) -> Result<Option<WitnessStack<FieldElement>>, NargoError<FieldElement>> {
-    repl::run(solver, program, initial_witness)
+    repl::run(
+        solver,
+        program,
+        initial_witness,
+        foreign_call_resolver_url,
+        root_path,
+        package_name,
+        true,
+    )
 }
 
+/// With `debug_on_failure`, the session starts at the opcode where the program fails instead of
+/// at its first opcode.
+#[allow(clippy::too_many_arguments)]
 pub fn run_dap_loop<R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>>(
     server: Server<R, W>,
     solver: &B,
     program: CompiledProgram,
     initial_witness: WitnessMap<FieldElement>,
+    test: Option<DebugTest>,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: Option<PathBuf>,
+    package_name: Option<String>,
+    debug_on_failure: bool,
 ) -> Result<(), ServerError> {
-    dap::run_session(server, solver, program, initial_witness)
+    dap::run_session(
+        server,
+        solver,
+        program,
+        initial_witness,
+        test,
+        foreign_call_resolver_url,
+        root_path,
+        package_name,
+        debug_on_failure,
+    )
 }
@@ -1,4 +1,6 @@
+use crate::breakpoints::{Breakpoint, Watchpoint};
 use crate::context::{DebugCommandResult, DebugContext, DebugLocation};
+use crate::expressions::Expression;
 
 use acvm::acir::brillig::BitSize;
 use acvm::acir::circuit::brillig::{BrilligBytecode, BrilligFunctionId};
@@ -17,6 +19,7 @@ use noirc_artifacts::debug::DebugArtifact;
 use easy_repl::{command, CommandStatus, Repl};
 use noirc_printable_type::PrintableValueDisplay;
 use std::cell::RefCell;
+use std::path::PathBuf;
 
 use crate::source_code_printer::print_source_code_location;
 
@@ -26,24 +29,40 @@ pub struct ReplDebugger<'a, B: BlackBoxFunctionSolver<FieldElement>> {
     debug_artifact: &'a DebugArtifact,
     initial_witness: WitnessMap<FieldElement>,
     last_result: DebugCommandResult,
+    /// Expressions evaluated and shown every time execution stops
+    watches: Vec<String>,
 
     // ACIR functions to debug
     circuits: &'a [Circuit<FieldElement>],
 
     // Brillig functions referenced from the ACIR circuits above
     unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
+
+    // Configuration of the foreign call executor, to create a new one when restarting
+    foreign_call_resolver_url: Option<String>,
+    root_path: Option<PathBuf>,
+    package_name: Option<String>,
 }
 
 impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         blackbox_solver: &'a B,
         circuits: &'a [Circuit<FieldElement>],
         debug_artifact: &'a DebugArtifact,
         initial_witness: WitnessMap<FieldElement>,
         unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
+        foreign_call_resolver_url: Option<String>,
+        root_path: Option<PathBuf>,
+        package_name: Option<String>,
     ) -> Self {
-        let foreign_call_executor =
-            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact));
+        let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            foreign_call_resolver_url.as_deref(),
+            root_path.clone(),
+            package_name.clone(),
+            debug_artifact,
+        ));
         let context = DebugContext::new(
             blackbox_solver,
             circuits,
@@ -65,7 +84,11 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
             debug_artifact,
             initial_witness,
             last_result,
+            watches: Vec::new(),
             unconstrained_functions,
+            foreign_call_resolver_url,
+            root_path,
+            package_name,
         }
     }
 
@@ -231,6 +254,58 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
         }
     }
 
+    fn set_breakpoint_condition_at(&mut self, location: DebugLocation, condition: String) {
+        match condition.parse() {
+            Ok(condition) => {
+                self.update_breakpoint_at(location, |breakpoint| {
+                    breakpoint.condition = Some(condition);
+                });
+            }
+            Err(error) => println!("Invalid condition: {error}"),
+        }
+    }
+
+    fn set_breakpoint_hit_condition_at(&mut self, location: DebugLocation, hit_condition: String) {
+        match hit_condition.parse() {
+            Ok(hit_condition) => {
+                self.update_breakpoint_at(location, |breakpoint| {
+                    breakpoint.hit_condition = Some(hit_condition);
+                });
+            }
+            Err(error) => println!("Invalid hit condition: {error}"),
+        }
+    }
+
+    fn set_logpoint_at(&mut self, location: DebugLocation, message: String) {
+        match message.parse() {
+            Ok(message) => {
+                self.update_breakpoint_at(location, |breakpoint| {
+                    breakpoint.log_message = Some(message);
+                });
+            }
+            Err(error) => println!("Invalid log message: {error}"),
+        }
+    }
+
+    /// Updates the options of the breakpoint at `location`, adding it if it's not set.
+    fn update_breakpoint_at(
+        &mut self,
+        location: DebugLocation,
+        update: impl FnOnce(&mut Breakpoint),
+    ) {
+        if !self.context.is_valid_debug_location(&location) {
+            println!("Invalid location {location}");
+            return;
+        }
+        let mut breakpoint = self.context.get_breakpoint(&location).cloned().unwrap_or_default();
+        update(&mut breakpoint);
+        if self.context.set_breakpoint(location, breakpoint) {
+            println!("Added breakpoint at {location}");
+        } else {
+            println!("Updated breakpoint at {location}");
+        }
+    }
+
     fn delete_breakpoint_at(&mut self, location: DebugLocation) {
         if self.context.delete_breakpoint(&location) {
             println!("Breakpoint at {location} deleted");
@@ -239,9 +314,53 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
         }
     }
 
+    fn add_variable_watchpoint(&mut self, name: String) {
+        match self.context.variable_watchpoint(&name) {
+            Some(watchpoint) => self.add_watchpoint(watchpoint),
+            None => println!("No function is being executed"),
+        }
+    }
+
+    fn add_watchpoint(&mut self, watchpoint: Watchpoint) {
+        if self.context.add_watchpoint(watchpoint.clone()) {
+            println!("Added watchpoint on {watchpoint}");
+        } else {
+            println!("Watchpoint on {watchpoint} already set");
+        }
+    }
+
+    fn delete_variable_watchpoint(&mut self, name: String) {
+        match self.context.variable_watchpoint(&name) {
+            Some(watchpoint) => self.delete_watchpoint(watchpoint),
+            None => println!("No function is being executed"),
+        }
+    }
+
+    fn delete_watchpoint(&mut self, watchpoint: Watchpoint) {
+        if self.context.delete_watchpoint(&watchpoint) {
+            println!("Watchpoint on {watchpoint} deleted");
+        } else {
+            println!("Watchpoint on {watchpoint} not set");
+        }
+    }
+
+    fn show_watchpoints(&self) {
+        let mut watchpoints: Vec<String> =
+            self.context.iterate_watchpoints().map(ToString::to_string).collect();
+        if watchpoints.is_empty() {
+            println!("No watchpoints set");
+        }
+        watchpoints.sort();
+        for watchpoint in watchpoints {
+            println!("{watchpoint}");
+        }
+    }
+
     fn validate_in_progress(&self) -> bool {
         match self.last_result {
-            DebugCommandResult::Ok | DebugCommandResult::BreakpointReached(..) => true,
+            DebugCommandResult::Ok
+            | DebugCommandResult::BreakpointReached(..)
+            | DebugCommandResult::WatchpointTriggered(..) => true,
             DebugCommandResult::Done => {
                 println!("Execution finished");
                 false
@@ -255,10 +374,16 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
     }
 
     fn handle_debug_command_result(&mut self, result: DebugCommandResult) {
+        for message in self.context.take_log_messages() {
+            println!("{message}");
+        }
         match &result {
             DebugCommandResult::BreakpointReached(location) => {
                 println!("Stopped at breakpoint in opcode {}", location);
             }
+            DebugCommandResult::WatchpointTriggered(hit) => {
+                println!("Stopped at watchpoint on {hit}");
+            }
             DebugCommandResult::Error(error) => {
                 println!("ERROR: {}", error);
             }
@@ -266,6 +391,7 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
         }
         self.last_result = result;
         self.show_current_vm_status();
+        self.show_watches();
     }
 
     fn step_acir_opcode(&mut self) {
@@ -311,10 +437,71 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
         }
     }
 
+    fn step_back_into_opcode(&mut self) {
+        let result = self.context.step_back_into_opcode();
+        self.handle_reverse_command_result(result);
+    }
+
+    fn reverse_next_into(&mut self) {
+        let result = self.context.reverse_next_into();
+        self.handle_reverse_command_result(result);
+    }
+
+    fn reverse_next_over(&mut self) {
+        let result = self.context.reverse_next_over();
+        self.handle_reverse_command_result(result);
+    }
+
+    fn reverse_next_out(&mut self) {
+        let result = self.context.reverse_next_out();
+        self.handle_reverse_command_result(result);
+    }
+
+    fn reverse_continue(&mut self) {
+        println!("(Reversing execution...)");
+        let result = self.context.reverse_continue();
+        self.handle_reverse_command_result(result);
+    }
+
+    /// Going back in time is possible even after execution finished or failed
+    fn handle_reverse_command_result(&mut self, result: DebugCommandResult) {
+        if self.context.is_at_start() {
+            println!("Reached the start of the program");
+        }
+        self.handle_debug_command_result(result);
+    }
+
+    /// Executes the program until it fails and shows the state of the execution at that point.
+    /// Returns false if the program finished without failing.
+    fn run_to_failure(&mut self) -> bool {
+        println!("(Executing until the program fails...)");
+        let result = self.context.cont();
+        if !matches!(result, DebugCommandResult::Error(..)) {
+            println!("Execution did not fail in the debugger");
+            self.last_result = result;
+            return false;
+        }
+        self.handle_debug_command_result(result);
+        self.show_current_call_stack();
+        self.show_vars();
+        println!("Use `witness` and `memory` to inspect the witness map and the Brillig memory");
+        true
+    }
+
     fn restart_session(&mut self) {
-        let breakpoints: Vec<DebugLocation> = self.context.iterate_breakpoints().copied().collect();
-        let foreign_call_executor =
-            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, self.debug_artifact));
+        let breakpoints: Vec<(DebugLocation, Breakpoint)> = self
+            .context
+            .iterate_breakpoints()
+            .map(|(location, breakpoint)| (*location, breakpoint.clone()))
+            .collect();
+        let watchpoints: Vec<Watchpoint> = self.context.iterate_watchpoints().cloned().collect();
+        let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            self.foreign_call_resolver_url.as_deref(),
+            self.root_path.clone(),
+            self.package_name.clone(),
+            self.debug_artifact,
+        ));
         self.context = DebugContext::new(
             self.blackbox_solver,
             self.circuits,
@@ -323,8 +510,11 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
             foreign_call_executor,
             self.unconstrained_functions,
         );
-        for debug_location in breakpoints {
-            self.context.add_breakpoint(debug_location);
+        for (debug_location, breakpoint) in breakpoints {
+            self.context.set_breakpoint(debug_location, breakpoint);
+        }
+        for watchpoint in watchpoints {
+            self.context.add_watchpoint(watchpoint);
         }
         self.last_result = DebugCommandResult::Ok;
         println!("Restarted debugging session.");
@@ -396,7 +586,9 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
             println!("Not executing a Brillig block");
             return;
         }
-        self.context.write_brillig_memory(index, field_value, bit_size);
+        if let Some(hit) = self.context.write_brillig_memory(index, field_value, bit_size) {
+            println!("Wrote watchpoint on {hit}");
+        }
     }
 
     pub fn show_vars(&self) {
@@ -410,6 +602,40 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
         }
     }
 
+    pub fn evaluate(&self, expression: String) {
+        match self.context.evaluate_expression(&expression, None) {
+            Ok(value) => println!("{value}"),
+            Err(error) => println!("ERROR: {error}"),
+        }
+    }
+
+    pub fn add_watch(&mut self, expression: String) {
+        if let Err(error) = expression.parse::<Expression>() {
+            println!("Invalid expression: {error}");
+            return;
+        }
+        self.watches.push(expression);
+        self.show_watches();
+    }
+
+    pub fn delete_watch(&mut self, index: usize) {
+        if index < self.watches.len() {
+            let expression = self.watches.remove(index);
+            println!("Watch #{index} deleted: {expression}");
+        } else {
+            println!("Watch #{index} not set");
+        }
+    }
+
+    fn show_watches(&self) {
+        for (index, expression) in self.watches.iter().enumerate() {
+            match self.context.evaluate_expression(expression, None) {
+                Ok(value) => println!("#{index} {expression} = {value}"),
+                Err(error) => println!("#{index} {expression} = <{error}>"),
+            }
+        }
+    }
+
     fn is_solved(&self) -> bool {
         self.context.is_solved()
     }
@@ -417,12 +643,30 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
     fn finalize(self) -> WitnessStack<FieldElement> {
         self.context.finalize()
     }
+
+    fn into_execution_result(
+        self,
+    ) -> Result<Option<WitnessStack<FieldElement>>, NargoError<FieldElement>> {
+        if self.is_solved() {
+            let solved_witness_stack = self.finalize();
+            Ok(Some(solved_witness_stack))
+        } else if let DebugCommandResult::Error(error) = self.last_result {
+            // Execution failed, so we return the error to report it like `nargo execute` would
+            Err(error)
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
     blackbox_solver: &B,
     program: CompiledProgram,
     initial_witness: WitnessMap<FieldElement>,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: Option<PathBuf>,
+    package_name: Option<String>,
+    start_at_failure: bool,
 ) -> Result<Option<WitnessStack<FieldElement>>, NargoError<FieldElement>> {
     let circuits = &program.program.functions;
     let debug_artifact =
@@ -434,10 +678,19 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
         debug_artifact,
         initial_witness,
         unconstrained_functions,
+        foreign_call_resolver_url.map(String::from),
+        root_path,
+        package_name,
     ));
     let ref_context = &context;
 
-    ref_context.borrow().show_current_vm_status();
+    if start_at_failure {
+        if !ref_context.borrow_mut().run_to_failure() {
+            return context.into_inner().into_execution_result();
+        }
+    } else {
+        ref_context.borrow().show_current_vm_status();
+    }
 
     let mut repl = Repl::builder()
         .add(
@@ -500,6 +753,56 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
                 }
             },
         )
+        .add(
+            "reverse-into",
+            command! {
+                "step back to the previous opcode",
+                () => || {
+                    ref_context.borrow_mut().step_back_into_opcode();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "reverse-next",
+            command! {
+                "step back to the start of the previous source location",
+                () => || {
+                    ref_context.borrow_mut().reverse_next_into();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "reverse-over",
+            command! {
+                "step back to the start of the previous source location without diving into function calls",
+                () => || {
+                    ref_context.borrow_mut().reverse_next_over();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "reverse-out",
+            command! {
+                "step back to the source location that called the current function",
+                () => || {
+                    ref_context.borrow_mut().reverse_next_out();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "reverse-continue",
+            command! {
+                "go back until the previous breakpoint or the start of the program",
+                () => || {
+                    ref_context.borrow_mut().reverse_continue();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
         .add(
             "restart",
             command! {
@@ -530,6 +833,36 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
                 }
             },
         )
+        .add(
+            "break",
+            command! {
+                "add a breakpoint at an opcode location which only stops when a condition holds",
+                (LOCATION:DebugLocation, CONDITION:String) => |location, condition| {
+                    ref_context.borrow_mut().set_breakpoint_condition_at(location, condition);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "hits",
+            command! {
+                "only stop at the breakpoint at an opcode location after a number of hits",
+                (LOCATION:DebugLocation, HIT_CONDITION:String) => |location, hit_condition| {
+                    ref_context.borrow_mut().set_breakpoint_hit_condition_at(location, hit_condition);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "logpoint",
+            command! {
+                "log a message instead of stopping at the breakpoint at an opcode location",
+                (LOCATION:DebugLocation, MESSAGE:String) => |location, message| {
+                    ref_context.borrow_mut().set_logpoint_at(location, message);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
         .add(
             "delete",
             command! {
@@ -540,6 +873,56 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
                 }
             },
         )
+        .add(
+            "watchvar",
+            command! {
+                "stop when a variable of the current function is assigned",
+                (NAME:String) => |name| {
+                    ref_context.borrow_mut().add_variable_watchpoint(name);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "watchmem",
+            command! {
+                "stop when a Brillig memory cell is written",
+                (ADDRESS:usize) => |address| {
+                    ref_context.borrow_mut().add_watchpoint(Watchpoint::BrilligMemory(address));
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "unwatchvar",
+            command! {
+                "delete the watchpoint on a variable of the current function",
+                (NAME:String) => |name| {
+                    ref_context.borrow_mut().delete_variable_watchpoint(name);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "unwatchmem",
+            command! {
+                "delete the watchpoint on a Brillig memory cell",
+                (ADDRESS:usize) => |address| {
+                    ref_context.borrow_mut().delete_watchpoint(Watchpoint::BrilligMemory(address));
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "watchpoints",
+            command! {
+                "show the watchpoints set",
+                () => || {
+                    ref_context.borrow().show_watchpoints();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
         .add(
             "witness",
             command! {
@@ -610,6 +993,36 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
                 }
             },
         )
+        .add(
+            "eval",
+            command! {
+                "evaluate an expression over the variables of the current function",
+                (EXPRESSION:String) => |expression| {
+                    ref_context.borrow().evaluate(expression);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "watch",
+            command! {
+                "evaluate an expression every time execution stops",
+                (EXPRESSION:String) => |expression| {
+                    ref_context.borrow_mut().add_watch(expression);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "unwatch",
+            command! {
+                "delete a watch expression",
+                (index:usize) => |index| {
+                    ref_context.borrow_mut().delete_watch(index);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
         .build()
         .expect("Failed to initialize debugger repl");
 
@@ -619,10 +1032,5 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
     // Drop it so that we can move fields out from `context` again.
     drop(repl);
 
-    if context.borrow().is_solved() {
-        let solved_witness_stack = context.into_inner().finalize();
-        Ok(Some(solved_witness_stack))
-    } else {
-        Ok(None)
-    }
+    context.into_inner().into_execution_result()
 }
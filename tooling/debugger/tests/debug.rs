@@ -49,4 +49,42 @@ mod tests {
         // Exit the bash session.
         dbg_session.send_line("exit").expect("Failed to quit bash session");
     }
+
+    #[test]
+    fn debugger_test_expected_failure() {
+        let nargo_bin =
+            cargo_bin("nargo").into_os_string().into_string().expect("Cannot parse nargo path");
+        let test_program_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../test_programs/noir_test_success/should_fail_with_matches"
+        );
+
+        let timeout_seconds = 25;
+        let mut dbg_session =
+            spawn_bash(Some(timeout_seconds * 1000)).expect("Could not start bash session");
+
+        dbg_session
+            .execute(
+                &format!(
+                    "{nargo_bin} debug --program-dir {test_program_dir} --test-name test_should_fail_with_runtime_match"
+                ),
+                ".*\\Starting debugger for test.*",
+            )
+            .expect("Could not start debugger");
+
+        // Continuing runs the test until its assertion fails, which is what it expects
+        dbg_session
+            .send_line("c")
+            .expect("Debugger panicked while attempting to step through program.");
+        dbg_session
+            .exp_string(">")
+            .expect("Failed while waiting for debugger to step through program.");
+
+        dbg_session.send_line("quit").expect("Failed to quit debugger");
+        dbg_session
+            .exp_regex(".*Test test_should_fail_with_runtime_match passed.*")
+            .expect("Expected the test to pass because it should fail.");
+
+        dbg_session.send_line("exit").expect("Failed to quit bash session");
+    }
 }
@@ -0,0 +1,89 @@
+//! A corpus of inputs which is persisted between fuzzing runs.
+//!
+//! Inputs are stored as `Prover.toml`-style files so that they can be inspected, edited
+//! or passed to `nargo execute` directly. Failing inputs are kept separately from inputs
+//! which are merely interesting so that they can be replayed first.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use noirc_abi::{input_parser::Format, Abi, InputMap};
+
+const FAILURES_DIR: &str = "failures";
+const INTERESTING_DIR: &str = "interesting";
+
+/// A directory holding the saved inputs of a single fuzzed program.
+#[derive(Debug, Clone)]
+pub struct Corpus {
+    dir: PathBuf,
+}
+
+impl Corpus {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns all saved inputs which still match the program's ABI, failing inputs first.
+    ///
+    /// Inputs which can't be parsed anymore, e.g. because the program's parameters changed,
+    /// are skipped.
+    pub fn load(&self, abi: &Abi) -> Vec<InputMap> {
+        let mut inputs = load_inputs(&self.dir.join(FAILURES_DIR), abi);
+        inputs.extend(load_inputs(&self.dir.join(INTERESTING_DIR), abi));
+        inputs
+    }
+
+    /// Saves an input which made the program fail, returning the path it was written to.
+    pub fn save_failure(&self, abi: &Abi, input_map: &InputMap) -> std::io::Result<PathBuf> {
+        save_input(&self.dir.join(FAILURES_DIR), abi, input_map)
+    }
+
+    /// Saves an input which is worth replaying in later runs, returning the path it was written to.
+    pub fn save_interesting(&self, abi: &Abi, input_map: &InputMap) -> std::io::Result<PathBuf> {
+        save_input(&self.dir.join(INTERESTING_DIR), abi, input_map)
+    }
+}
+
+fn load_inputs(dir: &Path, abi: &Abi) -> Vec<InputMap> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == Format::Toml.ext()))
+        .collect();
+    // Sort the files so that inputs are always replayed in the same order
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let contents = fs::read_to_string(path).ok()?;
+            Format::Toml.parse(&contents, abi).ok()
+        })
+        .collect()
+}
+
+fn save_input(dir: &Path, abi: &Abi, input_map: &InputMap) -> std::io::Result<PathBuf> {
+    let contents = Format::Toml
+        .serialize(input_map, abi)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))?;
+
+    // Name files after their contents so that the same input is only ever saved once
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    let path = dir.join(format!("{:016x}.{}", hasher.finish(), Format::Toml.ext()));
+
+    fs::create_dir_all(dir)?;
+    fs::write(&path, contents)?;
+    Ok(path)
+}
@@ -0,0 +1,129 @@
+//! This module tracks which branches of a [Program] were taken while executing it, which is used
+//! as feedback to guide the fuzzer towards inputs which reach new code.
+//!
+//! ACIR circuits have no control flow: every opcode is executed for any input which doesn't fail,
+//! so only the conditional jumps of the Brillig functions give meaningful feedback. These are
+//! recovered from the VM's profiling samples, each of which records the program counter of
+//! an executed Brillig opcode.
+use std::collections::HashSet;
+
+use acvm::{
+    acir::circuit::{brillig::BrilligFunctionId, OpcodeLocation, Program},
+    brillig_vm::brillig::Opcode as BrilligOpcode,
+    pwg::ProfilingSample,
+    AcirField,
+};
+
+/// A conditional jump in a Brillig function, from the `JumpIf`/`JumpIfNot` opcode at `from`
+/// to the opcode at `to`. Both outcomes of a jump are different edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct BranchEdge {
+    function_id: BrilligFunctionId,
+    from: usize,
+    to: usize,
+}
+
+/// The set of branch edges reached by one or more executions of a program.
+#[derive(Debug, Default)]
+pub(super) struct Coverage {
+    edges: HashSet<BranchEdge>,
+}
+
+impl Coverage {
+    pub(super) fn from_profiling_samples<F: AcirField>(
+        program: &Program<F>,
+        samples: &[ProfilingSample],
+    ) -> Self {
+        let mut edges = HashSet::new();
+
+        // The Brillig function, ACIR call site and program counter of the previous sample
+        let mut previous: Option<(BrilligFunctionId, usize, usize)> = None;
+        for sample in samples {
+            let current = match (sample.brillig_function_id, sample.call_stack.last()) {
+                (
+                    Some(function_id),
+                    Some(OpcodeLocation::Brillig { acir_index, brillig_index }),
+                ) => Some((function_id, *acir_index, *brillig_index)),
+                _ => None,
+            };
+
+            if let (Some((previous_id, previous_call, from)), Some((function_id, call, to))) =
+                (previous, current)
+            {
+                let same_execution = previous_id == function_id && previous_call == call;
+                let bytecode = &program.unconstrained_functions[function_id.as_usize()].bytecode;
+                let is_conditional_jump = matches!(
+                    bytecode.get(from),
+                    Some(BrilligOpcode::JumpIf { .. } | BrilligOpcode::JumpIfNot { .. })
+                );
+                if same_execution && is_conditional_jump {
+                    edges.insert(BranchEdge { function_id, from, to });
+                }
+            }
+
+            previous = current;
+        }
+
+        Coverage { edges }
+    }
+
+    /// Adds the edges of `other` to this coverage, returning whether any of them is new.
+    pub(super) fn merge(&mut self, other: Coverage) -> bool {
+        let edges_before = self.edges.len();
+        self.edges.extend(other.edges);
+        self.edges.len() > edges_before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::circuit::{
+            brillig::{BrilligBytecode, BrilligFunctionId},
+            OpcodeLocation, Program,
+        },
+        brillig_vm::brillig::{MemoryAddress, Opcode as BrilligOpcode},
+        pwg::ProfilingSample,
+        FieldElement,
+    };
+
+    use super::Coverage;
+
+    fn sample(brillig_index: usize) -> ProfilingSample {
+        ProfilingSample {
+            call_stack: vec![
+                OpcodeLocation::Acir(0),
+                OpcodeLocation::Brillig { acir_index: 0, brillig_index },
+            ],
+            brillig_function_id: Some(BrilligFunctionId(0)),
+        }
+    }
+
+    #[test]
+    fn collects_taken_branches() {
+        let condition = MemoryAddress::direct(0);
+        let bytecode = vec![
+            BrilligOpcode::JumpIf { condition, location: 2 },
+            BrilligOpcode::Return,
+            BrilligOpcode::Return,
+        ];
+        let program: Program<FieldElement> = Program {
+            functions: Vec::new(),
+            unconstrained_functions: vec![BrilligBytecode { bytecode }],
+        };
+
+        let mut coverage = Coverage::default();
+
+        // The jump is taken
+        let jump = Coverage::from_profiling_samples(&program, &[sample(0), sample(2)]);
+        assert!(coverage.merge(jump));
+
+        // The same jump again doesn't add anything
+        let jump = Coverage::from_profiling_samples(&program, &[sample(0), sample(2)]);
+        assert!(!coverage.merge(jump));
+
+        // Falling through is a different edge
+        let fallthrough = Coverage::from_profiling_samples(&program, &[sample(0), sample(1)]);
+        assert!(coverage.merge(fallthrough));
+    }
+}
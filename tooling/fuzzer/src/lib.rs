@@ -3,21 +3,32 @@
 //!
 //! Code is used under the MIT license.
 
+use std::collections::HashSet;
+
 use acvm::{
     acir::{
         circuit::Program,
         native_types::{WitnessMap, WitnessStack},
     },
+    pwg::ProfilingSamples,
     FieldElement,
 };
+use coverage::Coverage;
 use dictionary::build_dictionary_from_program;
 use noirc_abi::InputMap;
-use proptest::test_runner::{TestCaseError, TestError, TestRunner};
+use proptest::{
+    strategy::{Strategy, ValueTree},
+    test_runner::{TestCaseError, TestError, TestRunner},
+};
+use rand::Rng;
 
+mod corpus;
+mod coverage;
 mod dictionary;
 mod strategies;
 mod types;
 
+pub use corpus::Corpus;
 use types::{CaseOutcome, CounterExampleOutcome, FuzzOutcome, FuzzTestResult};
 
 use noirc_artifacts::program::ProgramArtifact;
@@ -27,32 +38,69 @@ use noirc_artifacts::program::ProgramArtifact;
 /// After instantiation, calling `fuzz` will proceed to hammer the program with
 /// inputs, until it finds a counterexample. The provided [`TestRunner`] contains all the
 /// configuration which can be overridden via [environment variables](proptest::test_runner::Config)
+///
+/// If a [`Corpus`] is attached, its saved inputs are replayed before any new input is generated
+/// and any counterexample found is saved to it.
+///
+/// In coverage-guided mode, inputs which take Brillig branches that no previous input took are kept
+/// and mutated to generate further inputs, instead of only generating inputs from scratch.
 pub struct FuzzedExecutor<E> {
     /// The program to be fuzzed
     program: ProgramArtifact,
 
-    /// A function which executes the programs with a given set of inputs
+    /// A function which executes the programs with a given set of inputs.
+    /// The profiling samples of the execution must be returned if its last argument is `true`,
+    /// they are used to compute coverage.
     executor: E,
 
     /// The fuzzer
     runner: TestRunner,
+
+    /// Inputs persisted between runs
+    corpus: Option<Corpus>,
+
+    /// Whether branch coverage is used to guide the generation of inputs
+    coverage_guided: bool,
 }
 
 impl<
         E: Fn(
             &Program<FieldElement>,
             WitnessMap<FieldElement>,
-        ) -> Result<WitnessStack<FieldElement>, String>,
+            bool,
+        ) -> Result<(WitnessStack<FieldElement>, ProfilingSamples), String>,
     > FuzzedExecutor<E>
 {
     /// Instantiates a fuzzed executor given a testrunner
     pub fn new(program: ProgramArtifact, executor: E, runner: TestRunner) -> Self {
-        Self { program, executor, runner }
+        Self { program, executor, runner, corpus: None, coverage_guided: false }
+    }
+
+    /// Enables the coverage-guided mode
+    pub fn with_coverage_guidance(mut self) -> Self {
+        self.coverage_guided = true;
+        self
+    }
+
+    /// Attaches a corpus to replay saved inputs from and save counterexamples to
+    pub fn with_corpus(mut self, corpus: Corpus) -> Self {
+        self.corpus = Some(corpus);
+        self
     }
 
     /// Fuzzes the provided program.
     pub fn fuzz(&self) -> FuzzTestResult {
+        let mut coverage = Coverage::default();
+        let mut seeds = Vec::new();
+        if let Some(result) = self.replay_corpus(&mut coverage, &mut seeds) {
+            return result;
+        }
+
         let dictionary = build_dictionary_from_program(&self.program.bytecode);
+        if self.coverage_guided {
+            return self.fuzz_with_coverage(dictionary, coverage, seeds);
+        }
+
         let strategy = strategies::arb_input_map(&self.program.abi, dictionary);
 
         let run_result: Result<(), TestError<InputMap>> =
@@ -77,19 +125,161 @@ impl<
                 counterexample: None,
             },
             Err(TestError::Fail(reason, counterexample)) => {
-                let reason = reason.to_string();
-                let reason = if reason.is_empty() { None } else { Some(reason) };
+                self.counterexample_result(reason.to_string(), counterexample)
+            }
+        }
+    }
+
+    /// Generates inputs either from scratch or by mutating the inputs which reached new branches,
+    /// until a counterexample is found or the configured number of cases is reached.
+    fn fuzz_with_coverage(
+        &self,
+        dictionary: HashSet<FieldElement>,
+        mut coverage: Coverage,
+        mut seeds: Vec<InputMap>,
+    ) -> FuzzTestResult {
+        let mut runner = self.runner.clone();
+        let strategy = strategies::arb_input_map(&self.program.abi, dictionary.clone());
 
-                FuzzTestResult { success: false, reason, counterexample: Some(counterexample) }
+        for _ in 0..runner.config().cases {
+            // Half of the inputs are mutations of known interesting inputs, once there are any.
+            let mutate = !seeds.is_empty() && runner.rng().gen_bool(0.5);
+            let (input_map, tree) = if mutate {
+                let seed = &seeds[runner.rng().gen_range(0..seeds.len())];
+                let input_map =
+                    strategies::mutate_input_map(&self.program.abi, &dictionary, seed, &mut runner);
+                (input_map, None)
+            } else {
+                match strategy.new_tree(&mut runner) {
+                    Ok(tree) => (tree.current(), Some(tree)),
+                    Err(reason) => {
+                        return FuzzTestResult {
+                            success: false,
+                            reason: Some(reason.to_string()),
+                            counterexample: None,
+                        }
+                    }
+                }
+            };
+
+            match self.execute_with_coverage(&input_map) {
+                Ok(input_coverage) => {
+                    if coverage.merge(input_coverage) {
+                        if let Some(corpus) = &self.corpus {
+                            let _ = corpus.save_interesting(&self.program.abi, &input_map);
+                        }
+                        seeds.push(input_map);
+                    }
+                }
+                Err(reason) => {
+                    // Only inputs generated from scratch can be shrunk: mutated ones have no value tree.
+                    let (counterexample, reason) = match tree {
+                        Some(tree) => self.shrink(tree, input_map, reason),
+                        None => (input_map, reason),
+                    };
+                    return self.counterexample_result(reason, counterexample);
+                }
             }
         }
+
+        FuzzTestResult { success: true, reason: None, counterexample: None }
+    }
+
+    /// Simplifies a failing input for as long as it keeps failing, up to the configured
+    /// maximum number of shrink iterations, returning the simplest failing input and its failure reason.
+    fn shrink(
+        &self,
+        mut tree: impl ValueTree<Value = InputMap>,
+        mut counterexample: InputMap,
+        mut reason: String,
+    ) -> (InputMap, String) {
+        if !tree.simplify() {
+            return (counterexample, reason);
+        }
+
+        for _ in 0..self.runner.config().max_shrink_iters {
+            let input_map = tree.current();
+            let keep_going = match self.single_fuzz(input_map) {
+                Ok(FuzzOutcome::CounterExample(outcome)) => {
+                    counterexample = outcome.counterexample;
+                    reason = outcome.exit_reason;
+                    tree.simplify()
+                }
+                _ => tree.complicate(),
+            };
+            if !keep_going {
+                break;
+            }
+        }
+
+        (counterexample, reason)
+    }
+
+    fn counterexample_result(&self, reason: String, counterexample: InputMap) -> FuzzTestResult {
+        let reason = if reason.is_empty() { None } else { Some(reason) };
+
+        if let Some(corpus) = &self.corpus {
+            // Failing to persist the counterexample shouldn't hide the failure itself
+            let _ = corpus.save_failure(&self.program.abi, &counterexample);
+        }
+
+        FuzzTestResult { success: false, reason, counterexample: Some(counterexample) }
+    }
+
+    /// Runs the program with every input saved in the corpus, returning the result
+    /// of the first one which fails.
+    ///
+    /// In coverage-guided mode, the coverage of the saved inputs is added to `coverage`
+    /// and those which reached new branches are added to `seeds`.
+    fn replay_corpus(
+        &self,
+        coverage: &mut Coverage,
+        seeds: &mut Vec<InputMap>,
+    ) -> Option<FuzzTestResult> {
+        let corpus = self.corpus.as_ref()?;
+        for input_map in corpus.load(&self.program.abi) {
+            let result = if self.coverage_guided {
+                self.execute_with_coverage(&input_map)
+            } else {
+                self.execute(&input_map, false).map(|_| Coverage::default())
+            };
+
+            match result {
+                Ok(input_coverage) => {
+                    if coverage.merge(input_coverage) {
+                        seeds.push(input_map);
+                    }
+                }
+                Err(exit_reason) => {
+                    return Some(FuzzTestResult {
+                        success: false,
+                        reason: Some(exit_reason),
+                        counterexample: Some(input_map),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn execute(
+        &self,
+        input_map: &InputMap,
+        profiling_active: bool,
+    ) -> Result<(WitnessStack<FieldElement>, ProfilingSamples), String> {
+        let initial_witness = self.program.abi.encode(input_map, None).unwrap();
+        (self.executor)(&self.program.bytecode, initial_witness, profiling_active)
+    }
+
+    fn execute_with_coverage(&self, input_map: &InputMap) -> Result<Coverage, String> {
+        let (_, profiling_samples) = self.execute(input_map, true)?;
+        Ok(Coverage::from_profiling_samples(&self.program.bytecode, &profiling_samples))
     }
 
     /// Granular and single-step function that runs only one fuzz and returns either a `CaseOutcome`
     /// or a `CounterExampleOutcome`
     pub fn single_fuzz(&self, input_map: InputMap) -> Result<FuzzOutcome, TestCaseError> {
-        let initial_witness = self.program.abi.encode(&input_map, None).unwrap();
-        let result = (self.executor)(&self.program.bytecode, initial_witness);
+        let result = self.execute(&input_map, false);
 
         // TODO: Add handling for `vm.assume` equivalent
 
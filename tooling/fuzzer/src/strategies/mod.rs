@@ -1,6 +1,7 @@
 use int::IntStrategy;
 use prop::collection::vec;
-use proptest::prelude::*;
+use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+use rand::Rng;
 
 use acvm::{AcirField, FieldElement};
 
@@ -25,12 +26,11 @@ pub(super) fn arb_value_from_abi_type(
                 .sboxed()
         }
         AbiType::Integer { width, .. } => {
-            let shift = 2i128.pow(*width);
+            let mask = u128::MAX >> (128 - width);
             IntStrategy::new(*width as usize)
-                .prop_map(move |mut int| {
-                    if int < 0 {
-                        int += shift
-                    }
+                .prop_map(move |int| {
+                    // Negative values are represented by their two's complement relative to the width
+                    let int = (int as u128) & mask;
                     InputValue::Field(int.into())
                 })
                 .sboxed()
@@ -97,3 +97,60 @@ pub(super) fn arb_input_map(
         })
         .boxed()
 }
+
+/// Returns a copy of `input_map` in which a few randomly picked values are regenerated.
+///
+/// Only leaves (fields, integers, booleans and strings) are regenerated, from the same strategies
+/// used to generate fresh inputs, so mutated inputs always stay within the bounds of their types.
+pub(super) fn mutate_input_map(
+    abi: &Abi,
+    dictionary: &HashSet<FieldElement>,
+    input_map: &InputMap,
+    runner: &mut TestRunner,
+) -> InputMap {
+    let mut input_map = input_map.clone();
+    if abi.parameters.is_empty() {
+        return input_map;
+    }
+
+    let mutations = runner.rng().gen_range(1..=3);
+    for _ in 0..mutations {
+        let param = &abi.parameters[runner.rng().gen_range(0..abi.parameters.len())];
+        if let Some(value) = input_map.get_mut(&param.name) {
+            mutate_value(&param.typ, value, dictionary, runner);
+        }
+    }
+    input_map
+}
+
+fn mutate_value(
+    abi_type: &AbiType,
+    value: &mut InputValue,
+    dictionary: &HashSet<FieldElement>,
+    runner: &mut TestRunner,
+) {
+    match (abi_type, value) {
+        (AbiType::Array { typ, .. }, InputValue::Vec(elements)) if !elements.is_empty() => {
+            let index = runner.rng().gen_range(0..elements.len());
+            mutate_value(typ, &mut elements[index], dictionary, runner);
+        }
+        (AbiType::Tuple { fields }, InputValue::Vec(elements)) if !fields.is_empty() => {
+            let index = runner.rng().gen_range(0..fields.len());
+            if let Some(element) = elements.get_mut(index) {
+                mutate_value(&fields[index], element, dictionary, runner);
+            }
+        }
+        (AbiType::Struct { fields, .. }, InputValue::Struct(values)) if !fields.is_empty() => {
+            let (name, typ) = &fields[runner.rng().gen_range(0..fields.len())];
+            if let Some(value) = values.get_mut(name) {
+                mutate_value(typ, value, dictionary, runner);
+            }
+        }
+        (abi_type, value) => {
+            if let Ok(tree) = arb_value_from_abi_type(abi_type, dictionary.clone()).new_tree(runner)
+            {
+                *value = tree.current();
+            }
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! The circuit cost of each function in a package: how many ACIR and Brillig opcodes it compiles
+//! to when it's a program's entry point, which is what `nargo info` reports for `main`.
+//! Costs are computed in the background once a package type-checks without errors, then shown
+//! in code lenses above each function and when hovering over a function.
+
+use std::{
+    collections::HashMap,
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use async_lsp::LanguageClient;
+use fm::FileManager;
+use nargo::package::Package;
+use noirc_driver::{check_crate, compile_no_check, CompileOptions, DEFAULT_EXPRESSION_WIDTH};
+use noirc_errors::Location;
+use noirc_frontend::{
+    ast::FunctionKind,
+    graph::CrateId,
+    hir::{Context, ParsedFiles},
+    node_interner::FuncId,
+};
+
+use crate::{parse_diff, spawn_background_compile, LspState, SourceFingerprint};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FunctionCircuitCost {
+    /// `None` for unconstrained functions, which only compile to Brillig
+    pub(crate) acir_opcodes: Option<usize>,
+    pub(crate) brillig_opcodes: usize,
+}
+
+impl FunctionCircuitCost {
+    pub(crate) fn summary(&self) -> String {
+        match self.acir_opcodes {
+            None => format!("Brillig opcodes: {}", self.brillig_opcodes),
+            Some(acir_opcodes) if self.brillig_opcodes == 0 => {
+                format!("ACIR opcodes: {acir_opcodes}")
+            }
+            Some(acir_opcodes) => {
+                format!("ACIR opcodes: {acir_opcodes}, Brillig opcodes: {}", self.brillig_opcodes)
+            }
+        }
+    }
+}
+
+/// The cost of each function that could be compiled, by the location of the function's name.
+pub(crate) type CircuitCosts = HashMap<Location, FunctionCircuitCost>;
+
+/// Compiles each function in `package` that could be a program's entry point, with the same
+/// pipeline `nargo info` uses for `main`.
+/// Returns `None` if the package doesn't type-check or if `cancelled` gets set before it's done.
+pub(crate) fn compute_circuit_costs(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    package: &Package,
+    cancelled: &AtomicBool,
+) -> Option<CircuitCosts> {
+    let (mut context, crate_id) = nargo::prepare_package(file_manager, parsed_files, package);
+    let options = CompileOptions::default();
+    check_crate(&mut context, crate_id, &options).ok()?;
+
+    let expression_width = package.expression_width.unwrap_or(DEFAULT_EXPRESSION_WIDTH);
+    let func_ids: Vec<FuncId> = context
+        .def_interner
+        .function_ids()
+        .filter(|func_id| can_be_entry_point(&context, crate_id, *func_id))
+        .collect();
+
+    let mut costs = CircuitCosts::new();
+    for func_id in func_ids {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let Ok(program) = compile_no_check(&mut context, &options, func_id, None, true) else {
+            continue;
+        };
+        let program = nargo::ops::transform_program(program, expression_width).program;
+
+        let is_unconstrained = context.def_interner.function_modifiers(&func_id).is_unconstrained;
+        let acir_opcodes = (!is_unconstrained).then(|| program.functions[0].opcodes.len());
+        let brillig_opcodes =
+            program.unconstrained_functions.iter().map(|function| function.bytecode.len()).sum();
+
+        let location = context.def_interner.function_meta(&func_id).name.location;
+        costs.insert(location, FunctionCircuitCost { acir_opcodes, brillig_opcodes });
+    }
+
+    Some(costs)
+}
+
+/// A function can be compiled on its own if it belongs to the crate, it's a regular function
+/// that can run at runtime, it isn't generic, and its parameters and return type are valid
+/// program inputs.
+fn can_be_entry_point(context: &Context, crate_id: CrateId, func_id: FuncId) -> bool {
+    let func_meta = context.def_interner.function_meta(&func_id);
+    let modifiers = context.def_interner.function_modifiers(&func_id);
+
+    func_meta.source_crate == crate_id
+        && func_meta.kind == FunctionKind::Normal
+        && !modifiers.is_comptime
+        && func_meta.trait_id.is_none()
+        && func_meta.all_generics.is_empty()
+        && func_meta.trait_constraints.is_empty()
+        && func_meta.parameters.iter().all(|(_, typ, _)| typ.is_valid_for_program_input())
+        && func_meta.return_type().is_valid_for_program_input()
+}
+
+/// Queues computing the circuit costs of a package that was just checked without errors.
+pub(crate) fn queue_circuit_costs(
+    state: &mut LspState,
+    workspace_root: &Path,
+    package: &Package,
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+) {
+    if !state.options.enable_circuit_costs {
+        return;
+    }
+
+    let event = ComputeCircuitCostsEvent {
+        workspace_root: workspace_root.to_path_buf(),
+        package: package.clone(),
+    };
+    if state.client.emit(event).is_ok() {
+        return;
+    }
+
+    // The main loop isn't running (this only happens in tests) so we compute them right away
+    let costs = compute_circuit_costs(file_manager, parsed_files, package, &AtomicBool::new(false));
+    if let Some(package_cache) = state.package_cache.get_mut(&package.root_dir) {
+        package_cache.circuit_costs = costs;
+    }
+}
+
+/// Emitted after a package is checked without errors, to compute its circuit costs once pending
+/// notifications are handled.
+pub(crate) struct ComputeCircuitCostsEvent {
+    workspace_root: PathBuf,
+    package: Package,
+}
+
+/// Emitted by the background thread computing a package's circuit costs once it's done.
+pub(crate) struct CircuitCostsComputedEvent {
+    package_root: PathBuf,
+    source_fingerprint: SourceFingerprint,
+    costs: CircuitCosts,
+}
+
+pub(crate) fn on_compute_circuit_costs_event(
+    state: &mut LspState,
+    event: ComputeCircuitCostsEvent,
+) -> ControlFlow<Result<(), async_lsp::Error>> {
+    let package_root = event.package.root_dir.clone();
+    let Some(package_cache) = state.package_cache.get(&package_root) else {
+        return ControlFlow::Continue(());
+    };
+    if package_cache.circuit_costs.is_some() || package_cache.circuit_costs_cancelled.is_some() {
+        return ControlFlow::Continue(());
+    }
+
+    // The package changed after this event was emitted, so its new check will queue another one
+    let Some(workspace_cache) = state.workspace_cache.get(&event.workspace_root) else {
+        return ControlFlow::Continue(());
+    };
+    if !package_cache.source_fingerprint.matches(&workspace_cache.file_manager) {
+        return ControlFlow::Continue(());
+    }
+
+    let source_fingerprint = package_cache.source_fingerprint.clone();
+    let file_manager = workspace_cache.file_manager.clone();
+    let parsed_files = parse_diff(&file_manager, state);
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    if let Some(package_cache) = state.package_cache.get_mut(&package_root) {
+        package_cache.circuit_costs_cancelled = Some(cancelled.clone());
+    }
+
+    let client = state.client.clone();
+    let package = event.package;
+    spawn_background_compile("circuit costs", move || {
+        let costs = compute_circuit_costs(&file_manager, &parsed_files, &package, &cancelled);
+        if let Some(costs) = costs {
+            let package_root = package.root_dir;
+            let _ =
+                client.emit(CircuitCostsComputedEvent { package_root, source_fingerprint, costs });
+        }
+    });
+
+    ControlFlow::Continue(())
+}
+
+pub(crate) fn on_circuit_costs_computed_event(
+    state: &mut LspState,
+    event: CircuitCostsComputedEvent,
+) -> ControlFlow<Result<(), async_lsp::Error>> {
+    // The costs are discarded if the package changed while they were being computed
+    let Some(package_cache) = state.package_cache.get_mut(&event.package_root) else {
+        return ControlFlow::Continue(());
+    };
+    if package_cache.source_fingerprint != event.source_fingerprint {
+        return ControlFlow::Continue(());
+    }
+
+    package_cache.circuit_costs = Some(event.costs);
+    package_cache.circuit_costs_cancelled = None;
+
+    // The request is sent right away, and we don't need to wait for its response,
+    // so the future that would resolve to it is dropped
+    if state.client_supports_code_lens_refresh {
+        drop(state.client.code_lens_refresh(()));
+    }
+
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod circuit_costs_tests {
+    use crate::{
+        notifications::on_did_open_text_document,
+        requests::{on_code_lens_request, on_hover_request},
+        test_utils,
+    };
+
+    use lsp_types::{
+        CodeLensParams, DidOpenTextDocumentParams, HoverContents, HoverParams, PartialResultParams,
+        Position, TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams,
+        WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    #[test]
+    async fn test_shows_circuit_costs_in_code_lenses_and_hovers() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("circuit_costs").await;
+
+        let file_path = noir_text_document.to_file_path().unwrap();
+        let _ = on_did_open_text_document(
+            &mut state,
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: noir_text_document.clone(),
+                    language_id: "noir".to_string(),
+                    version: 0,
+                    text: std::fs::read_to_string(&file_path).unwrap(),
+                },
+            },
+        );
+
+        let lenses = on_code_lens_request(
+            &mut state,
+            CodeLensParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document.clone() },
+                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+                partial_result_params: PartialResultParams { partial_result_token: None },
+            },
+        )
+        .await
+        .expect("Could not execute code lens request")
+        .unwrap();
+
+        let cost_lens_title = |name: &str| {
+            let range = test_utils::search_in_file(file_path.to_str().unwrap(), name)[0];
+            lenses.iter().find_map(|lens| {
+                let title = &lens.command.as_ref()?.title;
+                let is_cost = title.starts_with("ACIR") || title.starts_with("Brillig");
+                (lens.range == range && is_cost).then(|| title.clone())
+            })
+        };
+
+        assert!(cost_lens_title("fn main").is_none());
+        assert!(cost_lens_title("main").unwrap().starts_with("ACIR opcodes: "));
+        assert!(cost_lens_title("double").unwrap().starts_with("ACIR opcodes: "));
+        assert!(cost_lens_title("triple").unwrap().starts_with("Brillig opcodes: "));
+        assert!(cost_lens_title("identity").is_none());
+        assert!(cost_lens_title("at_compile_time").is_none());
+
+        // Hovering a call site shows the cost of the called function
+        let call_site = test_utils::search_in_file(file_path.to_str().unwrap(), "double(x)")[0];
+        let hover = on_hover_request(
+            &mut state,
+            HoverParams {
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri: noir_text_document },
+                    position: Position {
+                        line: call_site.start.line,
+                        character: call_site.start.character + 1,
+                    },
+                },
+                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            },
+        )
+        .await
+        .expect("Could not execute hover")
+        .unwrap();
+
+        let HoverContents::Markup(markup) = hover.contents else {
+            panic!("Expected hover contents to be Markup");
+        };
+        let expected = cost_lens_title("double").unwrap();
+        assert!(markup.value.ends_with(&expected), "{}", markup.value);
+    }
+}
@@ -0,0 +1,154 @@
+//! The dependencies of a package, the standard library included, elaborated ahead of time so that
+//! checking the package after an edit to its own files only elaborates the package's crate.
+//!
+//! The elaborated state can't be shared between checks, as elaborating a crate may still modify
+//! the definitions of its dependencies (for example by binding the type of a global), so each
+//! check takes the state prepared for it and queues elaborating the dependencies again for the
+//! next one, once pending notifications are handled.
+
+use std::{
+    collections::BTreeMap,
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+};
+
+use fm::{FileId, FileManager};
+use nargo::package::Package;
+use noirc_errors::{CustomDiagnostic, FileDiagnostic, Location};
+use noirc_frontend::{
+    graph::{CrateGraph, CrateId},
+    hir::{def_map::CrateDefMap, Context, ParsedFiles},
+    node_interner::NodeInterner,
+    usage_tracker::UsageTracker,
+};
+
+use crate::{parse_diff, LspState, SourceFingerprint};
+
+/// The state of a package's context once its dependencies are elaborated, before its own crate is.
+pub(crate) struct DependencyCacheData {
+    // The crate graph of the package, which must be the same for this data to be used.
+    crate_graph: CrateGraph,
+    node_interner: NodeInterner,
+    def_maps: BTreeMap<CrateId, CrateDefMap>,
+    usage_tracker: UsageTracker,
+    visited_files: BTreeMap<FileId, Location>,
+    // The files of the dependencies, used to tell whether they changed since they were elaborated.
+    source_fingerprint: SourceFingerprint,
+    // The errors and warnings found in the dependencies.
+    pub(crate) file_diagnostics: Vec<FileDiagnostic>,
+}
+
+/// Elaborates the dependencies of the package's crate into `context`, or moves in the ones
+/// elaborated ahead of time if none of their files changed since, and returns their errors and
+/// warnings. `check_crate` then only elaborates the package's crate, as it skips the crates that
+/// already are.
+pub(crate) fn elaborate_dependencies(
+    state: &mut LspState,
+    context: &mut Context,
+    crate_id: CrateId,
+    package: &Package,
+) -> Vec<FileDiagnostic> {
+    if let Some(dependency_cache) = state.dependency_cache.remove(&package.root_dir) {
+        if dependency_cache.crate_graph == context.crate_graph
+            && dependency_cache.source_fingerprint.matches(&context.file_manager)
+        {
+            context.def_interner = dependency_cache.node_interner;
+            context.def_maps = dependency_cache.def_maps;
+            context.usage_tracker = dependency_cache.usage_tracker;
+            context.visited_files = dependency_cache.visited_files;
+            return dependency_cache.file_diagnostics;
+        }
+    }
+
+    collect_dependencies(context, crate_id)
+}
+
+/// Elaborates the dependencies of `crate_id`, in the same order as when checking it.
+fn collect_dependencies(context: &mut Context, crate_id: CrateId) -> Vec<FileDiagnostic> {
+    let mut file_diagnostics = Vec::new();
+    for dependency in context.crate_graph[crate_id].dependencies.clone() {
+        let error_on_unused_items = false;
+        let errors =
+            CrateDefMap::collect_defs(dependency.crate_id, context, None, error_on_unused_items);
+        file_diagnostics.extend(
+            errors
+                .into_iter()
+                .map(|(error, file_id)| CustomDiagnostic::from(&error).in_file(file_id)),
+        );
+    }
+    file_diagnostics
+}
+
+/// Queues elaborating the dependencies of a package that was just checked, for its next check.
+pub(crate) fn queue_dependency_elaboration(
+    state: &mut LspState,
+    workspace_root: &Path,
+    package: &Package,
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+) {
+    let event = ElaborateDependenciesEvent {
+        workspace_root: workspace_root.to_path_buf(),
+        package: package.clone(),
+    };
+    if state.client.emit(event).is_ok() {
+        return;
+    }
+
+    // The main loop isn't running (this only happens in tests) so we elaborate them right away
+    let dependency_cache = elaborate_dependencies_ahead(file_manager, parsed_files, package);
+    state.dependency_cache.insert(package.root_dir.clone(), dependency_cache);
+}
+
+/// Emitted after a package is checked, to elaborate its dependencies for the next check once
+/// pending notifications are handled.
+pub(crate) struct ElaborateDependenciesEvent {
+    workspace_root: PathBuf,
+    package: Package,
+}
+
+pub(crate) fn on_elaborate_dependencies_event(
+    state: &mut LspState,
+    event: ElaborateDependenciesEvent,
+) -> ControlFlow<Result<(), async_lsp::Error>> {
+    let Some(workspace_cache) = state.workspace_cache.get(&event.workspace_root) else {
+        return ControlFlow::Continue(());
+    };
+
+    // The dependencies were already elaborated by an earlier event for the same sources
+    let package_root = &event.package.root_dir;
+    if let Some(dependency_cache) = state.dependency_cache.get(package_root) {
+        if dependency_cache.source_fingerprint.matches(&workspace_cache.file_manager) {
+            return ControlFlow::Continue(());
+        }
+    }
+
+    let file_manager = workspace_cache.file_manager.clone();
+    let parsed_files = parse_diff(&file_manager, state);
+    let dependency_cache =
+        elaborate_dependencies_ahead(&file_manager, &parsed_files, &event.package);
+    state.dependency_cache.insert(package_root.clone(), dependency_cache);
+
+    ControlFlow::Continue(())
+}
+
+/// Prepares the context of `package` and elaborates its dependencies, keeping its state for
+/// `elaborate_dependencies`.
+fn elaborate_dependencies_ahead(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    package: &Package,
+) -> DependencyCacheData {
+    let (mut context, crate_id) = crate::prepare_package(file_manager, parsed_files, package);
+    let file_diagnostics = collect_dependencies(&mut context, crate_id);
+
+    DependencyCacheData {
+        source_fingerprint: SourceFingerprint::new(&context.def_maps, file_manager),
+        crate_graph: context.crate_graph,
+        node_interner: context.def_interner,
+        def_maps: context.def_maps,
+        usage_tracker: context.usage_tracker,
+        visited_files: context.visited_files,
+        file_diagnostics,
+    }
+}
@@ -4,12 +4,13 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies, unused_extern_crates))]
 
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     future::Future,
     ops::{self, ControlFlow},
     path::{Path, PathBuf},
     pin::Pin,
     str::FromStr,
+    sync::{atomic::AtomicBool, Arc},
     task::{self, Poll},
 };
 
@@ -18,12 +19,15 @@ use async_lsp::{
     router::Router, AnyEvent, AnyNotification, AnyRequest, ClientSocket, Error, LspService,
     ResponseError,
 };
-use fm::{codespan_files as files, FileManager};
+use fm::{codespan_files as files, FileId, FileManager};
 use fxhash::FxHashSet;
 use lsp_types::{
     request::{
-        CodeActionRequest, Completion, DocumentSymbolRequest, HoverRequest, InlayHintRequest,
-        PrepareRenameRequest, References, Rename, SignatureHelpRequest,
+        CallHierarchyIncomingCalls, CallHierarchyOutgoingCalls, CallHierarchyPrepare,
+        CodeActionRequest, Completion, DocumentHighlightRequest, DocumentSymbolRequest,
+        FoldingRangeRequest, GotoImplementation, HoverRequest, InlayHintRequest,
+        PrepareRenameRequest, References, Rename, SelectionRangeRequest, SemanticTokensFullRequest,
+        SemanticTokensRangeRequest, SignatureHelpRequest, WorkspaceSymbolRequest,
     },
     CodeLens,
 };
@@ -34,6 +38,7 @@ use nargo::{
 };
 use nargo_toml::{find_file_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::{file_manager_with_stdlib, prepare_crate, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_errors::FileDiagnostic;
 use noirc_frontend::{
     graph::{CrateGraph, CrateId, CrateName},
     hir::{
@@ -47,27 +52,46 @@ use noirc_frontend::{
 };
 use rayon::prelude::*;
 
+use circuit_costs::{
+    on_circuit_costs_computed_event, on_compute_circuit_costs_event, CircuitCosts,
+    CircuitCostsComputedEvent, ComputeCircuitCostsEvent,
+};
+use dependency_cache::{
+    on_elaborate_dependencies_event, DependencyCacheData, ElaborateDependenciesEvent,
+};
 use notifications::{
     on_did_change_configuration, on_did_change_text_document, on_did_close_text_document,
     on_did_open_text_document, on_did_save_text_document, on_exit, on_initialized,
+    on_process_workspace_event, ProcessWorkspaceEvent,
 };
 use requests::{
+    on_call_hierarchy_incoming_calls_request, on_call_hierarchy_outgoing_calls_request,
     on_code_action_request, on_code_lens_request, on_completion_request,
-    on_document_symbol_request, on_formatting, on_goto_declaration_request,
-    on_goto_definition_request, on_goto_type_definition_request, on_hover_request, on_initialize,
-    on_inlay_hint_request, on_prepare_rename_request, on_references_request, on_rename_request,
+    on_document_highlight_request, on_document_symbol_request, on_expand_macro_request,
+    on_folding_range_request, on_formatting, on_goto_declaration_request,
+    on_goto_definition_request, on_goto_implementation_request, on_goto_type_definition_request,
+    on_hover_request, on_initialize, on_inlay_hint_request, on_prepare_call_hierarchy_request,
+    on_prepare_rename_request, on_range_formatting, on_references_request, on_rename_request,
+    on_selection_range_request, on_semantic_tokens_full_request, on_semantic_tokens_range_request,
     on_shutdown, on_signature_help_request, on_test_run_request, on_tests_request,
-    LspInitializationOptions,
+    on_type_formatting, on_workspace_symbol_request, LspInitializationOptions,
 };
 use serde_json::Value as JsonValue;
+use ssa_diagnostics::{
+    on_compute_ssa_diagnostics_event, on_ssa_diagnostics_computed_event,
+    ComputeSsaDiagnosticsEvent, SsaDiagnosticsComputedEvent,
+};
 use thiserror::Error;
 use tower::Service;
 
 mod attribute_reference_finder;
+mod circuit_costs;
+mod dependency_cache;
 mod modules;
 mod notifications;
 mod requests;
 mod solver;
+mod ssa_diagnostics;
 mod tests;
 mod trait_impl_method_stub_generator;
 mod types;
@@ -95,11 +119,15 @@ pub struct LspState {
     solver: WrapperSolver,
     open_documents_count: usize,
     input_files: HashMap<String, String>,
+    // The latest version of each open document, used to skip checks made stale by newer edits.
+    document_versions: HashMap<String, i32>,
     cached_lenses: HashMap<String, Vec<CodeLens>>,
     cached_parsed_files: HashMap<PathBuf, (usize, (ParsedModule, Vec<ParserError>))>,
     workspace_cache: HashMap<PathBuf, WorkspaceCacheData>,
     package_cache: HashMap<PathBuf, PackageCacheData>,
+    dependency_cache: HashMap<PathBuf, DependencyCacheData>,
     options: LspInitializationOptions,
+    client_supports_code_lens_refresh: bool,
 
     // Tracks files that currently have errors, by package root.
     files_with_errors: HashMap<PathBuf, HashSet<Url>>,
@@ -115,6 +143,54 @@ struct PackageCacheData {
     node_interner: NodeInterner,
     def_maps: BTreeMap<CrateId, CrateDefMap>,
     usage_tracker: UsageTracker,
+    // The files the package was checked against, used to reuse this data when none of them changed.
+    source_fingerprint: SourceFingerprint,
+    // The errors and warnings found when checking the package, republished when it's reused.
+    file_diagnostics: Vec<FileDiagnostic>,
+    // The lenses in each of the package's files, cached again when one of them is opened or changed.
+    lenses: Vec<(PathBuf, CodeLens)>,
+    // The circuit cost of the package's functions, once computed for these sources.
+    circuit_costs: Option<CircuitCosts>,
+    // Set to cancel computing the circuit costs, if that was started for these sources.
+    circuit_costs_cancelled: Option<Arc<AtomicBool>>,
+}
+
+/// The sources of all the files a package depends on (its own, its dependencies' and the standard
+/// library's), identified by their `FileId`, path and content hash, along with the total number of
+/// files in the workspace so that added or removed files are noticed too.
+#[derive(Clone, PartialEq, Eq)]
+struct SourceFingerprint {
+    files: Vec<(FileId, PathBuf, usize)>,
+    workspace_file_count: usize,
+}
+
+impl SourceFingerprint {
+    fn new(def_maps: &BTreeMap<CrateId, CrateDefMap>, file_manager: &FileManager) -> Self {
+        let file_ids: BTreeSet<FileId> = def_maps
+            .values()
+            .flat_map(|def_map| def_map.modules().iter().map(|(_, module)| module.location.file))
+            .collect();
+        let files = file_ids
+            .into_iter()
+            .filter_map(|file_id| {
+                let path = file_manager.path(file_id)?.to_path_buf();
+                let hash = fxhash::hash(file_manager.fetch_file(file_id)?);
+                Some((file_id, path, hash))
+            })
+            .collect();
+        let workspace_file_count = file_manager.as_file_map().all_file_ids().count();
+        Self { files, workspace_file_count }
+    }
+
+    /// Returns true if `file_manager` has exactly the same files, under the same `FileId`s,
+    /// as the ones this fingerprint was taken from.
+    fn matches(&self, file_manager: &FileManager) -> bool {
+        self.workspace_file_count == file_manager.as_file_map().all_file_ids().count()
+            && self.files.iter().all(|(file_id, path, hash)| {
+                file_manager.path(*file_id) == Some(path.as_path())
+                    && file_manager.fetch_file(*file_id).map(fxhash::hash) == Some(*hash)
+            })
+    }
 }
 
 impl LspState {
@@ -127,12 +203,15 @@ impl LspState {
             root_path: None,
             solver: WrapperSolver(Box::new(solver)),
             input_files: HashMap::new(),
+            document_versions: HashMap::new(),
             cached_lenses: HashMap::new(),
             cached_parsed_files: HashMap::new(),
             workspace_cache: HashMap::new(),
             package_cache: HashMap::new(),
+            dependency_cache: HashMap::new(),
             open_documents_count: 0,
             options: Default::default(),
+            client_supports_code_lens_refresh: false,
             files_with_errors: HashMap::new(),
         }
     }
@@ -152,29 +231,48 @@ impl NargoLspService {
         router
             .request::<request::Initialize, _>(on_initialize)
             .request::<request::Formatting, _>(on_formatting)
+            .request::<request::RangeFormatting, _>(on_range_formatting)
+            .request::<request::OnTypeFormatting, _>(on_type_formatting)
             .request::<request::Shutdown, _>(on_shutdown)
             .request::<request::CodeLens, _>(on_code_lens_request)
             .request::<request::NargoTests, _>(on_tests_request)
             .request::<request::NargoTestRun, _>(on_test_run_request)
+            .request::<request::NargoExpandMacro, _>(on_expand_macro_request)
             .request::<request::GotoDefinition, _>(on_goto_definition_request)
             .request::<request::GotoDeclaration, _>(on_goto_declaration_request)
             .request::<request::GotoTypeDefinition, _>(on_goto_type_definition_request)
+            .request::<GotoImplementation, _>(on_goto_implementation_request)
             .request::<DocumentSymbolRequest, _>(on_document_symbol_request)
+            .request::<WorkspaceSymbolRequest, _>(on_workspace_symbol_request)
             .request::<References, _>(on_references_request)
             .request::<PrepareRenameRequest, _>(on_prepare_rename_request)
             .request::<Rename, _>(on_rename_request)
             .request::<HoverRequest, _>(on_hover_request)
             .request::<InlayHintRequest, _>(on_inlay_hint_request)
+            .request::<CallHierarchyPrepare, _>(on_prepare_call_hierarchy_request)
+            .request::<CallHierarchyIncomingCalls, _>(on_call_hierarchy_incoming_calls_request)
+            .request::<CallHierarchyOutgoingCalls, _>(on_call_hierarchy_outgoing_calls_request)
+            .request::<SemanticTokensFullRequest, _>(on_semantic_tokens_full_request)
+            .request::<SemanticTokensRangeRequest, _>(on_semantic_tokens_range_request)
             .request::<Completion, _>(on_completion_request)
             .request::<SignatureHelpRequest, _>(on_signature_help_request)
             .request::<CodeActionRequest, _>(on_code_action_request)
+            .request::<FoldingRangeRequest, _>(on_folding_range_request)
+            .request::<SelectionRangeRequest, _>(on_selection_range_request)
+            .request::<DocumentHighlightRequest, _>(on_document_highlight_request)
             .notification::<notification::Initialized>(on_initialized)
             .notification::<notification::DidChangeConfiguration>(on_did_change_configuration)
             .notification::<notification::DidOpenTextDocument>(on_did_open_text_document)
             .notification::<notification::DidChangeTextDocument>(on_did_change_text_document)
             .notification::<notification::DidCloseTextDocument>(on_did_close_text_document)
             .notification::<notification::DidSaveTextDocument>(on_did_save_text_document)
-            .notification::<notification::Exit>(on_exit);
+            .notification::<notification::Exit>(on_exit)
+            .event::<ProcessWorkspaceEvent>(on_process_workspace_event)
+            .event::<ElaborateDependenciesEvent>(on_elaborate_dependencies_event)
+            .event::<ComputeCircuitCostsEvent>(on_compute_circuit_costs_event)
+            .event::<CircuitCostsComputedEvent>(on_circuit_costs_computed_event)
+            .event::<ComputeSsaDiagnosticsEvent>(on_compute_ssa_diagnostics_event)
+            .event::<SsaDiagnosticsComputedEvent>(on_ssa_diagnostics_computed_event);
         Self { router }
     }
 }
@@ -275,7 +373,7 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
             PackageSelection::All,
             Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
         ) {
-            Ok(workspace) => return Ok(workspace),
+            Ok(workspace) => return Ok(resolve_enclosing_workspace(workspace)),
             Err(error) => {
                 eprintln!("Error while processing {:?}: {}", toml_path, error);
             }
@@ -309,6 +407,7 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
         entry_path: PathBuf::from(file_path),
         name: crate_name,
         dependencies: BTreeMap::new(),
+        enabled_features: BTreeSet::new(),
         expression_width: None,
     };
     let workspace = Workspace {
@@ -320,6 +419,30 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
     Ok(workspace)
 }
 
+/// A package can also be a member of a workspace in one of its parent directories, in which case
+/// that workspace is used instead so that the package is checked together with the packages that
+/// depend on it, all sharing the same files.
+fn resolve_enclosing_workspace(mut workspace: Workspace) -> Workspace {
+    while let Some(toml_path) = workspace.root_dir.parent().and_then(find_file_manifest) {
+        let Ok(enclosing_workspace) = resolve_workspace_from_toml(
+            &toml_path,
+            PackageSelection::All,
+            Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        ) else {
+            break;
+        };
+
+        let is_member = workspace.members.iter().all(|package| {
+            enclosing_workspace.members.iter().any(|member| member.root_dir == package.root_dir)
+        });
+        if !is_member {
+            break;
+        }
+        workspace = enclosing_workspace;
+    }
+    workspace
+}
+
 pub(crate) fn workspace_package_for_file<'a>(
     workspace: &'a Workspace,
     file_path: &Path,
@@ -341,6 +464,16 @@ pub(crate) fn prepare_package<'file_manager, 'parsed_files>(
     (context, crate_id)
 }
 
+/// Runs `compile` in a separate thread, as compiling a package can take a while and requests keep
+/// being answered in the meantime. The thread gets the larger stack `nargo compile` uses.
+/// `what` describes what is being computed, for the error logged if the thread can't be started.
+pub(crate) fn spawn_background_compile(what: &str, compile: impl FnOnce() + Send + 'static) {
+    let spawned = std::thread::Builder::new().stack_size(4 * 1024 * 1024).spawn(compile);
+    if let Err(error) = spawned {
+        eprintln!("Could not start computing {what}: {error}");
+    }
+}
+
 /// Prepares a package from a source string
 /// This is useful for situations when we don't need dependencies
 /// and just need to operate on single file.
@@ -1,14 +1,22 @@
 use std::collections::HashSet;
 use std::ops::ControlFlow;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 
 use crate::{
-    insert_all_files_for_workspace_into_file_manager, PackageCacheData, WorkspaceCacheData,
+    circuit_costs::queue_circuit_costs,
+    dependency_cache::{elaborate_dependencies, queue_dependency_elaboration},
+    insert_all_files_for_workspace_into_file_manager,
+    ssa_diagnostics::queue_ssa_diagnostics,
+    PackageCacheData, SourceFingerprint, WorkspaceCacheData,
 };
 use async_lsp::{ErrorCode, LanguageClient, ResponseError};
 use fm::{FileId, FileManager, FileMap};
 use fxhash::FxHashMap as HashMap;
-use lsp_types::{DiagnosticRelatedInformation, DiagnosticTag, Url};
+use lsp_types::{
+    CodeLens, DiagnosticRelatedInformation, DiagnosticTag, TextDocumentContentChangeEvent, Url,
+};
+use nargo::workspace::Workspace;
 use noirc_driver::check_crate;
 use noirc_errors::reporter::CustomLabel;
 use noirc_errors::{DiagnosticKind, FileDiagnostic, Location};
@@ -16,12 +24,13 @@ use noirc_errors::{DiagnosticKind, FileDiagnostic, Location};
 use crate::types::{
     notification, Diagnostic, DiagnosticSeverity, DidChangeConfigurationParams,
     DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, InitializedParams, NargoPackageTests, PublishDiagnosticsParams,
+    DidSaveTextDocumentParams, InitializedParams, NargoPackageTests, Position,
+    PublishDiagnosticsParams,
 };
 
 use crate::{
     byte_span_to_range, get_package_tests_in_crate, parse_diff, resolve_workspace_for_source_path,
-    LspState,
+    utils::character_to_line_offset, LspState,
 };
 
 pub(super) fn on_initialized(
@@ -43,6 +52,9 @@ pub(crate) fn on_did_open_text_document(
     params: DidOpenTextDocumentParams,
 ) -> ControlFlow<Result<(), async_lsp::Error>> {
     state.input_files.insert(params.text_document.uri.to_string(), params.text_document.text);
+    state
+        .document_versions
+        .insert(params.text_document.uri.to_string(), params.text_document.version);
 
     let document_uri = params.text_document.uri;
     let output_diagnostics = true;
@@ -60,23 +72,86 @@ pub(super) fn on_did_change_text_document(
     state: &mut LspState,
     params: DidChangeTextDocumentParams,
 ) -> ControlFlow<Result<(), async_lsp::Error>> {
-    let text = params.content_changes.into_iter().next().unwrap().text;
-    state.input_files.insert(params.text_document.uri.to_string(), text.clone());
-
     let document_uri = params.text_document.uri;
-    let output_diagnostics = false;
+    let version = params.text_document.version;
 
-    match process_workspace_for_noir_document(state, document_uri, output_diagnostics) {
+    let text = state.input_files.entry(document_uri.to_string()).or_default();
+    for change in params.content_changes {
+        apply_text_change(text, change);
+    }
+    state.document_versions.insert(document_uri.to_string(), version);
+
+    // Checking the workspace is queued so that, if more edits arrive in the meantime, only the
+    // check for the latest one is done.
+    let event = ProcessWorkspaceEvent { document_uri: document_uri.clone(), version };
+    if state.client.emit(event).is_err() {
+        // The main loop isn't running (this only happens in tests) so we check right away
+        return on_process_workspace_event(state, ProcessWorkspaceEvent { document_uri, version });
+    }
+    ControlFlow::Continue(())
+}
+
+/// Emitted after a document changes, to check its workspace once pending notifications are handled.
+pub(crate) struct ProcessWorkspaceEvent {
+    document_uri: Url,
+    version: i32,
+}
+
+pub(crate) fn on_process_workspace_event(
+    state: &mut LspState,
+    event: ProcessWorkspaceEvent,
+) -> ControlFlow<Result<(), async_lsp::Error>> {
+    // A newer edit was made to the document after this event was emitted, and that edit
+    // emitted its own event, so checking the workspace now would be wasted work.
+    let latest_version = state.document_versions.get(&event.document_uri.to_string());
+    if latest_version.is_some_and(|latest_version| *latest_version != event.version) {
+        return ControlFlow::Continue(());
+    }
+
+    let output_diagnostics = true;
+
+    match process_workspace_for_noir_document(state, event.document_uri, output_diagnostics) {
         Ok(_) => ControlFlow::Continue(()),
         Err(err) => ControlFlow::Break(Err(err)),
     }
 }
 
+/// Applies an incremental change to a document's text. A change without a range replaces the
+/// whole text.
+fn apply_text_change(text: &mut String, change: TextDocumentContentChangeEvent) {
+    let Some(range) = change.range else {
+        *text = change.text;
+        return;
+    };
+
+    let start = position_to_text_offset(text, range.start);
+    let end = position_to_text_offset(text, range.end).max(start);
+    text.replace_range(start..end, &change.text);
+}
+
+/// Converts an LSP position (a line and a UTF-16 character offset) to a byte offset in `text`.
+/// Positions past the end of a line or past the end of the text are clamped, like clients expect.
+fn position_to_text_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(index) => line_start += index + 1,
+            None => return text.len(),
+        }
+    }
+
+    let line = &text[line_start..];
+    let line = line.find('\n').map_or(line, |index| &line[..index]);
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    line_start + character_to_line_offset(line, position.character).unwrap_or(line.len())
+}
+
 pub(super) fn on_did_close_text_document(
     state: &mut LspState,
     params: DidCloseTextDocumentParams,
 ) -> ControlFlow<Result<(), async_lsp::Error>> {
     state.input_files.remove(&params.text_document.uri.to_string());
+    state.document_versions.remove(&params.text_document.uri.to_string());
     state.cached_lenses.remove(&params.text_document.uri.to_string());
 
     state.open_documents_count -= 1;
@@ -102,10 +177,16 @@ pub(super) fn on_did_save_text_document(
     let document_uri = params.text_document.uri;
     let output_diagnostics = true;
 
-    match process_workspace_for_noir_document(state, document_uri, output_diagnostics) {
-        Ok(_) => ControlFlow::Continue(()),
-        Err(err) => ControlFlow::Break(Err(err)),
+    if let Err(err) =
+        process_workspace_for_noir_document(state, document_uri.clone(), output_diagnostics)
+    {
+        return ControlFlow::Break(Err(err));
     }
+
+    if let Ok(file_path) = document_uri.to_file_path() {
+        queue_ssa_diagnostics(state, &file_path);
+    }
+    ControlFlow::Continue(())
 }
 
 // Given a Noir document, find the workspace it's contained in (an assumed workspace is created if
@@ -124,24 +205,60 @@ pub(crate) fn process_workspace_for_noir_document(
         ResponseError::new(ErrorCode::REQUEST_FAILED, lsp_error.to_string())
     })?;
 
+    // The document is in one package, but lenses are collected from all of them
+    let lenses = check_workspace(state, &workspace, output_diagnostics);
+    state.cached_lenses.insert(document_uri.to_string(), lenses_in_file(&lenses, &file_path));
+
+    Ok(())
+}
+
+/// Type-checks the packages of a workspace, caching their type definitions and the workspace's
+/// files, and notifying about compilation errors. Returns the lenses of every package, along
+/// with the path of the file each one is in.
+///
+/// Work is reused at the granularity of files, crates and packages: only the files that changed are
+/// parsed again, a package is only type-checked again if one of the files it depends on changed,
+/// and its dependencies are only elaborated while checking it if one of their files changed (see
+/// `dependency_cache`). A check can't be interrupted once started though, so only the checks
+/// queued for edits that were superseded by newer ones are cancelled.
+pub(crate) fn check_workspace(
+    state: &mut LspState,
+    workspace: &Workspace,
+    output_diagnostics: bool,
+) -> Vec<(PathBuf, CodeLens)> {
     let mut workspace_file_manager = workspace.new_file_manager();
 
-    insert_all_files_for_workspace_into_file_manager(
-        state,
-        &workspace,
-        &mut workspace_file_manager,
-    );
+    insert_all_files_for_workspace_into_file_manager(state, workspace, &mut workspace_file_manager);
 
     let parsed_files = parse_diff(&workspace_file_manager, state);
 
+    let mut workspace_lenses = Vec::new();
+
     for package in workspace.into_iter() {
+        // Nothing this package depends on changed since it was last checked, so neither did
+        // the result of checking it, which is reused for its lenses and diagnostics
+        if let Some(package_cache) = state.package_cache.get(&package.root_dir) {
+            if package_cache.source_fingerprint.matches(&workspace_file_manager) {
+                workspace_lenses.extend(package_cache.lenses.iter().cloned());
+
+                if output_diagnostics {
+                    let file_diagnostics = package_cache.file_diagnostics.clone();
+                    let fm = &workspace_file_manager;
+                    let files = fm.as_file_map();
+                    publish_diagnostics(state, &package.root_dir, files, fm, file_diagnostics);
+                }
+                continue;
+            }
+        }
+
         let (mut context, crate_id) =
             crate::prepare_package(&workspace_file_manager, &parsed_files, package);
 
-        let file_diagnostics = match check_crate(&mut context, crate_id, &Default::default()) {
+        let mut file_diagnostics = elaborate_dependencies(state, &mut context, crate_id, package);
+        file_diagnostics.extend(match check_crate(&mut context, crate_id, &Default::default()) {
             Ok(((), warnings)) => warnings,
             Err(errors_and_warnings) => errors_and_warnings,
-        };
+        });
 
         // We don't add test headings for a package if it contains no `#[test]` functions
         if let Some(tests) = get_package_tests_in_crate(&context, &crate_id, &package.name) {
@@ -151,15 +268,12 @@ pub(crate) fn process_workspace_for_noir_document(
             });
         }
 
-        let collected_lenses = crate::requests::collect_lenses_for_package(
-            &context,
-            crate_id,
-            &workspace,
-            package,
-            Some(&file_path),
-        );
-        state.cached_lenses.insert(document_uri.to_string(), collected_lenses);
-        state.package_cache.insert(
+        let lenses =
+            crate::requests::collect_lenses_for_package(&context, crate_id, workspace, package);
+        workspace_lenses.extend(lenses.iter().cloned());
+
+        let source_fingerprint = SourceFingerprint::new(&context.def_maps, &workspace_file_manager);
+        let previous_package_cache = state.package_cache.insert(
             package.root_dir.clone(),
             PackageCacheData {
                 crate_id,
@@ -167,9 +281,40 @@ pub(crate) fn process_workspace_for_noir_document(
                 node_interner: context.def_interner,
                 def_maps: context.def_maps,
                 usage_tracker: context.usage_tracker,
+                source_fingerprint,
+                file_diagnostics: file_diagnostics.clone(),
+                lenses,
+                circuit_costs: None,
+                circuit_costs_cancelled: None,
             },
         );
 
+        // The circuit costs being computed are for sources that just changed
+        if let Some(cancelled) =
+            previous_package_cache.and_then(|package_cache| package_cache.circuit_costs_cancelled)
+        {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+
+        queue_dependency_elaboration(
+            state,
+            &workspace.root_dir,
+            package,
+            &workspace_file_manager,
+            &parsed_files,
+        );
+
+        let has_errors = file_diagnostics.iter().any(|diagnostic| diagnostic.diagnostic.is_error());
+        if !has_errors {
+            queue_circuit_costs(
+                state,
+                &workspace.root_dir,
+                package,
+                &workspace_file_manager,
+                &parsed_files,
+            );
+        }
+
         let fm = &context.file_manager;
         let files = fm.as_file_map();
 
@@ -183,10 +328,14 @@ pub(crate) fn process_workspace_for_noir_document(
         WorkspaceCacheData { file_manager: workspace_file_manager },
     );
 
-    Ok(())
+    workspace_lenses
 }
 
-fn publish_diagnostics(
+fn lenses_in_file(lenses: &[(PathBuf, CodeLens)], file_path: &Path) -> Vec<CodeLens> {
+    lenses.iter().filter(|(path, _)| path == file_path).map(|(_, lens)| lens.clone()).collect()
+}
+
+pub(crate) fn publish_diagnostics(
     state: &mut LspState,
     package_root_dir: &PathBuf,
     files: &FileMap,
@@ -329,6 +478,8 @@ pub(super) fn on_exit(
 #[cfg(test)]
 mod notification_tests {
     use crate::test_utils;
+    use noirc_errors::CustomDiagnostic;
+    use noirc_frontend::graph::CrateGraph;
 
     use super::*;
     use lsp_types::{
@@ -403,4 +554,183 @@ mod notification_tests {
             panic!("Expected InlayHintLabel::LabelParts, got {:?}", inlay_hint.label);
         }
     }
+
+    fn change(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range {
+                start: Position { line: start.0, character: start.1 },
+                end: Position { line: end.0, character: end.1 },
+            }),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    async fn test_apply_text_change() {
+        let mut text = "fn main() {\n    let x = 1;\n}\n".to_string();
+
+        // Replace `1` with `2`
+        apply_text_change(&mut text, change((1, 12), (1, 13), "2"));
+        assert_eq!(text, "fn main() {\n    let x = 2;\n}\n");
+
+        // Insert a new line
+        apply_text_change(&mut text, change((2, 0), (2, 0), "    let y = x;\n"));
+        assert_eq!(text, "fn main() {\n    let x = 2;\n    let y = x;\n}\n");
+
+        // Delete across lines
+        apply_text_change(&mut text, change((1, 14), (2, 14), ""));
+        assert_eq!(text, "fn main() {\n    let x = 2;\n}\n");
+
+        // Replace everything
+        apply_text_change(
+            &mut text,
+            TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "fn main() {}".to_string(),
+            },
+        );
+        assert_eq!(text, "fn main() {}");
+    }
+
+    #[test]
+    async fn test_apply_text_change_with_multi_byte_characters() {
+        // `😀` is two UTF-16 code units and four bytes long
+        let mut text = "// 😀 黑\r\nfn main() {}".to_string();
+        apply_text_change(&mut text, change((0, 6), (0, 7), "白"));
+        assert_eq!(text, "// 😀 白\r\nfn main() {}");
+
+        // Positions past the end of a line or of the text are clamped
+        apply_text_change(&mut text, change((0, 100), (0, 100), "!"));
+        assert_eq!(text, "// 😀 白!\r\nfn main() {}");
+        apply_text_change(&mut text, change((5, 0), (5, 0), "\n"));
+        assert_eq!(text, "// 😀 白!\r\nfn main() {}\n");
+    }
+
+    #[test]
+    async fn test_skips_stale_workspace_checks() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("inlay_hints").await;
+        state.document_versions.insert(noir_text_document.to_string(), 2);
+
+        let event = ProcessWorkspaceEvent { document_uri: noir_text_document.clone(), version: 1 };
+        let _ = on_process_workspace_event(&mut state, event);
+        assert!(state.package_cache.is_empty());
+
+        let event = ProcessWorkspaceEvent { document_uri: noir_text_document, version: 2 };
+        let _ = on_process_workspace_event(&mut state, event);
+        assert_eq!(state.package_cache.len(), 1);
+    }
+
+    #[test]
+    async fn test_only_checks_packages_whose_sources_changed() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("workspace").await;
+
+        // noir_text_document is always `src/main.nr` in the workspace directory, so let's go to the workspace dir
+        let noir_text_document_path = noir_text_document.to_file_path().unwrap();
+        let workspace_dir = noir_text_document_path.parent().unwrap().parent().unwrap();
+        let one_lib = Url::from_file_path(workspace_dir.join("one/src/lib.nr")).unwrap();
+        let two_lib = Url::from_file_path(workspace_dir.join("two/src/lib.nr")).unwrap();
+        let one_root = workspace_dir.join("one");
+        let two_root = workspace_dir.join("two");
+
+        // A document of a package only resolves that package, so we check the entire workspace
+        // through a document in its root
+        process_workspace_for_noir_document(&mut state, noir_text_document.clone(), false).unwrap();
+
+        // Clear the crate graphs to tell whether a package's data was replaced by a new check
+        let clear_crate_graphs = |state: &mut LspState| {
+            for package_cache in state.package_cache.values_mut() {
+                package_cache.crate_graph = CrateGraph::default();
+            }
+        };
+        let was_checked = |state: &LspState, root: &PathBuf| {
+            state.package_cache[root].crate_graph.number_of_crates() > 0
+        };
+
+        // Nothing changed, so nothing is checked again
+        clear_crate_graphs(&mut state);
+        process_workspace_for_noir_document(&mut state, noir_text_document.clone(), false).unwrap();
+        assert!(!was_checked(&state, &one_root));
+        assert!(!was_checked(&state, &two_root));
+
+        // `one` doesn't depend on `two`, so only `two` is checked when it changes
+        for (uri, version) in [(&two_lib, 1), (&one_lib, 2)] {
+            let source = std::fs::read_to_string(uri.to_file_path().unwrap()).unwrap();
+            state.input_files.insert(uri.to_string(), source);
+            state.document_versions.insert(uri.to_string(), version - 1);
+
+            clear_crate_graphs(&mut state);
+            on_did_change_text_document(
+                &mut state,
+                DidChangeTextDocumentParams {
+                    text_document: VersionedTextDocumentIdentifier { uri: uri.clone(), version },
+                    content_changes: vec![change((0, 0), (0, 0), "fn unused() {}\n")],
+                },
+            );
+
+            // ...but `two` depends on `one`, so both are checked when `one` changes
+            assert_eq!(was_checked(&state, &one_root), uri == &one_lib);
+            assert!(was_checked(&state, &two_root));
+        }
+    }
+
+    #[test]
+    async fn test_only_elaborates_dependencies_whose_sources_changed() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("workspace").await;
+
+        let noir_text_document_path = noir_text_document.to_file_path().unwrap();
+        let workspace_dir = noir_text_document_path.parent().unwrap().parent().unwrap();
+        let one_lib = Url::from_file_path(workspace_dir.join("one/src/lib.nr")).unwrap();
+        let two_lib = Url::from_file_path(workspace_dir.join("two/src/lib.nr")).unwrap();
+        let two_root = workspace_dir.join("two");
+
+        process_workspace_for_noir_document(&mut state, noir_text_document.clone(), false).unwrap();
+
+        // Mark the cached dependencies of `two` to tell whether they are reused by a new check
+        let marker = "elaborated dependencies were reused";
+        let mark_dependency_cache = |state: &mut LspState| {
+            let dependency_cache = state.dependency_cache.get_mut(&two_root).unwrap();
+            let diagnostic = CustomDiagnostic::from_message(marker).in_file(FileId::dummy());
+            dependency_cache.file_diagnostics.push(diagnostic);
+        };
+        let reused_dependencies = |state: &LspState| {
+            let file_diagnostics = &state.package_cache[&two_root].file_diagnostics;
+            file_diagnostics.iter().any(|diagnostic| diagnostic.diagnostic.message == marker)
+        };
+
+        // Only `two` itself changed, so the standard library and `one` aren't elaborated again
+        // when checking it...
+        for (uri, version) in [(&two_lib, 1), (&one_lib, 2)] {
+            let source = std::fs::read_to_string(uri.to_file_path().unwrap()).unwrap();
+            state.input_files.insert(uri.to_string(), source);
+            state.document_versions.insert(uri.to_string(), version - 1);
+
+            mark_dependency_cache(&mut state);
+            on_did_change_text_document(
+                &mut state,
+                DidChangeTextDocumentParams {
+                    text_document: VersionedTextDocumentIdentifier { uri: uri.clone(), version },
+                    content_changes: vec![change((0, 0), (0, 0), "fn unused() {}\n")],
+                },
+            );
+
+            // ...but they are once `one` changes
+            assert_eq!(reused_dependencies(&state), uri == &two_lib);
+        }
+    }
+
+    #[test]
+    async fn test_caches_lenses_when_reusing_a_package_check() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("inlay_hints").await;
+        let uri = noir_text_document.to_string();
+
+        process_workspace_for_noir_document(&mut state, noir_text_document.clone(), false).unwrap();
+        let lenses = state.cached_lenses.remove(&uri).expect("Expected lenses for `main`");
+        assert!(!lenses.is_empty());
+
+        // Nothing changed so the package isn't checked again, but its lenses are still cached
+        process_workspace_for_noir_document(&mut state, noir_text_document, false).unwrap();
+        assert_eq!(state.cached_lenses.get(&uri), Some(&lenses));
+    }
 }
@@ -0,0 +1,403 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use fm::FileMap;
+use lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams, Range,
+    SymbolKind, SymbolTag, TextDocumentIdentifier, TextDocumentPositionParams,
+};
+use noirc_errors::Location;
+use noirc_frontend::node_interner::{DefinitionKind, FuncId, NodeInterner, ReferenceId};
+
+use crate::LspState;
+
+use super::{find_trait_method_implementations, process_request, to_lsp_location};
+
+pub(crate) fn on_prepare_call_hierarchy_request(
+    state: &mut LspState,
+    params: CallHierarchyPrepareParams,
+) -> impl Future<Output = Result<Option<Vec<CallHierarchyItem>>, ResponseError>> {
+    let result = process_request(state, params.text_document_position_params, |args| {
+        let func_id = function_at_location(args.interner, args.location)?;
+        let item = call_hierarchy_item(args.interner, args.files, func_id)?;
+        Some(vec![item])
+    });
+    future::ready(result)
+}
+
+pub(crate) fn on_call_hierarchy_incoming_calls_request(
+    state: &mut LspState,
+    params: CallHierarchyIncomingCallsParams,
+) -> impl Future<Output = Result<Option<Vec<CallHierarchyIncomingCall>>, ResponseError>> {
+    let result = process_request(state, item_position(&params.item), |args| {
+        let func_id = function_at_location(args.interner, args.location)?;
+
+        // A function may be called through the trait method it implements
+        let mut callee_locations = vec![args.interner.function_modifiers(&func_id).name_location];
+        if let Some(trait_method_id) = trait_method_declaration(args.interner, func_id) {
+            callee_locations.push(args.interner.function_modifiers(&trait_method_id).name_location);
+        }
+
+        // Calls may come from any package in the workspace. All packages share the same
+        // FileManager so a Location in one interner is the same Location in another one.
+        let interners = std::iter::once(args.interner)
+            .chain(args.package_cache.values().map(|cache_data| &cache_data.node_interner));
+
+        let mut calls: Vec<CallHierarchyIncomingCall> = Vec::new();
+        for interner in interners {
+            for callee_location in &callee_locations {
+                let Some(references) = interner.find_all_references(*callee_location, false, true)
+                else {
+                    continue;
+                };
+
+                for reference in references {
+                    let Some(caller_id) = interner.find_function_containing(reference) else {
+                        continue;
+                    };
+                    let Some(caller) = call_hierarchy_item(interner, args.files, caller_id) else {
+                        continue;
+                    };
+                    let Some(from_range) = to_lsp_range(args.files, reference) else {
+                        continue;
+                    };
+
+                    let existing_call =
+                        calls.iter_mut().find(|call| same_item(&call.from, &caller));
+                    if let Some(call) = existing_call {
+                        call.from_ranges.push(from_range);
+                    } else {
+                        calls.push(CallHierarchyIncomingCall {
+                            from: caller,
+                            from_ranges: vec![from_range],
+                        });
+                    }
+                }
+            }
+        }
+
+        for call in &mut calls {
+            sort_and_dedup_ranges(&mut call.from_ranges);
+        }
+        calls.sort_by_key(|call| item_sort_key(&call.from));
+        Some(calls)
+    });
+    future::ready(result)
+}
+
+pub(crate) fn on_call_hierarchy_outgoing_calls_request(
+    state: &mut LspState,
+    params: CallHierarchyOutgoingCallsParams,
+) -> impl Future<Output = Result<Option<Vec<CallHierarchyOutgoingCall>>, ResponseError>> {
+    let result = process_request(state, item_position(&params.item), |args| {
+        let interner = args.interner;
+        let func_id = function_at_location(interner, args.location)?;
+        let body = interner.function(&func_id).try_as_expr()?;
+        let body_location = interner.expr_location(&body);
+
+        let mut calls: Vec<CallHierarchyOutgoingCall> = Vec::new();
+        for (reference, referenced) in interner.find_references_in_location(body_location) {
+            let ReferenceId::Function(callee_id) = referenced else {
+                continue;
+            };
+            let Some(from_range) = to_lsp_range(args.files, reference) else {
+                continue;
+            };
+
+            for callee_id in trait_method_implementations(interner, callee_id) {
+                let Some(callee) = call_hierarchy_item(interner, args.files, callee_id) else {
+                    continue;
+                };
+
+                if let Some(call) = calls.iter_mut().find(|call| same_item(&call.to, &callee)) {
+                    call.from_ranges.push(from_range);
+                } else {
+                    calls.push(CallHierarchyOutgoingCall {
+                        to: callee,
+                        from_ranges: vec![from_range],
+                    });
+                }
+            }
+        }
+
+        for call in &mut calls {
+            sort_and_dedup_ranges(&mut call.from_ranges);
+        }
+        calls.sort_by_key(|call| item_sort_key(&call.to));
+        Some(calls)
+    });
+    future::ready(result)
+}
+
+fn function_at_location(interner: &NodeInterner, location: Location) -> Option<FuncId> {
+    match interner.find_referenced(location)? {
+        ReferenceId::Function(func_id) => Some(func_id),
+        ReferenceId::Local(definition_id) => match interner.definition(definition_id).kind {
+            DefinitionKind::Function(func_id) => Some(func_id),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// If `func_id` is a method in a trait impl, returns the trait method it implements.
+fn trait_method_declaration(interner: &NodeInterner, func_id: FuncId) -> Option<FuncId> {
+    let trait_impl_id = interner.function_meta(&func_id).trait_impl?;
+    let trait_impl = interner.get_trait_implementation(trait_impl_id);
+    let trait_id = trait_impl.borrow().trait_id;
+    let name = interner.function_name(&func_id);
+    interner.get_trait(trait_id).method_ids.get(name).copied()
+}
+
+/// If `func_id` is a method declared in a trait, returns the methods implementing it in every
+/// impl of that trait (or the method itself if there are none). Otherwise returns `func_id`.
+fn trait_method_implementations(interner: &NodeInterner, func_id: FuncId) -> Vec<FuncId> {
+    let implementations = find_trait_method_implementations(interner, func_id);
+    if implementations.is_empty() {
+        vec![func_id]
+    } else {
+        implementations
+    }
+}
+
+fn call_hierarchy_item(
+    interner: &NodeInterner,
+    files: &FileMap,
+    func_id: FuncId,
+) -> Option<CallHierarchyItem> {
+    let func_meta = interner.function_meta(&func_id);
+    let modifiers = interner.function_modifiers(&func_id);
+    let name_location = modifiers.name_location;
+    let selection = to_lsp_location(files, name_location.file, name_location.span)?;
+
+    // The item's range goes from its name to the end of its body
+    let range = interner
+        .function(&func_id)
+        .try_as_expr()
+        .and_then(|body| interner.try_expr_span(&body))
+        .and_then(|body_span| {
+            let span = name_location.span.start() as usize..body_span.end() as usize;
+            crate::byte_span_to_range(files, name_location.file, span)
+        })
+        .unwrap_or(selection.range);
+
+    let kind = if func_meta.self_type.is_some() || func_meta.trait_id.is_some() {
+        SymbolKind::METHOD
+    } else {
+        SymbolKind::FUNCTION
+    };
+
+    let tags = modifiers.attributes.get_deprecated_note().map(|_| vec![SymbolTag::DEPRECATED]);
+
+    // Mark functions whose calls don't end up constrained the same way as the caller's
+    let mut markers = Vec::new();
+    if let Some(self_type) = &func_meta.self_type {
+        markers.push(self_type.to_string());
+    }
+    if modifiers.is_unconstrained {
+        markers.push("unconstrained".to_string());
+    }
+    if modifiers.is_comptime {
+        markers.push("comptime".to_string());
+    }
+    if modifiers.attributes.is_foldable() {
+        markers.push("#[fold]".to_string());
+    }
+    let detail = if markers.is_empty() { None } else { Some(markers.join(" ")) };
+
+    Some(CallHierarchyItem {
+        name: modifiers.name.clone(),
+        kind,
+        tags,
+        detail,
+        uri: selection.uri,
+        range,
+        selection_range: selection.range,
+        data: None,
+    })
+}
+
+fn item_position(item: &CallHierarchyItem) -> TextDocumentPositionParams {
+    TextDocumentPositionParams {
+        text_document: TextDocumentIdentifier { uri: item.uri.clone() },
+        position: item.selection_range.start,
+    }
+}
+
+fn same_item(item1: &CallHierarchyItem, item2: &CallHierarchyItem) -> bool {
+    item1.uri == item2.uri && item1.selection_range == item2.selection_range
+}
+
+fn item_sort_key(item: &CallHierarchyItem) -> (String, u32, u32) {
+    let start = item.selection_range.start;
+    (item.uri.to_string(), start.line, start.character)
+}
+
+fn to_lsp_range(files: &FileMap, location: Location) -> Option<Range> {
+    crate::byte_span_to_range(files, location.file, location.span.into())
+}
+
+fn sort_and_dedup_ranges(ranges: &mut Vec<Range>) {
+    ranges.sort_by_key(|range| (range.start.line, range.start.character));
+    ranges.dedup();
+}
+
+#[cfg(test)]
+mod call_hierarchy_tests {
+    use crate::{notifications, test_utils};
+
+    use super::*;
+    use lsp_types::{PartialResultParams, Position, Url, WorkDoneProgressParams};
+    use tokio::test;
+
+    async fn prepare(
+        state: &mut LspState,
+        uri: &Url,
+        line: u32,
+        character: u32,
+    ) -> CallHierarchyItem {
+        let params = CallHierarchyPrepareParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line, character },
+            },
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+        };
+        let mut items = on_prepare_call_hierarchy_request(state, params)
+            .await
+            .expect("Could not execute on_prepare_call_hierarchy_request")
+            .expect("Expected a call hierarchy item");
+        assert_eq!(items.len(), 1);
+        items.remove(0)
+    }
+
+    async fn incoming_calls(
+        state: &mut LspState,
+        item: CallHierarchyItem,
+    ) -> Vec<CallHierarchyIncomingCall> {
+        let params = CallHierarchyIncomingCallsParams {
+            item,
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+        };
+        on_call_hierarchy_incoming_calls_request(state, params)
+            .await
+            .expect("Could not execute on_call_hierarchy_incoming_calls_request")
+            .unwrap()
+    }
+
+    async fn outgoing_calls(
+        state: &mut LspState,
+        item: CallHierarchyItem,
+    ) -> Vec<CallHierarchyOutgoingCall> {
+        let params = CallHierarchyOutgoingCallsParams {
+            item,
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+        };
+        on_call_hierarchy_outgoing_calls_request(state, params)
+            .await
+            .expect("Could not execute on_call_hierarchy_outgoing_calls_request")
+            .unwrap()
+    }
+
+    #[test]
+    async fn test_prepare_call_hierarchy() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("call_hierarchy").await;
+
+        // unconstrained fn triple(x: Field) -> Field {
+        let item = prepare(&mut state, &noir_text_document, 10, 17).await;
+        assert_eq!(item.name, "triple");
+        assert_eq!(item.kind, SymbolKind::FUNCTION);
+        assert_eq!(item.detail, Some("unconstrained".to_string()));
+        assert_eq!(
+            item.selection_range,
+            Range {
+                start: Position { line: 10, character: 17 },
+                end: Position { line: 10, character: 23 },
+            }
+        );
+        assert_eq!(item.range.end, Position { line: 12, character: 1 });
+    }
+
+    #[test]
+    async fn test_incoming_calls_mark_unconstrained_and_fold_callers() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("call_hierarchy").await;
+
+        // fn double(x: Field) -> Field {
+        let item = prepare(&mut state, &noir_text_document, 6, 3).await;
+        let calls = incoming_calls(&mut state, item).await;
+
+        let callers: Vec<_> =
+            calls.iter().map(|call| (call.from.name.as_str(), call.from.detail.clone())).collect();
+        assert_eq!(
+            callers,
+            vec![
+                ("main", None),
+                ("triple", Some("unconstrained".to_string())),
+                ("sum", Some("#[fold]".to_string())),
+                ("area", Some("Rectangle".to_string())),
+            ]
+        );
+
+        assert_eq!(
+            calls[0].from_ranges,
+            vec![Range {
+                start: Position { line: 1, character: 12 },
+                end: Position { line: 1, character: 18 },
+            }]
+        );
+    }
+
+    #[test]
+    async fn test_outgoing_calls() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("call_hierarchy").await;
+
+        // fn main(x: Field) {
+        let item = prepare(&mut state, &noir_text_document, 0, 3).await;
+        let calls = outgoing_calls(&mut state, item).await;
+
+        let callees: Vec<_> = calls.iter().map(|call| call.to.name.as_str()).collect();
+        assert_eq!(callees, vec!["double", "triple", "sum"]);
+    }
+
+    #[test]
+    async fn test_outgoing_trait_method_calls_resolve_to_every_impl() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("call_hierarchy").await;
+
+        // fn total_area<T>(shape: T) -> Field where T: Shape {
+        let item = prepare(&mut state, &noir_text_document, 44, 3).await;
+        let calls = outgoing_calls(&mut state, item).await;
+
+        let callees: Vec<_> =
+            calls.iter().map(|call| (call.to.name.as_str(), call.to.detail.clone())).collect();
+        assert_eq!(
+            callees,
+            vec![("area", Some("Square".to_string())), ("area", Some("Rectangle".to_string()))]
+        );
+    }
+
+    #[test]
+    async fn test_incoming_calls_across_workspace_packages() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("workspace").await;
+
+        // noir_text_document is always `src/main.nr` in the workspace directory, so let's go to the workspace dir
+        let noir_text_document = noir_text_document.to_file_path().unwrap();
+        let workspace_dir = noir_text_document.parent().unwrap().parent().unwrap();
+        let one_lib = Url::from_file_path(workspace_dir.join("one/src/lib.nr")).unwrap();
+        let two_lib = Url::from_file_path(workspace_dir.join("two/src/lib.nr")).unwrap();
+
+        // We call this to open the document, so that the entire workspace is analyzed
+        notifications::process_workspace_for_noir_document(&mut state, one_lib.clone(), true)
+            .unwrap();
+
+        // pub fn function_one<A, B>() {}
+        let item = prepare(&mut state, &one_lib, 0, 7).await;
+        let calls = incoming_calls(&mut state, item).await;
+
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].from.name, "function_two");
+        assert_eq!(calls[0].from.uri, two_lib);
+    }
+}
@@ -13,8 +13,8 @@ use lsp_types::{
 use noirc_errors::Span;
 use noirc_frontend::{
     ast::{
-        CallExpression, ConstructorExpression, ItemVisibility, MethodCallExpression, NoirTraitImpl,
-        Path, UseTree, Visitor,
+        CallExpression, ConstructorExpression, ItemVisibility, MethodCallExpression, NoirFunction,
+        NoirTraitImpl, Path, UseTree, Visitor,
     },
     graph::CrateId,
     hir::def_map::{CrateDefMap, LocalModuleId, ModuleId},
@@ -30,9 +30,12 @@ use crate::{use_segment_positions::UseSegmentPositions, utils, LspState};
 
 use super::{process_request, to_lsp_location};
 
+mod extract_function;
+mod extract_variable;
 mod fill_struct_fields;
 mod implement_missing_members;
 mod import_or_qualify;
+mod inline_variable;
 mod remove_bang_from_call;
 mod remove_unused_import;
 mod tests;
@@ -92,6 +95,8 @@ struct CodeActionFinder<'a> {
     use_segment_positions: UseSegmentPositions,
     /// Text edits for the "Remove all unused imports" code action
     unused_imports_text_edits: Vec<TextEdit>,
+    /// The span of the innermost item (function, impl, trait, etc.) that contains the selection
+    item_span: Option<Span>,
     code_actions: Vec<CodeAction>,
 }
 
@@ -133,6 +138,7 @@ impl<'a> CodeActionFinder<'a> {
             auto_import_line: 0,
             use_segment_positions: UseSegmentPositions::default(),
             unused_imports_text_edits: vec![],
+            item_span: None,
             code_actions: vec![],
         }
     }
@@ -166,6 +172,15 @@ impl<'a> CodeActionFinder<'a> {
     }
 
     fn new_quick_fix_multiple_edits(&self, title: String, text_edits: Vec<TextEdit>) -> CodeAction {
+        self.new_code_action(title, CodeActionKind::QUICKFIX, text_edits)
+    }
+
+    fn new_code_action(
+        &self,
+        title: String,
+        kind: CodeActionKind,
+        text_edits: Vec<TextEdit>,
+    ) -> CodeAction {
         let mut changes = HashMap::new();
         changes.insert(self.uri.clone(), text_edits);
 
@@ -177,7 +192,7 @@ impl<'a> CodeActionFinder<'a> {
 
         CodeAction {
             title,
-            kind: Some(CodeActionKind::QUICKFIX),
+            kind: Some(kind),
             diagnostics: None,
             edit: Some(workspace_edit),
             command: None,
@@ -191,6 +206,36 @@ impl<'a> CodeActionFinder<'a> {
         let byte_range_span = Span::from(self.byte_range.start as u32..self.byte_range.end as u32);
         span.intersects(&byte_range_span)
     }
+
+    /// Returns the selected span, without leading and trailing whitespace.
+    fn selection_span(&self) -> Span {
+        let selected = &self.source[self.byte_range.clone()];
+        let start = self.byte_range.start + (selected.len() - selected.trim_start().len());
+        let end = self.byte_range.end - (selected.len() - selected.trim_end().len());
+        Span::from(start as u32..end.max(start) as u32)
+    }
+
+    /// Returns `name`, or `name` followed by a number if `name` already appears in `span`,
+    /// so that a new variable or function doesn't shadow or clash with an existing one.
+    fn unused_name(&self, name: &str, span: Span) -> String {
+        let text = &self.source[span.start() as usize..span.end() as usize];
+        let is_used = |candidate: &str| {
+            text.split(|char: char| !(char.is_ascii_alphanumeric() || char == '_'))
+                .any(|word| word == candidate)
+        };
+
+        if !is_used(name) {
+            return name.to_string();
+        }
+        (2..).map(|index| format!("{name}{index}")).find(|name| !is_used(name)).unwrap()
+    }
+
+    /// Returns the whitespace that the line containing `index` starts with.
+    fn indentation_at(&self, index: usize) -> &'a str {
+        let line_start = self.source[..index].rfind('\n').map_or(0, |index| index + 1);
+        let line = &self.source[line_start..];
+        &line[..line.len() - line.trim_start().len()]
+    }
 }
 
 impl<'a> Visitor for CodeActionFinder<'a> {
@@ -202,7 +247,11 @@ impl<'a> Visitor for CodeActionFinder<'a> {
             self.use_segment_positions.add(use_tree);
         }
 
-        self.includes_span(item.span)
+        let includes_span = self.includes_span(item.span);
+        if includes_span {
+            self.item_span = Some(item.span);
+        }
+        includes_span
     }
 
     fn visit_parsed_submodule(&mut self, parsed_sub_module: &ParsedSubModule, span: Span) -> bool {
@@ -254,6 +303,16 @@ impl<'a> Visitor for CodeActionFinder<'a> {
         true
     }
 
+    fn visit_noir_function(&mut self, noir_function: &NoirFunction, span: Span) -> bool {
+        if self.includes_span(span) {
+            self.extract_variable(noir_function, span);
+            self.extract_function(noir_function, span);
+            self.inline_variable(noir_function);
+        }
+
+        true
+    }
+
     fn visit_noir_trait_impl(&mut self, noir_trait_impl: &NoirTraitImpl, span: Span) -> bool {
         self.implement_missing_members(noir_trait_impl, span);
 
@@ -0,0 +1,593 @@
+use lsp_types::{CodeActionKind, TextEdit};
+use noirc_errors::{Location, Span};
+use noirc_frontend::{
+    ast::{
+        BlockExpression, Expression, ForLoopStatement, Ident, LValue, Lambda, MethodCallExpression,
+        NoirFunction, PrefixExpression, Statement, StatementKind, UnaryOp, Visitor,
+    },
+    node_interner::{DefinitionId, DefinitionKind, ReferenceId},
+    Kind, Type, TypeBinding,
+};
+
+use crate::byte_span_to_range;
+
+use super::{extract_variable::place_variable, CodeActionFinder};
+
+impl<'a> CodeActionFinder<'a> {
+    pub(super) fn extract_function(&mut self, noir_function: &NoirFunction, span: Span) {
+        let selection = self.selection_span();
+        if selection.start() == selection.end() {
+            return;
+        }
+        let Some(item_span) = self.item_span else {
+            return;
+        };
+
+        let mut finder = SelectedStatementsFinder::new(selection);
+        noir_function.def.body.accept(None, &mut finder);
+        let Some(selected) = finder.found else {
+            return;
+        };
+        if finder.inside_unsafe_or_comptime {
+            return;
+        }
+
+        // The new function can't assign to, or take a mutable reference of, variables outside of it
+        let is_outer_variable = |span: &Span| {
+            self.local_at(*span)
+                .is_some_and(|(_, definition_span)| !selection.contains(&definition_span))
+        };
+        if selected.assigned.iter().chain(&selected.mutably_referenced).any(is_outer_variable) {
+            return;
+        }
+        if selected.method_receivers.iter().any(|span| {
+            is_outer_variable(span)
+                && self.local_at(*span).is_some_and(|(id, _)| self.interner.definition(id).mutable)
+        }) {
+            return;
+        }
+
+        // Variables declared before the selection and used in it become parameters,
+        // while variables declared in the selection and used after it are returned.
+        let mut parameters = Vec::new();
+        for (id, definition_span) in self.locals_referenced_in(selection) {
+            if !selection.contains(&definition_span) && !parameters.contains(&id) {
+                parameters.push(id);
+            }
+        }
+
+        let after_selection = Span::from(selection.end()..span.end());
+        let mut returned: Vec<_> = self
+            .locals_referenced_in(after_selection)
+            .into_iter()
+            .filter(|(_, definition_span)| selection.contains(definition_span))
+            .collect();
+        returned.sort_by_key(|(_, definition_span)| definition_span.start());
+        returned.dedup();
+        let returned: Vec<_> = returned.into_iter().map(|(id, _)| id).collect();
+
+        let mut value_type = None;
+        if let Some(value_span) = selected.value {
+            let location = Location::new(value_span, self.file);
+            match self.interner.find_expression_type_at_location(location) {
+                Some(Type::Unit) => (),
+                Some(typ) => value_type = Some(typ),
+                None => return,
+            }
+        }
+        if value_type.is_some() && !returned.is_empty() {
+            return;
+        }
+
+        let mut parameter_list = Vec::new();
+        let mut arguments = Vec::new();
+        for id in &parameters {
+            let definition = self.interner.definition(*id);
+            let typ = self.interner.definition_type(*id);
+            if definition.name == "self" || !is_writable_type(&typ) {
+                return;
+            }
+            parameter_list.push(format!("{}: {}", definition.name, typ));
+            arguments.push(definition.name.clone());
+        }
+
+        let mut return_types = Vec::new();
+        let mut returned_names = Vec::new();
+        let mut returned_patterns = Vec::new();
+        for id in &returned {
+            let definition = self.interner.definition(*id);
+            let typ = self.interner.definition_type(*id);
+            if !is_writable_type(&typ) {
+                return;
+            }
+            return_types.push(typ.to_string());
+            returned_names.push(definition.name.clone());
+            let mutable = if definition.mutable { "mut " } else { "" };
+            returned_patterns.push(format!("{mutable}{}", definition.name));
+        }
+        if let Some(value_type) = &value_type {
+            if !is_writable_type(value_type) {
+                return;
+            }
+            return_types.push(value_type.to_string());
+        }
+
+        let whole_file = Span::from(0..self.source.len() as u32);
+        let name = self.unused_name("extracted_function", whole_file);
+        let call = format!("{name}({})", arguments.join(", "));
+        let call = match returned_patterns.len() {
+            0 if selected.value.is_some() => call,
+            0 => format!("{call};"),
+            1 => format!("let {} = {call};", returned_patterns[0]),
+            _ => format!("let ({}) = {call};", returned_patterns.join(", ")),
+        };
+
+        // The new function goes right after the item the selection is in, with the same indent
+        let item_indentation = self.indentation_at(item_span.start() as usize);
+        let body_indentation = format!("{item_indentation}    ");
+        let selection_indentation = self.indentation_at(selection.start() as usize);
+        let selected_text = &self.source[selection.start() as usize..selection.end() as usize];
+        let mut body: Vec<String> = selected_text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let line = if index == 0 {
+                    line
+                } else {
+                    line.strip_prefix(selection_indentation).unwrap_or(line.trim_start())
+                };
+                format!("{body_indentation}{line}")
+            })
+            .collect();
+        match returned_names.len() {
+            0 => (),
+            1 => body.push(format!("{body_indentation}{}", returned_names[0])),
+            _ => body.push(format!("{body_indentation}({})", returned_names.join(", "))),
+        }
+
+        let return_type = match return_types.len() {
+            0 => String::new(),
+            1 => format!(" -> {}", return_types[0]),
+            _ => format!(" -> ({})", return_types.join(", ")),
+        };
+        let mut modifiers = String::new();
+        if noir_function.def.is_unconstrained {
+            modifiers.push_str("unconstrained ");
+        }
+        if noir_function.def.is_comptime {
+            modifiers.push_str("comptime ");
+        }
+        let new_function = format!(
+            "\n\n{item_indentation}{modifiers}fn {name}({}){return_type} {{\n{}\n{item_indentation}}}",
+            parameter_list.join(", "),
+            body.join("\n"),
+        );
+
+        let item_end = item_span.end() as usize;
+        let Some(function_range) = byte_span_to_range(self.files, self.file, item_end..item_end)
+        else {
+            return;
+        };
+        let Some(selection_range) = byte_span_to_range(
+            self.files,
+            self.file,
+            selection.start() as usize..selection.end() as usize,
+        ) else {
+            return;
+        };
+
+        let text_edits = vec![
+            TextEdit { range: selection_range, new_text: call },
+            TextEdit { range: function_range, new_text: new_function },
+        ];
+
+        let title = "Extract into function".to_string();
+        let code_action = self.new_code_action(title, CodeActionKind::REFACTOR_EXTRACT, text_edits);
+        self.code_actions.push(code_action);
+    }
+
+    /// Returns the local variable referenced at the given span, together with its declaration span.
+    fn local_at(&self, span: Span) -> Option<(DefinitionId, Span)> {
+        let location = Location::new(span, self.file);
+        let ReferenceId::Local(id) = self.interner.find_referenced(location)? else {
+            return None;
+        };
+        let definition = self.interner.definition(id);
+        matches!(definition.kind, DefinitionKind::Local(_))
+            .then_some((id, definition.location.span))
+    }
+
+    /// Returns the local variables referenced in the given span, in the order they are referenced,
+    /// together with their declaration spans.
+    fn locals_referenced_in(&self, span: Span) -> Vec<(DefinitionId, Span)> {
+        let location = Location::new(span, self.file);
+        let mut references = self.interner.find_references_in_location(location);
+        references.sort_by_key(|(location, _)| location.span.start());
+        references
+            .into_iter()
+            .filter_map(|(location, referenced)| match referenced {
+                ReferenceId::Local(_) => self.local_at(location.span),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// The statements that are exactly selected, in a single block.
+struct SelectedStatements {
+    /// The span of the value the statements evaluate to, if the last one is the block's value
+    value: Option<Span>,
+    /// The variables that are assigned to in the statements
+    assigned: Vec<Span>,
+    /// The variables that have a mutable reference taken to them in the statements
+    mutably_referenced: Vec<Span>,
+    /// The variables that methods are called on in the statements (they might take `&mut self`)
+    method_receivers: Vec<Span>,
+}
+
+struct SelectedStatementsFinder {
+    selection: Span,
+    inside_unsafe_or_comptime: bool,
+    found: Option<SelectedStatements>,
+}
+
+impl SelectedStatementsFinder {
+    fn new(selection: Span) -> Self {
+        Self { selection, inside_unsafe_or_comptime: false, found: None }
+    }
+}
+
+impl Visitor for SelectedStatementsFinder {
+    fn visit_block_expression(&mut self, block: &BlockExpression, _: Option<Span>) -> bool {
+        let statements = &block.statements;
+        let first = statements.iter().position(|statement| {
+            statement.span.start() == self.selection.start()
+                && statement.span.end() <= self.selection.end()
+        });
+        let last = statements.iter().position(|statement| {
+            statement.span.end() == self.selection.end()
+                && statement.span.start() >= self.selection.start()
+        });
+
+        let (Some(first), Some(last)) = (first, last) else {
+            return statements.iter().any(|statement| statement.span.contains(&self.selection));
+        };
+
+        let selected = &statements[first..=last];
+        let mut checker = SelectedStatementsChecker::default();
+        for statement in selected {
+            statement.accept(&mut checker);
+        }
+        if checker.has_jump_out {
+            return false;
+        }
+
+        let value = match &selected[selected.len() - 1].kind {
+            StatementKind::Expression(expression) if last == statements.len() - 1 => {
+                Some(expression.span)
+            }
+            _ => None,
+        };
+
+        self.found = Some(SelectedStatements {
+            value,
+            assigned: checker.assigned,
+            mutably_referenced: checker.mutably_referenced,
+            method_receivers: checker.method_receivers,
+        });
+        false
+    }
+
+    fn visit_unsafe(&mut self, _: &BlockExpression, span: Span) -> bool {
+        self.inside_unsafe_or_comptime |= span.contains(&self.selection);
+        true
+    }
+
+    fn visit_comptime_expression(&mut self, _: &BlockExpression, span: Span) -> bool {
+        self.inside_unsafe_or_comptime |= span.contains(&self.selection);
+        true
+    }
+
+    fn visit_comptime_statement(&mut self, statement: &Statement) -> bool {
+        self.inside_unsafe_or_comptime |= statement.span.contains(&self.selection);
+        true
+    }
+}
+
+/// Finds what in the selected statements prevents moving them to a new function,
+/// or requires checking the variables they refer to.
+#[derive(Default)]
+struct SelectedStatementsChecker {
+    assigned: Vec<Span>,
+    mutably_referenced: Vec<Span>,
+    method_receivers: Vec<Span>,
+    loop_depth: usize,
+    lambda_depth: usize,
+    /// A `break` or `continue` for a loop that isn't part of the selection,
+    /// or a `return` from the function the selection is in
+    has_jump_out: bool,
+}
+
+impl Visitor for SelectedStatementsChecker {
+    fn visit_lvalue_ident(&mut self, ident: &Ident) {
+        self.assigned.push(ident.span());
+    }
+
+    fn visit_lvalue_dereference(&mut self, _: &LValue, _: Span) -> bool {
+        // Assigning through a reference doesn't change the variable holding the reference
+        false
+    }
+
+    fn visit_prefix_expression(&mut self, prefix: &PrefixExpression, _: Span) -> bool {
+        if prefix.operator == UnaryOp::MutableReference {
+            if let Some(path) = place_variable(&prefix.rhs) {
+                self.mutably_referenced.push(path.span);
+            }
+        }
+        true
+    }
+
+    fn visit_method_call_expression(
+        &mut self,
+        method_call: &MethodCallExpression,
+        _: Span,
+    ) -> bool {
+        if let Some(path) = place_variable(&method_call.object) {
+            self.method_receivers.push(path.span);
+        }
+        true
+    }
+
+    fn visit_for_loop_statement(&mut self, for_loop: &ForLoopStatement) -> bool {
+        for_loop.range.accept(self);
+        self.loop_depth += 1;
+        for_loop.block.accept(self);
+        self.loop_depth -= 1;
+        false
+    }
+
+    fn visit_lambda(&mut self, lambda: &Lambda, _: Span) -> bool {
+        self.lambda_depth += 1;
+        lambda.body.accept(self);
+        self.lambda_depth -= 1;
+        false
+    }
+
+    fn visit_break(&mut self) {
+        self.has_jump_out |= self.loop_depth == 0;
+    }
+
+    fn visit_continue(&mut self) {
+        self.has_jump_out |= self.loop_depth == 0;
+    }
+
+    fn visit_return(&mut self, _: Option<&Expression>, _: Span) -> bool {
+        self.has_jump_out |= self.lambda_depth == 0;
+        true
+    }
+}
+
+/// Returns true if the type can be written in a function signature as it's displayed,
+/// so not if it refers to the generics of the function the code is extracted from.
+fn is_writable_type(typ: &Type) -> bool {
+    match typ {
+        Type::FieldElement
+        | Type::Integer(..)
+        | Type::Bool
+        | Type::Unit
+        | Type::Constant(..)
+        | Type::Quoted(..) => true,
+        Type::Array(length, element) => is_writable_type(length) && is_writable_type(element),
+        Type::Slice(element) => is_writable_type(element),
+        Type::String(length) => is_writable_type(length),
+        Type::FmtString(length, elements) => is_writable_type(length) && is_writable_type(elements),
+        Type::Tuple(types) | Type::Struct(_, types) | Type::Alias(_, types) => {
+            types.iter().all(is_writable_type)
+        }
+        Type::MutableReference(typ) => is_writable_type(typ),
+        Type::CheckedCast { to, .. } => is_writable_type(to),
+        Type::InfixExpr(lhs, _, rhs) => is_writable_type(lhs) && is_writable_type(rhs),
+        Type::Function(arguments, return_type, env, _) => {
+            // Closures capturing variables have an environment type that can't be written
+            **env == Type::Unit
+                && arguments.iter().all(is_writable_type)
+                && is_writable_type(return_type)
+        }
+        Type::TypeVariable(var) => match &*var.borrow() {
+            TypeBinding::Bound(typ) => is_writable_type(typ),
+            TypeBinding::Unbound(_, kind) => matches!(kind, Kind::Integer | Kind::IntegerOrField),
+        },
+        Type::NamedGeneric(..) | Type::TraitAsType(..) | Type::Forall(..) | Type::Error => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::test;
+
+    use crate::requests::code_action::tests::{assert_code_action, assert_no_code_action};
+
+    #[test]
+    async fn test_extract_function_with_parameters_and_returned_variable() {
+        let title = "Extract into function";
+
+        let src = r#"
+        fn main(x: Field, y: Field) {
+            let a = x + 1;
+            >|let b = a * y;
+            let c = b + x;|<
+            assert(c == 10);
+        }
+        "#;
+
+        let expected = r#"
+        fn main(x: Field, y: Field) {
+            let a = x + 1;
+            let c = extracted_function(a, y, x);
+            assert(c == 10);
+        }
+
+        fn extracted_function(a: Field, y: Field, x: Field) -> Field {
+            let b = a * y;
+            let c = b + x;
+            c
+        }
+        "#;
+
+        assert_code_action(title, src, expected).await;
+    }
+
+    #[test]
+    async fn test_extract_function_returning_block_value() {
+        let title = "Extract into function";
+
+        let src = r#"
+        unconstrained fn foo(x: u32) -> u32 {
+            let y = x * 2;
+            >|let z = y + 1;
+            z * z|<
+        }
+        "#;
+
+        let expected = r#"
+        unconstrained fn foo(x: u32) -> u32 {
+            let y = x * 2;
+            extracted_function(y)
+        }
+
+        unconstrained fn extracted_function(y: u32) -> u32 {
+            let z = y + 1;
+            z * z
+        }
+        "#;
+
+        assert_code_action(title, src, expected).await;
+    }
+
+    #[test]
+    async fn test_extract_function_returning_multiple_variables() {
+        let title = "Extract into function";
+
+        let src = r#"
+        fn main(x: u8) {
+            >|let a = x + 1;
+            let mut b = x + 2;|<
+            b += a;
+            assert(b == 3);
+        }
+        "#;
+
+        let expected = r#"
+        fn main(x: u8) {
+            let (a, mut b) = extracted_function(x);
+            b += a;
+            assert(b == 3);
+        }
+
+        fn extracted_function(x: u8) -> (u8, u8) {
+            let a = x + 1;
+            let mut b = x + 2;
+            (a, b)
+        }
+        "#;
+
+        assert_code_action(title, src, expected).await;
+    }
+
+    #[test]
+    async fn test_extract_function_without_return_value() {
+        let title = "Extract into function";
+
+        let src = r#"
+        struct Foo {}
+
+        impl Foo {
+            fn foo(x: Field) {
+                >|assert(x == 1);|<
+            }
+        }
+        "#;
+
+        let expected = r#"
+        struct Foo {}
+
+        impl Foo {
+            fn foo(x: Field) {
+                extracted_function(x);
+            }
+        }
+
+        fn extracted_function(x: Field) {
+            assert(x == 1);
+        }
+        "#;
+
+        assert_code_action(title, src, expected).await;
+    }
+
+    #[test]
+    async fn test_does_not_extract_function_assigning_outer_variable() {
+        let src = r#"
+        fn main(x: Field) {
+            let mut a = x;
+            >|a = a + 1;|<
+            assert(a == 2);
+        }
+        "#;
+
+        assert_no_code_action("Extract into function", src).await;
+    }
+
+    #[test]
+    async fn test_does_not_extract_function_with_break_outside_loop() {
+        let src = r#"
+        unconstrained fn main(x: Field) {
+            for i in 0..10 {
+                >|if i == x {
+                    break;
+                }|<
+            }
+        }
+        "#;
+
+        assert_no_code_action("Extract into function", src).await;
+    }
+
+    #[test]
+    async fn test_does_not_extract_function_with_return() {
+        let src = r#"
+        fn main(x: Field) -> Field {
+            >|if x == 0 {
+                return 1;
+            }|<
+            x
+        }
+        "#;
+
+        assert_no_code_action("Extract into function", src).await;
+    }
+
+    #[test]
+    async fn test_does_not_extract_function_using_generics() {
+        let src = r#"
+        fn main<T>(x: T) {
+            >|let y = x;|<
+        }
+        "#;
+
+        assert_no_code_action("Extract into function", src).await;
+    }
+
+    #[test]
+    async fn test_does_not_extract_partial_statements() {
+        let src = r#"
+        fn main(x: Field) {
+            let a = x + 1;
+            let b = >|a * 2;
+            let c = b|< + 1;
+        }
+        "#;
+
+        assert_no_code_action("Extract into function", src).await;
+    }
+}
@@ -0,0 +1,300 @@
+use lsp_types::{CodeActionKind, TextEdit};
+use noirc_errors::Span;
+use noirc_frontend::ast::{
+    BlockExpression, Expression, ExpressionKind, Lambda, MethodCallExpression, NoirFunction, Path,
+    PrefixExpression, StatementKind, UnaryOp, Visitor,
+};
+
+use crate::byte_span_to_range;
+
+use super::CodeActionFinder;
+
+impl<'a> CodeActionFinder<'a> {
+    pub(super) fn extract_variable(&mut self, noir_function: &NoirFunction, span: Span) {
+        let selection = self.selection_span();
+        if selection.start() == selection.end() {
+            return;
+        }
+
+        let mut finder = ExtractableExpressionFinder::new(selection);
+        noir_function.def.body.accept(None, &mut finder);
+        let Some((statement_span, name)) = finder.found else {
+            return;
+        };
+
+        let statement_start = statement_span.start() as usize;
+        let name = self.unused_name(&name, span);
+        let expression_text = &self.source[selection.start() as usize..selection.end() as usize];
+
+        // If the statement starts its line the new `let` goes in its own line, with the same indent
+        let indentation = self.indentation_at(statement_start);
+        let line_start = statement_start - indentation.len();
+        let separator = if self.source[..line_start].ends_with('\n') || line_start == 0 {
+            format!("\n{indentation}")
+        } else {
+            " ".to_string()
+        };
+
+        let Some(let_range) =
+            byte_span_to_range(self.files, self.file, statement_start..statement_start)
+        else {
+            return;
+        };
+        let Some(selection_range) = byte_span_to_range(
+            self.files,
+            self.file,
+            selection.start() as usize..selection.end() as usize,
+        ) else {
+            return;
+        };
+
+        let text_edits = vec![
+            TextEdit {
+                range: let_range,
+                new_text: format!("let {name} = {expression_text};{separator}"),
+            },
+            TextEdit { range: selection_range, new_text: name },
+        ];
+
+        let title = "Extract into variable".to_string();
+        let code_action = self.new_code_action(title, CodeActionKind::REFACTOR_EXTRACT, text_edits);
+        self.code_actions.push(code_action);
+    }
+}
+
+/// Finds the expression that is exactly selected, together with the statement that a `let`
+/// holding its value must be inserted before: the innermost one that contains the expression,
+/// in a block where the expression's variables are in scope.
+struct ExtractableExpressionFinder {
+    selection: Span,
+    statement: Option<Span>,
+    /// Expressions that can't be moved to a variable without changing the program's meaning,
+    /// like the operand of `&mut` (the reference would point to the new variable instead).
+    non_extractable: Vec<Span>,
+    found: Option<(Span, String)>,
+}
+
+impl ExtractableExpressionFinder {
+    fn new(selection: Span) -> Self {
+        Self { selection, statement: None, non_extractable: Vec::new(), found: None }
+    }
+}
+
+impl Visitor for ExtractableExpressionFinder {
+    fn visit_block_expression(&mut self, block: &BlockExpression, _: Option<Span>) -> bool {
+        let statement =
+            block.statements.iter().find(|statement| statement.span.contains(&self.selection));
+        let Some(statement) = statement else {
+            return false;
+        };
+
+        // Extracting a whole expression statement would only move it around
+        if let StatementKind::Semi(expression) = &statement.kind {
+            self.non_extractable.push(expression.span);
+        }
+
+        self.statement = Some(statement.span);
+        true
+    }
+
+    fn visit_lambda(&mut self, lambda: &Lambda, span: Span) -> bool {
+        // The lambda's parameters are only in scope in its body, so unless a block inside
+        // the body contains the selection there's no statement to insert a `let` before
+        if lambda.body.span.contains(&self.selection) {
+            self.statement = None;
+        }
+        span.contains(&self.selection)
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) -> bool {
+        if expression.span == self.selection
+            && self.found.is_none()
+            && !self.non_extractable.contains(&expression.span)
+        {
+            if let Some(statement) = self.statement {
+                self.found = Some((statement, variable_name(expression)));
+            }
+        }
+
+        expression.span.contains(&self.selection)
+    }
+
+    fn visit_prefix_expression(&mut self, prefix: &PrefixExpression, _: Span) -> bool {
+        if prefix.operator == UnaryOp::MutableReference && place_variable(&prefix.rhs).is_some() {
+            self.non_extractable.push(prefix.rhs.span);
+        }
+        true
+    }
+
+    fn visit_method_call_expression(
+        &mut self,
+        method_call: &MethodCallExpression,
+        _: Span,
+    ) -> bool {
+        // The method might take `&mut self`, in which case it must mutate the original value
+        if place_variable(&method_call.object).is_some() {
+            self.non_extractable.push(method_call.object.span);
+        }
+        true
+    }
+}
+
+/// Returns the variable an expression refers to, if it refers to a variable or to a part of one.
+pub(super) fn place_variable(expression: &Expression) -> Option<&Path> {
+    match &expression.kind {
+        ExpressionKind::Variable(path) => Some(path),
+        ExpressionKind::MemberAccess(member_access) => place_variable(&member_access.lhs),
+        ExpressionKind::Index(index) => place_variable(&index.collection),
+        ExpressionKind::Parenthesized(expression) => place_variable(expression),
+        _ => None,
+    }
+}
+
+/// Suggests a name for a variable holding the value of the given expression.
+fn variable_name(expression: &Expression) -> String {
+    let name = match &expression.kind {
+        ExpressionKind::Call(call) => match &call.func.kind {
+            ExpressionKind::Variable(path) => path.last_name().to_string(),
+            _ => return "value".to_string(),
+        },
+        ExpressionKind::MethodCall(method_call) => method_call.method_name.to_string(),
+        ExpressionKind::MemberAccess(member_access) => member_access.rhs.to_string(),
+        ExpressionKind::Parenthesized(expression) => return variable_name(expression),
+        _ => return "value".to_string(),
+    };
+
+    let name = name.strip_prefix("get_").unwrap_or(&name);
+    if name.is_empty() || name.starts_with(|char: char| char.is_ascii_uppercase()) {
+        "value".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::test;
+
+    use crate::requests::code_action::tests::{assert_code_action, assert_no_code_action};
+
+    #[test]
+    async fn test_extract_variable() {
+        let title = "Extract into variable";
+
+        let src = r#"
+        fn main(x: Field, y: Field) {
+            let z = >|x * y|< + 2;
+            assert(z == 4);
+        }
+        "#;
+
+        let expected = r#"
+        fn main(x: Field, y: Field) {
+            let value = x * y;
+            let z = value + 2;
+            assert(z == 4);
+        }
+        "#;
+
+        assert_code_action(title, src, expected).await;
+    }
+
+    #[test]
+    async fn test_extract_variable_names_it_after_call() {
+        let title = "Extract into variable";
+
+        let src = r#"
+        fn get_total(x: Field) -> Field {
+            x
+        }
+
+        fn main(total: Field) {
+            assert(>|get_total(1)|< == total);
+        }
+        "#;
+
+        let expected = r#"
+        fn get_total(x: Field) -> Field {
+            x
+        }
+
+        fn main(total: Field) {
+            let total2 = get_total(1);
+            assert(total2 == total);
+        }
+        "#;
+
+        assert_code_action(title, src, expected).await;
+    }
+
+    #[test]
+    async fn test_extract_variable_inside_nested_block() {
+        let title = "Extract into variable";
+
+        let src = r#"
+        fn main(x: Field) {
+            if x == 1 {
+                assert(>|x + 1|< == 2);
+            }
+        }
+        "#;
+
+        let expected = r#"
+        fn main(x: Field) {
+            if x == 1 {
+                let value = x + 1;
+                assert(value == 2);
+            }
+        }
+        "#;
+
+        assert_code_action(title, src, expected).await;
+    }
+
+    #[test]
+    async fn test_extract_variable_inside_lambda_block() {
+        let title = "Extract into variable";
+
+        let src = r#"
+        fn main() {
+            let f = |x: Field| {
+                >|x * 2|< + 1
+            };
+        }
+        "#;
+
+        let expected = r#"
+        fn main() {
+            let f = |x: Field| {
+                let value = x * 2;
+                value + 1
+            };
+        }
+        "#;
+
+        assert_code_action(title, src, expected).await;
+    }
+
+    #[test]
+    async fn test_does_not_extract_variable_from_lambda_without_block() {
+        let src = r#"
+        fn main() {
+            let f = |x: Field| >|x * 2|< + 1;
+        }
+        "#;
+
+        assert_no_code_action("Extract into variable", src).await;
+    }
+
+    #[test]
+    async fn test_does_not_extract_mutable_reference_operand() {
+        let src = r#"
+        fn main() {
+            let mut x = 1;
+            let y = &mut >|x|<;
+        }
+        "#;
+
+        assert_no_code_action("Extract into variable", src).await;
+    }
+}
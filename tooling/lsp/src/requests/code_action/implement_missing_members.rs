@@ -44,7 +44,7 @@ impl<'a> CodeActionFinder<'a> {
                     method_ids.remove(noir_function.name());
                 }
                 TraitImplItemKind::Constant(..) => (),
-                TraitImplItemKind::Type { name, alias } => {
+                TraitImplItemKind::Type { name, alias, attributes: _ } => {
                     if let UnresolvedTypeData::Unspecified = alias.typ {
                         continue;
                     }
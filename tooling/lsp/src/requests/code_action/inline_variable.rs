@@ -0,0 +1,383 @@
+use lsp_types::{CodeActionKind, TextEdit};
+use noirc_errors::{Location, Span};
+use noirc_frontend::{
+    ast::{
+        BlockExpression, CallExpression, ConstructorExpression, ExpressionKind, ForLoopStatement,
+        Ident, MethodCallExpression, NoirFunction, Pattern, Statement, StatementKind, Visitor,
+    },
+    node_interner::{DefinitionId, ReferenceId},
+};
+
+use crate::byte_span_to_range;
+
+use super::CodeActionFinder;
+
+impl<'a> CodeActionFinder<'a> {
+    pub(super) fn inline_variable(&mut self, noir_function: &NoirFunction) {
+        // The cursor can be on the variable's declaration or on any of its uses
+        let cursor = Location::new(Span::single_char(self.byte_range.start as u32), self.file);
+        let mut collector = LetStatementsCollector::default();
+        noir_function.def.body.accept(None, &mut collector);
+
+        let id = match self.interner.find_referenced(cursor) {
+            Some(ReferenceId::Local(id)) => id,
+            // A shorthand field (`Foo { x }`) is found as the struct field rather than the variable
+            _ => {
+                let Some(id) = self.local_in_shorthand_field(&collector, cursor) else {
+                    return;
+                };
+                id
+            }
+        };
+        let definition = self.interner.definition(id);
+        if definition.mutable {
+            return;
+        }
+
+        let Some(let_statement) = collector
+            .let_statements
+            .iter()
+            .find(|let_statement| let_statement.name_span == definition.location.span)
+        else {
+            return;
+        };
+
+        let Some(references) = self.interner.find_all_references(definition.location, false, false)
+        else {
+            return;
+        };
+        let Some(last_reference) = references.iter().map(|location| location.span.start()).max()
+        else {
+            return;
+        };
+        if references.iter().any(|location| location.file != self.file) {
+            return;
+        }
+
+        // Calls would run once per use instead of once, and where the variable is used
+        // instead of where it's declared, which may change the program and its constraints
+        if let_statement.has_calls && references.len() != 1 {
+            return;
+        }
+
+        // The expression must mean the same where it's moved to: it can't depend on mutable
+        // variables, nor on names that are declared again before the variable's last use.
+        let expression_span = let_statement.expression_span;
+        let expression_location = Location::new(expression_span, self.file);
+        for (location, referenced) in self.interner.find_references_in_location(expression_location)
+        {
+            if let ReferenceId::Local(id) = referenced {
+                if self.interner.definition(id).mutable {
+                    return;
+                }
+            }
+
+            let name = &self.source[location.span.start() as usize..location.span.end() as usize];
+            let is_shadowed = collector.bindings.iter().any(|(binding, span)| {
+                binding == name
+                    && span.start() > let_statement.statement_span.end()
+                    && span.start() < last_reference
+            });
+            if is_shadowed {
+                return;
+            }
+        }
+
+        let expression_text =
+            &self.source[expression_span.start() as usize..expression_span.end() as usize];
+        let expression_text = if let_statement.needs_parentheses {
+            format!("({expression_text})")
+        } else {
+            expression_text.to_string()
+        };
+
+        let mut text_edits = Vec::new();
+        for reference in references {
+            let span = reference.span;
+            let new_text = if collector.shorthand_fields.contains(&span) {
+                format!("{}: {expression_text}", definition.name)
+            } else {
+                expression_text.clone()
+            };
+            let Some(range) = byte_span_to_range(
+                self.files,
+                self.file,
+                span.start() as usize..span.end() as usize,
+            ) else {
+                return;
+            };
+            text_edits.push(TextEdit { range, new_text });
+        }
+
+        // Remove the `let`, together with its line if nothing else is in it
+        let mut start = let_statement.statement_span.start() as usize;
+        let mut end = let_statement.statement_span.end() as usize;
+        let line_start = self.source[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = self.source[end..].find('\n').map_or(self.source.len(), |index| end + index);
+        if self.source[line_start..start].trim().is_empty()
+            && self.source[end..line_end].trim().is_empty()
+            && line_end < self.source.len()
+        {
+            start = line_start;
+            end = line_end + 1;
+        }
+        let Some(range) = byte_span_to_range(self.files, self.file, start..end) else {
+            return;
+        };
+        text_edits.push(TextEdit { range, new_text: String::new() });
+
+        let title = "Inline variable".to_string();
+        let code_action = self.new_code_action(title, CodeActionKind::REFACTOR_INLINE, text_edits);
+        self.code_actions.push(code_action);
+    }
+
+    /// Returns the variable declared by one of the collected `let` statements that's used
+    /// in the shorthand field at the cursor, if any.
+    fn local_in_shorthand_field(
+        &self,
+        collector: &LetStatementsCollector,
+        cursor: Location,
+    ) -> Option<DefinitionId> {
+        let field_span = *collector.shorthand_fields.iter().find(|span| {
+            span.start() <= cursor.span.start() && cursor.span.start() <= span.end()
+        })?;
+
+        collector.let_statements.iter().find_map(|let_statement| {
+            let location = Location::new(let_statement.name_span, self.file);
+            let Some(ReferenceId::Local(id)) = self.interner.find_referenced(location) else {
+                return None;
+            };
+            let references = self.interner.find_all_references(location, false, false)?;
+            let is_used_in_field = references
+                .iter()
+                .any(|reference| reference.file == self.file && reference.span == field_span);
+            is_used_in_field.then_some(id)
+        })
+    }
+}
+
+struct LetStatementInfo {
+    statement_span: Span,
+    name_span: Span,
+    expression_span: Span,
+    /// Whether the expression must be wrapped in parentheses when it replaces the variable
+    needs_parentheses: bool,
+    /// Whether the expression calls functions or methods, or has an `unsafe` block
+    has_calls: bool,
+}
+
+/// Collects the `let` statements that bind a single immutable variable, together with every
+/// name bound in a function and the constructor fields written in shorthand form (`Foo { x }`).
+#[derive(Default)]
+struct LetStatementsCollector {
+    let_statements: Vec<LetStatementInfo>,
+    bindings: Vec<(String, Span)>,
+    shorthand_fields: Vec<Span>,
+}
+
+impl Visitor for LetStatementsCollector {
+    fn visit_statement(&mut self, statement: &Statement) -> bool {
+        if let StatementKind::Let(let_statement) = &statement.kind {
+            if let Pattern::Identifier(ident) = &let_statement.pattern {
+                let needs_parentheses = !matches!(
+                    let_statement.expression.kind,
+                    ExpressionKind::Literal(_)
+                        | ExpressionKind::Block(_)
+                        | ExpressionKind::Index(_)
+                        | ExpressionKind::Call(_)
+                        | ExpressionKind::MethodCall(_)
+                        | ExpressionKind::Constructor(_)
+                        | ExpressionKind::MemberAccess(_)
+                        | ExpressionKind::Variable(_)
+                        | ExpressionKind::Tuple(_)
+                        | ExpressionKind::Parenthesized(_)
+                        | ExpressionKind::Unsafe(..)
+                        | ExpressionKind::AsTraitPath(_)
+                        | ExpressionKind::TypePath(_)
+                );
+                let mut calls_finder = CallsFinder::default();
+                let_statement.expression.accept(&mut calls_finder);
+                self.let_statements.push(LetStatementInfo {
+                    statement_span: statement.span,
+                    name_span: ident.span(),
+                    expression_span: let_statement.expression.span,
+                    needs_parentheses,
+                    has_calls: calls_finder.found,
+                });
+            }
+        }
+        true
+    }
+
+    fn visit_identifier_pattern(&mut self, ident: &Ident) {
+        self.bindings.push((ident.to_string(), ident.span()));
+    }
+
+    fn visit_for_loop_statement(&mut self, for_loop: &ForLoopStatement) -> bool {
+        self.bindings.push((for_loop.identifier.to_string(), for_loop.identifier.span()));
+        true
+    }
+
+    fn visit_constructor_expression(
+        &mut self,
+        constructor: &ConstructorExpression,
+        _: Span,
+    ) -> bool {
+        for (field, expression) in &constructor.fields {
+            if field.span() == expression.span {
+                self.shorthand_fields.push(expression.span);
+            }
+        }
+        true
+    }
+}
+
+/// Finds out whether an expression calls functions or methods, or has an `unsafe` block.
+#[derive(Default)]
+struct CallsFinder {
+    found: bool,
+}
+
+impl Visitor for CallsFinder {
+    fn visit_call_expression(&mut self, _: &CallExpression, _: Span) -> bool {
+        self.found = true;
+        false
+    }
+
+    fn visit_method_call_expression(&mut self, _: &MethodCallExpression, _: Span) -> bool {
+        self.found = true;
+        false
+    }
+
+    fn visit_unsafe(&mut self, _: &BlockExpression, _: Span) -> bool {
+        self.found = true;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::test;
+
+    use crate::requests::code_action::tests::{assert_code_action, assert_no_code_action};
+
+    #[test]
+    async fn test_inline_variable_from_declaration() {
+        let title = "Inline variable";
+
+        let src = r#"
+        fn main(x: Field) {
+            let >|<y = x + 1;
+            assert(y * 2 == 4);
+            assert(y != 0);
+        }
+        "#;
+
+        let expected = r#"
+        fn main(x: Field) {
+            assert((x + 1) * 2 == 4);
+            assert((x + 1) != 0);
+        }
+        "#;
+
+        assert_code_action(title, src, expected).await;
+    }
+
+    #[test]
+    async fn test_inline_variable_from_use_in_shorthand_field() {
+        let title = "Inline variable";
+
+        let src = r#"
+        struct Foo {
+            a: Field,
+        }
+
+        fn bar() -> Field {
+            1
+        }
+
+        fn main() {
+            let a = bar();
+            let _ = Foo { a>|< };
+        }
+        "#;
+
+        let expected = r#"
+        struct Foo {
+            a: Field,
+        }
+
+        fn bar() -> Field {
+            1
+        }
+
+        fn main() {
+            let _ = Foo { a: bar() };
+        }
+        "#;
+
+        assert_code_action(title, src, expected).await;
+    }
+
+    #[test]
+    async fn test_does_not_inline_variable_depending_on_mutable_variable() {
+        let src = r#"
+        fn main() {
+            let mut x = 1;
+            let >|<y = x;
+            x = 2;
+            assert(y == 1);
+        }
+        "#;
+
+        assert_no_code_action("Inline variable", src).await;
+    }
+
+    #[test]
+    async fn test_does_not_inline_variable_if_shadowed_before_use() {
+        let src = r#"
+        fn main(x: Field) {
+            let >|<y = x;
+            let x = 2;
+            assert(y == x);
+        }
+        "#;
+
+        assert_no_code_action("Inline variable", src).await;
+    }
+
+    #[test]
+    async fn test_does_not_inline_call_used_more_than_once() {
+        let src = r#"
+        fn bar() -> Field {
+            1
+        }
+
+        fn main() {
+            let >|<y = bar() + 1;
+            assert(y * 2 == 4);
+            assert(y != 0);
+        }
+        "#;
+
+        assert_no_code_action("Inline variable", src).await;
+    }
+
+    #[test]
+    async fn test_does_not_inline_unused_variable_with_unsafe_call() {
+        let src = r#"
+        #[oracle(get_value)]
+        unconstrained fn get_value_oracle() -> Field {}
+
+        unconstrained fn get_value() -> Field {
+            get_value_oracle()
+        }
+
+        fn main() {
+            let >|<_y = unsafe { get_value() };
+        }
+        "#;
+
+        assert_no_code_action("Inline variable", src).await;
+    }
+}
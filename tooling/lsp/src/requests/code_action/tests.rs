@@ -10,16 +10,12 @@ use lsp_types::{
 
 use super::on_code_action_request;
 
-async fn get_code_action(src: &str) -> CodeActionResponse {
+/// Returns the code actions for the given source, where `>|<` marks the cursor position,
+/// or `>|` and `|<` mark the start and end of the selection.
+async fn get_code_action(src: &str) -> Option<CodeActionResponse> {
     let (mut state, noir_text_document) = test_utils::init_lsp_server("document_symbol").await;
 
-    let (line, column) = src
-        .lines()
-        .enumerate()
-        .find_map(|(line_index, line)| line.find(">|<").map(|char_index| (line_index, char_index)))
-        .expect("Expected to find one >|< in the source code");
-
-    let src = src.replace(">|<", "");
+    let (src, range) = remove_selection_markers(src);
 
     on_did_open_text_document(
         &mut state,
@@ -28,18 +24,16 @@ async fn get_code_action(src: &str) -> CodeActionResponse {
                 uri: noir_text_document.clone(),
                 language_id: "noir".to_string(),
                 version: 0,
-                text: src.to_string(),
+                text: src,
             },
         },
     );
 
-    let position = Position { line: line as u32, character: column as u32 };
-
     on_code_action_request(
         &mut state,
         CodeActionParams {
             text_document: TextDocumentIdentifier { uri: noir_text_document },
-            range: Range { start: position, end: position },
+            range,
             context: CodeActionContext { diagnostics: Vec::new(), only: None, trigger_kind: None },
             work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
             partial_result_params: PartialResultParams { partial_result_token: None },
@@ -47,11 +41,30 @@ async fn get_code_action(src: &str) -> CodeActionResponse {
     )
     .await
     .expect("Could not execute on_code_action_request")
-    .unwrap()
+}
+
+fn remove_selection_markers(src: &str) -> (String, Range) {
+    let position_of = |src: &str, marker: &str| {
+        src.lines().enumerate().find_map(|(line_index, line)| {
+            line.find(marker).map(|char_index| Position {
+                line: line_index as u32,
+                character: char_index as u32,
+            })
+        })
+    };
+
+    if let Some(position) = position_of(src, ">|<") {
+        return (src.replace(">|<", ""), Range { start: position, end: position });
+    }
+
+    let start = position_of(src, ">|").expect("Expected to find >|< or >| in the source code");
+    let src = src.replacen(">|", "", 1);
+    let end = position_of(&src, "|<").expect("Expected to find |< in the source code");
+    (src.replacen("|<", "", 1), Range { start, end })
 }
 
 pub(crate) async fn assert_code_action(title: &str, src: &str, expected: &str) {
-    let actions = get_code_action(src).await;
+    let actions = get_code_action(src).await.expect("Expected code actions");
     let action = actions
         .iter()
         .filter_map(|action| {
@@ -71,9 +84,18 @@ pub(crate) async fn assert_code_action(title: &str, src: &str, expected: &str) {
     let workspace_edit = action.edit.as_ref().unwrap();
     let text_edits = workspace_edit.changes.as_ref().unwrap().iter().next().unwrap().1;
 
-    let result = apply_text_edits(&src.replace(">|<", ""), text_edits);
+    let (src, _) = remove_selection_markers(src);
+    let result = apply_text_edits(&src, text_edits);
     if result != expected {
         println!("Expected:\n```\n{}\n```\n\nGot:\n```\n{}\n```", expected, result);
         assert_eq!(result, expected);
     }
 }
+
+pub(crate) async fn assert_no_code_action(title: &str, src: &str) {
+    let actions = get_code_action(src).await.unwrap_or_default();
+    let has_action = actions.iter().any(
+        |action| matches!(action, CodeActionOrCommand::CodeAction(action) if action.title == title),
+    );
+    assert!(!has_action, "Expected no action with title {title:?}");
+}
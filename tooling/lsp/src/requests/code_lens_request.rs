@@ -1,4 +1,7 @@
-use std::future::{self, Future};
+use std::{
+    future::{self, Future},
+    path::Path,
+};
 
 use async_lsp::{ErrorCode, ResponseError};
 
@@ -52,17 +55,31 @@ fn on_code_lens_request_inner(
         ResponseError::new(ErrorCode::REQUEST_FAILED, "URI is not a valid file path")
     })?;
 
-    if let Some(collected_lenses) = state.cached_lenses.get(&params.text_document.uri.to_string()) {
-        return Ok(Some(collected_lenses.clone()));
+    let mut lenses = match state.cached_lenses.get(&params.text_document.uri.to_string()) {
+        Some(collected_lenses) => collected_lenses.clone(),
+        None => collect_and_cache_lenses(state, &params, &file_path)?,
+    };
+    lenses.extend(collect_circuit_cost_lenses(state, &file_path));
+
+    if lenses.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(lenses))
     }
+}
 
-    let source_string = std::fs::read_to_string(&file_path).map_err(|_| {
+fn collect_and_cache_lenses(
+    state: &mut LspState,
+    params: &CodeLensParams,
+    file_path: &Path,
+) -> Result<Vec<CodeLens>, ResponseError> {
+    let source_string = std::fs::read_to_string(file_path).map_err(|_| {
         ResponseError::new(ErrorCode::REQUEST_FAILED, "Could not read file from disk")
     })?;
 
-    let workspace = resolve_workspace_for_source_path(file_path.as_path()).unwrap();
+    let workspace = resolve_workspace_for_source_path(file_path).unwrap();
 
-    let package = crate::workspace_package_for_file(&workspace, &file_path).ok_or_else(|| {
+    let package = crate::workspace_package_for_file(&workspace, file_path).ok_or_else(|| {
         ResponseError::new(ErrorCode::REQUEST_FAILED, "Could not find package for file")
     })?;
 
@@ -71,28 +88,69 @@ fn on_code_lens_request_inner(
     // because we can still get the test functions even if compilation fails
     let _ = check_crate(&mut context, crate_id, &Default::default());
 
-    let collected_lenses =
-        collect_lenses_for_package(&context, crate_id, &workspace, package, None);
+    let collected_lenses: Vec<_> =
+        collect_lenses_for_package(&context, crate_id, &workspace, package)
+            .into_iter()
+            .map(|(_, lens)| lens)
+            .collect();
 
     if collected_lenses.is_empty() {
         state.cached_lenses.remove(&params.text_document.uri.to_string());
-        Ok(None)
     } else {
         state
             .cached_lenses
             .insert(params.text_document.uri.to_string().clone(), collected_lenses.clone());
-        Ok(Some(collected_lenses))
     }
+    Ok(collected_lenses)
 }
 
+/// Returns a lens above each function in the given file with its circuit cost,
+/// if those were already computed for the file's package.
+fn collect_circuit_cost_lenses(state: &LspState, file_path: &Path) -> Vec<CodeLens> {
+    let Ok(workspace) = resolve_workspace_for_source_path(file_path) else {
+        return Vec::new();
+    };
+    let Some(package) = crate::workspace_package_for_file(&workspace, file_path) else {
+        return Vec::new();
+    };
+    let Some(circuit_costs) = state
+        .package_cache
+        .get(&package.root_dir)
+        .and_then(|package_cache| package_cache.circuit_costs.as_ref())
+    else {
+        return Vec::new();
+    };
+    let Some(workspace_cache) = state.workspace_cache.get(&workspace.root_dir) else {
+        return Vec::new();
+    };
+
+    let fm = &workspace_cache.file_manager;
+    let files = fm.as_file_map();
+    let mut lenses: Vec<CodeLens> = circuit_costs
+        .iter()
+        .filter(|(location, _)| fm.path(location.file) == Some(file_path))
+        .filter_map(|(location, cost)| {
+            let range = byte_span_to_range(files, location.file, location.span.into())?;
+            let command = Command {
+                title: cost.summary(),
+                command: INFO_COMMAND.into(),
+                arguments: Some(package_selection_args(&workspace, package)),
+            };
+            Some(CodeLens { range, command: Some(command), data: None })
+        })
+        .collect();
+    lenses.sort_by_key(|lens| lens.range.start);
+    lenses
+}
+
+/// Collects the lenses for all the files in a package, along with the path of the file each one is in.
 pub(crate) fn collect_lenses_for_package(
     context: &noirc_frontend::hir::Context,
     crate_id: noirc_frontend::graph::CrateId,
     workspace: &Workspace,
     package: &Package,
-    file_path: Option<&std::path::PathBuf>,
-) -> Vec<CodeLens> {
-    let mut lenses: Vec<CodeLens> = vec![];
+) -> Vec<(std::path::PathBuf, CodeLens)> {
+    let mut lenses = vec![];
     let fm = &context.file_manager;
     let files = fm.as_file_map();
     let tests =
@@ -100,14 +158,7 @@ pub(crate) fn collect_lenses_for_package(
     for (func_name, test_function) in tests {
         let location = context.function_meta(&test_function.get_id()).name.location;
         let file_id = location.file;
-
-        // Ignore diagnostics for any file that wasn't the file we saved
-        // TODO: In the future, we could create "related" diagnostics for these files
-        if let Some(file_path) = file_path {
-            if fm.path(file_id).expect("file must exist to contain tests") != *file_path {
-                continue;
-            }
-        }
+        let file_path = fm.path(file_id).expect("file must exist to contain tests");
 
         let range = byte_span_to_range(files, file_id, location.span.into()).unwrap_or_default();
 
@@ -125,23 +176,14 @@ pub(crate) fn collect_lenses_for_package(
 
         let test_lens = CodeLens { range, command: Some(test_command), data: None };
 
-        lenses.push(test_lens);
+        lenses.push((file_path.to_path_buf(), test_lens));
     }
 
     if package.is_binary() {
         if let Some(main_func_id) = context.get_main_function(&crate_id) {
             let location = context.function_meta(&main_func_id).name.location;
             let file_id = location.file;
-
-            // Ignore diagnostics for any file that wasn't the file we saved
-            // TODO: In the future, we could create "related" diagnostics for these files
-            if let Some(file_path) = file_path {
-                if fm.path(file_id).expect("file must exist to contain `main` function")
-                    != *file_path
-                {
-                    return lenses;
-                }
-            }
+            let file_path = fm.path(file_id).expect("file must exist to contain `main` function");
 
             let range =
                 byte_span_to_range(files, file_id, location.span.into()).unwrap_or_default();
@@ -154,7 +196,7 @@ pub(crate) fn collect_lenses_for_package(
 
             let compile_lens = CodeLens { range, command: Some(compile_command), data: None };
 
-            lenses.push(compile_lens);
+            lenses.push((file_path.to_path_buf(), compile_lens));
 
             let internal_command_lenses = [
                 (INFO_CODELENS_TITLE, INFO_COMMAND),
@@ -167,10 +209,10 @@ pub(crate) fn collect_lenses_for_package(
                     command: command.into(),
                     arguments: Some(package_selection_args(workspace, package)),
                 };
-                CodeLens { range, command: Some(command), data: None }
+                (file_path.to_path_buf(), CodeLens { range, command: Some(command), data: None })
             });
 
-            lenses.append(&mut Vec::from(internal_command_lenses));
+            lenses.extend(internal_command_lenses);
         }
     }
 
@@ -179,14 +221,7 @@ pub(crate) fn collect_lenses_for_package(
         for contract in context.get_all_contracts(&crate_id) {
             let location = contract.location;
             let file_id = location.file;
-
-            // Ignore diagnostics for any file that wasn't the file we saved
-            // TODO: In the future, we could create "related" diagnostics for these files
-            if let Some(file_path) = file_path {
-                if fm.path(file_id).expect("file must exist to contain a contract") != *file_path {
-                    continue;
-                }
-            }
+            let file_path = fm.path(file_id).expect("file must exist to contain a contract");
 
             let range =
                 byte_span_to_range(files, file_id, location.span.into()).unwrap_or_default();
@@ -199,7 +234,7 @@ pub(crate) fn collect_lenses_for_package(
 
             let compile_lens = CodeLens { range, command: Some(compile_command), data: None };
 
-            lenses.push(compile_lens);
+            lenses.push((file_path.to_path_buf(), compile_lens));
 
             let info_command = Command {
                 title: INFO_CODELENS_TITLE.to_string(),
@@ -209,7 +244,7 @@ pub(crate) fn collect_lenses_for_package(
 
             let info_lens = CodeLens { range, command: Some(info_command), data: None };
 
-            lenses.push(info_lens);
+            lenses.push((file_path.to_path_buf(), info_lens));
         }
     }
 
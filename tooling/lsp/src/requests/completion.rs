@@ -523,7 +523,7 @@ impl<'a> NodeFinder<'a> {
                 self.local_variables.insert(ident.to_string(), ident.span());
             }
             Pattern::Mutable(pattern, _, _) => self.collect_local_variables(pattern),
-            Pattern::Tuple(patterns, _) => {
+            Pattern::Tuple(patterns, _) | Pattern::Array(patterns, _, _) => {
                 for pattern in patterns {
                     self.collect_local_variables(pattern);
                 }
@@ -1009,7 +1009,10 @@ impl<'a> NodeFinder<'a> {
                 }
             }
             Pattern::Mutable(pattern, ..) => self.try_set_self_type(pattern),
-            Pattern::Tuple(..) | Pattern::Struct(..) | Pattern::Interned(..) => (),
+            Pattern::Tuple(..)
+            | Pattern::Array(..)
+            | Pattern::Struct(..)
+            | Pattern::Interned(..) => (),
         }
     }
 
@@ -93,6 +93,7 @@ impl<'a> NodeFinder<'a> {
             }
             AttributeTarget::Function => {
                 let no_arguments_attributes = &[
+                    "bench",
                     "contract_library_method",
                     "deprecated",
                     "export",
@@ -133,6 +134,15 @@ impl<'a> NodeFinder<'a> {
                         None,
                     ));
                 }
+
+                if name_matches("test", prefix) || name_matches("cases", prefix) {
+                    self.completion_items.push(snippet_completion_item(
+                        "test(cases = ..., seed = ...)",
+                        CompletionItemKind::METHOD,
+                        "test(cases = ${1:256}, seed = ${2:0})",
+                        None,
+                    ));
+                }
             }
             AttributeTarget::Let => {
                 if name_matches("allow", prefix) || name_matches("unused_variables", prefix) {
@@ -147,8 +157,8 @@ impl<'a> NodeFinder<'a> {
     }
 }
 
-pub(super) fn builtin_integer_types() -> [&'static str; 8] {
-    ["i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64"]
+pub(super) fn builtin_integer_types() -> [&'static str; 10] {
+    ["i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128"]
 }
 
 /// If a keyword corresponds to a built-in type, returns that type's name.
@@ -409,7 +409,9 @@ impl<'a> NodeFinder<'a> {
                 text.push_str(self.interner.definition_name(hir_ident.id));
             }
             HirPattern::Mutable(pattern, _) => self.hir_pattern_to_argument(pattern, text),
-            HirPattern::Tuple(_, _) | HirPattern::Struct(_, _, _) => text.push('_'),
+            HirPattern::Tuple(_, _) | HirPattern::Array(_, _, _) | HirPattern::Struct(_, _, _) => {
+                text.push('_');
+            }
         }
     }
 
@@ -420,7 +422,9 @@ impl<'a> NodeFinder<'a> {
                 name == "self" || name == "_self"
             }
             HirPattern::Mutable(pattern, _) => self.hir_pattern_is_self_type(pattern),
-            HirPattern::Tuple(_, _) | HirPattern::Struct(_, _, _) => false,
+            HirPattern::Tuple(_, _) | HirPattern::Array(_, _, _) | HirPattern::Struct(_, _, _) => {
+                false
+            }
         }
     }
 }
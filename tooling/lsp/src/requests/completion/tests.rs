@@ -774,6 +774,11 @@ mod completion_tests {
                 simple_completion_item("i16", CompletionItemKind::STRUCT, Some("i16".to_string())),
                 simple_completion_item("i32", CompletionItemKind::STRUCT, Some("i32".to_string())),
                 simple_completion_item("i64", CompletionItemKind::STRUCT, Some("i64".to_string())),
+                simple_completion_item(
+                    "i128",
+                    CompletionItemKind::STRUCT,
+                    Some("i128".to_string()),
+                ),
             ],
         );
     }
@@ -0,0 +1,218 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use lsp_types::{DocumentHighlight, DocumentHighlightKind, DocumentHighlightParams};
+use noirc_errors::Span;
+use noirc_frontend::{
+    ast::{Ident, LValue, Visitor},
+    node_interner::ReferenceId,
+};
+
+use crate::{byte_span_to_range, LspState};
+
+use super::process_request;
+
+pub(crate) fn on_document_highlight_request(
+    state: &mut LspState,
+    params: DocumentHighlightParams,
+) -> impl Future<Output = Result<Option<Vec<DocumentHighlight>>, ResponseError>> {
+    let result = process_request(state, params.text_document_position_params, |args| {
+        let file_id = args.location.file;
+        let referenced = args.interner.find_referenced(args.location)?;
+        let declaration = args.interner.reference_location(referenced);
+        let locations = args.interner.find_all_references(args.location, true, true)?;
+
+        let file = args.files.get_file(file_id)?;
+        let (parsed_module, _errors) = noirc_frontend::parse_program(file.source());
+        let mut collector = AssignedSpansCollector::default();
+        parsed_module.accept(&mut collector);
+
+        // Declaring a variable or a global gives it a value, which counts as writing it
+        let declaration_writes =
+            matches!(referenced, ReferenceId::Local(_) | ReferenceId::Global(_));
+
+        let mut highlights: Vec<DocumentHighlight> = locations
+            .into_iter()
+            .filter(|location| location.file == file_id)
+            .filter_map(|location| {
+                let span = location.span;
+                let kind = if collector.spans.contains(&span)
+                    || (location == declaration && declaration_writes)
+                {
+                    DocumentHighlightKind::WRITE
+                } else if location == declaration {
+                    DocumentHighlightKind::TEXT
+                } else {
+                    DocumentHighlightKind::READ
+                };
+                let range = byte_span_to_range(
+                    args.files,
+                    file_id,
+                    span.start() as usize..span.end() as usize,
+                )?;
+                Some(DocumentHighlight { range, kind: Some(kind) })
+            })
+            .collect();
+
+        highlights
+            .sort_by_key(|highlight| (highlight.range.start.line, highlight.range.start.character));
+        highlights.dedup_by_key(|highlight| highlight.range);
+
+        if highlights.is_empty() {
+            None
+        } else {
+            Some(highlights)
+        }
+    });
+    future::ready(result)
+}
+
+/// Collects the spans of the names being assigned to, like `x` and `y` in `x.y = 1`.
+#[derive(Default)]
+struct AssignedSpansCollector {
+    spans: Vec<Span>,
+}
+
+impl Visitor for AssignedSpansCollector {
+    fn visit_lvalue_ident(&mut self, ident: &Ident) {
+        self.spans.push(ident.span());
+    }
+
+    fn visit_lvalue_member_access(
+        &mut self,
+        _object: &LValue,
+        field_name: &Ident,
+        _span: Span,
+    ) -> bool {
+        self.spans.push(field_name.span());
+        true
+    }
+}
+
+#[cfg(test)]
+mod document_highlight_tests {
+    use crate::{notifications::on_did_open_text_document, test_utils};
+
+    use super::*;
+    use lsp_types::{
+        DidOpenTextDocumentParams, Position, TextDocumentIdentifier, TextDocumentItem,
+        TextDocumentPositionParams, WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    /// Returns the highlights for the symbol at `>|<`, as the line of each one and its kind.
+    async fn get_highlights(src: &str) -> Vec<(u32, DocumentHighlightKind)> {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("document_symbol").await;
+
+        let (line, character) = src
+            .lines()
+            .enumerate()
+            .find_map(|(line, text)| text.find(">|<").map(|character| (line, character)))
+            .expect("Expected to find >|< in the source code");
+        let src = src.replace(">|<", "");
+
+        let _ = on_did_open_text_document(
+            &mut state,
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: noir_text_document.clone(),
+                    language_id: "noir".to_string(),
+                    version: 0,
+                    text: src,
+                },
+            },
+        );
+
+        let highlights = on_document_highlight_request(
+            &mut state,
+            DocumentHighlightParams {
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri: noir_text_document },
+                    position: Position { line: line as u32, character: character as u32 },
+                },
+                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+                partial_result_params: Default::default(),
+            },
+        )
+        .await
+        .expect("Could not execute on_document_highlight_request")
+        .unwrap_or_default();
+
+        highlights
+            .into_iter()
+            .map(|highlight| (highlight.range.start.line, highlight.kind.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    async fn test_highlights_reads_and_writes_of_local() {
+        let src = r#"fn main() {
+    let mut >|<x = 1;
+    x = x + 1;
+    assert(x == 2);
+}
+"#;
+
+        let highlights = get_highlights(src).await;
+        assert_eq!(
+            highlights,
+            vec![
+                (1, DocumentHighlightKind::WRITE),
+                (2, DocumentHighlightKind::WRITE),
+                (2, DocumentHighlightKind::READ),
+                (3, DocumentHighlightKind::READ),
+            ]
+        );
+    }
+
+    #[test]
+    async fn test_highlights_struct_field_writes() {
+        let src = r#"struct Foo {
+    >|<value: Field,
+}
+
+fn main() {
+    let mut foo = Foo { value: 1 };
+    foo.value = 2;
+    assert(foo.value == 2);
+}
+"#;
+
+        let highlights = get_highlights(src).await;
+        assert_eq!(
+            highlights,
+            vec![
+                (1, DocumentHighlightKind::TEXT),
+                (5, DocumentHighlightKind::READ),
+                (6, DocumentHighlightKind::WRITE),
+                (7, DocumentHighlightKind::READ),
+            ]
+        );
+    }
+
+    #[test]
+    async fn test_highlights_index_and_member_assignments_as_writes() {
+        let src = r#"struct Foo {
+    values: [Field; 2],
+}
+
+fn main() {
+    let mut >|<foo = Foo { values: [1, 2] };
+    foo.values[0] = 3;
+    foo.values = [4, 5];
+    assert(foo.values[1] == 5);
+}
+"#;
+
+        let highlights = get_highlights(src).await;
+        assert_eq!(
+            highlights,
+            vec![
+                (5, DocumentHighlightKind::WRITE),
+                (6, DocumentHighlightKind::WRITE),
+                (7, DocumentHighlightKind::WRITE),
+                (8, DocumentHighlightKind::READ),
+            ]
+        );
+    }
+}
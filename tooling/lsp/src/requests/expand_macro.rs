@@ -0,0 +1,144 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use lsp_types::TextDocumentPositionParams;
+use noirc_errors::Span;
+use noirc_frontend::{
+    ast::{NoirFunction, Visitor},
+    parser::Item,
+};
+
+use crate::{
+    types::{NargoExpandMacroResult, NargoExpandedMacro},
+    LspState,
+};
+
+use super::process_request;
+
+/// Shows the code produced at comptime for the item under the cursor: the items generated by its
+/// attributes, and what each comptime block, statement or global in it evaluated to.
+pub(crate) fn on_expand_macro_request(
+    state: &mut LspState,
+    params: TextDocumentPositionParams,
+) -> impl Future<Output = Result<NargoExpandMacroResult, ResponseError>> {
+    let result = process_request(state, params, |args| {
+        let file_id = args.location.file;
+        let cursor = args.location.span;
+
+        let file = args.files.get_file(file_id)?;
+        let (parsed_module, _errors) = noirc_frontend::parse_program(file.source());
+
+        let mut finder = ItemFinder { cursor, item_span: None };
+        parsed_module.accept(&mut finder);
+        let item_span = finder.item_span.unwrap_or(cursor);
+
+        let mut expansions: Vec<_> = args
+            .interner
+            .comptime_expansions()
+            .iter()
+            .filter(|expansion| {
+                let span = expansion.location.span;
+                expansion.location.file == file_id
+                    && (span.contains(&cursor)
+                        || (span.start() < item_span.end() && item_span.start() < span.end()))
+            })
+            .collect();
+        if expansions.is_empty() {
+            return None;
+        }
+
+        expansions.sort_by_key(|expansion| expansion.location.span.start());
+        expansions.dedup_by(|a, b| {
+            a.location == b.location && a.title == b.title && a.expansion == b.expansion
+        });
+
+        let name = expansions
+            .iter()
+            .map(|expansion| expansion.title.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let expansion = expansions
+            .iter()
+            .map(|expansion| format!("// {}\n{}", expansion.title, expansion.expansion))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Some(NargoExpandedMacro { name, expansion })
+    });
+    future::ready(result)
+}
+
+/// Finds the innermost item (or function inside an impl or trait impl) containing the cursor.
+struct ItemFinder {
+    cursor: Span,
+    item_span: Option<Span>,
+}
+
+impl ItemFinder {
+    fn found(&mut self, span: Span) -> bool {
+        if !span.contains(&self.cursor) {
+            return false;
+        }
+
+        if self.item_span.map_or(true, |item_span| item_span.contains(&span)) {
+            self.item_span = Some(span);
+        }
+        true
+    }
+}
+
+impl Visitor for ItemFinder {
+    fn visit_item(&mut self, item: &Item) -> bool {
+        self.found(item.span)
+    }
+
+    fn visit_noir_function(&mut self, _: &NoirFunction, span: Span) -> bool {
+        self.found(span);
+        false
+    }
+}
+
+#[cfg(test)]
+mod expand_macro_tests {
+    use crate::test_utils;
+
+    use super::*;
+    use lsp_types::{Position, TextDocumentIdentifier};
+    use tokio::test;
+
+    async fn expand_macro(line: u32, character: u32) -> NargoExpandMacroResult {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("expand_macro").await;
+
+        let params = TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: noir_text_document },
+            position: Position { line, character },
+        };
+
+        on_expand_macro_request(&mut state, params)
+            .await
+            .expect("Could not execute on_expand_macro_request")
+    }
+
+    #[test]
+    async fn expands_attribute_on_function() {
+        // fn one() -> Field {
+        let expanded = expand_macro(1, 4).await.expect("Expected an expansion");
+        assert_eq!(expanded.name, "#[make_double]");
+        assert!(expanded.expansion.starts_with("// #[make_double]\n"), "{}", expanded.expansion);
+        assert!(expanded.expansion.contains("fn double() -> Field"), "{}", expanded.expansion);
+    }
+
+    #[test]
+    async fn expands_comptime_block_in_function() {
+        //     assert(three == one() + double());
+        let expanded = expand_macro(15, 8).await.expect("Expected an expansion");
+        assert_eq!(expanded.name, "comptime block");
+        assert_eq!(expanded.expansion, "// comptime block\n3");
+    }
+
+    #[test]
+    async fn does_not_expand_item_without_comptime_code() {
+        // comptime fn make_double(_f: FunctionDefinition) -> Quoted {
+        assert!(expand_macro(5, 14).await.is_none());
+    }
+}
@@ -0,0 +1,305 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use fm::{FileId, FileMap};
+use lsp_types::{
+    FoldingRange, FoldingRangeKind, FoldingRangeParams, Position, Range, TextDocumentPositionParams,
+};
+use noirc_errors::Span;
+use noirc_frontend::{
+    ast::{
+        BlockExpression, ItemVisibility, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl,
+        TypeImpl, UseTree, Visitor,
+    },
+    lexer::Lexer,
+    parser::ParsedSubModule,
+    token::Token,
+};
+
+use crate::{byte_span_to_range, LspState};
+
+use super::process_request;
+
+pub(crate) fn on_folding_range_request(
+    state: &mut LspState,
+    params: FoldingRangeParams,
+) -> impl Future<Output = Result<Option<Vec<FoldingRange>>, ResponseError>> {
+    let text_document_position_params = TextDocumentPositionParams {
+        text_document: params.text_document,
+        position: Position { line: 0, character: 0 },
+    };
+
+    let result = process_request(state, text_document_position_params, |args| {
+        let file_id = args.location.file;
+        let file = args.files.get_file(file_id)?;
+        let source = file.source();
+        let (parsed_module, _errors) = noirc_frontend::parse_program(source);
+
+        let mut collector = FoldingRangeCollector::new(file_id, args.files);
+        parsed_module.accept(&mut collector);
+        collector.collect_comments(source);
+        Some(collector.finish())
+    });
+
+    future::ready(result)
+}
+
+/// Collects foldable regions: modules, types, traits, impls, functions and blocks from the AST,
+/// plus runs of consecutive imports and comments.
+struct FoldingRangeCollector<'a> {
+    file_id: FileId,
+    files: &'a FileMap,
+    ranges: Vec<FoldingRange>,
+    imports: Vec<Range>,
+}
+
+impl<'a> FoldingRangeCollector<'a> {
+    fn new(file_id: FileId, files: &'a FileMap) -> Self {
+        Self { file_id, files, ranges: Vec::new(), imports: Vec::new() }
+    }
+
+    fn to_range(&self, span: Span) -> Option<Range> {
+        byte_span_to_range(self.files, self.file_id, span.start() as usize..span.end() as usize)
+    }
+
+    fn add_span(&mut self, span: Span, kind: Option<FoldingRangeKind>) {
+        if let Some(range) = self.to_range(span) {
+            self.add_lines(range.start.line, range.end.line, kind);
+        }
+    }
+
+    fn add_lines(&mut self, start_line: u32, end_line: u32, kind: Option<FoldingRangeKind>) {
+        if end_line > start_line {
+            self.ranges.push(FoldingRange {
+                start_line,
+                start_character: None,
+                end_line,
+                end_character: None,
+                kind,
+                collapsed_text: None,
+            });
+        }
+    }
+
+    /// Folds multi-line block comments, and runs of line comments on consecutive lines.
+    fn collect_comments(&mut self, source: &str) {
+        let mut line_comments: Option<(u32, u32)> = None;
+
+        for token in Lexer::new(source).skip_comments(false).flatten() {
+            let Some(range) = self.to_range(token.to_span()) else {
+                continue;
+            };
+
+            match token.token() {
+                Token::LineComment(..) => {
+                    line_comments = match line_comments {
+                        Some((start_line, end_line)) if end_line + 1 == range.start.line => {
+                            Some((start_line, range.end.line))
+                        }
+                        _ => {
+                            self.add_line_comments(line_comments);
+                            Some((range.start.line, range.end.line))
+                        }
+                    };
+                }
+                Token::BlockComment(..) => {
+                    self.add_lines(
+                        range.start.line,
+                        range.end.line,
+                        Some(FoldingRangeKind::Comment),
+                    );
+                }
+                _ => {
+                    self.add_line_comments(line_comments.take());
+                }
+            }
+        }
+
+        self.add_line_comments(line_comments);
+    }
+
+    fn add_line_comments(&mut self, line_comments: Option<(u32, u32)>) {
+        if let Some((start_line, end_line)) = line_comments {
+            self.add_lines(start_line, end_line, Some(FoldingRangeKind::Comment));
+        }
+    }
+
+    /// Returns the ranges sorted by their start line, keeping only the outermost one when
+    /// several start on the same line (for example a function and its body).
+    fn finish(mut self) -> Vec<FoldingRange> {
+        let mut imports = std::mem::take(&mut self.imports);
+        imports.sort_by_key(|range| range.start.line);
+
+        let mut run: Option<(u32, u32)> = None;
+        for range in imports {
+            run = match run {
+                Some((start_line, end_line)) if range.start.line <= end_line + 1 => {
+                    Some((start_line, end_line.max(range.end.line)))
+                }
+                _ => {
+                    if let Some((start_line, end_line)) = run {
+                        self.add_lines(start_line, end_line, Some(FoldingRangeKind::Imports));
+                    }
+                    Some((range.start.line, range.end.line))
+                }
+            };
+        }
+        if let Some((start_line, end_line)) = run {
+            self.add_lines(start_line, end_line, Some(FoldingRangeKind::Imports));
+        }
+
+        let mut ranges = self.ranges;
+        ranges.sort_by_key(|range| (range.start_line, std::cmp::Reverse(range.end_line)));
+        ranges.dedup_by_key(|range| range.start_line);
+        ranges
+    }
+}
+
+impl<'a> Visitor for FoldingRangeCollector<'a> {
+    fn visit_parsed_submodule(&mut self, _: &ParsedSubModule, span: Span) -> bool {
+        self.add_span(span, None);
+        true
+    }
+
+    fn visit_noir_function(&mut self, _: &NoirFunction, span: Span) -> bool {
+        self.add_span(span, None);
+        true
+    }
+
+    fn visit_noir_trait_impl(&mut self, _: &NoirTraitImpl, span: Span) -> bool {
+        self.add_span(span, None);
+        true
+    }
+
+    fn visit_type_impl(&mut self, _: &TypeImpl, span: Span) -> bool {
+        self.add_span(span, None);
+        true
+    }
+
+    fn visit_noir_trait(&mut self, _: &NoirTrait, span: Span) -> bool {
+        self.add_span(span, None);
+        true
+    }
+
+    fn visit_noir_struct(&mut self, _: &NoirStruct, span: Span) -> bool {
+        self.add_span(span, None);
+        true
+    }
+
+    fn visit_block_expression(&mut self, _: &BlockExpression, span: Option<Span>) -> bool {
+        if let Some(span) = span {
+            self.add_span(span, None);
+        }
+        true
+    }
+
+    fn visit_comptime_expression(&mut self, _: &BlockExpression, span: Span) -> bool {
+        self.add_span(span, None);
+        true
+    }
+
+    fn visit_unsafe(&mut self, _: &BlockExpression, span: Span) -> bool {
+        self.add_span(span, None);
+        true
+    }
+
+    fn visit_import(&mut self, _: &UseTree, span: Span, _visibility: ItemVisibility) -> bool {
+        if let Some(range) = self.to_range(span) {
+            self.imports.push(range);
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod folding_range_tests {
+    use crate::{notifications::on_did_open_text_document, test_utils};
+
+    use super::*;
+    use lsp_types::{
+        DidOpenTextDocumentParams, PartialResultParams, TextDocumentIdentifier, TextDocumentItem,
+        WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    async fn get_folding_ranges(src: &str) -> Vec<(u32, u32, Option<FoldingRangeKind>)> {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("document_symbol").await;
+
+        let _ = on_did_open_text_document(
+            &mut state,
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: noir_text_document.clone(),
+                    language_id: "noir".to_string(),
+                    version: 0,
+                    text: src.to_string(),
+                },
+            },
+        );
+
+        let ranges = on_folding_range_request(
+            &mut state,
+            FoldingRangeParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document },
+                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+                partial_result_params: PartialResultParams { partial_result_token: None },
+            },
+        )
+        .await
+        .expect("Could not execute on_folding_range_request")
+        .unwrap();
+
+        ranges.into_iter().map(|range| (range.start_line, range.end_line, range.kind)).collect()
+    }
+
+    #[test]
+    async fn test_folding_ranges() {
+        let src = r#"use std::hash::Hash;
+use std::cmp::{
+    Eq, Ord,
+};
+
+// A point
+// in space
+struct Point {
+    x: Field,
+    y: Field,
+}
+
+mod moo {
+    fn foo(x: Field) -> Field {
+        if x == 0 {
+            1
+        } else {
+            /* many
+               lines */
+            2
+        }
+    }
+}
+
+fn main() {
+    let _ = unsafe {
+        1
+    };
+}
+"#;
+
+        let ranges = get_folding_ranges(src).await;
+        assert_eq!(
+            ranges,
+            vec![
+                (0, 3, Some(FoldingRangeKind::Imports)),
+                (5, 6, Some(FoldingRangeKind::Comment)),
+                (7, 10, None),
+                (12, 22, None),
+                (13, 21, None),
+                (14, 16, None),
+                (16, 20, None),
+                (17, 18, Some(FoldingRangeKind::Comment)),
+                (24, 28, None),
+                (25, 27, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use lsp_types::request::{GotoImplementationParams, GotoImplementationResponse};
+use noirc_errors::Location;
+use noirc_frontend::{
+    node_interner::{NodeInterner, ReferenceId},
+    Type,
+};
+
+use crate::LspState;
+
+use super::{find_trait_method_implementations, process_request, to_lsp_location};
+
+pub(crate) fn on_goto_implementation_request(
+    state: &mut LspState,
+    params: GotoImplementationParams,
+) -> impl Future<Output = Result<Option<GotoImplementationResponse>, ResponseError>> {
+    let result = process_request(state, params.text_document_position_params, |args| {
+        let referenced = args.interner.find_referenced(args.location)?;
+        let declaration_location = args.interner.reference_location(referenced);
+
+        // Implementations may be in any package in the workspace. All packages share the same
+        // FileManager so a Location in one interner is the same Location in another one.
+        let interners = std::iter::once(args.interner)
+            .chain(args.package_cache.values().map(|cache_data| &cache_data.node_interner));
+
+        let mut locations = Vec::new();
+        for interner in interners {
+            let Some(referenced) = interner.find_referenced(declaration_location) else {
+                continue;
+            };
+            for location in find_implementations(interner, referenced) {
+                // A trait impl that doesn't override a default method points to the trait itself
+                if location != declaration_location {
+                    locations.extend(to_lsp_location(args.files, location.file, location.span));
+                }
+            }
+        }
+
+        locations.sort_by_key(|location| {
+            (location.uri.to_string(), location.range.start.line, location.range.start.character)
+        });
+        locations.dedup();
+
+        if locations.is_empty() {
+            None
+        } else {
+            Some(GotoImplementationResponse::Array(locations))
+        }
+    });
+    future::ready(result)
+}
+
+/// Returns the locations of the implementations of a trait, a trait method or a struct:
+/// - for a trait, the trait name in each of its impls
+/// - for a trait method, the method name in each of the trait's impls
+/// - for a struct, the struct name in each of its impls, and the trait name in each of its trait impls
+fn find_implementations(interner: &NodeInterner, referenced: ReferenceId) -> Vec<Location> {
+    match referenced {
+        ReferenceId::Trait(trait_id) => interner
+            .get_all_trait_implementations()
+            .into_iter()
+            .filter_map(|(_, trait_impl)| {
+                let trait_impl = trait_impl.borrow();
+                (trait_impl.trait_id == trait_id)
+                    .then(|| Location::new(trait_impl.ident.span(), trait_impl.file))
+            })
+            .collect(),
+        ReferenceId::Function(func_id) => find_trait_method_implementations(interner, func_id)
+            .into_iter()
+            .map(|func_id| interner.function_modifiers(&func_id).name_location)
+            .collect(),
+        ReferenceId::Struct(struct_id) => {
+            let mut locations = interner.struct_impl_locations(struct_id).to_vec();
+            for (_, trait_impl) in interner.get_all_trait_implementations() {
+                let trait_impl = trait_impl.borrow();
+                if let Type::Struct(struct_type, _) = &trait_impl.typ {
+                    if struct_type.borrow().id == struct_id {
+                        locations.push(Location::new(trait_impl.ident.span(), trait_impl.file));
+                    }
+                }
+            }
+            locations
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod goto_implementation_tests {
+    use crate::test_utils;
+
+    use super::*;
+    use lsp_types::{
+        PartialResultParams, Position, Range, TextDocumentIdentifier, TextDocumentPositionParams,
+        WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    async fn expect_implementations(line: u32, character: u32, expected: Vec<Range>) {
+        let (mut state, noir_text_document) =
+            test_utils::init_lsp_server("goto_implementation").await;
+
+        let params = GotoImplementationParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document.clone() },
+                position: Position { line, character },
+            },
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+        };
+
+        let response = on_goto_implementation_request(&mut state, params)
+            .await
+            .expect("Could not execute on_goto_implementation_request")
+            .expect("Expected implementations");
+        let GotoImplementationResponse::Array(locations) = response else {
+            panic!("Expected an array of locations");
+        };
+
+        let ranges: Vec<_> = locations
+            .into_iter()
+            .map(|location| {
+                assert_eq!(location.uri, noir_text_document);
+                location.range
+            })
+            .collect();
+        assert_eq!(ranges, expected);
+    }
+
+    fn range(line: u32, start: u32, end: u32) -> Range {
+        Range { start: Position { line, character: start }, end: Position { line, character: end } }
+    }
+
+    #[test]
+    async fn goto_trait_implementations() {
+        // trait Shape {
+        expect_implementations(0, 6, vec![range(14, 5, 10), range(24, 5, 10)]).await;
+    }
+
+    #[test]
+    async fn goto_trait_method_implementations() {
+        //     fn area(self) -> Field;
+        expect_implementations(1, 7, vec![range(15, 7, 11), range(25, 7, 11)]).await;
+    }
+
+    #[test]
+    async fn goto_struct_implementations() {
+        // struct Square {
+        expect_implementations(4, 7, vec![range(8, 5, 11), range(14, 5, 10)]).await;
+    }
+}
@@ -441,6 +441,12 @@ fn format_function(id: FuncId, args: &ProcessRequestCallbackArgs) -> String {
 
     append_doc_comments(args.interner, ReferenceId::Function(id), &mut string);
 
+    let cost = args.circuit_costs.and_then(|costs| costs.get(&func_meta.name.location));
+    if let Some(cost) = cost {
+        string.push_str("\n\n---\n\n");
+        string.push_str(&cost.summary());
+    }
+
     string
 }
 
@@ -557,7 +563,7 @@ fn format_pattern(pattern: &HirPattern, interner: &NodeInterner, string: &mut St
             string.push_str("mut ");
             format_pattern(pattern, interner, string);
         }
-        HirPattern::Tuple(..) | HirPattern::Struct(..) => {
+        HirPattern::Tuple(..) | HirPattern::Array(..) | HirPattern::Struct(..) => {
             string.push('_');
         }
     }
@@ -570,7 +576,7 @@ fn pattern_is_self(pattern: &HirPattern, interner: &NodeInterner) -> bool {
             definition.name == "self"
         }
         HirPattern::Mutable(pattern, _) => pattern_is_self(pattern, interner),
-        HirPattern::Tuple(..) | HirPattern::Struct(..) => false,
+        HirPattern::Tuple(..) | HirPattern::Array(..) | HirPattern::Struct(..) => false,
     }
 }
 
@@ -216,7 +216,7 @@ impl<'a> InlayHintCollector<'a> {
                 Some(definition.name.clone())
             }
             HirPattern::Mutable(pattern, _location) => self.get_pattern_name(pattern),
-            HirPattern::Tuple(..) | HirPattern::Struct(..) => None,
+            HirPattern::Tuple(..) | HirPattern::Array(..) | HirPattern::Struct(..) => None,
         }
     }
 
@@ -244,7 +244,7 @@ impl<'a> InlayHintCollector<'a> {
                 definition_info.name == "self"
             }
             HirPattern::Mutable(pattern, _location) => self.is_self_parameter(pattern),
-            HirPattern::Tuple(..) | HirPattern::Struct(..) => false,
+            HirPattern::Tuple(..) | HirPattern::Array(..) | HirPattern::Struct(..) => false,
         }
     }
 
@@ -2,24 +2,34 @@ use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::{collections::HashMap, future::Future};
 
-use crate::{insert_all_files_for_workspace_into_file_manager, parse_diff, PackageCacheData};
+use crate::{
+    circuit_costs::CircuitCosts, insert_all_files_for_workspace_into_file_manager, parse_diff,
+    PackageCacheData,
+};
 use crate::{
     resolve_workspace_for_source_path,
     types::{CodeLensOptions, InitializeParams},
 };
 use async_lsp::{ErrorCode, ResponseError};
-use fm::{codespan_files::Error, FileMap, PathString};
+use fm::{
+    codespan_files::{Error, SimpleFile},
+    FileMap, PathString,
+};
 use lsp_types::{
-    CodeActionKind, DeclarationCapability, Location, Position, TextDocumentPositionParams,
-    TextDocumentSyncCapability, TextDocumentSyncKind, TypeDefinitionProviderCapability, Url,
-    WorkDoneProgressOptions,
+    CodeActionKind, DeclarationCapability, ImplementationProviderCapability, Location, Position,
+    TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TypeDefinitionProviderCapability, Url, WorkDoneProgressOptions,
 };
 use nargo_fmt::Config;
 
 use noirc_frontend::graph::CrateId;
 use noirc_frontend::hir::def_map::CrateDefMap;
 use noirc_frontend::usage_tracker::UsageTracker;
-use noirc_frontend::{graph::Dependency, node_interner::NodeInterner};
+use noirc_frontend::{
+    graph::Dependency,
+    node_interner::{FuncId, NodeInterner},
+    token::SecondaryAttribute,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -37,29 +47,44 @@ use crate::{
 // They are not attached to the `NargoLspService` struct so they can be unit tested with only `LspState`
 // and params passed in.
 
+mod call_hierarchy;
 mod code_action;
 mod code_lens_request;
 mod completion;
+mod document_highlight;
 mod document_symbol;
+mod expand_macro;
+mod folding_range;
 mod goto_declaration;
 mod goto_definition;
+mod goto_implementation;
 mod hover;
 mod inlay_hint;
 mod references;
 mod rename;
+mod selection_range;
+mod semantic_tokens;
 mod signature_help;
 mod test_run;
 mod tests;
+mod workspace_symbol;
 
 pub(crate) use {
-    code_action::on_code_action_request, code_lens_request::collect_lenses_for_package,
-    code_lens_request::on_code_lens_request, completion::on_completion_request,
-    document_symbol::on_document_symbol_request, goto_declaration::on_goto_declaration_request,
+    call_hierarchy::on_call_hierarchy_incoming_calls_request,
+    call_hierarchy::on_call_hierarchy_outgoing_calls_request,
+    call_hierarchy::on_prepare_call_hierarchy_request, code_action::on_code_action_request,
+    code_lens_request::collect_lenses_for_package, code_lens_request::on_code_lens_request,
+    completion::on_completion_request, document_highlight::on_document_highlight_request,
+    document_symbol::on_document_symbol_request, expand_macro::on_expand_macro_request,
+    folding_range::on_folding_range_request, goto_declaration::on_goto_declaration_request,
     goto_definition::on_goto_definition_request, goto_definition::on_goto_type_definition_request,
-    hover::on_hover_request, inlay_hint::on_inlay_hint_request, references::on_references_request,
+    goto_implementation::on_goto_implementation_request, hover::on_hover_request,
+    inlay_hint::on_inlay_hint_request, references::on_references_request,
     rename::on_prepare_rename_request, rename::on_rename_request,
-    signature_help::on_signature_help_request, test_run::on_test_run_request,
-    tests::on_tests_request,
+    selection_range::on_selection_range_request, semantic_tokens::on_semantic_tokens_full_request,
+    semantic_tokens::on_semantic_tokens_range_request, signature_help::on_signature_help_request,
+    test_run::on_test_run_request, tests::on_tests_request,
+    workspace_symbol::on_workspace_symbol_request,
 };
 
 /// LSP client will send initialization request after the server has started.
@@ -74,6 +99,18 @@ pub(crate) struct LspInitializationOptions {
     #[serde(rename = "enableParsingCache", default = "default_enable_parsing_cache")]
     pub(crate) enable_parsing_cache: bool,
 
+    /// Controls whether the ACIR and Brillig opcode counts of each function are computed
+    /// in the background, to show them in code lenses and hovers.
+    /// By default this will be set to true (enabled).
+    #[serde(rename = "enableCircuitCosts", default = "default_enable_circuit_costs")]
+    pub(crate) enable_circuit_costs: bool,
+
+    /// Controls whether the entry points of each package are compiled through SSA in the
+    /// background after a document is saved, to report diagnostics like underconstrained values.
+    /// By default this will be set to false (disabled).
+    #[serde(rename = "enableSsaDiagnostics", default = "default_enable_ssa_diagnostics")]
+    pub(crate) enable_ssa_diagnostics: bool,
+
     #[serde(rename = "inlayHints", default = "default_inlay_hints")]
     pub(crate) inlay_hints: InlayHintsOptions,
 }
@@ -119,6 +156,14 @@ fn default_enable_parsing_cache() -> bool {
     true
 }
 
+fn default_enable_circuit_costs() -> bool {
+    true
+}
+
+fn default_enable_ssa_diagnostics() -> bool {
+    false
+}
+
 fn default_inlay_hints() -> InlayHintsOptions {
     InlayHintsOptions {
         type_hints: default_type_hints(),
@@ -163,6 +208,8 @@ impl Default for LspInitializationOptions {
         Self {
             enable_code_lens: default_enable_code_lens(),
             enable_parsing_cache: default_enable_parsing_cache(),
+            enable_circuit_costs: default_enable_circuit_costs(),
+            enable_ssa_diagnostics: default_enable_ssa_diagnostics(),
             inlay_hints: default_inlay_hints(),
         }
     }
@@ -178,9 +225,16 @@ pub(crate) fn on_initialize(
         .and_then(|value| serde_json::from_value(value).ok())
         .unwrap_or_default();
     state.options = initialization_options;
+    state.client_supports_code_lens_refresh = params
+        .capabilities
+        .workspace
+        .and_then(|workspace| workspace.code_lens)
+        .and_then(|code_lens| code_lens.refresh_support)
+        .unwrap_or(false);
 
     async move {
-        let text_document_sync = TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL);
+        let text_document_sync =
+            TextDocumentSyncCapability::Kind(TextDocumentSyncKind::INCREMENTAL);
 
         let code_lens = if initialization_options.enable_code_lens {
             Some(CodeLensOptions { resolve_provider: Some(false) })
@@ -194,6 +248,7 @@ pub(crate) fn on_initialize(
                 run: Some(true),
                 update: Some(true),
             }),
+            expand_macro: Some(true),
         };
 
         Ok(InitializeResult {
@@ -201,6 +256,13 @@ pub(crate) fn on_initialize(
                 text_document_sync: Some(text_document_sync),
                 code_lens_provider: code_lens,
                 document_formatting_provider: true,
+                document_range_formatting_provider: Some(lsp_types::OneOf::Left(true)),
+                document_on_type_formatting_provider: Some(
+                    lsp_types::DocumentOnTypeFormattingOptions {
+                        first_trigger_character: "}".to_string(),
+                        more_trigger_character: Some(vec![";".to_string()]),
+                    },
+                ),
                 nargo: Some(nargo),
                 definition_provider: Some(lsp_types::OneOf::Left(true)),
                 declaration_provider: Some(DeclarationCapability::Simple(true)),
@@ -227,6 +289,23 @@ pub(crate) fn on_initialize(
                     },
                     resolve_provider: None,
                 })),
+                implementation_provider: Some(ImplementationProviderCapability::Simple(true)),
+                workspace_symbol_provider: Some(lsp_types::OneOf::Left(true)),
+                call_hierarchy_provider: Some(lsp_types::CallHierarchyServerCapability::Simple(
+                    true,
+                )),
+                semantic_tokens_provider: Some(
+                    lsp_types::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        lsp_types::SemanticTokensOptions {
+                            work_done_progress_options: WorkDoneProgressOptions {
+                                work_done_progress: None,
+                            },
+                            legend: semantic_tokens::semantic_tokens_legend(),
+                            range: Some(true),
+                            full: Some(lsp_types::SemanticTokensFullOptions::Bool(true)),
+                        },
+                    ),
+                ),
                 document_symbol_provider: Some(lsp_types::OneOf::Right(
                     lsp_types::DocumentSymbolOptions {
                         work_done_progress_options: WorkDoneProgressOptions {
@@ -258,12 +337,23 @@ pub(crate) fn on_initialize(
                     },
                 )),
                 code_action_provider: Some(lsp_types::OneOf::Right(lsp_types::CodeActionOptions {
-                    code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                    code_action_kinds: Some(vec![
+                        CodeActionKind::QUICKFIX,
+                        CodeActionKind::REFACTOR_EXTRACT,
+                        CodeActionKind::REFACTOR_INLINE,
+                    ]),
                     work_done_progress_options: WorkDoneProgressOptions {
                         work_done_progress: None,
                     },
                     resolve_provider: None,
                 })),
+                folding_range_provider: Some(lsp_types::FoldingRangeProviderCapability::Simple(
+                    true,
+                )),
+                selection_range_provider: Some(
+                    lsp_types::SelectionRangeProviderCapability::Simple(true),
+                ),
+                document_highlight_provider: Some(lsp_types::OneOf::Left(true)),
             },
             server_info: None,
         })
@@ -306,6 +396,64 @@ fn on_formatting_inner(
     }
 }
 
+pub(crate) fn on_range_formatting(
+    state: &mut LspState,
+    params: lsp_types::DocumentRangeFormattingParams,
+) -> impl Future<Output = Result<Option<Vec<lsp_types::TextEdit>>, ResponseError>> {
+    std::future::ready(on_range_formatting_inner(state, &params.text_document, params.range))
+}
+
+pub(crate) fn on_type_formatting(
+    state: &mut LspState,
+    params: lsp_types::DocumentOnTypeFormattingParams,
+) -> impl Future<Output = Result<Option<Vec<lsp_types::TextEdit>>, ResponseError>> {
+    // Format the item or statement ended by the character that was just typed (`}` or `;`)
+    let position = params.text_document_position.position;
+    let typed_character_position =
+        Position { line: position.line, character: position.character.saturating_sub(1) };
+    let range = lsp_types::Range::new(typed_character_position, position);
+
+    let text_document = params.text_document_position.text_document;
+    std::future::ready(on_range_formatting_inner(state, &text_document, range))
+}
+
+fn on_range_formatting_inner(
+    state: &LspState,
+    text_document: &lsp_types::TextDocumentIdentifier,
+    range: lsp_types::Range,
+) -> Result<Option<Vec<lsp_types::TextEdit>>, ResponseError> {
+    let path = text_document.uri.to_string();
+
+    let Some(source) = state.input_files.get(&path) else {
+        return Ok(None);
+    };
+
+    let (module, errors) = noirc_frontend::parse_program(source);
+    if !errors.is_empty() {
+        return Ok(None);
+    }
+
+    let file = SimpleFile::new(path.as_str(), source.as_str());
+    let (Ok(start), Ok(end)) = (
+        position_to_byte_index(&file, (), &range.start),
+        position_to_byte_index(&file, (), &range.end),
+    ) else {
+        return Ok(None);
+    };
+    let range = noirc_errors::Span::from(start as u32..end as u32);
+
+    let edits = nargo_fmt::format_range(source, module, &Config::default(), range)
+        .into_iter()
+        .filter_map(|(span, new_text)| {
+            let range =
+                crate::byte_span_to_range(&file, (), span.start() as usize..span.end() as usize)?;
+            Some(lsp_types::TextEdit { range, new_text })
+        })
+        .collect();
+
+    Ok(Some(edits))
+}
+
 pub(crate) fn position_to_byte_index<'a, F>(
     files: &'a F,
     file_id: F::FileId,
@@ -399,6 +547,11 @@ where
     Some(Location { uri, range })
 }
 
+/// Returns true if the given attributes of a struct or global mark it as deprecated.
+pub(crate) fn is_deprecated(attributes: &[SecondaryAttribute]) -> bool {
+    attributes.iter().any(|attribute| matches!(attribute, SecondaryAttribute::Deprecated(_)))
+}
+
 pub(crate) fn on_shutdown(
     _state: &mut LspState,
     _params: (),
@@ -416,6 +569,7 @@ pub(crate) struct ProcessRequestCallbackArgs<'a> {
     dependencies: &'a Vec<Dependency>,
     def_maps: &'a BTreeMap<CrateId, CrateDefMap>,
     usage_tracker: &'a UsageTracker,
+    circuit_costs: Option<&'a CircuitCosts>,
 }
 
 pub(crate) fn process_request<F, T>(
@@ -474,6 +628,7 @@ where
         dependencies: &crate_graph[crate_id].dependencies,
         def_maps,
         usage_tracker,
+        circuit_costs: package_cache_data.circuit_costs.as_ref(),
     }))
 }
 
@@ -539,6 +694,7 @@ where
         dependencies: &context.crate_graph[crate_id].dependencies,
         def_maps,
         usage_tracker,
+        circuit_costs: None,
     }))
 }
 
@@ -599,6 +755,31 @@ pub(crate) fn find_all_references_in_workspace(
     }
 }
 
+/// If `func_id` is a method declared in a trait, returns the methods implementing it in every
+/// impl of that trait. Otherwise returns an empty list.
+pub(crate) fn find_trait_method_implementations(
+    interner: &NodeInterner,
+    func_id: FuncId,
+) -> Vec<FuncId> {
+    let func_meta = interner.function_meta(&func_id);
+    let (Some(trait_id), None) = (func_meta.trait_id, func_meta.trait_impl) else {
+        return Vec::new();
+    };
+
+    let name = interner.function_name(&func_id);
+    interner
+        .get_all_trait_implementations()
+        .into_iter()
+        .filter_map(|(_, trait_impl)| {
+            let trait_impl = trait_impl.borrow();
+            if trait_impl.trait_id != trait_id {
+                return None;
+            }
+            trait_impl.methods.iter().find(|method| interner.function_name(method) == name).copied()
+        })
+        .collect()
+}
+
 pub(crate) fn find_all_references(
     referenced_location: noirc_errors::Location,
     interner: &NodeInterner,
@@ -638,7 +819,7 @@ mod initialization {
             response.capabilities,
             ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL
+                    TextDocumentSyncKind::INCREMENTAL
                 )),
                 code_lens_provider: Some(CodeLensOptions { resolve_provider: Some(false) }),
                 document_formatting_provider: true,
@@ -670,3 +851,90 @@ mod character_to_line_offset_tests {
         assert_eq!(result, 10);
     }
 }
+
+#[cfg(test)]
+mod formatting_tests {
+    use crate::{notifications::on_did_open_text_document, test_utils};
+
+    use super::*;
+    use lsp_types::{
+        DidOpenTextDocumentParams, DocumentOnTypeFormattingParams, DocumentRangeFormattingParams,
+        FormattingOptions, Range, TextDocumentIdentifier, TextDocumentItem, TextEdit,
+        WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    async fn open_document(src: &str) -> (LspState, Url) {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("document_symbol").await;
+
+        let _ = on_did_open_text_document(
+            &mut state,
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: noir_text_document.clone(),
+                    language_id: "noir".to_string(),
+                    version: 0,
+                    text: src.to_string(),
+                },
+            },
+        );
+
+        (state, noir_text_document)
+    }
+
+    #[test]
+    async fn test_range_formatting_only_formats_items_in_range() {
+        let src = "fn  one ( )  { }\nfn  two ( )  { }\nfn  three ( )  { }\n";
+        let (mut state, noir_text_document) = open_document(src).await;
+
+        let edits = on_range_formatting(
+            &mut state,
+            DocumentRangeFormattingParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document },
+                range: Range::new(Position::new(1, 0), Position::new(1, 16)),
+                options: FormattingOptions::default(),
+                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            },
+        )
+        .await
+        .expect("Could not execute on_range_formatting")
+        .unwrap();
+
+        assert_eq!(
+            edits,
+            vec![TextEdit {
+                range: Range::new(Position::new(1, 0), Position::new(1, 16)),
+                new_text: "fn two() {}".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    async fn test_on_type_formatting_formats_statement_ended_by_semicolon() {
+        let src = "fn main() {\n    let  x  =  1 ;\n    let  y  =  x+2;\n}\n";
+        let (mut state, noir_text_document) = open_document(src).await;
+
+        let edits = on_type_formatting(
+            &mut state,
+            DocumentOnTypeFormattingParams {
+                text_document_position: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri: noir_text_document },
+                    position: Position::new(2, 19),
+                },
+                ch: ";".to_string(),
+                options: FormattingOptions::default(),
+            },
+        )
+        .await
+        .expect("Could not execute on_type_formatting")
+        .unwrap();
+
+        assert_eq!(
+            edits,
+            vec![TextEdit {
+                range: Range::new(Position::new(2, 0), Position::new(2, 19)),
+                new_text: "    let y = x + 2;".to_string(),
+            }]
+        );
+    }
+}
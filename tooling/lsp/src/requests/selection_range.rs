@@ -0,0 +1,260 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use lsp_types::{
+    Position, Range, SelectionRange, SelectionRangeParams, TextDocumentPositionParams,
+};
+use noirc_errors::Span;
+use noirc_frontend::{
+    ast::{
+        Expression, Ident, ItemVisibility, LValue, NoirFunction, NoirStruct, NoirTrait,
+        NoirTraitImpl, Pattern, Statement, TypeImpl, UnresolvedType, UseTree, Visitor,
+    },
+    parser::{Item, ParsedSubModule},
+};
+
+use crate::{byte_span_to_range, LspState};
+
+use super::{position_to_byte_index, process_request};
+
+pub(crate) fn on_selection_range_request(
+    state: &mut LspState,
+    params: SelectionRangeParams,
+) -> impl Future<Output = Result<Option<Vec<SelectionRange>>, ResponseError>> {
+    let text_document_position_params = TextDocumentPositionParams {
+        text_document: params.text_document,
+        position: Position { line: 0, character: 0 },
+    };
+
+    let result = process_request(state, text_document_position_params, |args| {
+        let file_id = args.location.file;
+        let file = args.files.get_file(file_id)?;
+        let source = file.source();
+        let (parsed_module, _errors) = noirc_frontend::parse_program(source);
+
+        let selection_ranges = params
+            .positions
+            .into_iter()
+            .map(|position| {
+                let empty_range = SelectionRange {
+                    range: Range { start: position, end: position },
+                    parent: None,
+                };
+                let Ok(byte_index) = position_to_byte_index(args.files, file_id, &position) else {
+                    return empty_range;
+                };
+
+                let mut collector = SelectionRangeCollector::new(byte_index as u32);
+                parsed_module.accept(&mut collector);
+                collector.add(Span::from(0..source.len() as u32));
+
+                // Nest the ranges from the outermost one to the innermost one
+                let mut spans = collector.spans;
+                spans.sort_by_key(|span| std::cmp::Reverse(span.end() - span.start()));
+                spans.dedup();
+
+                let mut selection_range = None;
+                for span in spans {
+                    let span = span.start() as usize..span.end() as usize;
+                    let Some(range) = byte_span_to_range(args.files, file_id, span) else {
+                        continue;
+                    };
+                    selection_range =
+                        Some(SelectionRange { range, parent: selection_range.map(Box::new) });
+                }
+                selection_range.unwrap_or(empty_range)
+            })
+            .collect();
+
+        Some(selection_ranges)
+    });
+
+    future::ready(result)
+}
+
+/// Collects the spans of all the AST nodes that contain a position.
+struct SelectionRangeCollector {
+    byte_index: u32,
+    spans: Vec<Span>,
+}
+
+impl SelectionRangeCollector {
+    fn new(byte_index: u32) -> Self {
+        Self { byte_index, spans: Vec::new() }
+    }
+
+    /// Adds `span` if it contains the position, and returns whether it does.
+    fn add(&mut self, span: Span) -> bool {
+        let contains = span.start() <= self.byte_index && self.byte_index <= span.end();
+        if contains {
+            self.spans.push(span);
+        }
+        contains
+    }
+}
+
+impl Visitor for SelectionRangeCollector {
+    fn visit_item(&mut self, item: &Item) -> bool {
+        self.add(item.span)
+    }
+
+    fn visit_parsed_submodule(&mut self, submodule: &ParsedSubModule, span: Span) -> bool {
+        self.add(submodule.name.span());
+        self.add(span)
+    }
+
+    fn visit_noir_function(&mut self, noir_function: &NoirFunction, span: Span) -> bool {
+        if !self.add(span) {
+            return false;
+        }
+
+        self.add(noir_function.name_ident().span());
+        for param in &noir_function.def.parameters {
+            if self.add(param.span) {
+                param.pattern.accept(self);
+            }
+        }
+        true
+    }
+
+    fn visit_noir_trait_impl(&mut self, _: &NoirTraitImpl, span: Span) -> bool {
+        self.add(span)
+    }
+
+    fn visit_type_impl(&mut self, _: &TypeImpl, span: Span) -> bool {
+        self.add(span)
+    }
+
+    fn visit_noir_trait(&mut self, noir_trait: &NoirTrait, span: Span) -> bool {
+        self.add(noir_trait.name.span());
+        self.add(span)
+    }
+
+    fn visit_noir_struct(&mut self, noir_struct: &NoirStruct, span: Span) -> bool {
+        self.add(noir_struct.name.span());
+        for field in &noir_struct.fields {
+            self.add(field.item.name.span());
+        }
+        self.add(span)
+    }
+
+    fn visit_import(&mut self, _: &UseTree, span: Span, _visibility: ItemVisibility) -> bool {
+        self.add(span);
+        false
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) -> bool {
+        self.add(expression.span)
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) -> bool {
+        self.add(statement.span)
+    }
+
+    fn visit_lvalue(&mut self, lvalue: &LValue) -> bool {
+        self.add(lvalue.span())
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) -> bool {
+        self.add(pattern.span())
+    }
+
+    fn visit_identifier_pattern(&mut self, ident: &Ident) {
+        self.add(ident.span());
+    }
+
+    fn visit_unresolved_type(&mut self, unresolved_type: &UnresolvedType) -> bool {
+        self.add(unresolved_type.span)
+    }
+}
+
+#[cfg(test)]
+mod selection_range_tests {
+    use crate::{notifications::on_did_open_text_document, test_utils};
+
+    use super::*;
+    use lsp_types::{
+        DidOpenTextDocumentParams, PartialResultParams, TextDocumentIdentifier, TextDocumentItem,
+        WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    /// Returns the text of each selection range at `>|<`, from the innermost to the outermost one.
+    async fn get_selections(src: &str) -> Vec<String> {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("document_symbol").await;
+
+        let (line, character) = src
+            .lines()
+            .enumerate()
+            .find_map(|(line, text)| text.find(">|<").map(|character| (line, character)))
+            .expect("Expected to find >|< in the source code");
+        let src = src.replace(">|<", "");
+        let position = Position { line: line as u32, character: character as u32 };
+
+        let _ = on_did_open_text_document(
+            &mut state,
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: noir_text_document.clone(),
+                    language_id: "noir".to_string(),
+                    version: 0,
+                    text: src.clone(),
+                },
+            },
+        );
+
+        let mut selection_ranges = on_selection_range_request(
+            &mut state,
+            SelectionRangeParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document },
+                positions: vec![position],
+                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+                partial_result_params: PartialResultParams { partial_result_token: None },
+            },
+        )
+        .await
+        .expect("Could not execute on_selection_range_request")
+        .unwrap();
+        assert_eq!(selection_ranges.len(), 1);
+
+        let lines: Vec<&str> = src.lines().collect();
+        let text_of = |range: Range| {
+            if range.start.line == range.end.line {
+                let line = lines[range.start.line as usize];
+                line[range.start.character as usize..range.end.character as usize].to_string()
+            } else {
+                format!("lines {}..{}", range.start.line, range.end.line)
+            }
+        };
+
+        let mut selections = Vec::new();
+        let mut selection_range = Some(selection_ranges.remove(0));
+        while let Some(range) = selection_range {
+            selections.push(text_of(range.range));
+            selection_range = range.parent.map(|parent| *parent);
+        }
+        selections
+    }
+
+    #[test]
+    async fn test_selection_ranges_follow_ast_nesting() {
+        let src = r#"fn main(x: Field) {
+    let y = (x + 1>|<) * 2;
+}
+"#;
+
+        let selections = get_selections(src).await;
+        assert_eq!(
+            selections,
+            vec![
+                "1",
+                "x + 1",
+                "(x + 1)",
+                "(x + 1) * 2",
+                "let y = (x + 1) * 2;",
+                "lines 0..2",
+                "lines 0..3"
+            ]
+        );
+    }
+}
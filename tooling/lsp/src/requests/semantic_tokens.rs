@@ -0,0 +1,613 @@
+use std::{
+    collections::HashSet,
+    future::{self, Future},
+};
+
+use async_lsp::ResponseError;
+use fm::{FileId, FileMap, PathString};
+use lsp_types::{
+    Position, Range, SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens,
+    SemanticTokensLegend, SemanticTokensParams, SemanticTokensRangeParams,
+    SemanticTokensRangeResult, SemanticTokensResult, TextDocumentIdentifier,
+    TextDocumentPositionParams,
+};
+use noirc_errors::{Location, Span};
+use noirc_frontend::{
+    ast::{
+        GenericTypeArgs, Lambda, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, Path, Pattern,
+        TraitItem, TypeImpl, UnresolvedGeneric, UnresolvedTypeExpression, Visitor,
+    },
+    lexer::Lexer,
+    node_interner::{DefinitionKind, FuncId, GlobalId, NodeInterner, ReferenceId},
+    token::Token,
+};
+
+use crate::{utils, LspState};
+
+use super::{is_deprecated, process_request};
+
+/// The token types reported by the server. A token's type is its index in this list.
+const TOKEN_TYPES: [SemanticTokenType; 10] = [
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::STRUCT,
+    SemanticTokenType::INTERFACE,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::TYPE_PARAMETER,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::METHOD,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenType {
+    Namespace,
+    Struct,
+    Trait,
+    TypeAlias,
+    TypeParameter,
+    Parameter,
+    Variable,
+    Field,
+    Function,
+    Method,
+}
+
+/// The token modifiers reported by the server. A modifier is set in a token's bitset
+/// at the position of its index in this list.
+const TOKEN_MODIFIERS: [SemanticTokenModifier; 9] = [
+    SemanticTokenModifier::DECLARATION,
+    SemanticTokenModifier::READONLY,
+    SemanticTokenModifier::DEPRECATED,
+    SemanticTokenModifier::DEFAULT_LIBRARY,
+    SemanticTokenModifier::new("mutable"),
+    SemanticTokenModifier::new("comptime"),
+    SemanticTokenModifier::new("unconstrained"),
+    SemanticTokenModifier::new("oracle"),
+    SemanticTokenModifier::new("trait"),
+];
+
+const DECLARATION: u32 = 1 << 0;
+const READONLY: u32 = 1 << 1;
+const DEPRECATED: u32 = 1 << 2;
+const DEFAULT_LIBRARY: u32 = 1 << 3;
+const MUTABLE: u32 = 1 << 4;
+const COMPTIME: u32 = 1 << 5;
+const UNCONSTRAINED: u32 = 1 << 6;
+const ORACLE: u32 = 1 << 7;
+const TRAIT: u32 = 1 << 8;
+
+pub(crate) fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+pub(crate) fn on_semantic_tokens_full_request(
+    state: &mut LspState,
+    params: SemanticTokensParams,
+) -> impl Future<Output = Result<Option<SemanticTokensResult>, ResponseError>> {
+    let result = semantic_tokens(state, params.text_document, None)
+        .map(|tokens| tokens.map(SemanticTokensResult::Tokens));
+    future::ready(result)
+}
+
+pub(crate) fn on_semantic_tokens_range_request(
+    state: &mut LspState,
+    params: SemanticTokensRangeParams,
+) -> impl Future<Output = Result<Option<SemanticTokensRangeResult>, ResponseError>> {
+    let result = semantic_tokens(state, params.text_document, Some(params.range))
+        .map(|tokens| tokens.map(SemanticTokensRangeResult::Tokens));
+    future::ready(result)
+}
+
+fn semantic_tokens(
+    state: &mut LspState,
+    text_document: TextDocumentIdentifier,
+    range: Option<Range>,
+) -> Result<Option<SemanticTokens>, ResponseError> {
+    let uri = text_document.uri.clone();
+    let text_document_position_params =
+        TextDocumentPositionParams { text_document, position: Position { line: 0, character: 0 } };
+
+    process_request(state, text_document_position_params, |args| {
+        let path = PathString::from_path(uri.to_file_path().unwrap());
+        args.files.get_file_id(&path).map(|file_id| {
+            let file = args.files.get_file(file_id).unwrap();
+            let source = file.source();
+            let (parsed_module, _errors) = noirc_frontend::parse_program(source);
+
+            let span = range
+                .and_then(|range| utils::range_to_byte_span(args.files, file_id, &range))
+                .map(|range| Span::from(range.start as u32..range.end as u32));
+
+            let mut collector =
+                SemanticTokenCollector::new(args.files, file_id, args.interner, span);
+            parsed_module.accept(&mut collector);
+            collector.collect(source)
+        })
+    })
+}
+
+/// Classifies every identifier in a file.
+///
+/// Identifiers are classified by looking up what they refer to in the `NodeInterner`. The parsed
+/// module is visited first to find out what the interner doesn't know about: which locals are
+/// parameters, and which names refer to generics.
+struct SemanticTokenCollector<'a> {
+    files: &'a FileMap,
+    file_id: FileId,
+    interner: &'a NodeInterner,
+    span: Option<Span>,
+    /// The spans of the names of function and lambda parameters.
+    parameters: HashSet<Span>,
+    /// The spans of generics, where they are declared and where they are used.
+    generics: HashSet<Span>,
+    /// The spans of the names of declared generics.
+    generic_declarations: HashSet<Span>,
+    /// The names of the generics in scope, for each item being visited.
+    generics_in_scope: Vec<Vec<String>>,
+}
+
+impl<'a> SemanticTokenCollector<'a> {
+    fn new(
+        files: &'a FileMap,
+        file_id: FileId,
+        interner: &'a NodeInterner,
+        span: Option<Span>,
+    ) -> Self {
+        Self {
+            files,
+            file_id,
+            interner,
+            span,
+            parameters: HashSet::new(),
+            generics: HashSet::new(),
+            generic_declarations: HashSet::new(),
+            generics_in_scope: Vec::new(),
+        }
+    }
+
+    /// Lexes `source` and returns the tokens for all identifiers that could be classified.
+    fn collect(self, source: &str) -> SemanticTokens {
+        let mut tokens = Vec::new();
+        for token in Lexer::new(source).flatten() {
+            let Token::Ident(_) = token.token() else {
+                continue;
+            };
+
+            let span = token.to_span();
+            if !self.intersects_span(span) {
+                continue;
+            }
+
+            if let Some((token_type, modifiers)) = self.classify(span) {
+                if let Some(range) =
+                    crate::byte_span_to_range(self.files, self.file_id, span.into())
+                {
+                    tokens.push((range, token_type, modifiers));
+                }
+            }
+        }
+
+        SemanticTokens { result_id: None, data: encode_tokens(tokens) }
+    }
+
+    fn classify(&self, span: Span) -> Option<(TokenType, u32)> {
+        if self.generics.contains(&span) {
+            let modifiers = if self.generic_declarations.contains(&span) { DECLARATION } else { 0 };
+            return Some((TokenType::TypeParameter, modifiers));
+        }
+
+        let location = Location::new(span, self.file_id);
+        let referenced = self.interner.find_referenced(location)?;
+
+        let (token_type, mut modifiers) = match referenced {
+            ReferenceId::Module(_) => (TokenType::Namespace, 0),
+            ReferenceId::Struct(struct_id) => {
+                let mut modifiers = 0;
+                if is_deprecated(self.interner.struct_attributes(&struct_id)) {
+                    modifiers |= DEPRECATED;
+                }
+                if struct_id.krate().is_stdlib() {
+                    modifiers |= DEFAULT_LIBRARY;
+                }
+                (TokenType::Struct, modifiers)
+            }
+            ReferenceId::StructMember(..) => (TokenType::Field, 0),
+            ReferenceId::Trait(trait_id) => {
+                let modifiers = if trait_id.0.krate.is_stdlib() { DEFAULT_LIBRARY } else { 0 };
+                (TokenType::Trait, modifiers)
+            }
+            ReferenceId::Alias(_) => (TokenType::TypeAlias, 0),
+            ReferenceId::Global(global_id) => self.classify_global(global_id),
+            ReferenceId::Function(func_id) => self.classify_function(func_id),
+            ReferenceId::Local(definition_id) => {
+                let definition = self.interner.definition(definition_id);
+                match &definition.kind {
+                    DefinitionKind::Function(func_id) => self.classify_function(*func_id),
+                    DefinitionKind::Global(global_id) => self.classify_global(*global_id),
+                    DefinitionKind::NumericGeneric(..) => (TokenType::TypeParameter, 0),
+                    DefinitionKind::Local(_) => {
+                        let token_type = if self.parameters.contains(&definition.location.span) {
+                            TokenType::Parameter
+                        } else {
+                            TokenType::Variable
+                        };
+                        let mut modifiers = 0;
+                        if definition.mutable {
+                            modifiers |= MUTABLE;
+                        }
+                        if definition.comptime {
+                            modifiers |= COMPTIME;
+                        }
+                        (token_type, modifiers)
+                    }
+                }
+            }
+            ReferenceId::Reference(..) => return None,
+        };
+
+        if self.declaration_location(referenced) == Some(location) {
+            modifiers |= DECLARATION;
+        }
+
+        Some((token_type, modifiers))
+    }
+
+    fn classify_function(&self, func_id: FuncId) -> (TokenType, u32) {
+        let func_meta = self.interner.function_meta(&func_id);
+        let func_modifiers = self.interner.function_modifiers(&func_id);
+
+        let is_trait_method = func_meta.trait_id.is_some() || func_meta.trait_impl.is_some();
+        let token_type = if is_trait_method || func_meta.self_type.is_some() {
+            TokenType::Method
+        } else {
+            TokenType::Function
+        };
+
+        let mut modifiers = 0;
+        if is_trait_method {
+            modifiers |= TRAIT;
+        }
+        if func_modifiers.is_unconstrained {
+            modifiers |= UNCONSTRAINED;
+        }
+        if func_modifiers.is_comptime {
+            modifiers |= COMPTIME;
+        }
+        if func_modifiers.attributes.function().is_some_and(|attribute| attribute.is_oracle()) {
+            modifiers |= ORACLE;
+        }
+        if func_modifiers.attributes.get_deprecated_note().is_some() {
+            modifiers |= DEPRECATED;
+        }
+        if func_meta.source_crate.is_stdlib() {
+            modifiers |= DEFAULT_LIBRARY;
+        }
+        (token_type, modifiers)
+    }
+
+    fn classify_global(&self, global_id: GlobalId) -> (TokenType, u32) {
+        let global_info = self.interner.get_global(global_id);
+        let definition = self.interner.definition(global_info.definition_id);
+
+        let mut modifiers = 0;
+        if definition.mutable {
+            modifiers |= MUTABLE;
+        } else {
+            modifiers |= READONLY;
+        }
+        if definition.comptime {
+            modifiers |= COMPTIME;
+        }
+        if is_deprecated(self.interner.global_attributes(&global_id)) {
+            modifiers |= DEPRECATED;
+        }
+        if global_info.crate_id.is_stdlib() {
+            modifiers |= DEFAULT_LIBRARY;
+        }
+        (TokenType::Variable, modifiers)
+    }
+
+    fn declaration_location(&self, referenced: ReferenceId) -> Option<Location> {
+        match referenced {
+            // Crate roots don't have module attributes
+            ReferenceId::Module(module_id) => self
+                .interner
+                .try_module_attributes(&module_id)
+                .map(|attributes| attributes.location),
+            _ => Some(self.interner.reference_location(referenced)),
+        }
+    }
+
+    fn intersects_span(&self, other_span: Span) -> bool {
+        self.span.map_or(true, |span| span.intersects(&other_span))
+    }
+
+    fn push_generics_scope(&mut self, generics: &[UnresolvedGeneric]) {
+        let mut names = Vec::new();
+        for generic in generics {
+            let ident = match generic {
+                UnresolvedGeneric::Variable(ident) | UnresolvedGeneric::Numeric { ident, .. } => {
+                    ident
+                }
+                UnresolvedGeneric::Resolved(..) => continue,
+            };
+            self.generics.insert(ident.span());
+            self.generic_declarations.insert(ident.span());
+            names.push(ident.0.contents.clone());
+        }
+        self.generics_in_scope.push(names);
+    }
+
+    fn pop_generics_scope(&mut self) {
+        self.generics_in_scope.pop();
+    }
+
+    /// Remembers `path` as a use of a generic if it's a single name referring to a generic in scope.
+    fn collect_generic_use(&mut self, path: &Path) {
+        let [segment] = path.segments.as_slice() else {
+            return;
+        };
+        if segment.generics.is_some() {
+            return;
+        }
+
+        let name = &segment.ident.0.contents;
+        if self.generics_in_scope.iter().any(|names| names.contains(name)) {
+            self.generics.insert(segment.ident.span());
+        }
+    }
+
+    fn collect_parameters(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Identifier(ident) => {
+                self.parameters.insert(ident.span());
+            }
+            Pattern::Mutable(pattern, ..) => self.collect_parameters(pattern),
+            Pattern::Tuple(patterns, _) | Pattern::Array(patterns, _, _) => {
+                for pattern in patterns {
+                    self.collect_parameters(pattern);
+                }
+            }
+            Pattern::Struct(_, fields, _) => {
+                for (_, pattern) in fields {
+                    self.collect_parameters(pattern);
+                }
+            }
+            Pattern::Interned(..) => (),
+        }
+    }
+}
+
+impl<'a> Visitor for SemanticTokenCollector<'a> {
+    fn visit_noir_function(&mut self, noir_function: &NoirFunction, span: Span) -> bool {
+        if !self.intersects_span(span) {
+            return false;
+        }
+
+        for parameter in &noir_function.def.parameters {
+            self.collect_parameters(&parameter.pattern);
+        }
+
+        self.push_generics_scope(&noir_function.def.generics);
+        noir_function.accept_children(self);
+        self.pop_generics_scope();
+        false
+    }
+
+    fn visit_noir_struct(&mut self, noir_struct: &NoirStruct, span: Span) -> bool {
+        if !self.intersects_span(span) {
+            return false;
+        }
+
+        self.push_generics_scope(&noir_struct.generics);
+        noir_struct.accept_children(self);
+        self.pop_generics_scope();
+        false
+    }
+
+    fn visit_noir_trait(&mut self, noir_trait: &NoirTrait, span: Span) -> bool {
+        if !self.intersects_span(span) {
+            return false;
+        }
+
+        self.push_generics_scope(&noir_trait.generics);
+        noir_trait.accept_children(self);
+        self.pop_generics_scope();
+        false
+    }
+
+    fn visit_trait_item(&mut self, trait_item: &TraitItem) -> bool {
+        let TraitItem::Function { generics, parameters, .. } = trait_item else {
+            return true;
+        };
+
+        for (name, _) in parameters {
+            self.parameters.insert(name.span());
+        }
+
+        self.push_generics_scope(generics);
+        trait_item.accept_children(self);
+        self.pop_generics_scope();
+        false
+    }
+
+    fn visit_type_impl(&mut self, type_impl: &TypeImpl, span: Span) -> bool {
+        if !self.intersects_span(span) {
+            return false;
+        }
+
+        self.push_generics_scope(&type_impl.generics);
+        type_impl.accept_children(self);
+        self.pop_generics_scope();
+        false
+    }
+
+    fn visit_noir_trait_impl(&mut self, noir_trait_impl: &NoirTraitImpl, span: Span) -> bool {
+        if !self.intersects_span(span) {
+            return false;
+        }
+
+        self.push_generics_scope(&noir_trait_impl.impl_generics);
+        noir_trait_impl.accept_children(self);
+        self.pop_generics_scope();
+        false
+    }
+
+    fn visit_lambda(&mut self, lambda: &Lambda, _: Span) -> bool {
+        for (pattern, _) in &lambda.parameters {
+            self.collect_parameters(pattern);
+        }
+        true
+    }
+
+    fn visit_named_type(&mut self, path: &Path, args: &GenericTypeArgs, _: Span) -> bool {
+        if args.is_empty() {
+            self.collect_generic_use(path);
+        }
+        true
+    }
+
+    fn visit_expression_type(&mut self, expression: &UnresolvedTypeExpression, _: Span) {
+        if let UnresolvedTypeExpression::Variable(path) = expression {
+            self.collect_generic_use(path);
+        }
+    }
+}
+
+/// Encodes tokens the way the LSP expects them: sorted, and with each token's position
+/// relative to the previous token.
+fn encode_tokens(mut tokens: Vec<(Range, TokenType, u32)>) -> Vec<SemanticToken> {
+    tokens.sort_by_key(|(range, ..)| (range.start.line, range.start.character));
+    tokens.dedup_by_key(|(range, ..)| range.start);
+
+    let mut previous = Position { line: 0, character: 0 };
+    tokens
+        .into_iter()
+        .map(|(range, token_type, modifiers)| {
+            let delta_line = range.start.line - previous.line;
+            let delta_start = if delta_line == 0 {
+                range.start.character - previous.character
+            } else {
+                range.start.character
+            };
+            previous = range.start;
+
+            SemanticToken {
+                delta_line,
+                delta_start,
+                length: range.end.character - range.start.character,
+                token_type: token_type as u32,
+                token_modifiers_bitset: modifiers,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod semantic_tokens_tests {
+    use crate::test_utils;
+
+    use super::*;
+    use lsp_types::{PartialResultParams, WorkDoneProgressParams};
+    use tokio::test;
+
+    /// A decoded token: its line, start character, length, type and modifiers.
+    type DecodedToken = (u32, u32, u32, TokenType, u32);
+
+    fn decode_tokens(tokens: &[SemanticToken]) -> Vec<DecodedToken> {
+        let token_types = [
+            TokenType::Namespace,
+            TokenType::Struct,
+            TokenType::Trait,
+            TokenType::TypeAlias,
+            TokenType::TypeParameter,
+            TokenType::Parameter,
+            TokenType::Variable,
+            TokenType::Field,
+            TokenType::Function,
+            TokenType::Method,
+        ];
+
+        let mut line = 0;
+        let mut character = 0;
+        tokens
+            .iter()
+            .map(|token| {
+                if token.delta_line == 0 {
+                    character += token.delta_start;
+                } else {
+                    line += token.delta_line;
+                    character = token.delta_start;
+                }
+                let token_type = token_types[token.token_type as usize];
+                (line, character, token.length, token_type, token.token_modifiers_bitset)
+            })
+            .collect()
+    }
+
+    async fn get_tokens() -> Vec<DecodedToken> {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("semantic_tokens").await;
+
+        let response = on_semantic_tokens_full_request(
+            &mut state,
+            SemanticTokensParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document },
+                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+                partial_result_params: PartialResultParams { partial_result_token: None },
+            },
+        )
+        .await
+        .expect("Could not execute on_semantic_tokens_full_request")
+        .unwrap();
+
+        let SemanticTokensResult::Tokens(tokens) = response else {
+            panic!("Expected semantic tokens");
+        };
+        decode_tokens(&tokens.data)
+    }
+
+    #[test]
+    async fn test_legend_matches_token_types() {
+        assert_eq!(semantic_tokens_legend().token_types.len(), TokenType::Method as usize + 1);
+        assert_eq!(semantic_tokens_legend().token_modifiers.len(), 9);
+    }
+
+    #[test]
+    async fn test_semantic_tokens() {
+        let tokens = get_tokens().await;
+
+        let expected = [
+            // struct Point<T> {
+            (0, 7, 5, TokenType::Struct, DECLARATION),
+            (0, 13, 1, TokenType::TypeParameter, DECLARATION),
+            //     x: T,
+            (1, 4, 1, TokenType::Field, DECLARATION),
+            (1, 7, 1, TokenType::TypeParameter, 0),
+            //     fn get_x(self) -> T {
+            (5, 7, 5, TokenType::Method, DECLARATION),
+            //         self.x
+            (6, 13, 1, TokenType::Field, 0),
+            // unconstrained fn helper(mut value: Field) -> Field {
+            (10, 17, 6, TokenType::Function, DECLARATION | UNCONSTRAINED),
+            (10, 28, 5, TokenType::Parameter, DECLARATION | MUTABLE),
+            //     value += 1;
+            (11, 4, 5, TokenType::Parameter, MUTABLE),
+            // global FOUR: Field = 4;
+            (15, 7, 4, TokenType::Variable, DECLARATION | READONLY),
+            //     let mut y = point.get_x();
+            (19, 12, 1, TokenType::Variable, DECLARATION | MUTABLE),
+            (19, 22, 5, TokenType::Method, 0),
+            //     y = unsafe { helper(y) } + FOUR;
+            (20, 17, 6, TokenType::Function, UNCONSTRAINED),
+            (20, 31, 4, TokenType::Variable, READONLY),
+        ];
+        for token in expected {
+            assert!(tokens.contains(&token), "Expected {token:?} in {tokens:?}");
+        }
+    }
+}
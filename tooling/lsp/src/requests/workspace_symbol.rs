@@ -0,0 +1,339 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    future::{self, Future},
+};
+
+use async_lsp::ResponseError;
+use fm::FileMap;
+use lsp_types::{
+    SymbolInformation, SymbolKind, SymbolTag, Url, WorkspaceSymbolParams, WorkspaceSymbolResponse,
+};
+use nargo::workspace::Workspace;
+use nargo_toml::find_file_manifest;
+use noirc_errors::Location;
+use noirc_frontend::{
+    graph::CrateId,
+    hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId, ModuleId},
+    node_interner::{FuncId, NodeInterner, ReferenceId},
+};
+
+use crate::{notifications::check_workspace, resolve_workspace_for_source_path, LspState};
+
+use super::{is_deprecated, to_lsp_location};
+
+/// The maximum number of symbols returned, as clients filter and sort them further anyway.
+const MAX_SYMBOLS: usize = 256;
+
+pub(crate) fn on_workspace_symbol_request(
+    state: &mut LspState,
+    params: WorkspaceSymbolParams,
+) -> impl Future<Output = Result<Option<WorkspaceSymbolResponse>, ResponseError>> {
+    // The workspace at the root of the project wasn't type-checked if none of its documents were
+    // opened yet, so we do that now, once, caching it like the workspaces of opened documents
+    if let Some(workspace) = unchecked_root_workspace(state) {
+        // The warnings and errors found are reported once the package's documents are opened
+        let output_diagnostics = false;
+        check_workspace(state, &workspace, output_diagnostics);
+    }
+
+    let mut collector = SymbolCollector::new(&params.query);
+    for (package_root, package_cache) in &state.package_cache {
+        let workspace_cache = state
+            .workspace_cache
+            .iter()
+            .find(|(workspace_root, _)| package_root.starts_with(workspace_root));
+        let Some((_, workspace_cache)) = workspace_cache else {
+            continue;
+        };
+
+        let files = workspace_cache.file_manager.as_file_map();
+        collector.collect_package(&package_cache.node_interner, &package_cache.def_maps, files);
+    }
+
+    future::ready(Ok(Some(WorkspaceSymbolResponse::Flat(collector.finish()))))
+}
+
+/// Returns the workspace at the root of the project, unless it was already checked or the root
+/// isn't part of any package.
+fn unchecked_root_workspace(state: &LspState) -> Option<Workspace> {
+    let root = state.root_path.as_ref()?;
+    if state.workspace_cache.keys().any(|workspace_root| root.starts_with(workspace_root)) {
+        return None;
+    }
+    find_file_manifest(root)?;
+    resolve_workspace_for_source_path(root).ok()
+}
+
+/// Collects every function, struct, trait, global and type alias matching a query, in all crates
+/// known to a package: the package itself, its dependencies and the standard library.
+struct SymbolCollector<'a> {
+    query: &'a str,
+    symbols: Vec<(usize, SymbolInformation)>,
+    /// Packages in a workspace share dependencies, so the same symbol may be found more than once,
+    /// possibly through different file managers, which is why it's identified by its URI and position.
+    seen: HashSet<(String, Url, u32, u32)>,
+}
+
+impl<'a> SymbolCollector<'a> {
+    fn new(query: &'a str) -> Self {
+        Self { query, symbols: Vec::new(), seen: HashSet::new() }
+    }
+
+    fn collect_package(
+        &mut self,
+        interner: &NodeInterner,
+        def_maps: &BTreeMap<CrateId, CrateDefMap>,
+        files: &FileMap,
+    ) {
+        for func_id in interner.function_ids() {
+            self.collect_function(interner, files, func_id);
+        }
+
+        for (crate_id, def_map) in def_maps {
+            for (index, module) in def_map.modules().iter() {
+                let module_id = ModuleId { krate: *crate_id, local_id: LocalModuleId(index) };
+                let definitions = module.definitions();
+                for (name, scope) in definitions.types().iter().chain(definitions.values()) {
+                    // Only look at the items declared in this module, not the ones it imports
+                    if let Some((module_def_id, ..)) = scope.get(&None) {
+                        let name = name.to_string();
+                        self.collect_item(interner, files, name, *module_def_id, module_id);
+                    }
+                }
+            }
+        }
+    }
+
+    fn collect_function(&mut self, interner: &NodeInterner, files: &FileMap, func_id: FuncId) {
+        let func_meta = interner.function_meta(&func_id);
+        let modifiers = interner.function_modifiers(&func_id);
+
+        // Methods declared in traits are contained in the trait, other methods in their self type
+        let trait_id = if func_meta.trait_impl.is_none() { func_meta.trait_id } else { None };
+        let (kind, container_name) = if let Some(trait_id) = trait_id {
+            (SymbolKind::METHOD, Some(interner.get_trait(trait_id).name.to_string()))
+        } else if let Some(self_type) = &func_meta.self_type {
+            (SymbolKind::METHOD, Some(self_type.to_string()))
+        } else {
+            let module_id =
+                ModuleId { krate: func_meta.source_crate, local_id: func_meta.source_module };
+            (SymbolKind::FUNCTION, module_path(interner, module_id))
+        };
+
+        let deprecated = modifiers.attributes.get_deprecated_note().is_some();
+        let name = modifiers.name.clone();
+        self.push(files, name, kind, modifiers.name_location, container_name, deprecated);
+    }
+
+    fn collect_item(
+        &mut self,
+        interner: &NodeInterner,
+        files: &FileMap,
+        name: String,
+        module_def_id: ModuleDefId,
+        module_id: ModuleId,
+    ) {
+        let (kind, reference_id, deprecated) = match module_def_id {
+            ModuleDefId::TypeId(struct_id) => {
+                let deprecated = is_deprecated(interner.struct_attributes(&struct_id));
+                (SymbolKind::STRUCT, ReferenceId::Struct(struct_id), deprecated)
+            }
+            ModuleDefId::TraitId(trait_id) => {
+                (SymbolKind::INTERFACE, ReferenceId::Trait(trait_id), false)
+            }
+            ModuleDefId::TypeAliasId(type_alias_id) => {
+                (SymbolKind::CLASS, ReferenceId::Alias(type_alias_id), false)
+            }
+            ModuleDefId::GlobalId(global_id) => {
+                let deprecated = is_deprecated(interner.global_attributes(&global_id));
+                (SymbolKind::CONSTANT, ReferenceId::Global(global_id), deprecated)
+            }
+            // Functions are collected separately so that methods are found too
+            ModuleDefId::FunctionId(_) | ModuleDefId::ModuleId(_) => return,
+        };
+
+        let container_name = module_path(interner, module_id);
+        let location = interner.reference_location(reference_id);
+        self.push(files, name, kind, location, container_name, deprecated);
+    }
+
+    fn push(
+        &mut self,
+        files: &FileMap,
+        name: String,
+        kind: SymbolKind,
+        location: Location,
+        container_name: Option<String>,
+        deprecated: bool,
+    ) {
+        let Some(score) = fuzzy_match_score(&name, self.query) else {
+            return;
+        };
+        let Some(location) = to_lsp_location(files, location.file, location.span) else {
+            return;
+        };
+        let start = location.range.start;
+        if !self.seen.insert((name.clone(), location.uri.clone(), start.line, start.character)) {
+            return;
+        }
+
+        let tags = deprecated.then(|| vec![SymbolTag::DEPRECATED]);
+
+        #[allow(deprecated)]
+        self.symbols.push((
+            score,
+            SymbolInformation { name, kind, tags, deprecated: None, location, container_name },
+        ));
+    }
+
+    fn finish(mut self) -> Vec<SymbolInformation> {
+        self.symbols.sort_by(|(score1, symbol1), (score2, symbol2)| {
+            let key1 = (score1, &symbol1.name, symbol1.location.uri.as_str());
+            let key2 = (score2, &symbol2.name, symbol2.location.uri.as_str());
+            key1.cmp(&key2).then(symbol1.location.range.start.cmp(&symbol2.location.range.start))
+        });
+        self.symbols.truncate(MAX_SYMBOLS);
+        self.symbols.into_iter().map(|(_, symbol)| symbol).collect()
+    }
+}
+
+/// Returns how well `name` matches `query`, or `None` if it doesn't match at all.
+/// Every character in `query` must appear in `name`, in the same order, ignoring case.
+/// Lower scores are better: exact matches come first, then prefixes, then substrings.
+fn fuzzy_match_score(name: &str, query: &str) -> Option<usize> {
+    let name = name.to_lowercase();
+    let query = query.to_lowercase();
+
+    let mut name_chars = name.chars();
+    if !query.chars().all(|query_char| name_chars.any(|name_char| name_char == query_char)) {
+        return None;
+    }
+
+    let score = if name == query {
+        0
+    } else if name.starts_with(&query) {
+        1
+    } else if name.contains(&query) {
+        2
+    } else {
+        3
+    };
+    Some(score)
+}
+
+/// Returns the path of a module relative to its crate root, or `None` for the crate root itself.
+fn module_path(interner: &NodeInterner, module_id: ModuleId) -> Option<String> {
+    let mut segments = Vec::new();
+    let mut current = interner.try_module_attributes(&module_id);
+    while let Some(attributes) = current {
+        segments.push(attributes.name.as_str());
+        current = attributes.parent.and_then(|local_id| {
+            interner.try_module_attributes(&ModuleId { krate: module_id.krate, local_id })
+        });
+    }
+
+    if segments.is_empty() {
+        return None;
+    }
+    segments.reverse();
+    Some(segments.join("::"))
+}
+
+#[cfg(test)]
+mod workspace_symbol_tests {
+    use crate::{notifications, test_utils};
+
+    use super::*;
+    use lsp_types::{PartialResultParams, WorkDoneProgressParams};
+    use tokio::test;
+
+    async fn workspace_symbols(query: &str) -> (Vec<SymbolInformation>, Url) {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("workspace").await;
+
+        // noir_text_document is always `src/main.nr` in the workspace directory, so let's go to the workspace dir
+        let noir_text_document = noir_text_document.to_file_path().unwrap();
+        let workspace_dir = noir_text_document.parent().unwrap().parent().unwrap();
+        let one_lib = Url::from_file_path(workspace_dir.join("one/src/lib.nr")).unwrap();
+
+        // We call this to open the document, so that the entire workspace is analyzed
+        notifications::process_workspace_for_noir_document(&mut state, one_lib.clone(), true)
+            .unwrap();
+
+        (request_workspace_symbols(&mut state, query).await, one_lib)
+    }
+
+    async fn request_workspace_symbols(
+        state: &mut LspState,
+        query: &str,
+    ) -> Vec<SymbolInformation> {
+        let params = WorkspaceSymbolParams {
+            query: query.to_string(),
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+        };
+        let response = on_workspace_symbol_request(state, params)
+            .await
+            .expect("Could not execute on_workspace_symbol_request")
+            .unwrap();
+        let WorkspaceSymbolResponse::Flat(symbols) = response else {
+            panic!("Expected flat symbols");
+        };
+        symbols
+    }
+
+    #[test]
+    async fn test_finds_symbols_across_workspace_packages() {
+        let (symbols, _) = workspace_symbols("function_").await;
+        let names: HashSet<_> = symbols.iter().map(|symbol| symbol.name.as_str()).collect();
+        assert!(names.contains("function_one"));
+        assert!(names.contains("function_two"));
+    }
+
+    #[test]
+    async fn test_finds_symbols_without_opening_any_document() {
+        let (mut state, _) = test_utils::init_lsp_server("workspace").await;
+
+        let symbols = request_workspace_symbols(&mut state, "function_").await;
+        let names: HashSet<_> = symbols.iter().map(|symbol| symbol.name.as_str()).collect();
+        assert!(names.contains("function_one"));
+        assert!(names.contains("function_two"));
+
+        // The packages were checked once and cached, so later queries are answered from the cache
+        assert_eq!(state.package_cache.len(), 2);
+        assert!(unchecked_root_workspace(&state).is_none());
+
+        let symbols = request_workspace_symbols(&mut state, "function_two").await;
+        assert_eq!(symbols[0].name, "function_two");
+    }
+
+    #[test]
+    async fn test_finds_struct_in_submodule() {
+        let (symbols, one_lib) = workspace_symbols("SubOneStruct").await;
+
+        let symbol = &symbols[0];
+        assert_eq!(symbol.name, "SubOneStruct");
+        assert_eq!(symbol.kind, SymbolKind::STRUCT);
+        assert_eq!(symbol.container_name, Some("subone".to_string()));
+        assert_eq!(symbol.location.uri, one_lib);
+    }
+
+    #[test]
+    async fn test_finds_methods() {
+        let (symbols, _) = workspace_symbols("foo").await;
+
+        let symbol =
+            symbols.iter().find(|symbol| symbol.name == "foo" && symbol.kind == SymbolKind::METHOD);
+        let symbol = symbol.expect("Expected to find method foo");
+        assert_eq!(symbol.container_name, Some("SubOneStruct".to_string()));
+    }
+
+    #[test]
+    async fn test_fuzzy_match_score() {
+        assert_eq!(fuzzy_match_score("function_one", "function_one"), Some(0));
+        assert_eq!(fuzzy_match_score("function_one", "FUNC"), Some(1));
+        assert_eq!(fuzzy_match_score("function_one", "one"), Some(2));
+        assert_eq!(fuzzy_match_score("function_one", "fone"), Some(3));
+        assert_eq!(fuzzy_match_score("function_one", "onef"), None);
+        assert_eq!(fuzzy_match_score("function_one", ""), Some(1));
+    }
+}
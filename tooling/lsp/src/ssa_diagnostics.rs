@@ -0,0 +1,223 @@
+//! Diagnostics that only show up when a package's entry points are compiled through SSA, like
+//! underconstrained values, constant return values and errors found while generating ACIR.
+//! Checking a package only runs the frontend, so when enabled these are computed in the
+//! background after a document is saved and published together with the frontend diagnostics.
+
+use std::{
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+};
+
+use fm::FileManager;
+use nargo::package::Package;
+use noirc_driver::{check_crate, compile_no_check, CompileOptions};
+use noirc_errors::FileDiagnostic;
+use noirc_frontend::{hir::ParsedFiles, node_interner::FuncId};
+
+use crate::{
+    notifications::publish_diagnostics, parse_diff, resolve_workspace_for_source_path,
+    spawn_background_compile, LspState, SourceFingerprint,
+};
+
+/// Compiles the entry points of `package` (`main` for binaries, the entry points of a contract)
+/// and returns the diagnostics of checking it together with the ones found by compiling them,
+/// the same ones `nargo compile` reports.
+/// Returns `None` if the package doesn't type-check or if it has no entry points.
+pub(crate) fn compute_ssa_diagnostics(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    package: &Package,
+) -> Option<Vec<FileDiagnostic>> {
+    let (mut context, crate_id) = nargo::prepare_package(file_manager, parsed_files, package);
+    let options = CompileOptions::default();
+    let ((), mut diagnostics) = check_crate(&mut context, crate_id, &options).ok()?;
+
+    let entry_points: Vec<FuncId> = if package.is_binary() {
+        context.get_main_function(&crate_id).into_iter().collect()
+    } else if package.is_contract() {
+        context
+            .get_all_contracts(&crate_id)
+            .into_iter()
+            .flat_map(|contract| contract.functions)
+            .filter(|function| function.is_entry_point)
+            .map(|function| function.function_id)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    if entry_points.is_empty() {
+        return None;
+    }
+
+    for func_id in entry_points {
+        match compile_no_check(&mut context, &options, func_id, None, true) {
+            Ok(program) => diagnostics.extend(program.warnings.into_iter().map(Into::into)),
+            Err(error) => diagnostics.push(error.into()),
+        }
+    }
+
+    Some(diagnostics)
+}
+
+/// Queues computing the SSA diagnostics of each package in the workspace of a document that was
+/// just saved, if they are enabled.
+pub(crate) fn queue_ssa_diagnostics(state: &mut LspState, file_path: &Path) {
+    if !state.options.enable_ssa_diagnostics {
+        return;
+    }
+
+    let Ok(workspace) = resolve_workspace_for_source_path(file_path) else {
+        return;
+    };
+
+    for package in workspace.into_iter() {
+        let event = ComputeSsaDiagnosticsEvent {
+            workspace_root: workspace.root_dir.clone(),
+            package: package.clone(),
+        };
+        if state.client.emit(event).is_ok() {
+            continue;
+        }
+
+        // The main loop isn't running (this only happens in tests) so we compute them right away
+        let Some(workspace_cache) = state.workspace_cache.get(&workspace.root_dir) else {
+            continue;
+        };
+        let file_manager = workspace_cache.file_manager.clone();
+        let parsed_files = parse_diff(&file_manager, state);
+        if let Some(diagnostics) = compute_ssa_diagnostics(&file_manager, &parsed_files, package) {
+            let files = file_manager.as_file_map();
+            publish_diagnostics(state, &package.root_dir, files, &file_manager, diagnostics);
+        }
+    }
+}
+
+/// Emitted after a document is saved, to compute the SSA diagnostics of a package once pending
+/// notifications are handled.
+pub(crate) struct ComputeSsaDiagnosticsEvent {
+    workspace_root: PathBuf,
+    package: Package,
+}
+
+/// Emitted by the background thread computing a package's SSA diagnostics once it's done.
+pub(crate) struct SsaDiagnosticsComputedEvent {
+    package_root: PathBuf,
+    source_fingerprint: SourceFingerprint,
+    file_manager: FileManager,
+    diagnostics: Vec<FileDiagnostic>,
+}
+
+pub(crate) fn on_compute_ssa_diagnostics_event(
+    state: &mut LspState,
+    event: ComputeSsaDiagnosticsEvent,
+) -> ControlFlow<Result<(), async_lsp::Error>> {
+    let Some(package_cache) = state.package_cache.get(&event.package.root_dir) else {
+        return ControlFlow::Continue(());
+    };
+    let Some(workspace_cache) = state.workspace_cache.get(&event.workspace_root) else {
+        return ControlFlow::Continue(());
+    };
+
+    // The package changed after this event was emitted, so its diagnostics would be outdated
+    if !package_cache.source_fingerprint.matches(&workspace_cache.file_manager) {
+        return ControlFlow::Continue(());
+    }
+
+    let source_fingerprint = package_cache.source_fingerprint.clone();
+    let file_manager = workspace_cache.file_manager.clone();
+    let parsed_files = parse_diff(&file_manager, state);
+
+    let client = state.client.clone();
+    let package = event.package;
+    spawn_background_compile("SSA diagnostics", move || {
+        if let Some(diagnostics) = compute_ssa_diagnostics(&file_manager, &parsed_files, &package) {
+            let _ = client.emit(SsaDiagnosticsComputedEvent {
+                package_root: package.root_dir,
+                source_fingerprint,
+                file_manager,
+                diagnostics,
+            });
+        }
+    });
+
+    ControlFlow::Continue(())
+}
+
+pub(crate) fn on_ssa_diagnostics_computed_event(
+    state: &mut LspState,
+    event: SsaDiagnosticsComputedEvent,
+) -> ControlFlow<Result<(), async_lsp::Error>> {
+    // The diagnostics are discarded if the package changed while they were being computed
+    let Some(package_cache) = state.package_cache.get(&event.package_root) else {
+        return ControlFlow::Continue(());
+    };
+    if package_cache.source_fingerprint != event.source_fingerprint {
+        return ControlFlow::Continue(());
+    }
+
+    let file_manager = event.file_manager;
+    let files = file_manager.as_file_map();
+    publish_diagnostics(state, &event.package_root, files, &file_manager, event.diagnostics);
+
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod ssa_diagnostics_tests {
+    use crate::{
+        insert_all_files_for_workspace_into_file_manager, parse_diff,
+        resolve_workspace_for_source_path, test_utils,
+    };
+
+    use super::compute_ssa_diagnostics;
+    use tokio::test;
+
+    #[test]
+    async fn test_reports_diagnostics_found_while_compiling_entry_points() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("ssa_diagnostics").await;
+
+        let file_path = noir_text_document.to_file_path().unwrap();
+        let workspace = resolve_workspace_for_source_path(&file_path).unwrap();
+        let mut file_manager = workspace.new_file_manager();
+        insert_all_files_for_workspace_into_file_manager(&state, &workspace, &mut file_manager);
+        let parsed_files = parse_diff(&file_manager, &mut state);
+
+        let package = workspace.into_iter().next().unwrap();
+        let diagnostics = compute_ssa_diagnostics(&file_manager, &parsed_files, package)
+            .expect("Expected the package to compile");
+
+        let diagnostic = diagnostics
+            .iter()
+            .find(|diagnostic| {
+                diagnostic.diagnostic.message == "Return variable contains a constant value"
+            })
+            .expect("Expected a warning about the constant return value");
+        assert!(file_manager.path(diagnostic.file_id).unwrap().ends_with("src/main.nr"));
+        assert!(!diagnostic.diagnostic.is_error());
+    }
+
+    #[test]
+    async fn test_reports_underconstrained_values() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("ssa_diagnostics").await;
+
+        let file_path = noir_text_document.to_file_path().unwrap();
+        let workspace = resolve_workspace_for_source_path(&file_path).unwrap();
+        let mut file_manager = workspace.new_file_manager();
+        insert_all_files_for_workspace_into_file_manager(&state, &workspace, &mut file_manager);
+        let parsed_files = parse_diff(&file_manager, &mut state);
+
+        let package = workspace.into_iter().next().unwrap();
+        let diagnostics = compute_ssa_diagnostics(&file_manager, &parsed_files, package)
+            .expect("Expected the package to compile");
+
+        let diagnostic = diagnostics
+            .iter()
+            .find(|diagnostic| {
+                diagnostic.diagnostic.message
+                    == "Input to brillig function is in a separate subgraph to output"
+            })
+            .expect("Expected a diagnostic about the unconstrained call to `double`");
+        assert!(file_manager.path(diagnostic.file_id).unwrap().ends_with("src/main.nr"));
+        assert!(!diagnostic.diagnostic.is_error());
+    }
+}
@@ -1,18 +1,20 @@
 #![cfg(test)]
 
-use lsp_types::TextEdit;
+use lsp_types::{Position, TextEdit};
 
 pub(crate) fn apply_text_edit(src: &str, text_edit: &TextEdit) -> String {
-    let mut lines: Vec<_> = src.lines().collect();
-    assert_eq!(text_edit.range.start.line, text_edit.range.end.line);
+    let offset = |position: Position| {
+        let line_start: usize =
+            src.split_inclusive('\n').take(position.line as usize).map(str::len).sum();
+        line_start + position.character as usize
+    };
 
-    let mut line = lines[text_edit.range.start.line as usize].to_string();
-    line.replace_range(
-        text_edit.range.start.character as usize..text_edit.range.end.character as usize,
+    let mut text = src.to_string();
+    text.replace_range(
+        offset(text_edit.range.start)..offset(text_edit.range.end),
         &text_edit.new_text,
     );
-    lines[text_edit.range.start.line as usize] = &line;
-    lines.join("\n")
+    text
 }
 
 pub(crate) fn apply_text_edits(src: &str, text_edits: &[TextEdit]) -> String {
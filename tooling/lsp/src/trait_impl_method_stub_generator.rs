@@ -141,6 +141,26 @@ impl<'a> TraitImplMethodStubGenerator<'a> {
                 self.string.push(')');
                 true
             }
+            HirPattern::Array(patterns, rest_index, _) => {
+                self.string.push('[');
+                for (index, pattern) in patterns.iter().enumerate() {
+                    if index > 0 {
+                        self.string.push_str(", ");
+                    }
+                    if *rest_index == Some(index) {
+                        self.string.push_str(".., ");
+                    }
+                    self.append_pattern(pattern);
+                }
+                if *rest_index == Some(patterns.len()) {
+                    if !patterns.is_empty() {
+                        self.string.push_str(", ");
+                    }
+                    self.string.push_str("..");
+                }
+                self.string.push(']');
+                true
+            }
             HirPattern::Struct(typ, patterns, _) => {
                 self.append_type(typ);
                 self.string.push_str(" { ");
@@ -1,7 +1,10 @@
 use lsp_types::{
-    CodeActionOptions, CompletionOptions, DeclarationCapability, DefinitionOptions,
-    DocumentSymbolOptions, HoverOptions, InlayHintOptions, OneOf, ReferencesOptions, RenameOptions,
-    SignatureHelpOptions, TypeDefinitionProviderCapability,
+    CallHierarchyServerCapability, CodeActionOptions, CompletionOptions, DeclarationCapability,
+    DefinitionOptions, DocumentHighlightOptions, DocumentOnTypeFormattingOptions,
+    DocumentRangeFormattingOptions, DocumentSymbolOptions, FoldingRangeProviderCapability,
+    HoverOptions, ImplementationProviderCapability, InlayHintOptions, OneOf, ReferencesOptions,
+    RenameOptions, SelectionRangeProviderCapability, SemanticTokensServerCapabilities,
+    SignatureHelpOptions, TypeDefinitionProviderCapability, WorkspaceSymbolOptions,
 };
 use noirc_frontend::graph::CrateName;
 use serde::{Deserialize, Serialize};
@@ -15,17 +18,17 @@ pub(crate) use lsp_types::{
 };
 
 pub(crate) mod request {
-    use lsp_types::{request::Request, InitializeParams};
+    use lsp_types::{request::Request, InitializeParams, TextDocumentPositionParams};
 
     use super::{
-        InitializeResult, NargoTestRunParams, NargoTestRunResult, NargoTestsParams,
-        NargoTestsResult,
+        InitializeResult, NargoExpandMacroResult, NargoTestRunParams, NargoTestRunResult,
+        NargoTestsParams, NargoTestsResult,
     };
 
     // Re-providing lsp_types that we don't need to override
     pub(crate) use lsp_types::request::{
         CodeLensRequest as CodeLens, Formatting, GotoDeclaration, GotoDefinition,
-        GotoTypeDefinition, Shutdown,
+        GotoTypeDefinition, OnTypeFormatting, RangeFormatting, Shutdown,
     };
 
     #[derive(Debug)]
@@ -51,6 +54,14 @@ pub(crate) mod request {
         type Result = NargoTestsResult;
         const METHOD: &'static str = "nargo/tests";
     }
+
+    #[derive(Debug)]
+    pub(crate) struct NargoExpandMacro;
+    impl Request for NargoExpandMacro {
+        type Params = TextDocumentPositionParams;
+        type Result = NargoExpandMacroResult;
+        const METHOD: &'static str = "nargo/expandMacro";
+    }
 }
 
 pub(crate) mod notification {
@@ -93,6 +104,10 @@ pub(crate) struct NargoCapability {
     /// The server will provide various features related to testing within Nargo.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) tests: Option<NargoTestsOptions>,
+
+    /// The code produced at comptime for an item can be requested from the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) expand_macro: Option<bool>,
 }
 
 #[derive(Debug, PartialEq, Clone, Default, Deserialize, Serialize)]
@@ -114,6 +129,10 @@ pub(crate) struct ServerCapabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) type_definition_provider: Option<TypeDefinitionProviderCapability>,
 
+    /// The server provides goto implementation support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) implementation_provider: Option<ImplementationProviderCapability>,
+
     /// The server provides code lens.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) code_lens_provider: Option<CodeLensOptions>,
@@ -121,6 +140,15 @@ pub(crate) struct ServerCapabilities {
     /// The server provides document formatting.
     pub(crate) document_formatting_provider: bool,
 
+    /// The server provides document range formatting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) document_range_formatting_provider:
+        Option<OneOf<bool, DocumentRangeFormattingOptions>>,
+
+    /// The server provides document formatting on typing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) document_on_type_formatting_provider: Option<DocumentOnTypeFormattingOptions>,
+
     /// The server handles and provides custom nargo messages.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) nargo: Option<NargoCapability>,
@@ -141,6 +169,18 @@ pub(crate) struct ServerCapabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) inlay_hint_provider: Option<OneOf<bool, InlayHintOptions>>,
 
+    /// The server provides workspace symbol support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) workspace_symbol_provider: Option<OneOf<bool, WorkspaceSymbolOptions>>,
+
+    /// The server provides call hierarchy support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) call_hierarchy_provider: Option<CallHierarchyServerCapability>,
+
+    /// The server provides semantic tokens support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) semantic_tokens_provider: Option<SemanticTokensServerCapabilities>,
+
     /// The server provides document symbol support.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) document_symbol_provider: Option<OneOf<bool, DocumentSymbolOptions>>,
@@ -156,6 +196,18 @@ pub(crate) struct ServerCapabilities {
     /// The server provides code action support.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) code_action_provider: Option<OneOf<bool, CodeActionOptions>>,
+
+    /// The server provides folding range support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) folding_range_provider: Option<FoldingRangeProviderCapability>,
+
+    /// The server provides selection range support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) selection_range_provider: Option<SelectionRangeProviderCapability>,
+
+    /// The server provides document highlight support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) document_highlight_provider: Option<OneOf<bool, DocumentHighlightOptions>>,
 }
 
 #[derive(Debug, PartialEq, Clone, Default, Deserialize, Serialize)]
@@ -241,6 +293,16 @@ pub(crate) struct NargoTestRunResult {
     pub(crate) message: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct NargoExpandedMacro {
+    /// What was evaluated at comptime, for example `#[derive(Eq)]`
+    pub(crate) name: String,
+    /// The code it produced, as Noir source
+    pub(crate) expansion: String,
+}
+
+pub(crate) type NargoExpandMacroResult = Option<NargoExpandedMacro>;
+
 pub(crate) type CodeLensResult = Option<Vec<CodeLens>>;
 pub(crate) type GotoDefinitionResult = Option<lsp_types::GotoDefinitionResponse>;
 pub(crate) type GotoDeclarationResult = Option<lsp_types::request::GotoDeclarationResponse>;
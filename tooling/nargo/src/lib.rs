@@ -39,6 +39,7 @@ pub fn prepare_dependencies(
         match dep {
             Dependency::Remote { package } | Dependency::Local { package } => {
                 let crate_id = prepare_dependency(context, &package.entry_path);
+                context.crate_graph.enable_features(crate_id, package.enabled_features.clone());
                 add_dep(context, parent_crate, crate_id, dep_name.clone());
                 prepare_dependencies(context, crate_id, &package.dependencies);
             }
@@ -178,6 +179,7 @@ pub fn prepare_package<'file_manager, 'parsed_files>(
     let mut context = Context::from_ref_file_manager(file_manager, parsed_files);
 
     let crate_id = prepare_crate(&mut context, &package.entry_path);
+    context.crate_graph.enable_features(crate_id, package.enabled_features.clone());
 
     prepare_dependencies(&mut context, crate_id, &package.dependencies);
 
@@ -0,0 +1,123 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use acvm::{
+    acir::{circuit::ExpressionWidth, native_types::WitnessMap},
+    BlackBoxFunctionSolver, FieldElement,
+};
+use noirc_driver::{compile_no_check, CompileOptions};
+use noirc_errors::FileDiagnostic;
+use noirc_frontend::{hir::Context, node_interner::FuncId};
+
+use crate::errors::try_to_diagnose_runtime_error;
+
+use super::{execute_program, transform_program, DefaultForeignCallExecutor};
+
+pub enum BenchStatus {
+    Pass(BenchResult),
+    Fail { message: String, error_diagnostic: Option<FileDiagnostic> },
+    CompileError(FileDiagnostic),
+}
+
+impl BenchStatus {
+    pub fn failed(&self) -> bool {
+        !matches!(self, BenchStatus::Pass(_))
+    }
+}
+
+/// The cost of a single `#[bench]` function.
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    /// Number of ACIR opcodes across all the circuits of the compiled function
+    pub acir_opcodes: usize,
+    /// Number of Brillig opcodes across all the unconstrained functions of the compiled function
+    pub brillig_opcodes: usize,
+    /// The wall time of each execution of the compiled function
+    pub execution_times: Vec<Duration>,
+}
+
+impl BenchResult {
+    pub fn min_time(&self) -> Duration {
+        self.execution_times.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn max_time(&self) -> Duration {
+        self.execution_times.iter().max().copied().unwrap_or_default()
+    }
+
+    pub fn mean_time(&self) -> Duration {
+        if self.execution_times.is_empty() {
+            return Duration::default();
+        }
+        self.execution_times.iter().sum::<Duration>() / self.execution_times.len() as u32
+    }
+}
+
+/// Compiles the given `#[bench]` function as an entry point, counts its opcodes
+/// and then executes it `iterations` times, timing each run.
+#[allow(clippy::too_many_arguments)]
+pub fn run_bench<B: BlackBoxFunctionSolver<FieldElement>>(
+    blackbox_solver: &B,
+    context: &mut Context,
+    bench_function: FuncId,
+    iterations: usize,
+    expression_width: ExpressionWidth,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: Option<PathBuf>,
+    package_name: Option<String>,
+    config: &CompileOptions,
+) -> BenchStatus {
+    let bench_function_has_arguments =
+        !context.def_interner.function_meta(&bench_function).function_signature().0.is_empty();
+    if bench_function_has_arguments {
+        return BenchStatus::Fail {
+            message: "error: Bench functions cannot take any arguments".to_string(),
+            error_diagnostic: None,
+        };
+    }
+
+    let compiled_program = match compile_no_check(context, config, bench_function, None, false) {
+        Ok(compiled_program) => transform_program(compiled_program, expression_width),
+        Err(err) => return BenchStatus::CompileError(err.into()),
+    };
+
+    let acir_opcodes =
+        compiled_program.program.functions.iter().map(|function| function.opcodes.len()).sum();
+    let brillig_opcodes = compiled_program
+        .program
+        .unconstrained_functions
+        .iter()
+        .map(|function| function.bytecode.len())
+        .sum();
+
+    let mut execution_times = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let mut foreign_call_executor = DefaultForeignCallExecutor::new(
+            false,
+            foreign_call_resolver_url,
+            root_path.clone(),
+            package_name.clone(),
+        );
+
+        let start = Instant::now();
+        let execution = execute_program(
+            &compiled_program.program,
+            WitnessMap::new(),
+            blackbox_solver,
+            &mut foreign_call_executor,
+        );
+        let elapsed = start.elapsed();
+
+        if let Err(err) = execution {
+            let error_diagnostic =
+                try_to_diagnose_runtime_error(&err, &compiled_program.abi, &compiled_program.debug);
+            return BenchStatus::Fail { message: err.to_string(), error_diagnostic };
+        }
+
+        execution_times.push(elapsed);
+    }
+
+    BenchStatus::Pass(BenchResult { acir_opcodes, brillig_opcodes, execution_times })
+}
@@ -1,3 +1,4 @@
+pub use self::bench::{run_bench, BenchResult, BenchStatus};
 pub use self::check::check_program;
 pub use self::compile::{
     collect_errors, compile_contract, compile_program, compile_program_with_debug_instrumenter,
@@ -8,8 +9,9 @@ pub use self::foreign_calls::{DefaultForeignCallExecutor, ForeignCall, ForeignCa
 pub use self::optimize::{optimize_contract, optimize_program};
 pub use self::transform::{transform_contract, transform_program};
 
-pub use self::test::{run_test, TestStatus};
+pub use self::test::{run_test, test_status_program_compile_pass, TestStatus};
 
+mod bench;
 mod check;
 mod compile;
 mod execute;
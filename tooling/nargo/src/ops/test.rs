@@ -1,9 +1,6 @@
 use std::path::PathBuf;
 
-use acvm::{
-    acir::native_types::{WitnessMap, WitnessStack},
-    BlackBoxFunctionSolver, FieldElement,
-};
+use acvm::{acir::native_types::WitnessMap, BlackBoxFunctionSolver, FieldElement};
 use noirc_abi::Abi;
 use noirc_driver::{compile_no_check, CompileError, CompileOptions};
 use noirc_errors::{debug_info::DebugInfo, FileDiagnostic};
@@ -26,6 +23,7 @@ impl TestStatus {
 }
 
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
 pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
     blackbox_solver: &B,
     context: &mut Context,
@@ -34,6 +32,7 @@ pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
     foreign_call_resolver_url: Option<&str>,
     root_path: Option<PathBuf>,
     package_name: Option<String>,
+    fuzzing_corpus_dir: Option<PathBuf>,
     config: &CompileOptions,
 ) -> TestStatus {
     let test_function_has_no_arguments = context
@@ -61,9 +60,9 @@ pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
                 );
                 test_status_program_compile_pass(
                     test_function,
-                    compiled_program.abi,
-                    compiled_program.debug,
-                    circuit_execution,
+                    &compiled_program.abi,
+                    &compiled_program.debug,
+                    circuit_execution.map(|_| ()),
                 )
             } else {
                 #[cfg(target_arch = "wasm32")]
@@ -78,28 +77,48 @@ pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
                 #[cfg(not(target_arch = "wasm32"))]
                 {
                     use acvm::acir::circuit::Program;
-                    use noir_fuzzer::FuzzedExecutor;
-                    use proptest::test_runner::TestRunner;
-                    let runner = TestRunner::default();
-
-                    let executor =
-                        |program: &Program<FieldElement>,
-                         initial_witness: WitnessMap<FieldElement>|
-                         -> Result<WitnessStack<FieldElement>, String> {
+                    use noir_fuzzer::{Corpus, FuzzedExecutor};
+
+                    use super::execute_program_with_profiling;
+
+                    let fuzz_config = test_function.fuzz_config();
+                    let runner = fuzzing_test_runner(fuzz_config);
+
+                    let executor = |program: &Program<FieldElement>,
+                                    initial_witness: WitnessMap<FieldElement>,
+                                    profiling_active: bool| {
+                        let mut foreign_call_executor =
+                            DefaultForeignCallExecutor::<FieldElement>::new(
+                                false,
+                                foreign_call_resolver_url,
+                                root_path.clone(),
+                                package_name.clone(),
+                            );
+                        let result = if profiling_active {
+                            execute_program_with_profiling(
+                                program,
+                                initial_witness,
+                                blackbox_solver,
+                                &mut foreign_call_executor,
+                            )
+                        } else {
                             execute_program(
                                 program,
                                 initial_witness,
                                 blackbox_solver,
-                                &mut DefaultForeignCallExecutor::<FieldElement>::new(
-                                    false,
-                                    foreign_call_resolver_url,
-                                    root_path.clone(),
-                                    package_name.clone(),
-                                ),
+                                &mut foreign_call_executor,
                             )
-                            .map_err(|err| err.to_string())
+                            .map(|witness_stack| (witness_stack, Vec::new()))
                         };
-                    let fuzzer = FuzzedExecutor::new(compiled_program.into(), executor, runner);
+                        result.map_err(|err| err.to_string())
+                    };
+                    let mut fuzzer = FuzzedExecutor::new(compiled_program.into(), executor, runner);
+                    if let Some(corpus_dir) = fuzzing_corpus_dir {
+                        fuzzer = fuzzer.with_corpus(Corpus::new(corpus_dir));
+                    }
+                    if fuzz_config.coverage_guided {
+                        fuzzer = fuzzer.with_coverage_guidance();
+                    }
 
                     let result = fuzzer.fuzz();
                     if result.success {
@@ -117,6 +136,32 @@ pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
     }
 }
 
+/// Builds the fuzzer's test runner, applying the options given in the `#[test]` attribute
+/// on top of the default configuration.
+#[cfg(not(target_arch = "wasm32"))]
+fn fuzzing_test_runner(
+    fuzz_config: &noirc_frontend::token::FuzzConfig,
+) -> proptest::test_runner::TestRunner {
+    use proptest::test_runner::{Config, RngAlgorithm, TestRng, TestRunner};
+
+    let default_config = Config::default();
+    let config = Config {
+        cases: fuzz_config.cases.unwrap_or(default_config.cases),
+        max_shrink_iters: fuzz_config.max_shrink_iters.unwrap_or(default_config.max_shrink_iters),
+        ..default_config
+    };
+
+    match fuzz_config.seed {
+        Some(seed) => {
+            let mut seed_bytes = [0; 32];
+            seed_bytes[..8].copy_from_slice(&seed.to_le_bytes());
+            let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &seed_bytes);
+            TestRunner::new_with_rng(config, rng)
+        }
+        None => TestRunner::new(config),
+    }
+}
+
 /// Test function failed to compile
 ///
 /// Note: This could be because the compiler was able to deduce
@@ -136,11 +181,12 @@ fn test_status_program_compile_fail(err: CompileError, test_function: &TestFunct
 ///
 /// We now check whether execution passed/failed and whether it should have
 /// passed/failed to determine the test status.
-fn test_status_program_compile_pass(
+/// This is also used when the test was executed by other means, like the debugger.
+pub fn test_status_program_compile_pass(
     test_function: &TestFunction,
-    abi: Abi,
-    debug: Vec<DebugInfo>,
-    circuit_execution: Result<WitnessStack<FieldElement>, NargoError<FieldElement>>,
+    abi: &Abi,
+    debug: &[DebugInfo],
+    circuit_execution: Result<(), NargoError<FieldElement>>,
 ) -> TestStatus {
     let circuit_execution_err = match circuit_execution {
         // Circuit execution was successful; ie no errors or unsatisfied constraints
@@ -160,7 +206,7 @@ fn test_status_program_compile_pass(
     // If we reach here, then the circuit execution failed.
     //
     // Check if the function should have passed
-    let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, &abi, &debug);
+    let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, abi, debug);
     let test_should_have_passed = !test_function.should_fail();
     if test_should_have_passed {
         return TestStatus::Fail {
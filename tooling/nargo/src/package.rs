@@ -1,4 +1,8 @@
-use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::PathBuf,
+};
 
 use acvm::acir::circuit::ExpressionWidth;
 pub use noirc_driver::CrateName;
@@ -52,6 +56,8 @@ pub struct Package {
     pub entry_path: PathBuf,
     pub name: CrateName,
     pub dependencies: BTreeMap<CrateName, Dependency>,
+    /// The features enabled for this package, either by default or by the package depending on it
+    pub enabled_features: BTreeSet<String>,
     pub expression_width: Option<ExpressionWidth>,
 }
 
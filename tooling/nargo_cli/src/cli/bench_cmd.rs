@@ -0,0 +1,446 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bn254_blackbox_solver::Bn254BlackBoxSolver;
+use clap::Args;
+use fm::FileManager;
+use nargo::{
+    insert_all_files_for_workspace_into_file_manager,
+    ops::{BenchResult, BenchStatus},
+    package::{CrateName, Package},
+    parse_all, prepare_package,
+};
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_frontend::hir::{FunctionNameMatch, ParsedFiles};
+use prettytable::{row, table};
+use serde::{Deserialize, Serialize};
+
+use crate::{cli::check_cmd::check_crate_and_report_errors, errors::CliError};
+
+use super::{
+    compile_cmd::get_target_width,
+    fs::{create_named_dir, write_to_file},
+    NargoConfig,
+};
+
+/// Run the benchmarks for this program, reporting the circuit size and execution time
+/// of each `#[bench]` function
+#[derive(Debug, Clone, Args)]
+pub(crate) struct BenchCommand {
+    /// If given, only benchmarks with names containing this string will be run
+    bench_name: Option<String>,
+
+    /// Only run benchmarks that match exactly
+    #[clap(long)]
+    exact: bool,
+
+    /// The name of the package to benchmark
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Benchmark all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    /// Number of times each benchmark is executed to measure its execution time
+    #[clap(long, default_value_t = 10)]
+    iterations: usize,
+
+    /// Compare the results against the baseline at this path, without overwriting it
+    #[clap(long, conflicts_with = "save_baseline")]
+    baseline: Option<PathBuf>,
+
+    /// Compare the results against the baseline at this path and then save them there.
+    /// Defaults to `target/bench/baseline.json`
+    #[clap(long)]
+    save_baseline: Option<PathBuf>,
+
+    /// Increase of the mean execution time, as a percentage, over which a benchmark is
+    /// reported as a regression. Any increase in the opcode counts is always a regression.
+    #[clap(long, default_value_t = 10.0)]
+    time_threshold: f64,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+
+    /// JSON RPC url to solve oracle calls
+    #[clap(long)]
+    oracle_resolver: Option<String>,
+}
+
+pub(crate) fn run(args: BenchCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.clone().map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    let mut workspace_file_manager = workspace.new_file_manager();
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let parsed_files = parse_all(&workspace_file_manager);
+
+    let pattern = match &args.bench_name {
+        Some(name) => {
+            if args.exact {
+                FunctionNameMatch::Exact(name)
+            } else {
+                FunctionNameMatch::Contains(name)
+            }
+        }
+        None => FunctionNameMatch::Anything,
+    };
+
+    // Benchmarks are run one after the other so that they don't compete with each other for CPU time.
+    let mut report = BenchReport::default();
+    let mut failed_benchmarks = 0;
+    for package in &workspace {
+        let results = run_benches(
+            &workspace_file_manager,
+            &parsed_files,
+            package,
+            pattern,
+            &args,
+            Some(workspace.root_dir.clone()),
+        )?;
+        for (name, status) in results {
+            match status {
+                BenchStatus::Pass(result) => {
+                    report.benchmarks.push(BenchEntry::new(package, name, &result));
+                }
+                BenchStatus::Fail { message, error_diagnostic } => {
+                    failed_benchmarks += 1;
+                    eprintln!("[{}] Benchmark {name} failed\n{message}\n", package.name);
+                    if let Some(diagnostic) = error_diagnostic {
+                        report_diagnostic(
+                            &workspace_file_manager,
+                            &args.compile_options,
+                            diagnostic,
+                        );
+                    }
+                }
+                BenchStatus::CompileError(diagnostic) => {
+                    failed_benchmarks += 1;
+                    report_diagnostic(&workspace_file_manager, &args.compile_options, diagnostic);
+                }
+            }
+        }
+    }
+
+    if report.benchmarks.is_empty() && failed_benchmarks == 0 {
+        return match &pattern {
+            FunctionNameMatch::Exact(pattern) => {
+                Err(CliError::Generic(format!("Found 0 benchmarks matching input '{pattern}'.")))
+            }
+            FunctionNameMatch::Contains(pattern) => {
+                Err(CliError::Generic(format!("Found 0 benchmarks containing '{pattern}'.")))
+            }
+            // If we are running all benchmarks in a crate, having none is not an error
+            FunctionNameMatch::Anything => Ok(()),
+        };
+    }
+
+    let default_baseline_path =
+        workspace.target_directory_path().join("bench").join("baseline.json");
+    let baseline_path =
+        args.baseline.as_ref().or(args.save_baseline.as_ref()).unwrap_or(&default_baseline_path);
+    let baseline = BenchReport::read_from_file(baseline_path)?;
+
+    let regressions = report.regressions(baseline.as_ref(), args.time_threshold);
+    report.print_table(baseline.as_ref());
+
+    if args.baseline.is_none() {
+        let save_path = args.save_baseline.as_ref().unwrap_or(&default_baseline_path);
+        let mut new_baseline = baseline.unwrap_or_default();
+        new_baseline.merge(report);
+        new_baseline.write_to_file(save_path);
+    }
+
+    for regression in &regressions {
+        eprintln!("Regression: {regression}");
+    }
+
+    if failed_benchmarks > 0 {
+        let plural = if failed_benchmarks == 1 { "" } else { "s" };
+        Err(CliError::Generic(format!("{failed_benchmarks} benchmark{plural} failed")))
+    } else if !regressions.is_empty() {
+        let count = regressions.len();
+        let plural = if count == 1 { "" } else { "s" };
+        Err(CliError::Generic(format!("Found {count} regression{plural} against the baseline")))
+    } else {
+        Ok(())
+    }
+}
+
+fn run_benches(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    package: &Package,
+    fn_name: FunctionNameMatch,
+    args: &BenchCommand,
+    root_path: Option<PathBuf>,
+) -> Result<Vec<(String, BenchStatus)>, CliError> {
+    let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
+    check_crate_and_report_errors(&mut context, crate_id, &args.compile_options)?;
+
+    let bench_functions = context.get_all_bench_functions_in_crate_matching(&crate_id, fn_name);
+
+    let count_all = bench_functions.len();
+    let plural = if count_all == 1 { "" } else { "s" };
+    println!("[{}] Running {count_all} bench function{plural}", package.name);
+
+    let expression_width =
+        get_target_width(package.expression_width, args.compile_options.expression_width);
+
+    let results = bench_functions
+        .into_iter()
+        .map(|(bench_name, bench_function)| {
+            let status = nargo::ops::run_bench(
+                &Bn254BlackBoxSolver,
+                &mut context,
+                bench_function,
+                args.iterations,
+                expression_width,
+                args.oracle_resolver.as_deref(),
+                root_path.clone(),
+                Some(package.name.to_string()),
+                &args.compile_options,
+            );
+            (bench_name, status)
+        })
+        .collect();
+
+    Ok(results)
+}
+
+fn report_diagnostic(
+    file_manager: &FileManager,
+    compile_options: &CompileOptions,
+    diagnostic: noirc_errors::FileDiagnostic,
+) {
+    noirc_errors::reporter::report_all(
+        file_manager.as_file_map(),
+        &[diagnostic],
+        compile_options.deny_warnings,
+        compile_options.silence_warnings,
+    );
+}
+
+/// The results of a benchmark run, serialized as JSON to be used as a baseline by later runs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BenchReport {
+    benchmarks: Vec<BenchEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct BenchEntry {
+    package_name: String,
+    name: String,
+    acir_opcodes: usize,
+    brillig_opcodes: usize,
+    mean_time_ns: u64,
+    min_time_ns: u64,
+    max_time_ns: u64,
+}
+
+impl BenchEntry {
+    fn new(package: &Package, name: String, result: &BenchResult) -> Self {
+        let nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        BenchEntry {
+            package_name: package.name.to_string(),
+            name,
+            acir_opcodes: result.acir_opcodes,
+            brillig_opcodes: result.brillig_opcodes,
+            mean_time_ns: nanos(result.mean_time()),
+            min_time_ns: nanos(result.min_time()),
+            max_time_ns: nanos(result.max_time()),
+        }
+    }
+
+    fn key(&self) -> (&str, &str) {
+        (&self.package_name, &self.name)
+    }
+}
+
+impl BenchReport {
+    fn read_from_file(path: &Path) -> Result<Option<Self>, CliError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read(path).map_err(|err| {
+            CliError::Generic(format!("Could not read baseline {}: {err}", path.display()))
+        })?;
+        let report = serde_json::from_slice(&contents).map_err(|err| {
+            CliError::Generic(format!("Could not parse baseline {}: {err}", path.display()))
+        })?;
+        Ok(Some(report))
+    }
+
+    fn write_to_file(&self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            create_named_dir(parent, "bench");
+        }
+        write_to_file(&serde_json::to_vec_pretty(self).unwrap(), path);
+    }
+
+    /// Replaces the entries of this report with the ones in `other` with the same package and name,
+    /// keeping the ones which weren't run again.
+    fn merge(&mut self, other: BenchReport) {
+        let mut entries: BTreeMap<(String, String), BenchEntry> =
+            std::mem::take(&mut self.benchmarks)
+                .into_iter()
+                .map(|entry| ((entry.package_name.clone(), entry.name.clone()), entry))
+                .collect();
+        for entry in other.benchmarks {
+            entries.insert((entry.package_name.clone(), entry.name.clone()), entry);
+        }
+        self.benchmarks = entries.into_values().collect();
+    }
+
+    fn find(&self, key: (&str, &str)) -> Option<&BenchEntry> {
+        self.benchmarks.iter().find(|entry| entry.key() == key)
+    }
+
+    /// Returns a description of every benchmark which got worse compared to the baseline:
+    /// any increase in opcodes, or an increase of the mean execution time above `time_threshold` percent.
+    fn regressions(&self, baseline: Option<&BenchReport>, time_threshold: f64) -> Vec<String> {
+        let Some(baseline) = baseline else {
+            return Vec::new();
+        };
+
+        let mut regressions = Vec::new();
+        for entry in &self.benchmarks {
+            let Some(previous) = baseline.find(entry.key()) else {
+                continue;
+            };
+            let name = format!("[{}] {}", entry.package_name, entry.name);
+
+            if entry.acir_opcodes > previous.acir_opcodes {
+                regressions.push(format!(
+                    "{name}: ACIR opcodes increased from {} to {}",
+                    previous.acir_opcodes, entry.acir_opcodes
+                ));
+            }
+            if entry.brillig_opcodes > previous.brillig_opcodes {
+                regressions.push(format!(
+                    "{name}: Brillig opcodes increased from {} to {}",
+                    previous.brillig_opcodes, entry.brillig_opcodes
+                ));
+            }
+
+            let time_change = percentage_change(previous.mean_time_ns, entry.mean_time_ns);
+            if time_change > time_threshold {
+                regressions.push(format!(
+                    "{name}: mean execution time increased by {time_change:.1}% ({} -> {})",
+                    format_nanos(previous.mean_time_ns),
+                    format_nanos(entry.mean_time_ns)
+                ));
+            }
+        }
+        regressions
+    }
+
+    fn print_table(&self, baseline: Option<&BenchReport>) {
+        let mut bench_table = table!([Fm->"Package", Fm->"Benchmark", Fm->"ACIR Opcodes", Fm->"Brillig Opcodes", Fm->"Mean Time", Fm->"Min Time", Fm->"Max Time"]);
+
+        for entry in &self.benchmarks {
+            let previous = baseline.and_then(|baseline| baseline.find(entry.key()));
+            let acir_opcodes = with_change(
+                entry.acir_opcodes.to_string(),
+                previous.map(|previous| entry.acir_opcodes as i64 - previous.acir_opcodes as i64),
+            );
+            let brillig_opcodes = with_change(
+                entry.brillig_opcodes.to_string(),
+                previous
+                    .map(|previous| entry.brillig_opcodes as i64 - previous.brillig_opcodes as i64),
+            );
+            let mean_time = match previous {
+                Some(previous) if previous.mean_time_ns > 0 => format!(
+                    "{} ({:+.1}%)",
+                    format_nanos(entry.mean_time_ns),
+                    percentage_change(previous.mean_time_ns, entry.mean_time_ns)
+                ),
+                _ => format_nanos(entry.mean_time_ns),
+            };
+
+            bench_table.add_row(row![
+                Fm->format!("{}", entry.package_name),
+                Fc->format!("{}", entry.name),
+                Fc->acir_opcodes,
+                Fc->brillig_opcodes,
+                mean_time,
+                format_nanos(entry.min_time_ns),
+                format_nanos(entry.max_time_ns),
+            ]);
+        }
+
+        bench_table.printstd();
+    }
+}
+
+fn with_change(value: String, change: Option<i64>) -> String {
+    match change {
+        Some(change) if change != 0 => format!("{value} ({change:+})"),
+        _ => value,
+    }
+}
+
+fn percentage_change(previous: u64, current: u64) -> f64 {
+    if previous == 0 {
+        return 0.0;
+    }
+    (current as f64 - previous as f64) / previous as f64 * 100.0
+}
+
+fn format_nanos(nanos: u64) -> String {
+    format!("{:?}", Duration::from_nanos(nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BenchEntry, BenchReport};
+
+    fn entry(name: &str, acir_opcodes: usize, mean_time_ns: u64) -> BenchEntry {
+        BenchEntry {
+            package_name: "foo".to_string(),
+            name: name.to_string(),
+            acir_opcodes,
+            brillig_opcodes: 0,
+            mean_time_ns,
+            min_time_ns: mean_time_ns,
+            max_time_ns: mean_time_ns,
+        }
+    }
+
+    #[test]
+    fn reports_opcode_and_time_regressions() {
+        let baseline = BenchReport {
+            benchmarks: vec![entry("a", 10, 1000), entry("b", 10, 1000), entry("c", 10, 1000)],
+        };
+        let report = BenchReport {
+            benchmarks: vec![entry("a", 11, 1000), entry("b", 9, 1050), entry("c", 10, 1200)],
+        };
+
+        let regressions = report.regressions(Some(&baseline), 10.0);
+        assert_eq!(regressions.len(), 2);
+        assert!(regressions[0].contains("[foo] a: ACIR opcodes increased from 10 to 11"));
+        assert!(regressions[1].contains("[foo] c: mean execution time increased by 20.0%"));
+    }
+
+    #[test]
+    fn merging_keeps_benchmarks_which_were_not_run() {
+        let mut baseline =
+            BenchReport { benchmarks: vec![entry("a", 10, 1000), entry("b", 10, 1000)] };
+        baseline.merge(BenchReport { benchmarks: vec![entry("b", 5, 500)] });
+
+        assert_eq!(baseline.benchmarks, vec![entry("a", 10, 1000), entry("b", 5, 500)]);
+    }
+}
@@ -11,7 +11,7 @@ use noirc_driver::{CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING
 use noirc_frontend::graph::CrateName;
 
 use std::io::{BufReader, BufWriter, Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use dap::requests::Command;
 use dap::responses::ResponseBody;
@@ -19,13 +19,14 @@ use dap::server::Server;
 use dap::types::Capabilities;
 use serde_json::Value;
 
-use super::debug_cmd::compile_bin_package_for_debugging;
+use super::debug_cmd::{compile_bin_package_for_debugging, compile_test_fn_for_debugging};
 use super::fs::inputs::read_inputs_from_file;
 use crate::errors::CliError;
 
 use super::NargoConfig;
 
 use noir_debugger::errors::{DapError, LoadError};
+use noir_debugger::DebugTest;
 
 #[derive(Debug, Clone, Args)]
 pub(crate) struct DapCommand {
@@ -50,6 +51,9 @@ pub(crate) struct DapCommand {
 
     #[clap(long)]
     preflight_skip_instrumentation: bool,
+
+    #[clap(long)]
+    preflight_test_name: Option<String>,
 }
 
 fn parse_expression_width(input: &str) -> Result<ExpressionWidth, std::io::Error> {
@@ -95,6 +99,15 @@ fn workspace_not_found_error_msg(project_folder: &str, package: Option<&str>) ->
     }
 }
 
+/// A program compiled for debugging, along with what's needed to execute it
+struct LoadedProgram {
+    compiled_program: CompiledProgram,
+    initial_witness: WitnessMap<FieldElement>,
+    test: Option<DebugTest>,
+    root_path: PathBuf,
+    package_name: String,
+}
+
 fn load_and_compile_project(
     project_folder: &str,
     package: Option<&str>,
@@ -102,9 +115,21 @@ fn load_and_compile_project(
     expression_width: ExpressionWidth,
     acir_mode: bool,
     skip_instrumentation: bool,
-) -> Result<(CompiledProgram, WitnessMap<FieldElement>), LoadError> {
+    test_name: Option<&str>,
+) -> Result<LoadedProgram, LoadError> {
     let workspace = find_workspace(project_folder, package)
         .ok_or(LoadError::Generic(workspace_not_found_error_msg(project_folder, package)))?;
+
+    if let Some(test_name) = test_name {
+        return load_and_compile_test(
+            &workspace,
+            test_name,
+            expression_width,
+            acir_mode,
+            skip_instrumentation,
+        );
+    }
+
     let package = workspace
         .into_iter()
         .find(|p| p.is_binary())
@@ -131,7 +156,49 @@ fn load_and_compile_project(
         .encode(&inputs_map, None)
         .map_err(|_| LoadError::Generic("Failed to encode inputs".into()))?;
 
-    Ok((compiled_program, initial_witness))
+    Ok(LoadedProgram {
+        compiled_program,
+        initial_witness,
+        test: None,
+        root_path: workspace.root_dir.clone(),
+        package_name: package.name.to_string(),
+    })
+}
+
+fn load_and_compile_test(
+    workspace: &Workspace,
+    test_name: &str,
+    expression_width: ExpressionWidth,
+    acir_mode: bool,
+    skip_instrumentation: bool,
+) -> Result<LoadedProgram, LoadError> {
+    for package in workspace {
+        let compiled_test = compile_test_fn_for_debugging(
+            workspace,
+            package,
+            test_name,
+            acir_mode,
+            skip_instrumentation,
+            CompileOptions::default(),
+        )
+        .map_err(|error| LoadError::Generic(format!("Failed to compile test: {error}")))?;
+
+        if let Some((compiled_program, test)) = compiled_test {
+            let compiled_program =
+                nargo::ops::transform_program(compiled_program, expression_width);
+
+            // Tests take no arguments, so they start with an empty witness
+            return Ok(LoadedProgram {
+                compiled_program,
+                initial_witness: WitnessMap::new(),
+                test: Some(test),
+                root_path: workspace.root_dir.clone(),
+                package_name: package.name.to_string(),
+            });
+        }
+    }
+
+    Err(LoadError::Generic(format!("No test matching {test_name} found in workspace")))
 }
 
 fn loop_uninitialized_dap<R: Read, W: Write>(
@@ -147,8 +214,14 @@ fn loop_uninitialized_dap<R: Read, W: Write>(
         match req.command {
             Command::Initialize(_) => {
                 let rsp = req.success(ResponseBody::Initialize(Capabilities {
+                    supports_conditional_breakpoints: Some(true),
+                    supports_data_breakpoints: Some(true),
                     supports_disassemble_request: Some(true),
+                    supports_evaluate_for_hovers: Some(true),
+                    supports_hit_conditional_breakpoints: Some(true),
                     supports_instruction_breakpoints: Some(true),
+                    supports_log_points: Some(true),
+                    supports_step_back: Some(true),
                     supports_stepping_granularity: Some(true),
                     ..Default::default()
                 }));
@@ -179,10 +252,29 @@ fn loop_uninitialized_dap<R: Read, W: Write>(
                     .get("skipInstrumentation")
                     .and_then(|v| v.as_bool())
                     .unwrap_or(generate_acir);
+                let test_name = additional_data.get("testName").and_then(|v| v.as_str());
+                let oracle_resolver = additional_data
+                    .get("oracleResolver")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string);
+                let debug_on_failure = additional_data
+                    .get("debugOnFailure")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
 
                 eprintln!("Project folder: {}", project_folder);
                 eprintln!("Package: {}", package.unwrap_or("(default)"));
-                eprintln!("Prover name: {}", prover_name);
+                if let Some(test_name) = test_name {
+                    eprintln!("Test name: {}", test_name);
+                } else {
+                    eprintln!("Prover name: {}", prover_name);
+                }
+                if let Some(oracle_resolver) = &oracle_resolver {
+                    eprintln!("Oracle resolver: {}", oracle_resolver);
+                }
+                if debug_on_failure {
+                    eprintln!("Debugging on failure");
+                }
 
                 match load_and_compile_project(
                     project_folder,
@@ -191,15 +283,21 @@ fn loop_uninitialized_dap<R: Read, W: Write>(
                     expression_width,
                     generate_acir,
                     skip_instrumentation,
+                    test_name,
                 ) {
-                    Ok((compiled_program, initial_witness)) => {
+                    Ok(program) => {
                         server.respond(req.ack()?)?;
 
                         noir_debugger::run_dap_loop(
                             server,
                             &Bn254BlackBoxSolver,
-                            compiled_program,
-                            initial_witness,
+                            program.compiled_program,
+                            program.initial_witness,
+                            program.test,
+                            oracle_resolver.as_deref(),
+                            Some(program.root_path),
+                            Some(program.package_name),
+                            debug_on_failure,
                         )?;
                         break;
                     }
@@ -243,6 +341,7 @@ fn run_preflight_check(
         expression_width,
         args.preflight_generate_acir,
         args.preflight_skip_instrumentation,
+        args.preflight_test_name.as_deref(),
     )?;
 
     Ok(())
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use acvm::acir::native_types::WitnessStack;
+use acvm::acir::native_types::{WitnessMap, WitnessStack};
 use acvm::FieldElement;
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
@@ -8,19 +8,26 @@ use clap::Args;
 use fm::FileManager;
 use nargo::constants::PROVER_INPUT_FILE;
 use nargo::errors::CompileError;
-use nargo::ops::{compile_program, compile_program_with_debug_instrumenter, report_errors};
+use nargo::ops::{
+    compile_program, compile_program_with_debug_instrumenter, report_errors,
+    test_status_program_compile_pass, TestStatus,
+};
 use nargo::package::{CrateName, Package};
 use nargo::workspace::Workspace;
-use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
+use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all, prepare_package};
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noir_debugger::DebugTest;
 use noirc_abi::input_parser::{Format, InputValue};
 use noirc_abi::InputMap;
 use noirc_driver::{
-    file_manager_with_stdlib, CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
+    compile_no_check, file_manager_with_stdlib, link_to_debug_crate, CompileOptions,
+    CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
 };
 use noirc_frontend::debug::DebugInstrumenter;
-use noirc_frontend::hir::ParsedFiles;
+use noirc_frontend::graph::CrateId;
+use noirc_frontend::hir::{Context, FunctionNameMatch, ParsedFiles};
 
+use super::check_cmd::check_crate_and_report_errors;
 use super::compile_cmd::get_target_width;
 use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
 use super::NargoConfig;
@@ -50,6 +57,14 @@ pub(crate) struct DebugCommand {
     /// Disable vars debug instrumentation (enabled by default)
     #[clap(long)]
     skip_instrumentation: Option<bool>,
+
+    /// The name of a `#[test]` function to debug instead of the package's `main`
+    #[clap(long)]
+    test_name: Option<String>,
+
+    /// JSON RPC url to solve oracle calls
+    #[clap(long)]
+    oracle_resolver: Option<String>,
 }
 
 pub(crate) fn run(args: DebugCommand, config: NargoConfig) -> Result<(), CliError> {
@@ -65,6 +80,17 @@ pub(crate) fn run(args: DebugCommand, config: NargoConfig) -> Result<(), CliErro
     )?;
     let target_dir = &workspace.target_directory_path();
 
+    if let Some(test_name) = args.test_name {
+        return debug_test(
+            &workspace,
+            &test_name,
+            acir_mode,
+            skip_instrumentation,
+            args.compile_options,
+            args.oracle_resolver.as_deref(),
+        );
+    }
+
     let Some(package) = workspace.into_iter().find(|p| p.is_binary()) else {
         println!(
             "No matching binary packages found in workspace. Only binary packages can be debugged."
@@ -85,7 +111,15 @@ pub(crate) fn run(args: DebugCommand, config: NargoConfig) -> Result<(), CliErro
 
     let compiled_program = nargo::ops::transform_program(compiled_program, target_width);
 
-    run_async(package, compiled_program, &args.prover_name, &args.witness_name, target_dir)
+    run_async(
+        package,
+        compiled_program,
+        &args.prover_name,
+        &args.witness_name,
+        target_dir,
+        args.oracle_resolver.as_deref(),
+        workspace.root_dir.clone(),
+    )
 }
 
 pub(crate) fn compile_bin_package_for_debugging(
@@ -137,6 +171,101 @@ pub(crate) fn compile_bin_package_for_debugging(
     )
 }
 
+/// Compiles the test function matching `test_name` in `package` as a standalone program,
+/// instrumented for debugging like `main` is. Returns `None` if the package has no such test.
+pub(crate) fn compile_test_fn_for_debugging(
+    workspace: &Workspace,
+    package: &Package,
+    test_name: &str,
+    acir_mode: bool,
+    skip_instrumentation: bool,
+    compile_options: CompileOptions,
+) -> Result<Option<(CompiledProgram, DebugTest)>, CliError> {
+    let mut workspace_file_manager = file_manager_with_stdlib(std::path::Path::new(""));
+    insert_all_files_for_workspace_into_file_manager(workspace, &mut workspace_file_manager);
+    let mut parsed_files = parse_all(&workspace_file_manager);
+
+    let compile_options = CompileOptions {
+        instrument_debug: !skip_instrumentation,
+        force_brillig: !acir_mode,
+        ..compile_options
+    };
+
+    let debug_instrumenter = if !skip_instrumentation {
+        instrument_package_files(&mut parsed_files, &workspace_file_manager, package)
+    } else {
+        DebugInstrumenter::default()
+    };
+
+    let (mut context, crate_id) = prepare_package(&workspace_file_manager, &parsed_files, package);
+    link_to_debug_crate(&mut context, crate_id);
+    context.debug_instrumenter = debug_instrumenter;
+    context.package_build_path = workspace.package_build_path(package);
+    check_crate_and_report_errors(&mut context, crate_id, &compile_options)?;
+
+    let Some(test) = find_test_function(&context, &crate_id, test_name)? else {
+        return Ok(None);
+    };
+
+    let test_function_has_arguments = !context
+        .def_interner
+        .function_meta(&test.function.get_id())
+        .function_signature()
+        .0
+        .is_empty();
+    if test_function_has_arguments {
+        return Err(CliError::Generic(format!(
+            "Test {} takes arguments, only tests without arguments can be debugged",
+            test.name
+        )));
+    }
+
+    let compilation_result =
+        compile_no_check(&mut context, &compile_options, test.function.get_id(), None, false)
+            .map(|program| (program, Vec::new()))
+            .map_err(|error| vec![error.into()]);
+    let compiled_program = report_errors(
+        compilation_result,
+        &workspace_file_manager,
+        compile_options.deny_warnings,
+        compile_options.silence_warnings,
+    )?;
+
+    Ok(Some((compiled_program, test)))
+}
+
+/// Finds the test function to debug: the one whose fully qualified name is `test_name`, or
+/// otherwise the only one whose name contains it.
+fn find_test_function(
+    context: &Context,
+    crate_id: &CrateId,
+    test_name: &str,
+) -> Result<Option<DebugTest>, CliError> {
+    let mut test_functions = context
+        .get_all_test_functions_in_crate_matching(crate_id, FunctionNameMatch::Contains(test_name));
+
+    if let Some(index) = test_functions.iter().position(|(name, _)| name == test_name) {
+        let (name, function) = test_functions.swap_remove(index);
+        return Ok(Some(DebugTest { name, function }));
+    }
+
+    match test_functions.len() {
+        0 => Ok(None),
+        1 => {
+            let (name, function) = test_functions.remove(0);
+            Ok(Some(DebugTest { name, function }))
+        }
+        _ => {
+            let names: Vec<_> = test_functions.into_iter().map(|(name, _)| name).collect();
+            Err(CliError::Generic(format!(
+                "Found {} tests containing '{test_name}', please select one of them: {}",
+                names.len(),
+                names.join(", ")
+            )))
+        }
+    }
+}
+
 /// Add debugging instrumentation to all parsed files belonging to the package
 /// being compiled
 fn instrument_package_files(
@@ -172,14 +301,21 @@ fn run_async(
     prover_name: &str,
     witness_name: &Option<String>,
     target_dir: &PathBuf,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: PathBuf,
 ) -> Result<(), CliError> {
     use tokio::runtime::Builder;
     let runtime = Builder::new_current_thread().enable_all().build().unwrap();
 
     runtime.block_on(async {
         println!("[{}] Starting debugger", package.name);
-        let (return_value, witness_stack) =
-            debug_program_and_decode(program, package, prover_name)?;
+        let (return_value, witness_stack) = debug_program_and_decode(
+            program,
+            package,
+            prover_name,
+            foreign_call_resolver_url,
+            root_path,
+        )?;
 
         if let Some(solved_witness_stack) = witness_stack {
             println!("[{}] Circuit witness successfully solved", package.name);
@@ -202,16 +338,209 @@ fn run_async(
     })
 }
 
+fn debug_test(
+    workspace: &Workspace,
+    test_name: &str,
+    acir_mode: bool,
+    skip_instrumentation: bool,
+    compile_options: CompileOptions,
+    foreign_call_resolver_url: Option<&str>,
+) -> Result<(), CliError> {
+    for package in workspace {
+        let Some((compiled_program, test)) = compile_test_fn_for_debugging(
+            workspace,
+            package,
+            test_name,
+            acir_mode,
+            skip_instrumentation,
+            compile_options.clone(),
+        )?
+        else {
+            continue;
+        };
+
+        let target_width =
+            get_target_width(package.expression_width, compile_options.expression_width);
+        let compiled_program = nargo::ops::transform_program(compiled_program, target_width);
+
+        return run_test_async(
+            package,
+            compiled_program,
+            test,
+            foreign_call_resolver_url,
+            workspace.root_dir.clone(),
+        );
+    }
+
+    Err(CliError::Generic(format!("Found 0 tests matching input '{test_name}'.")))
+}
+
+fn run_test_async(
+    package: &Package,
+    program: CompiledProgram,
+    test: DebugTest,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: PathBuf,
+) -> Result<(), CliError> {
+    use tokio::runtime::Builder;
+    let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+
+    runtime.block_on(async {
+        println!("[{}] Starting debugger for test {}", package.name, test.name);
+        let abi = program.abi.clone();
+        let debug = program.debug.clone();
+
+        // Tests take no arguments, so they start with an empty witness
+        let circuit_execution = match noir_debugger::run_repl_session(
+            &Bn254BlackBoxSolver,
+            program,
+            WitnessMap::new(),
+            foreign_call_resolver_url,
+            Some(root_path),
+            Some(package.name.to_string()),
+        ) {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => {
+                println!("Debugger execution halted.");
+                return Ok(());
+            }
+            Err(error) => Err(error),
+        };
+
+        match test_status_program_compile_pass(&test.function, &abi, &debug, circuit_execution) {
+            TestStatus::Pass => {
+                println!("[{}] Test {} passed", package.name, test.name);
+                Ok(())
+            }
+            TestStatus::Fail { message, .. } => Err(CliError::Generic(format!(
+                "[{}] Test {} failed: {}",
+                package.name,
+                test.name,
+                message.trim()
+            ))),
+            TestStatus::CompileError(diagnostic) => Err(CliError::Generic(format!(
+                "[{}] Test {} failed to compile: {}",
+                package.name, test.name, diagnostic.diagnostic.message
+            ))),
+        }
+    })
+}
+
+/// Compiles `package` for debugging and executes it with the inputs in `prover_name` until it
+/// fails, starting the REPL at the failing opcode.
+pub(crate) fn debug_program_at_failure(
+    workspace: &Workspace,
+    package: &Package,
+    prover_name: &str,
+    compile_options: &CompileOptions,
+    foreign_call_resolver_url: Option<&str>,
+) -> Result<(), CliError> {
+    // The program is compiled like it was when it failed, so that it fails at the same opcode
+    let acir_mode = !compile_options.force_brillig;
+    let skip_instrumentation = false;
+    let compiled_program = compile_bin_package_for_debugging(
+        workspace,
+        package,
+        acir_mode,
+        skip_instrumentation,
+        compile_options.clone(),
+    )?;
+    let target_width = get_target_width(package.expression_width, compile_options.expression_width);
+    let compiled_program = nargo::ops::transform_program(compiled_program, target_width);
+
+    let (inputs_map, _) =
+        read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &compiled_program.abi)?;
+    let initial_witness = compiled_program.abi.encode(&inputs_map, None)?;
+
+    println!("[{}] Starting debugger at the failure", package.name);
+    run_at_failure_async(
+        package,
+        compiled_program,
+        initial_witness,
+        foreign_call_resolver_url,
+        workspace.root_dir.clone(),
+    )
+}
+
+/// Compiles the test function `test_name` of `package` for debugging and executes it until it
+/// fails, starting the REPL at the failing opcode.
+pub(crate) fn debug_test_at_failure(
+    workspace: &Workspace,
+    package: &Package,
+    test_name: &str,
+    compile_options: &CompileOptions,
+    foreign_call_resolver_url: Option<&str>,
+) -> Result<(), CliError> {
+    // The test is compiled like it was when it failed, so that it fails at the same opcode
+    let acir_mode = !compile_options.force_brillig;
+    let skip_instrumentation = false;
+    let Some((compiled_program, test)) = compile_test_fn_for_debugging(
+        workspace,
+        package,
+        test_name,
+        acir_mode,
+        skip_instrumentation,
+        compile_options.clone(),
+    )?
+    else {
+        return Err(CliError::Generic(format!("Found 0 tests matching input '{test_name}'.")));
+    };
+    let target_width = get_target_width(package.expression_width, compile_options.expression_width);
+    let compiled_program = nargo::ops::transform_program(compiled_program, target_width);
+
+    println!("[{}] Starting debugger at the failure of test {}", package.name, test.name);
+    // Tests take no arguments, so they start with an empty witness
+    run_at_failure_async(
+        package,
+        compiled_program,
+        WitnessMap::new(),
+        foreign_call_resolver_url,
+        workspace.root_dir.clone(),
+    )
+}
+
+fn run_at_failure_async(
+    package: &Package,
+    program: CompiledProgram,
+    initial_witness: WitnessMap<FieldElement>,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: PathBuf,
+) -> Result<(), CliError> {
+    use tokio::runtime::Builder;
+    let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+
+    runtime.block_on(async {
+        // The failure was already reported, so the result of the debugging session is ignored
+        let _ = noir_debugger::run_repl_session_at_failure(
+            &Bn254BlackBoxSolver,
+            program,
+            initial_witness,
+            foreign_call_resolver_url,
+            Some(root_path),
+            Some(package.name.to_string()),
+        );
+        Ok(())
+    })
+}
+
 fn debug_program_and_decode(
     program: CompiledProgram,
     package: &Package,
     prover_name: &str,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: PathBuf,
 ) -> Result<(Option<InputValue>, Option<WitnessStack<FieldElement>>), CliError> {
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
     let program_abi = program.abi.clone();
-    let witness_stack = debug_program(program, &inputs_map)?;
+    let witness_stack = debug_program(
+        program,
+        &inputs_map,
+        foreign_call_resolver_url,
+        Some(root_path),
+        Some(package.name.to_string()),
+    )?;
 
     match witness_stack {
         Some(witness_stack) => {
@@ -229,9 +558,19 @@ fn debug_program_and_decode(
 pub(crate) fn debug_program(
     compiled_program: CompiledProgram,
     inputs_map: &InputMap,
+    foreign_call_resolver_url: Option<&str>,
+    root_path: Option<PathBuf>,
+    package_name: Option<String>,
 ) -> Result<Option<WitnessStack<FieldElement>>, CliError> {
     let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
 
-    noir_debugger::run_repl_session(&Bn254BlackBoxSolver, compiled_program, initial_witness)
-        .map_err(CliError::from)
+    noir_debugger::run_repl_session(
+        &Bn254BlackBoxSolver,
+        compiled_program,
+        initial_witness,
+        foreign_call_resolver_url,
+        root_path,
+        package_name,
+    )
+    .map_err(CliError::from)
 }
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use clap::Args;
+use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all, prepare_package};
+use nargo_doc::{crates_page, generate_docs, CrateDocs, CrateNames, DocOptions};
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_frontend::graph::CrateName;
+
+use crate::errors::CliError;
+
+use super::check_cmd::check_crate_and_report_errors;
+use super::fs::{create_named_dir, write_to_file};
+use super::NargoConfig;
+
+/// Generate documentation for a package and its dependencies
+#[derive(Debug, Clone, Args)]
+pub(crate) struct DocCommand {
+    /// The name of the package to document
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Document all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    /// Also document items which are not `pub`
+    #[arg(long)]
+    document_private_items: bool,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(args: DocCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    let mut workspace_file_manager = workspace.new_file_manager();
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let parsed_files = parse_all(&workspace_file_manager);
+
+    let options = DocOptions { document_private_items: args.document_private_items };
+
+    // Packages of a workspace may share dependencies, which only need to be written once.
+    // Crates are keyed by their root file since different crates may have the same name.
+    let mut crate_names = CrateNames::default();
+    let mut crates: BTreeMap<PathBuf, CrateDocs> = BTreeMap::new();
+    for package in &workspace {
+        let (mut context, crate_id) =
+            prepare_package(&workspace_file_manager, &parsed_files, package);
+        check_crate_and_report_errors(&mut context, crate_id, &args.compile_options)?;
+
+        let package_name = package.name.to_string();
+        let crate_docs =
+            generate_docs(&context, crate_id, &package_name, &options, &mut crate_names);
+        for crate_docs in crate_docs {
+            crates.entry(crate_docs.root_file.clone()).or_insert(crate_docs);
+        }
+    }
+
+    let doc_dir = workspace.target_directory_path().join("doc");
+    let mut names: Vec<&str> = crates.values().map(|docs| docs.name.as_str()).collect();
+    names.sort_unstable();
+    let index_page = crates_page(names);
+    let pages = crates.values().flat_map(|docs| &docs.pages);
+    for page in pages.chain(std::iter::once(&index_page)) {
+        let path = doc_dir.join(&page.path);
+        if let Some(dir) = path.parent() {
+            create_named_dir(dir, "doc");
+        }
+        write_to_file(page.contents.as_bytes(), &path);
+    }
+
+    println!("Documentation generated at {}", doc_dir.join(&index_page.path).display());
+    Ok(())
+}
@@ -9,6 +9,7 @@ use nargo::constants::PROVER_INPUT_FILE;
 use nargo::errors::try_to_diagnose_runtime_error;
 use nargo::ops::DefaultForeignCallExecutor;
 use nargo::package::{CrateName, Package};
+use nargo::NargoError;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::input_parser::{Format, InputValue};
 use noirc_abi::InputMap;
@@ -16,6 +17,7 @@ use noirc_artifacts::debug::DebugArtifact;
 use noirc_driver::{CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING};
 
 use super::compile_cmd::compile_workspace_full;
+use super::debug_cmd::debug_program_at_failure;
 use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
 use super::NargoConfig;
 use crate::cli::fs::program::read_program_from_file;
@@ -48,6 +50,10 @@ pub(crate) struct ExecuteCommand {
     /// JSON RPC url to solve oracle calls
     #[clap(long)]
     oracle_resolver: Option<String>,
+
+    /// If the execution fails, start the debugger at the failing opcode
+    #[clap(long)]
+    debug_on_failure: bool,
 }
 
 pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliError> {
@@ -71,14 +77,30 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
         let program: CompiledProgram =
             read_program_from_file(program_artifact_path.clone())?.into();
 
-        let (return_value, witness_stack) = execute_program_and_decode(
+        let execution_result = execute_program_and_decode(
             program,
             package,
             &args.prover_name,
             args.oracle_resolver.as_deref(),
             Some(workspace.root_dir.clone()),
             Some(package.name.to_string()),
-        )?;
+        );
+        let (return_value, witness_stack) = match execution_result {
+            Ok(result) => result,
+            Err(CliError::NargoError(error @ NargoError::ExecutionError(_)))
+                if args.debug_on_failure =>
+            {
+                debug_program_at_failure(
+                    &workspace,
+                    package,
+                    &args.prover_name,
+                    &args.compile_options,
+                    args.oracle_resolver.as_deref(),
+                )?;
+                return Err(CliError::NargoError(error));
+            }
+            Err(error) => return Err(error),
+        };
 
         println!("[{}] Circuit witness successfully solved", package.name);
         if let Some(return_value) = return_value {
@@ -8,10 +8,12 @@ use color_eyre::eyre;
 
 mod fs;
 
+mod bench_cmd;
 mod check_cmd;
 mod compile_cmd;
 mod dap_cmd;
 mod debug_cmd;
+mod doc_cmd;
 mod execute_cmd;
 mod export_cmd;
 mod fmt_cmd;
@@ -65,7 +67,9 @@ enum NargoCommand {
     #[command(hide = true)] // Hidden while the feature is being built out
     Export(export_cmd::ExportCommand),
     Debug(debug_cmd::DebugCommand),
+    Doc(doc_cmd::DocCommand),
     Test(test_cmd::TestCommand),
+    Bench(bench_cmd::BenchCommand),
     Info(info_cmd::InfoCommand),
     Lsp(lsp_cmd::LspCommand),
     #[command(hide = true)]
@@ -90,7 +94,9 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         | NargoCommand::Execute(..)
         | NargoCommand::Export(..)
         | NargoCommand::Debug(..)
+        | NargoCommand::Doc(..)
         | NargoCommand::Test(..)
+        | NargoCommand::Bench(..)
         | NargoCommand::Info(..) => {
             config.program_dir = find_package_root(&config.program_dir)?;
         }
@@ -107,9 +113,11 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::Check(args) => check_cmd::run(args, config),
         NargoCommand::Compile(args) => compile_cmd::run(args, config),
         NargoCommand::Debug(args) => debug_cmd::run(args, config),
+        NargoCommand::Doc(args) => doc_cmd::run(args, config),
         NargoCommand::Execute(args) => execute_cmd::run(args, config),
         NargoCommand::Export(args) => export_cmd::run(args, config),
         NargoCommand::Test(args) => test_cmd::run(args, config),
+        NargoCommand::Bench(args) => bench_cmd::run(args, config),
         NargoCommand::Info(args) => info_cmd::run(args, config),
         NargoCommand::Lsp(args) => lsp_cmd::run(args, config),
         NargoCommand::Dap(args) => dap_cmd::run(args, config),
@@ -9,6 +9,7 @@ use nargo::{
     ops::TestStatus,
     package::{CrateName, Package},
     parse_all, prepare_package,
+    workspace::Workspace,
 };
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::{check_crate, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
@@ -16,7 +17,10 @@ use noirc_frontend::hir::{FunctionNameMatch, ParsedFiles};
 use rayon::prelude::{IntoParallelIterator, ParallelBridge, ParallelIterator};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
-use crate::{cli::check_cmd::check_crate_and_report_errors, errors::CliError};
+use crate::{
+    cli::{check_cmd::check_crate_and_report_errors, debug_cmd::debug_test_at_failure},
+    errors::CliError,
+};
 
 use super::NargoConfig;
 
@@ -49,6 +53,10 @@ pub(crate) struct TestCommand {
     /// JSON RPC url to solve oracle calls
     #[clap(long)]
     oracle_resolver: Option<String>,
+
+    /// Start the debugger at the failing opcode of each failed test, once all tests ran
+    #[clap(long)]
+    debug_on_failure: bool,
 }
 
 pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError> {
@@ -80,7 +88,7 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
     // Configure a thread pool with a larger stack size to prevent overflowing stack in large programs.
     // Default is 2MB.
     let pool = rayon::ThreadPoolBuilder::new().stack_size(4 * 1024 * 1024).build().unwrap();
-    let test_reports: Vec<Vec<(String, TestStatus)>> = pool.install(|| {
+    let test_reports: Vec<(&Package, Vec<(String, TestStatus)>)> = pool.install(|| {
         workspace
             .into_iter()
             .par_bridge()
@@ -94,12 +102,25 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
                     args.oracle_resolver.as_deref(),
                     Some(workspace.root_dir.clone()),
                     Some(package.name.to_string()),
+                    workspace.target_directory_path().join("fuzz").join(package.name.to_string()),
                     &args.compile_options,
                 )
+                .map(|test_report| (package, test_report))
             })
             .collect::<Result<_, _>>()
     })?;
-    let test_report: Vec<(String, TestStatus)> = test_reports.into_iter().flatten().collect();
+
+    if args.debug_on_failure {
+        debug_failed_tests(
+            &workspace,
+            &test_reports,
+            &args.compile_options,
+            args.oracle_resolver.as_deref(),
+        );
+    }
+
+    let test_report: Vec<(String, TestStatus)> =
+        test_reports.into_iter().flat_map(|(_, test_report)| test_report).collect();
 
     if test_report.is_empty() {
         match &pattern {
@@ -123,6 +144,32 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
     }
 }
 
+/// Starts the debugger at the failure of each failed test, one after the other
+fn debug_failed_tests(
+    workspace: &Workspace,
+    test_reports: &[(&Package, Vec<(String, TestStatus)>)],
+    compile_options: &CompileOptions,
+    foreign_call_resolver_url: Option<&str>,
+) {
+    for (package, test_report) in test_reports {
+        for (test_name, test_status) in test_report {
+            if !matches!(test_status, TestStatus::Fail { .. }) {
+                continue;
+            }
+            // Tests that can't be debugged, like the ones taking arguments, are skipped
+            if let Err(error) = debug_test_at_failure(
+                workspace,
+                package,
+                test_name,
+                compile_options,
+                foreign_call_resolver_url,
+            ) {
+                eprintln!("[{}] Could not debug test {test_name}: {error}", package.name);
+            }
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_tests<S: BlackBoxFunctionSolver<FieldElement> + Default>(
     file_manager: &FileManager,
@@ -133,6 +180,7 @@ fn run_tests<S: BlackBoxFunctionSolver<FieldElement> + Default>(
     foreign_call_resolver_url: Option<&str>,
     root_path: Option<PathBuf>,
     package_name: Option<String>,
+    fuzzing_corpus_dir: PathBuf,
     compile_options: &CompileOptions,
 ) -> Result<Vec<(String, TestStatus)>, CliError> {
     let test_functions =
@@ -155,6 +203,8 @@ fn run_tests<S: BlackBoxFunctionSolver<FieldElement> + Default>(
                 foreign_call_resolver_url,
                 root_path.clone(),
                 package_name.clone(),
+                // Test names contain `::`, which isn't allowed in paths on every platform
+                fuzzing_corpus_dir.join(test_name.replace("::", "__")),
                 compile_options,
             );
 
@@ -176,6 +226,7 @@ fn run_test<S: BlackBoxFunctionSolver<FieldElement> + Default>(
     foreign_call_resolver_url: Option<&str>,
     root_path: Option<PathBuf>,
     package_name: Option<String>,
+    fuzzing_corpus_dir: PathBuf,
     compile_options: &CompileOptions,
 ) -> TestStatus {
     // This is really hacky but we can't share `Context` or `S` across threads.
@@ -199,6 +250,7 @@ fn run_test<S: BlackBoxFunctionSolver<FieldElement> + Default>(
         foreign_call_resolver_url,
         root_path,
         package_name,
+        Some(fuzzing_corpus_dir),
         compile_options,
     )
 }
@@ -0,0 +1,60 @@
+//! Checks that coverage-guided fuzzing reaches branches of unconstrained code which
+//! random inputs don't, by running `nargo test` on the same fuzz test with and without it.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+
+/// Each byte of the magic value is checked by its own branch, so inputs matching a longer prefix
+/// of it reach new branches and are kept to be mutated further when fuzzing is coverage-guided.
+const GUARDED_BRANCH_SOURCE: &str = r#"
+unconstrained fn check_magic(bytes: [u8; 4]) {
+    if bytes[0] == 0x4e {
+        if bytes[1] == 0x6f {
+            if bytes[2] == 0x69 {
+                if bytes[3] == 0x72 {
+                    assert(false, "reached the guarded branch");
+                }
+            }
+        }
+    }
+}
+
+#[test(seed = 1, cases = 5000)]
+fn random_inputs(bytes: [u8; 4]) {
+    unsafe {
+        check_magic(bytes)
+    }
+}
+
+#[test(seed = 1, cases = 5000, coverage_guided)]
+fn coverage_guided_inputs(bytes: [u8; 4]) {
+    unsafe {
+        check_magic(bytes)
+    }
+}
+"#;
+
+#[test]
+fn coverage_guided_fuzzing_reaches_guarded_branch() {
+    let project_dir = assert_fs::TempDir::new().unwrap();
+    project_dir
+        .child("Nargo.toml")
+        .write_str("[package]\nname = \"guarded_branch\"\ntype = \"bin\"\nauthors = [\"\"]\n")
+        .unwrap();
+    project_dir.child("src/main.nr").write_str(GUARDED_BRANCH_SOURCE).unwrap();
+
+    // Random inputs never get past the guards, so the test passes
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.arg("test").arg("--program-dir").arg(project_dir.path());
+    cmd.arg("random_inputs").arg("--exact");
+    cmd.assert().success().stderr(predicate::str::contains("1 test passed"));
+
+    // Coverage-guided inputs reach the failing assertion behind them
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.arg("test").arg("--program-dir").arg(project_dir.path());
+    cmd.arg("coverage_guided_inputs").arg("--exact");
+    cmd.assert().failure().stderr(predicate::str::contains("Failed assertion"));
+}
@@ -5,7 +5,10 @@ use fm::FileManager;
 use noirc_driver::{check_crate, file_manager_with_stdlib, CompileOptions};
 use noirc_frontend::hir::FunctionNameMatch;
 use std::io::Write;
-use std::{collections::BTreeMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
 
 use nargo::{
     ops::{report_errors, run_test, TestStatus},
@@ -62,6 +65,7 @@ fn run_stdlib_tests(force_brillig: bool, inliner_aggressiveness: i64) {
         entry_path: PathBuf::from("main.nr"),
         name: "stdlib".parse().unwrap(),
         dependencies: BTreeMap::new(),
+        enabled_features: BTreeSet::new(),
         expression_width: None,
     };
 
@@ -90,6 +94,7 @@ fn run_stdlib_tests(force_brillig: bool, inliner_aggressiveness: i64) {
                 None,
                 Some(dummy_package.root_dir.clone()),
                 Some(dummy_package.name.to_string()),
+                None,
                 &CompileOptions { force_brillig, inliner_aggressiveness, ..Default::default() },
             );
             (test_name, status)
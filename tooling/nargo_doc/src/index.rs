@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use noirc_frontend::{
+    ast::ItemVisibility,
+    graph::CrateId,
+    hir::{
+        def_map::{ModuleDefId, ModuleId},
+        Context,
+    },
+    node_interner::{FuncId, GlobalId, StructId, TraitId, TypeAliasId},
+};
+
+use crate::DocOptions;
+
+/// A module which gets its own documentation page, along with the items documented in it.
+pub(crate) struct ModuleEntry {
+    pub(crate) id: ModuleId,
+    /// The name of the crate followed by the names of all modules leading to this one.
+    pub(crate) path: Vec<String>,
+    pub(crate) modules: Vec<(String, ModuleId)>,
+    pub(crate) structs: Vec<(StructId, ItemVisibility)>,
+    pub(crate) traits: Vec<(TraitId, ItemVisibility)>,
+    pub(crate) functions: Vec<FuncId>,
+    pub(crate) globals: Vec<(GlobalId, ItemVisibility)>,
+    pub(crate) type_aliases: Vec<(TypeAliasId, ItemVisibility)>,
+}
+
+/// All modules and items which are documented, used to know which page to link to
+/// when an item is referenced from another page.
+pub(crate) struct DocIndex {
+    pub(crate) crates: Vec<(CrateId, String)>,
+    pub(crate) modules: Vec<ModuleEntry>,
+    pub(crate) document_private_items: bool,
+    module_paths: HashMap<ModuleId, Vec<String>>,
+    structs: HashMap<StructId, ModuleId>,
+    traits: HashMap<TraitId, ModuleId>,
+    type_aliases: HashMap<TypeAliasId, ModuleId>,
+}
+
+impl DocIndex {
+    pub(crate) fn new(
+        context: &Context,
+        crates: Vec<(CrateId, String)>,
+        options: &DocOptions,
+    ) -> Self {
+        let mut index = DocIndex {
+            crates: Vec::new(),
+            modules: Vec::new(),
+            document_private_items: options.document_private_items,
+            module_paths: HashMap::new(),
+            structs: HashMap::new(),
+            traits: HashMap::new(),
+            type_aliases: HashMap::new(),
+        };
+
+        for (crate_id, crate_name) in &crates {
+            let Some(def_map) = context.def_map(crate_id) else {
+                continue;
+            };
+            let root = ModuleId { krate: *crate_id, local_id: def_map.root() };
+            index.add_module(context, root, vec![crate_name.clone()]);
+        }
+        index.crates = crates;
+
+        index
+    }
+
+    /// Returns whether an item with the given visibility should be documented.
+    pub(crate) fn is_documented(&self, visibility: ItemVisibility) -> bool {
+        self.document_private_items || visibility == ItemVisibility::Public
+    }
+
+    pub(crate) fn module_path(&self, id: ModuleId) -> Option<&[String]> {
+        self.module_paths.get(&id).map(Vec::as_slice)
+    }
+
+    pub(crate) fn struct_module(&self, id: StructId) -> Option<ModuleId> {
+        self.structs.get(&id).copied()
+    }
+
+    pub(crate) fn trait_module(&self, id: TraitId) -> Option<ModuleId> {
+        self.traits.get(&id).copied()
+    }
+
+    pub(crate) fn type_alias_module(&self, id: TypeAliasId) -> Option<ModuleId> {
+        self.type_aliases.get(&id).copied()
+    }
+
+    fn add_module(&mut self, context: &Context, id: ModuleId, path: Vec<String>) {
+        let module = context.module(id);
+        let interner = &context.def_interner;
+
+        // Only look at the items declared in this module, not the ones it imports. Struct
+        // and trait modules aren't declared in their parent so they are skipped here as well.
+        let definitions = module.definitions();
+        let mut items: Vec<(String, ModuleDefId, ItemVisibility)> = definitions
+            .types()
+            .iter()
+            .chain(definitions.values())
+            .filter_map(|(name, scope)| {
+                let (item, visibility, _) = scope.get(&None)?;
+                Some((name.to_string(), *item, *visibility))
+            })
+            .collect();
+        items.sort_by(|(name1, ..), (name2, ..)| name1.cmp(name2));
+
+        let mut entry = ModuleEntry {
+            id,
+            path: path.clone(),
+            modules: Vec::new(),
+            structs: Vec::new(),
+            traits: Vec::new(),
+            functions: Vec::new(),
+            globals: Vec::new(),
+            type_aliases: Vec::new(),
+        };
+
+        for (name, item, visibility) in items {
+            // Functions may be less visible than the name they were declared with
+            let visibility = match item {
+                ModuleDefId::FunctionId(func_id) => {
+                    interner.function_modifiers(&func_id).visibility
+                }
+                _ => visibility,
+            };
+            if !self.is_documented(visibility) {
+                continue;
+            }
+
+            match item {
+                ModuleDefId::ModuleId(module_id) => entry.modules.push((name, module_id)),
+                ModuleDefId::FunctionId(func_id) => entry.functions.push(func_id),
+                ModuleDefId::TypeId(struct_id) => {
+                    self.structs.insert(struct_id, id);
+                    entry.structs.push((struct_id, visibility));
+                }
+                ModuleDefId::TypeAliasId(type_alias_id) => {
+                    self.type_aliases.insert(type_alias_id, id);
+                    entry.type_aliases.push((type_alias_id, visibility));
+                }
+                ModuleDefId::TraitId(trait_id) => {
+                    self.traits.insert(trait_id, id);
+                    entry.traits.push((trait_id, visibility));
+                }
+                ModuleDefId::GlobalId(global_id) => entry.globals.push((global_id, visibility)),
+            }
+        }
+
+        let children = entry.modules.clone();
+        self.module_paths.insert(id, path.clone());
+        self.modules.push(entry);
+
+        for (name, child_id) in children {
+            let mut child_path = path.clone();
+            child_path.push(name);
+            self.add_module(context, child_id, child_path);
+        }
+    }
+}
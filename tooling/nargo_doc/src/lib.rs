@@ -0,0 +1,273 @@
+#![forbid(unsafe_code)]
+#![warn(unreachable_pub)]
+#![warn(clippy::semicolon_if_nothing_returned)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies, unused_extern_crates))]
+
+//! The Noir documentation generator.
+//!
+//! Documentation is generated from a crate which has already been type checked: the modules
+//! and items come from the crate's `CrateDefMap` while signatures, trait implementations and
+//! doc comments are looked up in the `NodeInterner`, just like the LSP does for hovers.
+//!
+//! Every documented module becomes a Markdown page at `<crate>/<module>/.../index.md`, and
+//! [crates_page] generates an `index.md` page listing all documented crates. Types in signatures
+//! link to the page documenting them, if any.
+mod index;
+mod page;
+mod signatures;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use noirc_frontend::{graph::CrateId, hir::Context};
+
+use index::DocIndex;
+
+/// Options controlling which items are documented.
+#[derive(Debug, Clone, Default)]
+pub struct DocOptions {
+    /// Also document items which are not `pub`.
+    pub document_private_items: bool,
+}
+
+/// The documentation pages of a single crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateDocs {
+    pub name: String,
+    /// The path of the crate's root file, which identifies the crate across packages.
+    pub root_file: PathBuf,
+    pub pages: Vec<DocPage>,
+}
+
+/// A generated documentation page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocPage {
+    /// The path of the page, relative to the documentation's root directory.
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// The names under which crates are documented, which are also the names of their directories.
+///
+/// Crates are identified by the path of their root file, so that a dependency shared by several
+/// packages is documented under the same name, while different crates with the same name (such
+/// as two dependencies both called `lib`) are given distinct names.
+#[derive(Debug, Clone, Default)]
+pub struct CrateNames {
+    names: BTreeMap<PathBuf, String>,
+}
+
+impl CrateNames {
+    fn name(&mut self, root_file: PathBuf, preferred_name: &str) -> String {
+        if let Some(name) = self.names.get(&root_file) {
+            return name.clone();
+        }
+
+        let mut name = preferred_name.to_string();
+        let mut suffix = 2;
+        while self.names.values().any(|existing_name| *existing_name == name) {
+            name = format!("{preferred_name}_{suffix}");
+            suffix += 1;
+        }
+        self.names.insert(root_file, name.clone());
+        name
+    }
+}
+
+/// Generates the documentation of the crate `crate_id` and all of its dependencies,
+/// except for the standard library.
+///
+/// `context` must hold the result of checking `crate_id`. `crate_names` should be shared
+/// between calls documenting crates of the same workspace.
+pub fn generate_docs(
+    context: &Context,
+    crate_id: CrateId,
+    crate_name: &str,
+    options: &DocOptions,
+    crate_names: &mut CrateNames,
+) -> Vec<CrateDocs> {
+    let crates = documented_crates(context, crate_id, crate_name)
+        .into_iter()
+        .map(|(crate_id, name)| {
+            let root_file = crate_root_file(context, crate_id);
+            (crate_id, crate_names.name(root_file, &name))
+        })
+        .collect();
+    let index = DocIndex::new(context, crates, options);
+
+    index
+        .crates
+        .iter()
+        .map(|(crate_id, name)| {
+            let pages = index
+                .modules
+                .iter()
+                .filter(|module| module.id.krate == *crate_id)
+                .map(|module| page::module_page(context, &index, module))
+                .collect();
+            let root_file = crate_root_file(context, *crate_id);
+            CrateDocs { name: name.clone(), root_file, pages }
+        })
+        .collect()
+}
+
+/// Generates the page at the root of the documentation, linking to the page of every crate.
+pub fn crates_page<'a>(crate_names: impl IntoIterator<Item = &'a str>) -> DocPage {
+    page::crates_page(crate_names)
+}
+
+/// Returns the crate `crate_id` followed by all of its dependencies, excluding the standard library.
+/// Dependencies are named as they were named by the first crate depending on them.
+fn documented_crates(
+    context: &Context,
+    crate_id: CrateId,
+    crate_name: &str,
+) -> Vec<(CrateId, String)> {
+    let mut crates = vec![(crate_id, crate_name.to_string())];
+
+    let mut next = 0;
+    while next < crates.len() {
+        let (crate_id, _) = crates[next];
+        next += 1;
+
+        for dependency in &context.crate_graph[crate_id].dependencies {
+            let is_documented = crates.iter().any(|(id, _)| *id == dependency.crate_id);
+            if !dependency.crate_id.is_stdlib() && !is_documented {
+                crates.push((dependency.crate_id, dependency.as_name()));
+            }
+        }
+    }
+
+    crates
+}
+
+fn crate_root_file(context: &Context, crate_id: CrateId) -> PathBuf {
+    let root_file_id = context.crate_graph[crate_id].root_file_id;
+    context.file_manager.path(root_file_id).map(PathBuf::from).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use nargo::parse_all;
+    use noirc_driver::{check_crate, file_manager_with_stdlib, prepare_crate, CompileOptions};
+    use noirc_frontend::hir::Context;
+
+    use super::{crates_page, generate_docs, CrateNames, DocOptions, DocPage};
+
+    fn generate(src: &str, options: &DocOptions) -> Vec<DocPage> {
+        let root = Path::new("");
+        let file_name = Path::new("main.nr");
+        let mut file_manager = file_manager_with_stdlib(root);
+        file_manager.add_file_with_source(file_name, src.to_string()).expect(
+            "Adding source buffer to file manager should never fail when file manager is empty",
+        );
+        let parsed_files = parse_all(&file_manager);
+
+        let mut context = Context::new(file_manager, parsed_files);
+        let crate_id = prepare_crate(&mut context, file_name);
+        check_crate(&mut context, crate_id, &CompileOptions::default())
+            .expect("Expected the program to type check");
+
+        let mut crate_names = CrateNames::default();
+        let mut crates = generate_docs(&context, crate_id, "my_crate", options, &mut crate_names);
+        assert_eq!(crates.len(), 1);
+        crates.remove(0).pages
+    }
+
+    fn page<'a>(pages: &'a [DocPage], path: &str) -> &'a str {
+        let path = PathBuf::from(path);
+        let page = pages.iter().find(|page| page.path == path);
+        &page.unwrap_or_else(|| panic!("Expected a page at {}", path.display())).contents
+    }
+
+    const SRC: &str = "
+    /// A point in the plane.
+    pub struct Point<T> {
+        pub x: T,
+        y: T,
+    }
+
+    impl<T> Point<T> {
+        /// Creates a point.
+        pub fn new(x: T, y: T) -> Self {
+            Point { x, y }
+        }
+
+        fn private_method(self) {}
+    }
+
+    impl Eq for Point<Field> {
+        fn eq(self, other: Self) -> bool {
+            (self.x == other.x) & (self.y == other.y)
+        }
+    }
+
+    pub mod shapes {
+        pub fn origin() -> crate::Point<Field> {
+            crate::Point::new(0, 0)
+        }
+    }
+
+    mod private_module {}
+
+    pub global ORIGIN_X: Field = 0;
+
+    fn main() {}
+    ";
+
+    #[test]
+    fn documents_public_items() {
+        let pages = generate(SRC, &DocOptions::default());
+
+        let root = page(&pages, "my_crate/index.md");
+        assert!(root.contains("pub struct Point<T> {\n    pub x: T,\n}"));
+        assert!(root.contains("A point in the plane."));
+        assert!(root.contains("pub fn new(x: T, y: T) -> Point<T>"));
+        assert!(root.contains("Creates a point."));
+        assert!(root.contains("impl Eq for Point<Field>"));
+        assert!(root.contains("[`shapes`](shapes/index.md)"));
+        assert!(root.contains("pub global ORIGIN_X: Field"));
+        assert!(!root.contains("private_method"));
+        assert!(!root.contains("private_module"));
+        assert!(!root.contains("fn main"));
+
+        let shapes = page(&pages, "my_crate/shapes/index.md");
+        assert!(shapes.contains("pub fn origin() -> Point<Field>"));
+        assert!(shapes.contains("[`Point`](../../my_crate/index.md#struct.Point)"));
+    }
+
+    #[test]
+    fn documents_private_items() {
+        let options = DocOptions { document_private_items: true };
+        let pages = generate(SRC, &options);
+
+        let root = page(&pages, "my_crate/index.md");
+        assert!(root.contains("    y: T,"));
+        assert!(root.contains("fn private_method(self)"));
+        assert!(root.contains("fn main()"));
+        page(&pages, "my_crate/private_module/index.md");
+    }
+
+    #[test]
+    fn links_to_all_crates() {
+        let page = crates_page(["dep", "my_crate"]);
+        assert_eq!(page.path, PathBuf::from("index.md"));
+        assert!(page
+            .contents
+            .contains("- [`dep`](dep/index.md)\n- [`my_crate`](my_crate/index.md)\n"));
+    }
+
+    #[test]
+    fn gives_distinct_names_to_distinct_crates() {
+        let mut crate_names = CrateNames::default();
+        let first = PathBuf::from("a/lib/src/lib.nr");
+        let second = PathBuf::from("b/lib/src/lib.nr");
+
+        assert_eq!(crate_names.name(first.clone(), "lib"), "lib");
+        assert_eq!(crate_names.name(second.clone(), "lib"), "lib_2");
+        assert_eq!(crate_names.name(first, "other_name"), "lib");
+        assert_eq!(crate_names.name(second, "lib"), "lib_2");
+    }
+}
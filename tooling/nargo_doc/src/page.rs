@@ -0,0 +1,409 @@
+//! Renders the Markdown documentation pages.
+use std::path::PathBuf;
+
+use noirc_frontend::{
+    ast::ItemVisibility,
+    hir::{def_map::ModuleId, Context},
+    node_interner::{FuncId, NodeInterner, ReferenceId, StructId, TraitId},
+    Type, TypeBinding, TypeVariable,
+};
+
+use crate::{
+    index::{DocIndex, ModuleEntry},
+    signatures, DocPage,
+};
+
+const INDEX_FILE: &str = "index.md";
+
+/// The page at the root of the documentation, linking to the page of every documented crate.
+pub(crate) fn crates_page<'a>(crate_names: impl IntoIterator<Item = &'a str>) -> DocPage {
+    let mut contents = String::new();
+    contents.push_str("# Crates\n\n");
+    for crate_name in crate_names {
+        contents.push_str(&format!("- [`{crate_name}`]({crate_name}/{INDEX_FILE})\n"));
+    }
+
+    DocPage { path: PathBuf::from(INDEX_FILE), contents }
+}
+
+pub(crate) fn module_page(context: &Context, index: &DocIndex, module: &ModuleEntry) -> DocPage {
+    let mut page = PageBuilder {
+        interner: &context.def_interner,
+        index,
+        path: &module.path,
+        contents: String::new(),
+    };
+    page.module(module);
+
+    let path = module.path.iter().collect::<PathBuf>().join(INDEX_FILE);
+    DocPage { path, contents: page.contents }
+}
+
+struct PageBuilder<'a> {
+    interner: &'a NodeInterner,
+    index: &'a DocIndex,
+    /// The path of the module this page documents.
+    path: &'a [String],
+    contents: String,
+}
+
+impl<'a> PageBuilder<'a> {
+    fn module(&mut self, module: &ModuleEntry) {
+        if self.path.len() == 1 {
+            self.contents.push_str(&format!("# Crate `{}`\n", self.path[0]));
+        } else {
+            self.contents.push_str(&format!("# Module `{}`\n", self.path.join("::")));
+        }
+        self.doc_comments(ReferenceId::Module(module.id));
+
+        if !module.modules.is_empty() {
+            self.contents.push_str("\n## Modules\n\n");
+            for (name, id) in &module.modules {
+                self.contents.push_str(&format!("- [`{name}`]({name}/{INDEX_FILE})"));
+                if let Some(summary) = self.summary(ReferenceId::Module(*id)) {
+                    self.contents.push_str(" - ");
+                    self.contents.push_str(summary);
+                }
+                self.contents.push('\n');
+            }
+        }
+
+        if !module.structs.is_empty() {
+            self.contents.push_str("\n## Structs\n");
+            for (id, visibility) in &module.structs {
+                self.struct_type(*id, *visibility);
+            }
+        }
+
+        if !module.traits.is_empty() {
+            self.contents.push_str("\n## Traits\n");
+            for (id, visibility) in &module.traits {
+                self.trait_type(*id, *visibility);
+            }
+        }
+
+        if !module.functions.is_empty() {
+            self.contents.push_str("\n## Functions\n");
+            for id in &module.functions {
+                self.item_heading("fn", self.interner.function_name(id));
+                self.function(*id);
+            }
+        }
+
+        if !module.globals.is_empty() {
+            self.contents.push_str("\n## Globals\n");
+            for (id, visibility) in &module.globals {
+                let global = self.interner.get_global(*id);
+                let typ = self.interner.definition_type(global.definition_id);
+                self.item_heading("global", &global.ident.0.contents);
+                self.signature(&signatures::format_global(self.interner, *id, *visibility));
+                self.type_links(&[&typ]);
+                self.doc_comments(ReferenceId::Global(*id));
+            }
+        }
+
+        if !module.type_aliases.is_empty() {
+            self.contents.push_str("\n## Type aliases\n");
+            for (id, visibility) in &module.type_aliases {
+                let type_alias = self.interner.get_type_alias(*id);
+                let type_alias = type_alias.borrow();
+                self.item_heading("type", &type_alias.name.0.contents);
+                self.signature(&signatures::format_type_alias(self.interner, *id, *visibility));
+                self.type_links(&[&type_alias.typ]);
+                self.doc_comments(ReferenceId::Alias(*id));
+            }
+        }
+    }
+
+    fn struct_type(&mut self, id: StructId, visibility: ItemVisibility) {
+        let struct_type = self.interner.get_struct(id);
+        let struct_type = struct_type.borrow();
+        let fields: Vec<_> = struct_type
+            .get_fields_as_written()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, field)| self.index.is_documented(field.visibility))
+            .collect();
+
+        self.item_heading("struct", &struct_type.name.0.contents);
+        self.signature(&signatures::format_struct(
+            self.interner,
+            id,
+            visibility,
+            self.index.document_private_items,
+        ));
+        self.type_links(&fields.iter().map(|(_, field)| &field.typ).collect::<Vec<_>>());
+        self.doc_comments(ReferenceId::Struct(id));
+
+        let field_docs: Vec<_> = fields
+            .iter()
+            .filter_map(|(field_index, field)| {
+                let doc_comments =
+                    self.interner.doc_comments(ReferenceId::StructMember(id, *field_index))?;
+                Some((field.name.0.contents.clone(), doc_comments))
+            })
+            .collect();
+        if !field_docs.is_empty() {
+            self.contents.push_str("\n#### Fields\n\n");
+            for (name, doc_comments) in field_docs {
+                self.contents.push_str(&format!("- `{name}`:"));
+                for line in doc_comments {
+                    self.contents.push(' ');
+                    self.contents.push_str(line.trim());
+                }
+                self.contents.push('\n');
+            }
+        }
+
+        let mut methods: Vec<FuncId> = self
+            .interner
+            .get_struct_methods(id)
+            .into_iter()
+            .flat_map(|methods| methods.values())
+            .flat_map(|methods| methods.direct.iter().copied())
+            .filter(|method| {
+                self.index.is_documented(self.interner.function_modifiers(method).visibility)
+            })
+            .collect();
+        methods.sort_by_key(|method| {
+            let location = self.interner.function_meta(method).location;
+            (self.interner.function_name(method).to_string(), location.span.start())
+        });
+        if !methods.is_empty() {
+            self.contents.push_str("\n#### Methods\n");
+            for method in methods {
+                self.function(method);
+            }
+        }
+
+        let trait_impls: Vec<_> = self
+            .interner
+            .get_all_trait_implementations()
+            .into_iter()
+            .filter(|(_, trait_impl)| match &trait_impl.borrow().typ {
+                Type::Struct(struct_type, _) => struct_type.borrow().id == id,
+                _ => false,
+            })
+            .collect();
+        if !trait_impls.is_empty() {
+            self.contents.push_str("\n#### Trait implementations\n");
+            for (_, trait_impl) in trait_impls {
+                let trait_impl = trait_impl.borrow();
+                self.signature(&signatures::format_trait_impl(self.interner, &trait_impl));
+                self.trait_links(&[trait_impl.trait_id]);
+            }
+        }
+    }
+
+    fn trait_type(&mut self, id: TraitId, visibility: ItemVisibility) {
+        let a_trait = self.interner.get_trait(id);
+
+        self.item_heading("trait", &a_trait.name.0.contents);
+        self.signature(&signatures::format_trait(self.interner, id, visibility));
+        let parents: Vec<_> = a_trait.trait_bounds.iter().map(|bound| bound.trait_id).collect();
+        self.trait_links(&parents);
+        self.doc_comments(ReferenceId::Trait(id));
+
+        if !a_trait.methods.is_empty() {
+            self.contents.push_str("\n#### Methods\n");
+            for method in &a_trait.methods {
+                if let Some(method_id) = a_trait.method_ids.get(&method.name.0.contents) {
+                    self.function(*method_id);
+                }
+            }
+        }
+
+        let trait_impls: Vec<_> = self
+            .interner
+            .get_all_trait_implementations()
+            .into_iter()
+            .filter(|(_, trait_impl)| trait_impl.borrow().trait_id == id)
+            .collect();
+        if !trait_impls.is_empty() {
+            self.contents.push_str("\n#### Implementations\n");
+            for (_, trait_impl) in trait_impls {
+                let trait_impl = trait_impl.borrow();
+                self.signature(&signatures::format_trait_impl(self.interner, &trait_impl));
+                self.type_links(&[&trait_impl.typ]);
+            }
+        }
+    }
+
+    fn function(&mut self, id: FuncId) {
+        let func_meta = self.interner.function_meta(&id);
+        let mut types: Vec<&Type> = func_meta.parameters.iter().map(|(_, typ, _)| typ).collect();
+        types.push(func_meta.return_type());
+
+        self.signature(&signatures::format_function(self.interner, id));
+        self.type_links(&types);
+        self.doc_comments(ReferenceId::Function(id));
+    }
+
+    /// Writes a heading for an item, along with an anchor other pages can link to.
+    fn item_heading(&mut self, kind: &str, name: &str) {
+        self.contents.push_str(&format!("\n<a id=\"{}\"></a>\n", anchor(kind, name)));
+        self.contents.push_str(&format!("### `{name}`\n"));
+    }
+
+    fn signature(&mut self, signature: &str) {
+        self.contents.push_str("\n```noir\n");
+        self.contents.push_str(signature);
+        self.contents.push_str("\n```\n");
+    }
+
+    fn doc_comments(&mut self, id: ReferenceId) {
+        if let Some(doc_comments) = self.interner.doc_comments(id) {
+            self.contents.push('\n');
+            for comment in doc_comments {
+                self.contents.push_str(comment.strip_prefix(' ').unwrap_or(comment));
+                self.contents.push('\n');
+            }
+        }
+    }
+
+    /// Returns the first line of an item's doc comments.
+    fn summary(&self, id: ReferenceId) -> Option<&'a str> {
+        let doc_comments = self.interner.doc_comments(id)?;
+        doc_comments.iter().map(|comment| comment.trim()).find(|comment| !comment.is_empty())
+    }
+
+    /// Writes links to the pages of the structs, traits and type aliases mentioned in `types`.
+    fn type_links(&mut self, types: &[&Type]) {
+        let mut gatherer = TypeLinksGatherer { builder: self, links: Vec::new() };
+        for typ in types {
+            gatherer.gather_type_links(typ);
+        }
+        let links = gatherer.links;
+        self.push_links(links);
+    }
+
+    fn trait_links(&mut self, trait_ids: &[TraitId]) {
+        let links = trait_ids.iter().filter_map(|trait_id| self.trait_link(*trait_id)).collect();
+        self.push_links(links);
+    }
+
+    fn push_links(&mut self, links: Vec<String>) {
+        if links.is_empty() {
+            return;
+        }
+
+        self.contents.push_str("\nTypes: ");
+        self.contents.push_str(&links.join(" | "));
+        self.contents.push('\n');
+    }
+
+    fn trait_link(&self, id: TraitId) -> Option<String> {
+        let module = self.index.trait_module(id)?;
+        let name = &self.interner.get_trait(id).name.0.contents;
+        self.link(module, "trait", name)
+    }
+
+    /// Returns a Markdown link to the item named `name` documented in `module`.
+    fn link(&self, module: ModuleId, kind: &str, name: &str) -> Option<String> {
+        let target = self.index.module_path(module)?;
+        let anchor = anchor(kind, name);
+        if target == self.path {
+            return Some(format!("[`{name}`](#{anchor})"));
+        }
+
+        // Go up to the documentation's root and then down to the target page
+        let mut url = "../".repeat(self.path.len());
+        url.push_str(&target.join("/"));
+        Some(format!("[`{name}`]({url}/{INDEX_FILE}#{anchor})"))
+    }
+}
+
+fn anchor(kind: &str, name: &str) -> String {
+    format!("{kind}.{name}")
+}
+
+struct TypeLinksGatherer<'a, 'b> {
+    builder: &'b PageBuilder<'a>,
+    links: Vec<String>,
+}
+
+impl<'a, 'b> TypeLinksGatherer<'a, 'b> {
+    fn gather_type_links(&mut self, typ: &Type) {
+        match typ {
+            Type::Array(length, typ) => {
+                self.gather_type_links(length);
+                self.gather_type_links(typ);
+            }
+            Type::Slice(typ) => self.gather_type_links(typ),
+            Type::Tuple(types) => {
+                for typ in types {
+                    self.gather_type_links(typ);
+                }
+            }
+            Type::Struct(struct_type, generics) => {
+                let struct_type = struct_type.borrow();
+                if let Some(module) = self.builder.index.struct_module(struct_type.id) {
+                    let link = self.builder.link(module, "struct", &struct_type.name.0.contents);
+                    self.push_link(link);
+                }
+                for generic in generics {
+                    self.gather_type_links(generic);
+                }
+            }
+            Type::Alias(type_alias, generics) => {
+                let type_alias = type_alias.borrow();
+                if let Some(module) = self.builder.index.type_alias_module(type_alias.id) {
+                    let link = self.builder.link(module, "type", &type_alias.name.0.contents);
+                    self.push_link(link);
+                }
+                for generic in generics {
+                    self.gather_type_links(generic);
+                }
+            }
+            Type::TypeVariable(var) | Type::NamedGeneric(var, _) => {
+                self.gather_type_variable_links(var);
+            }
+            Type::TraitAsType(trait_id, _, generics) => {
+                self.push_link(self.builder.trait_link(*trait_id));
+                for generic in &generics.ordered {
+                    self.gather_type_links(generic);
+                }
+                for named_type in &generics.named {
+                    self.gather_type_links(&named_type.typ);
+                }
+            }
+            Type::Function(args, return_type, env, _) => {
+                for arg in args {
+                    self.gather_type_links(arg);
+                }
+                self.gather_type_links(return_type);
+                self.gather_type_links(env);
+            }
+            Type::MutableReference(typ) => self.gather_type_links(typ),
+            Type::InfixExpr(lhs, _, rhs) => {
+                self.gather_type_links(lhs);
+                self.gather_type_links(rhs);
+            }
+            Type::CheckedCast { to, .. } => self.gather_type_links(to),
+            Type::FieldElement
+            | Type::Integer(..)
+            | Type::Bool
+            | Type::String(_)
+            | Type::FmtString(_, _)
+            | Type::Unit
+            | Type::Forall(_, _)
+            | Type::Constant(..)
+            | Type::Quoted(_)
+            | Type::Error => (),
+        }
+    }
+
+    fn gather_type_variable_links(&mut self, var: &TypeVariable) {
+        if let TypeBinding::Bound(typ) = &*var.borrow() {
+            self.gather_type_links(typ);
+        }
+    }
+
+    fn push_link(&mut self, link: Option<String>) {
+        if let Some(link) = link {
+            if !self.links.contains(&link) {
+                self.links.push(link);
+            }
+        }
+    }
+}
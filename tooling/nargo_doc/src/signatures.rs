@@ -0,0 +1,307 @@
+//! Formats the signatures of items the way they'd be written in Noir source code.
+use noirc_frontend::{
+    ast::{ItemVisibility, Visibility},
+    hir_def::{
+        stmt::HirPattern,
+        traits::{TraitConstraint, TraitImpl},
+    },
+    node_interner::{FuncId, GlobalId, NodeInterner, StructId, TraitId, TypeAliasId},
+    Generics, Kind, Type,
+};
+
+pub(crate) fn format_struct(
+    interner: &NodeInterner,
+    id: StructId,
+    visibility: ItemVisibility,
+    document_private_items: bool,
+) -> String {
+    let struct_type = interner.get_struct(id);
+    let struct_type = struct_type.borrow();
+
+    let mut string = String::new();
+    format_visibility(visibility, &mut string);
+    string.push_str("struct ");
+    string.push_str(&struct_type.name.0.contents);
+    format_generics(&struct_type.generics, &mut string);
+
+    let fields: Vec<_> = struct_type
+        .get_fields_as_written()
+        .into_iter()
+        .filter(|field| document_private_items || field.visibility == ItemVisibility::Public)
+        .collect();
+    if fields.is_empty() {
+        string.push_str(" {}");
+        return string;
+    }
+
+    string.push_str(" {\n");
+    for field in fields {
+        string.push_str("    ");
+        format_visibility(field.visibility, &mut string);
+        string.push_str(&field.name.0.contents);
+        string.push_str(": ");
+        string.push_str(&field.typ.to_string());
+        string.push_str(",\n");
+    }
+    string.push('}');
+    string
+}
+
+pub(crate) fn format_trait(
+    interner: &NodeInterner,
+    id: TraitId,
+    visibility: ItemVisibility,
+) -> String {
+    let a_trait = interner.get_trait(id);
+
+    let mut string = String::new();
+    format_visibility(visibility, &mut string);
+    string.push_str("trait ");
+    string.push_str(&a_trait.name.0.contents);
+    format_generics(&a_trait.generics, &mut string);
+
+    if !a_trait.trait_bounds.is_empty() {
+        string.push_str(": ");
+        for (index, bound) in a_trait.trait_bounds.iter().enumerate() {
+            if index > 0 {
+                string.push_str(" + ");
+            }
+            string.push_str(&interner.get_trait(bound.trait_id).name.0.contents);
+            string.push_str(&bound.trait_generics.to_string());
+        }
+    }
+
+    format_where_clause(interner, &a_trait.where_clause, &mut string);
+    string
+}
+
+pub(crate) fn format_trait_impl(interner: &NodeInterner, trait_impl: &TraitImpl) -> String {
+    let a_trait = interner.get_trait(trait_impl.trait_id);
+
+    let mut string = String::new();
+    string.push_str("impl");
+
+    let generics: Vec<_> = trait_impl
+        .trait_generics
+        .iter()
+        .chain(std::iter::once(&trait_impl.typ))
+        .flat_map(named_generics)
+        .fold(Vec::new(), |mut generics, name| {
+            if !generics.contains(&name) {
+                generics.push(name);
+            }
+            generics
+        });
+    if !generics.is_empty() {
+        string.push('<');
+        string.push_str(&generics.join(", "));
+        string.push('>');
+    }
+
+    string.push(' ');
+    string.push_str(&a_trait.name.0.contents);
+    if !trait_impl.trait_generics.is_empty() {
+        let generics: Vec<_> = trait_impl.trait_generics.iter().map(ToString::to_string).collect();
+        string.push('<');
+        string.push_str(&generics.join(", "));
+        string.push('>');
+    }
+    string.push_str(" for ");
+    string.push_str(&trait_impl.typ.to_string());
+
+    format_where_clause(interner, &trait_impl.where_clause, &mut string);
+    string
+}
+
+pub(crate) fn format_function(interner: &NodeInterner, id: FuncId) -> String {
+    let func_meta = interner.function_meta(&id);
+    let func_modifiers = interner.function_modifiers(&id);
+
+    let mut string = String::new();
+    // Trait methods and methods of trait impls can't have a visibility
+    if func_meta.trait_id.is_none() && func_meta.trait_impl.is_none() {
+        format_visibility(func_modifiers.visibility, &mut string);
+    }
+    if func_modifiers.is_unconstrained {
+        string.push_str("unconstrained ");
+    }
+    if func_modifiers.is_comptime {
+        string.push_str("comptime ");
+    }
+
+    string.push_str("fn ");
+    string.push_str(&func_modifiers.name);
+    format_generics(&func_meta.direct_generics, &mut string);
+    string.push('(');
+    let parameters = &func_meta.parameters;
+    for (index, (pattern, typ, visibility)) in parameters.iter().enumerate() {
+        format_pattern(pattern, interner, &mut string);
+        if !pattern_is_self(pattern, interner) {
+            string.push_str(": ");
+            if matches!(visibility, Visibility::Public) {
+                string.push_str("pub ");
+            }
+            string.push_str(&typ.to_string());
+        }
+        if index != parameters.len() - 1 {
+            string.push_str(", ");
+        }
+    }
+    string.push(')');
+
+    let return_type = func_meta.return_type();
+    if !matches!(return_type, Type::Unit) {
+        string.push_str(" -> ");
+        string.push_str(&return_type.to_string());
+    }
+
+    format_where_clause(interner, &func_meta.trait_constraints, &mut string);
+    string
+}
+
+pub(crate) fn format_global(
+    interner: &NodeInterner,
+    id: GlobalId,
+    visibility: ItemVisibility,
+) -> String {
+    let global_info = interner.get_global(id);
+    let definition = interner.definition(global_info.definition_id);
+    let typ = interner.definition_type(global_info.definition_id);
+
+    let mut string = String::new();
+    format_visibility(visibility, &mut string);
+    if definition.comptime {
+        string.push_str("comptime ");
+    }
+    if definition.mutable {
+        string.push_str("mut ");
+    }
+    string.push_str("global ");
+    string.push_str(&global_info.ident.0.contents);
+    string.push_str(": ");
+    string.push_str(&typ.to_string());
+    string
+}
+
+pub(crate) fn format_type_alias(
+    interner: &NodeInterner,
+    id: TypeAliasId,
+    visibility: ItemVisibility,
+) -> String {
+    let type_alias = interner.get_type_alias(id);
+    let type_alias = type_alias.borrow();
+
+    let mut string = String::new();
+    format_visibility(visibility, &mut string);
+    string.push_str("type ");
+    string.push_str(&type_alias.name.0.contents);
+    format_generics(&type_alias.generics, &mut string);
+    string.push_str(" = ");
+    string.push_str(&type_alias.typ.to_string());
+    string
+}
+
+fn format_visibility(visibility: ItemVisibility, string: &mut String) {
+    if visibility != ItemVisibility::Private {
+        string.push_str(&visibility.to_string());
+        string.push(' ');
+    }
+}
+
+fn format_generics(generics: &Generics, string: &mut String) {
+    if generics.is_empty() {
+        return;
+    }
+
+    string.push('<');
+    for (index, generic) in generics.iter().enumerate() {
+        if index > 0 {
+            string.push_str(", ");
+        }
+
+        match generic.kind() {
+            Kind::Any | Kind::Normal => {
+                string.push_str(&generic.name);
+            }
+            Kind::IntegerOrField | Kind::Integer => {
+                string.push_str("let ");
+                string.push_str(&generic.name);
+                string.push_str(": u32");
+            }
+            Kind::Numeric(typ) => {
+                string.push_str("let ");
+                string.push_str(&generic.name);
+                string.push_str(": ");
+                string.push_str(&typ.to_string());
+            }
+        }
+    }
+    string.push('>');
+}
+
+fn format_where_clause(
+    interner: &NodeInterner,
+    constraints: &[TraitConstraint],
+    string: &mut String,
+) {
+    if constraints.is_empty() {
+        return;
+    }
+
+    string.push_str("\nwhere\n");
+    for constraint in constraints {
+        let a_trait = interner.get_trait(constraint.trait_bound.trait_id);
+        string.push_str("    ");
+        string.push_str(&constraint.typ.to_string());
+        string.push_str(": ");
+        string.push_str(&a_trait.name.0.contents);
+        string.push_str(&constraint.trait_bound.trait_generics.to_string());
+        string.push_str(",\n");
+    }
+    // Remove the last newline so the where clause ends like any other signature
+    string.pop();
+}
+
+/// Returns the names of the generics used in `typ`, e.g. `T` and `N` in `[T; N]`.
+fn named_generics(typ: &Type) -> Vec<String> {
+    match typ {
+        Type::NamedGeneric(_, name) => vec![name.to_string()],
+        Type::Array(length, typ) => {
+            let mut names = named_generics(length);
+            names.extend(named_generics(typ));
+            names
+        }
+        Type::Slice(typ) | Type::MutableReference(typ) => named_generics(typ),
+        Type::Tuple(types) | Type::Struct(_, types) | Type::Alias(_, types) => {
+            types.iter().flat_map(named_generics).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn format_pattern(pattern: &HirPattern, interner: &NodeInterner, string: &mut String) {
+    match pattern {
+        HirPattern::Identifier(ident) => {
+            let definition = interner.definition(ident.id);
+            string.push_str(&definition.name);
+        }
+        HirPattern::Mutable(pattern, _) => {
+            string.push_str("mut ");
+            format_pattern(pattern, interner, string);
+        }
+        HirPattern::Tuple(..) | HirPattern::Array(..) | HirPattern::Struct(..) => {
+            string.push('_');
+        }
+    }
+}
+
+fn pattern_is_self(pattern: &HirPattern, interner: &NodeInterner) -> bool {
+    match pattern {
+        HirPattern::Identifier(ident) => {
+            let definition = interner.definition(ident.id);
+            definition.name == "self"
+        }
+        HirPattern::Mutable(pattern, _) => pattern_is_self(pattern, interner),
+        HirPattern::Tuple(..) | HirPattern::Array(..) | HirPattern::Struct(..) => false,
+    }
+}
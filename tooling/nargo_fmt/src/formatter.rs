@@ -24,6 +24,7 @@ mod lvalue;
 mod module;
 mod path;
 mod pattern;
+mod range;
 mod statement;
 mod structs;
 mod trait_impl;
@@ -35,6 +36,8 @@ mod use_tree_merge;
 mod visibility;
 mod where_clause;
 
+pub(crate) use range::format_range;
+
 pub(crate) struct Formatter<'a> {
     pub(crate) config: &'a Config,
     source: &'a str,
@@ -1,5 +1,5 @@
 use noirc_frontend::token::{
-    Attribute, Attributes, FunctionAttribute, MetaAttribute, SecondaryAttribute, TestScope, Token,
+    Attribute, Attributes, FunctionAttribute, MetaAttribute, SecondaryAttribute, Token,
 };
 
 use crate::chunks::ChunkGroup;
@@ -49,8 +49,9 @@ impl<'a> Formatter<'a> {
             FunctionAttribute::Foreign(_)
             | FunctionAttribute::Builtin(_)
             | FunctionAttribute::Oracle(_) => self.format_one_arg_attribute(),
-            FunctionAttribute::Test(test_scope) => self.format_test_attribute(test_scope),
-            FunctionAttribute::Fold
+            FunctionAttribute::Test(..) => self.format_test_attribute(),
+            FunctionAttribute::Bench
+            | FunctionAttribute::Fold
             | FunctionAttribute::NoPredicates
             | FunctionAttribute::InlineAlways => self.format_no_args_attribute(),
         }
@@ -87,6 +88,9 @@ impl<'a> Formatter<'a> {
             SecondaryAttribute::Meta(meta_attribute) => {
                 self.format_meta_attribute(meta_attribute);
             }
+            SecondaryAttribute::Cfg(_) => {
+                self.format_cfg_attribute();
+            }
         }
 
         self.write_line();
@@ -107,35 +111,54 @@ impl<'a> Formatter<'a> {
         self.write_right_bracket(); // ]
     }
 
-    fn format_test_attribute(&mut self, test_scope: TestScope) {
+    fn format_test_attribute(&mut self) {
         self.write_current_token_and_bump(); // #[
         self.skip_comments_and_whitespace();
         self.write_current_token_and_bump(); // test
+        self.skip_comments_and_whitespace();
 
-        match test_scope {
-            TestScope::None => (),
-            TestScope::ShouldFailWith { reason: None } => {
-                self.write_left_paren(); // (
+        if self.is_at(Token::LeftParen) {
+            self.write_left_paren(); // (
+            loop {
                 self.skip_comments_and_whitespace();
-                self.write_current_token_and_bump(); // should_fail
-                self.write_right_paren(); // )
-            }
-            TestScope::ShouldFailWith { reason: Some(..) } => {
-                self.write_left_paren(); // (
+                self.write_current_token_and_bump(); // should_fail, should_fail_with or a fuzzer option
                 self.skip_comments_and_whitespace();
-                self.write_current_token_and_bump(); // should_fail_with
-                self.write_space();
-                self.write_token(Token::Assign);
+                if self.is_at(Token::Assign) {
+                    self.write_space();
+                    self.write_token(Token::Assign);
+                    self.write_space();
+                    self.skip_comments_and_whitespace();
+                    self.write_current_token_and_bump(); // "reason" or number
+                    self.skip_comments_and_whitespace();
+                }
+                if !self.is_at(Token::Comma) {
+                    break;
+                }
+                self.write_comma();
                 self.write_space();
-                self.skip_comments_and_whitespace();
-                self.write_current_token_and_bump(); // "reason"
-                self.write_right_paren(); // )
             }
+            self.write_right_paren(); // )
         }
 
         self.write_right_bracket(); // ]
     }
 
+    fn format_cfg_attribute(&mut self) {
+        self.write_current_token_and_bump(); // #[
+        self.skip_comments_and_whitespace();
+        self.write_current_token_and_bump(); // cfg
+        self.write_left_paren(); // (
+        self.skip_comments_and_whitespace();
+        self.write_current_token_and_bump(); // feature
+        self.write_space();
+        self.write_token(Token::Assign);
+        self.write_space();
+        self.skip_comments_and_whitespace();
+        self.write_current_token_and_bump(); // "name"
+        self.write_right_paren(); // )
+        self.write_right_bracket(); // ]
+    }
+
     fn format_meta_attribute(&mut self, meta_attribute: MetaAttribute) {
         self.write_current_token_and_bump(); // #[
         self.skip_comments_and_whitespace();
@@ -270,6 +293,13 @@ mod tests {
         assert_format_attribute(src, expected);
     }
 
+    #[test]
+    fn format_cfg_attribute() {
+        let src = "  #[ cfg ( feature=\"debug_checks\" ) ] ";
+        let expected = "#[cfg(feature = \"debug_checks\")]";
+        assert_format_attribute(src, expected);
+    }
+
     #[test]
     fn format_meta_attribute_without_arguments() {
         let src = "  #[ custom  ] ";
@@ -312,6 +342,13 @@ mod tests {
         assert_format_attribute(src, expected);
     }
 
+    #[test]
+    fn format_bench_attribute() {
+        let src = "  #[ bench ] ";
+        let expected = "#[bench]";
+        assert_format_attribute(src, expected);
+    }
+
     #[test]
     fn format_test_attribute() {
         let src = "  #[ test ] ";
@@ -333,6 +370,14 @@ mod tests {
         assert_format_attribute(src, expected);
     }
 
+    #[test]
+    fn format_test_attribute_with_fuzz_config() {
+        let src = "  #[ test ( should_fail , cases=100,seed = 42 ,max_shrink_iters= 5,coverage_guided )] ";
+        let expected =
+            "#[test(should_fail, cases = 100, seed = 42, max_shrink_iters = 5, coverage_guided)]";
+        assert_format_attribute(src, expected);
+    }
+
     #[test]
     fn format_multiple_function_attributes() {
         let src = " #[foo] #[test] #[bar]  ";
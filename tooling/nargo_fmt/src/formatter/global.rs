@@ -9,9 +9,10 @@ use crate::chunks::{ChunkFormatter, ChunkGroup};
 impl<'a> Formatter<'a> {
     pub(super) fn format_global(
         &mut self,
-        let_statement: LetStatement,
+        mut let_statement: LetStatement,
         visibility: ItemVisibility,
     ) {
+        self.format_secondary_attributes(std::mem::take(&mut let_statement.attributes));
         let group = self.chunk_formatter().format_global(let_statement, visibility);
         self.write_indentation();
         self.format_chunk_group(group);
@@ -51,8 +52,11 @@ impl<'a, 'b> ChunkFormatter<'a, 'b> {
 
                 *pattern
             }
-            Pattern::Tuple(..) | Pattern::Struct(..) | Pattern::Interned(..) => {
-                unreachable!("Global pattern cannot be a tuple, struct or interned")
+            Pattern::Tuple(..)
+            | Pattern::Array(..)
+            | Pattern::Struct(..)
+            | Pattern::Interned(..) => {
+                unreachable!("Global pattern cannot be a tuple, array, struct or interned")
             }
         };
 
@@ -99,4 +103,11 @@ mod tests {
         let expected = "pub comptime mut global x: Field = 1;\n";
         assert_format(src, expected);
     }
+
+    #[test]
+    fn format_global_with_attribute() {
+        let src = " #[ cfg ( feature=\"a\" ) ]  pub  global  x  =  1  ; ";
+        let expected = "#[cfg(feature = \"a\")]\npub global x = 1;\n";
+        assert_format(src, expected);
+    }
 }
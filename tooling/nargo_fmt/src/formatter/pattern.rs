@@ -48,6 +48,33 @@ impl<'a> Formatter<'a> {
 
                 self.write_right_paren();
             }
+            Pattern::Array(patterns, rest_index, _span) => {
+                // `None` stands for the `..` rest pattern
+                let mut elements: Vec<Option<Pattern>> = patterns.into_iter().map(Some).collect();
+                if let Some(rest_index) = rest_index {
+                    elements.insert(rest_index, None);
+                }
+
+                self.write_left_bracket();
+                for (index, element) in elements.into_iter().enumerate() {
+                    if index > 0 {
+                        self.write_comma();
+                        self.write_space();
+                    }
+                    match element {
+                        Some(pattern) => self.format_pattern(pattern),
+                        None => self.write_token(Token::DoubleDot),
+                    }
+                }
+
+                // Skip trailing comma
+                self.skip_comments_and_whitespace();
+                if self.is_at(Token::Comma) {
+                    self.bump();
+                }
+
+                self.write_right_bracket();
+            }
             Pattern::Struct(path, fields, _span) => {
                 self.format_path(path);
                 self.write_space();
@@ -139,6 +166,20 @@ mod tests {
         assert_format(src, expected);
     }
 
+    #[test]
+    fn format_array_pattern() {
+        let src = "fn foo( [  x  ,  mut y , ] : [Field; 2]) {}";
+        let expected = "fn foo([x, mut y]: [Field; 2]) {}\n";
+        assert_format(src, expected);
+    }
+
+    #[test]
+    fn format_array_pattern_with_rest() {
+        let src = "fn foo( [  x  ,  ..  , y ] : [Field; 3]) {}";
+        let expected = "fn foo([x, .., y]: [Field; 3]) {}\n";
+        assert_format(src, expected);
+    }
+
     #[test]
     fn format_struct_pattern_empty() {
         let src = "fn foo( Foo {  } : i32) {}";
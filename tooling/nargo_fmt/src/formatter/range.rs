@@ -0,0 +1,276 @@
+use noirc_frontend::{
+    ast::{Documented, NoirFunction, Statement},
+    hir::resolution::errors::Span,
+    parser::{Item, ItemKind},
+    token::Token,
+    ParsedModule,
+};
+
+use crate::{chunks::ChunkGroup, Config};
+
+use super::Formatter;
+
+/// Formats the items and statements of `parsed_module` that intersect `range`.
+///
+/// Items fully inside the range are formatted as a whole. When the range only covers part of an
+/// item we look inside it: the items of a module, the methods of an impl or the statements of a
+/// function body. Anything that can't be split further (a struct, a statement, etc.) is formatted
+/// as a whole, even if the range only covers part of it.
+///
+/// Returns the spans of the source that need to change, together with their formatted text.
+pub(crate) fn format_range(
+    source: &str,
+    parsed_module: ParsedModule,
+    config: &Config,
+    range: Span,
+) -> Vec<(Span, String)> {
+    let mut range_formatter = RangeFormatter { source, config, range, edits: Vec::new() };
+    range_formatter.format_items(parsed_module.items, 0);
+    range_formatter.edits
+}
+
+struct RangeFormatter<'a> {
+    source: &'a str,
+    config: &'a Config,
+    range: Span,
+    edits: Vec<(Span, String)>,
+}
+
+impl<'a> RangeFormatter<'a> {
+    fn format_items(&mut self, items: Vec<Item>, indentation: i32) {
+        let mut previous_span = None;
+
+        for item in items {
+            // A single item in the source might produce multiple items with the same span
+            if !self.intersects(item.span) || previous_span == Some(item.span) {
+                continue;
+            }
+            previous_span = Some(item.span);
+
+            if self.range_covers(item.span) {
+                self.format_node(item.span, indentation, |formatter| {
+                    formatter.format_item(item, false);
+                });
+                continue;
+            }
+
+            match item.kind {
+                ItemKind::Submodules(submodule) => {
+                    self.format_items(submodule.contents.items, indentation + 1);
+                }
+                ItemKind::Function(function) if self.range_is_inside(function.def.span) => {
+                    self.format_statements(function.def.body.statements, indentation + 1);
+                }
+                ItemKind::Impl(type_impl) => {
+                    self.format_methods(type_impl.methods, indentation + 1);
+                }
+                kind => {
+                    let item = Item { kind, span: item.span, doc_comments: item.doc_comments };
+                    self.format_node(item.span, indentation, |formatter| {
+                        formatter.format_item(item, false);
+                    });
+                }
+            }
+        }
+    }
+
+    fn format_methods(&mut self, methods: Vec<(Documented<NoirFunction>, Span)>, indentation: i32) {
+        for (method, span) in methods {
+            if !self.intersects(span) {
+                continue;
+            }
+
+            let method = method.item;
+            if self.range_is_inside(method.def.span) {
+                self.format_statements(method.def.body.statements, indentation + 1);
+            } else {
+                self.format_node(span, indentation, |formatter| {
+                    formatter.format_function(method);
+                });
+            }
+        }
+    }
+
+    fn format_statements(&mut self, statements: Vec<Statement>, indentation: i32) {
+        for statement in statements {
+            if self.intersects(statement.span) {
+                let span = statement.span;
+                self.format_node(span, indentation, |formatter| {
+                    formatter.format_single_statement(statement);
+                });
+            }
+        }
+    }
+
+    /// Formats the node at `span` with a new formatter, and records an edit if the result differs
+    /// from the source. If the node is the first thing in its line, its indentation is fixed too.
+    fn format_node(&mut self, span: Span, indentation: i32, f: impl FnOnce(&mut Formatter)) {
+        let mut formatter = Formatter::new(self.source, self.config);
+        let ignore = formatter.skip_to_node_start(span.start());
+        if ignore {
+            return;
+        }
+
+        formatter.indentation = indentation;
+        f(&mut formatter);
+        let formatted = formatter.buffer.contents();
+
+        let start = span.start() as usize;
+        let line_start = self.source[..start].rfind('\n').map_or(0, |index| index + 1);
+        let (start, formatted) = if self.source[line_start..start].trim().is_empty() {
+            (line_start, formatted.trim_end())
+        } else {
+            (start, formatted.trim())
+        };
+
+        if &self.source[start..span.end() as usize] != formatted {
+            self.edits.push((Span::from(start as u32..span.end()), formatted.to_string()));
+        }
+    }
+
+    fn intersects(&self, span: Span) -> bool {
+        span.start() < self.range.end() && self.range.start() < span.end()
+    }
+
+    fn range_covers(&self, span: Span) -> bool {
+        self.range.start() <= span.start() && span.end() <= self.range.end()
+    }
+
+    fn range_is_inside(&self, span: Span) -> bool {
+        span.start() < self.range.start() && self.range.end() < span.end()
+    }
+}
+
+impl<'a> Formatter<'a> {
+    /// Advances the lexer until the token at `start`, without writing anything.
+    /// Returns true if the node that starts there comes right after a `noir-fmt:ignore` comment.
+    fn skip_to_node_start(&mut self, start: u32) -> bool {
+        let mut ignore_next = false;
+
+        while self.token_span.start() < start && self.token != Token::EOF {
+            match &self.token {
+                Token::LineComment(comment, None) | Token::BlockComment(comment, None) => {
+                    ignore_next = comment.trim() == "noir-fmt:ignore";
+                }
+                Token::Whitespace(..) => (),
+                _ => ignore_next = false,
+            }
+            self.bump();
+        }
+
+        ignore_next
+    }
+
+    fn format_single_statement(&mut self, statement: Statement) {
+        let mut group = ChunkGroup::new();
+        self.chunk_formatter().format_statement(
+            statement, &mut group, false, // ignore next
+        );
+        self.write_indentation();
+        self.format_chunk_group(group);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noirc_errors::Span;
+    use noirc_frontend::parser;
+
+    use crate::{format_range, Config};
+
+    /// Formats the first occurrence of `selection` in `src` and returns the resulting source.
+    fn format_selection(src: &str, selection: &str) -> String {
+        let (parsed_module, errors) = parser::parse_program(src);
+        if !errors.is_empty() {
+            panic!("Expected no errors, got: {:?}", errors);
+        }
+
+        let start = src.find(selection).expect("Expected to find the selection in the source");
+        let range = Span::from(start as u32..(start + selection.len()) as u32);
+
+        let mut edits = format_range(src, parsed_module, &Config::default(), range);
+        edits.sort_by_key(|(span, _)| std::cmp::Reverse(span.start()));
+
+        let mut result = src.to_string();
+        for (span, text) in edits {
+            result.replace_range(span.start() as usize..span.end() as usize, &text);
+        }
+        result
+    }
+
+    #[test]
+    fn formats_only_items_in_range() {
+        let src = "fn  one ( )  { }
+
+fn  two ( )  { }
+
+fn  three ( )  { }
+";
+        let expected = "fn  one ( )  { }
+
+fn two() {}
+
+fn  three ( )  { }
+";
+        similar_asserts::assert_eq!(format_selection(src, "fn  two ( )  { }"), expected);
+    }
+
+    #[test]
+    fn formats_only_statements_in_range() {
+        let src = "fn main() {
+    let  x  =  1 ;
+      let  y  =  x+2 ;
+    let  z  =  3 ;
+}
+";
+        let expected = "fn main() {
+    let  x  =  1 ;
+    let y = x + 2;
+    let  z  =  3 ;
+}
+";
+        similar_asserts::assert_eq!(format_selection(src, "x+2"), expected);
+    }
+
+    #[test]
+    fn formats_statements_in_impl_method() {
+        let src = "impl Foo {
+    fn foo() {
+        let  x  =  [1,2,3] ;
+    }
+}
+";
+        let expected = "impl Foo {
+    fn foo() {
+        let x = [1, 2, 3];
+    }
+}
+";
+        similar_asserts::assert_eq!(format_selection(src, ";"), expected);
+    }
+
+    #[test]
+    fn formats_items_in_module() {
+        let src = "mod moo {
+    fn  one ( )  { }
+  struct  Foo { x : Field }
+}
+";
+        let expected = "mod moo {
+    fn  one ( )  { }
+    struct Foo {
+        x: Field,
+    }
+}
+";
+        similar_asserts::assert_eq!(format_selection(src, "Foo"), expected);
+    }
+
+    #[test]
+    fn does_not_format_ignored_item() {
+        let src = "// noir-fmt:ignore
+fn  one ( )  { }
+";
+        similar_asserts::assert_eq!(format_selection(src, "one"), src);
+    }
+}
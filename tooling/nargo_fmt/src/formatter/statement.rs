@@ -77,6 +77,9 @@ impl<'a, 'b> ChunkFormatter<'a, 'b> {
                     formatter.write_semicolon();
                 }));
             }
+            StatementKind::Return(value) => {
+                group.group(self.format_return_statement(value));
+            }
             StatementKind::Comptime(statement) => {
                 group.group(self.format_comptime_statement(*statement));
             }
@@ -267,6 +270,29 @@ impl<'a, 'b> ChunkFormatter<'a, 'b> {
         group
     }
 
+    fn format_return_statement(&mut self, value: Option<Expression>) -> ChunkGroup {
+        let mut group = ChunkGroup::new();
+
+        group.text(self.chunk(|formatter| {
+            formatter.write_keyword(Keyword::Return);
+        }));
+
+        if let Some(value) = value {
+            group.space(self);
+            self.format_expression(value, &mut group);
+        }
+
+        // A `return` at the end of a block might not have a semicolon
+        group.text(self.chunk(|formatter| {
+            formatter.skip_whitespace_if_it_is_not_a_newline();
+        }));
+        if self.is_at(Token::Semicolon) {
+            group.semicolon(self);
+        }
+
+        group
+    }
+
     fn format_comptime_statement(&mut self, statement: Statement) -> ChunkGroup {
         let mut group = ChunkGroup::new();
 
@@ -343,6 +369,28 @@ mod tests {
         assert_format(src, expected);
     }
 
+    #[test]
+    fn format_return_statement() {
+        let src = " fn foo() { return  1 ; } ";
+        let expected = "fn foo() {
+    return 1;
+}
+";
+        assert_format(src, expected);
+    }
+
+    #[test]
+    fn format_return_statement_without_value() {
+        let src = " fn foo() { if true { return  ; } } ";
+        let expected = "fn foo() {
+    if true {
+        return;
+    }
+}
+";
+        assert_format(src, expected);
+    }
+
     #[test]
     fn format_let_statement_no_type() {
         let src = " fn foo() { let  x  =  1 ; } ";
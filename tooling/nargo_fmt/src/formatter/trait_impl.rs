@@ -76,7 +76,8 @@ impl<'a> Formatter<'a> {
                 let noir_function = NoirFunction { def, ..noir_function };
                 self.format_function(noir_function);
             }
-            TraitImplItemKind::Constant(name, typ, value) => {
+            TraitImplItemKind::Constant(name, typ, value, attributes) => {
+                self.format_secondary_attributes(attributes);
                 let pattern = Pattern::Identifier(name);
                 let chunks = self.chunk_formatter().format_let_or_global(
                     Keyword::Let,
@@ -89,7 +90,8 @@ impl<'a> Formatter<'a> {
                 self.write_indentation();
                 self.format_chunk_group(chunks);
             }
-            TraitImplItemKind::Type { name, alias } => {
+            TraitImplItemKind::Type { name, alias, attributes } => {
+                self.format_secondary_attributes(attributes);
                 self.write_indentation();
                 self.write_keyword(Keyword::Type);
                 self.write_space();
@@ -217,6 +219,24 @@ fn foo ( ) { }
         type X = i32;
     }
 }
+";
+        assert_format(src, expected);
+    }
+
+    #[test]
+    fn format_trait_impl_items_with_attributes() {
+        let src = " mod moo { impl  Foo  for  Bar {  
+            #[ cfg ( feature=\"a\" ) ]  let X : i32=42 ;
+            #[ cfg ( feature=\"a\" ) ]  type  X  =  i32 ;
+         } }";
+        let expected = "mod moo {
+    impl Foo for Bar {
+        #[cfg(feature = \"a\")]
+        let X: i32 = 42;
+        #[cfg(feature = \"a\")]
+        type X = i32;
+    }
+}
 ";
         assert_format(src, expected);
     }
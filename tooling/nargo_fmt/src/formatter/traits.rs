@@ -92,6 +92,7 @@ impl<'a> Formatter<'a> {
                 return_type,
                 where_clause,
                 body,
+                attributes,
             } => {
                 let parameters = parameters
                     .into_iter()
@@ -104,7 +105,7 @@ impl<'a> Formatter<'a> {
                     .collect();
 
                 let func = FunctionToFormat {
-                    attributes: Attributes::empty(),
+                    attributes: Attributes { function: None, secondary: attributes },
                     visibility,
                     name,
                     generics,
@@ -116,7 +117,8 @@ impl<'a> Formatter<'a> {
                 };
                 self.format_function_impl(func);
             }
-            TraitItem::Constant { name, typ, default_value } => {
+            TraitItem::Constant { name, typ, default_value, attributes } => {
+                self.format_secondary_attributes(attributes);
                 let pattern = Pattern::Identifier(name);
                 let chunks = self.chunk_formatter().format_let_or_global(
                     Keyword::Let,
@@ -128,7 +130,8 @@ impl<'a> Formatter<'a> {
                 self.write_indentation();
                 self.format_chunk_group(chunks);
             }
-            TraitItem::Type { name } => {
+            TraitItem::Type { name, attributes } => {
+                self.format_secondary_attributes(attributes);
                 self.write_indentation();
                 self.write_keyword(Keyword::Type);
                 self.write_space();
@@ -306,6 +309,29 @@ mod tests {
         let expected = "trait Foo {}
 
 trait Bar {}
+";
+        assert_format(src, expected);
+    }
+
+    #[test]
+    fn format_trait_items_with_attributes() {
+        let src = " mod moo { trait Foo { 
+    /// hello
+            #[ cfg ( feature=\"a\" ) ]  type X;
+            #[ cfg ( feature=\"a\" ) ]  let  x  : i32 ;
+            #[ cfg ( feature=\"a\" ) ]  fn  foo ( ) ;
+         } }";
+        let expected = "mod moo {
+    trait Foo {
+        /// hello
+        #[cfg(feature = \"a\")]
+        type X;
+        #[cfg(feature = \"a\")]
+        let x: i32;
+        #[cfg(feature = \"a\")]
+        fn foo();
+    }
+}
 ";
         assert_format(src, expected);
     }
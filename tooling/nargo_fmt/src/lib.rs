@@ -40,6 +40,7 @@ pub mod errors;
 mod formatter;
 
 use formatter::Formatter;
+use noirc_errors::Span;
 use noirc_frontend::ParsedModule;
 
 pub use config::Config;
@@ -50,6 +51,17 @@ pub fn format(source: &str, parsed_module: ParsedModule, config: &Config) -> Str
     formatter.buffer.contents()
 }
 
+/// Formats only the items and statements that intersect `range`, leaving the rest of the source
+/// untouched. Returns the spans of the source to replace together with their formatted text.
+pub fn format_range(
+    source: &str,
+    parsed_module: ParsedModule,
+    config: &Config,
+    range: Span,
+) -> Vec<(Span, String)> {
+    formatter::format_range(source, parsed_module, config, range)
+}
+
 #[cfg(test)]
 pub(crate) fn assert_format(src: &str, expected: &str) {
     assert_format_with_config(src, expected, Config::default());
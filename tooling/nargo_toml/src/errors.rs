@@ -75,6 +75,9 @@ pub enum ManifestError {
 
     #[error("Failed to parse expression width with the following error: {0}")]
     ParseExpressionWidth(String),
+
+    #[error("Feature `{feature}` is not declared in the `[features]` table of {toml}")]
+    UnknownFeature { toml: PathBuf, feature: String },
 }
 
 #[allow(clippy::enum_variant_names)]
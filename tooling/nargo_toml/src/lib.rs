@@ -4,7 +4,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies, unused_extern_crates))]
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     path::{Component, Path, PathBuf},
 };
 
@@ -118,12 +118,16 @@ struct PackageConfig {
     package: PackageMetadata,
     #[serde(default)]
     dependencies: BTreeMap<String, DependencyConfig>,
+    /// Maps each feature of the package to the other features it enables.
+    #[serde(default)]
+    features: BTreeMap<String, Vec<String>>,
 }
 
 impl PackageConfig {
     fn resolve_to_package(
         &self,
         root_dir: &Path,
+        requested_features: &[String],
         processed: &mut Vec<String>,
     ) -> Result<Package, ManifestError> {
         let name: CrateName = if let Some(name) = &self.package.name {
@@ -210,6 +214,8 @@ impl PackageConfig {
             })
             .map_or(Ok(None), |res| res.map(Some))?;
 
+        let enabled_features = self.resolve_enabled_features(root_dir, requested_features)?;
+
         Ok(Package {
             version: self.package.version.clone(),
             compiler_required_version: self.package.compiler_version.clone(),
@@ -218,9 +224,45 @@ impl PackageConfig {
             package_type,
             name,
             dependencies,
+            enabled_features,
             expression_width,
         })
     }
+
+    /// Returns the requested features, along with the `default` feature if the package declares one,
+    /// and every feature transitively enabled by them.
+    fn resolve_enabled_features(
+        &self,
+        root_dir: &Path,
+        requested_features: &[String],
+    ) -> Result<BTreeSet<String>, ManifestError> {
+        let unknown_feature = |feature: &String| ManifestError::UnknownFeature {
+            toml: root_dir.join("Nargo.toml"),
+            feature: feature.clone(),
+        };
+
+        for implied_feature in self.features.values().flatten() {
+            if !self.features.contains_key(implied_feature) {
+                return Err(unknown_feature(implied_feature));
+            }
+        }
+
+        let mut features_to_enable = requested_features.to_vec();
+        if self.features.contains_key("default") {
+            features_to_enable.push("default".to_string());
+        }
+
+        let mut enabled_features = BTreeSet::new();
+        while let Some(feature) = features_to_enable.pop() {
+            let implied_features =
+                self.features.get(&feature).ok_or_else(|| unknown_feature(&feature))?;
+            if enabled_features.insert(feature) {
+                features_to_enable.extend(implied_features.iter().cloned());
+            }
+        }
+
+        Ok(enabled_features)
+    }
 }
 
 /// Contains all the information about a package, as loaded from a `Nargo.toml`.
@@ -295,8 +337,18 @@ struct PackageMetadata {
 /// Enum representing the different types of ways to
 /// supply a source for the dependency
 enum DependencyConfig {
-    Github { git: String, tag: String, directory: Option<String> },
-    Path { path: String },
+    Github {
+        git: String,
+        tag: String,
+        directory: Option<String>,
+        #[serde(default)]
+        features: Vec<String>,
+    },
+    Path {
+        path: String,
+        #[serde(default)]
+        features: Vec<String>,
+    },
 }
 
 impl DependencyConfig {
@@ -306,7 +358,7 @@ impl DependencyConfig {
         processed: &mut Vec<String>,
     ) -> Result<Dependency, ManifestError> {
         let dep = match self {
-            Self::Github { git, tag, directory } => {
+            Self::Github { git, tag, directory, features } => {
                 let dir_path = clone_git_repo(git, tag).map_err(ManifestError::GitError)?;
                 let project_path = if let Some(directory) = directory {
                     let internal_path = dir_path.join(directory).normalize();
@@ -321,13 +373,13 @@ impl DependencyConfig {
                     dir_path
                 };
                 let toml_path = project_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path, processed)?;
+                let package = resolve_package_from_toml(&toml_path, features, processed)?;
                 Dependency::Remote { package }
             }
-            Self::Path { path } => {
+            Self::Path { path, features } => {
                 let dir_path = pkg_root.join(path);
                 let toml_path = dir_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path, processed)?;
+                let package = resolve_package_from_toml(&toml_path, features, processed)?;
                 Dependency::Local { package }
             }
         };
@@ -349,7 +401,8 @@ fn toml_to_workspace(
     let mut resolved = Vec::new();
     let workspace = match nargo_toml.config {
         Config::Package { package_config } => {
-            let member = package_config.resolve_to_package(&nargo_toml.root_dir, &mut resolved)?;
+            let member =
+                package_config.resolve_to_package(&nargo_toml.root_dir, &[], &mut resolved)?;
             match &package_selection {
                 PackageSelection::Selected(selected_name) if selected_name != &member.name => {
                     return Err(ManifestError::MissingSelectedPackage(member.name))
@@ -368,7 +421,7 @@ fn toml_to_workspace(
             for (index, member_path) in workspace_config.members.into_iter().enumerate() {
                 let package_root_dir = nargo_toml.root_dir.join(&member_path);
                 let package_toml_path = package_root_dir.join("Nargo.toml");
-                let member = resolve_package_from_toml(&package_toml_path, &mut resolved)?;
+                let member = resolve_package_from_toml(&package_toml_path, &[], &mut resolved)?;
 
                 match &package_selection {
                     PackageSelection::Selected(selected_name) => {
@@ -429,9 +482,11 @@ fn read_toml(toml_path: &Path) -> Result<NargoToml, ManifestError> {
     Ok(nargo_toml)
 }
 
-/// Resolves a Nargo.toml file into a `Package` struct as defined by our `nargo` core.
+/// Resolves a Nargo.toml file into a `Package` struct as defined by our `nargo` core,
+/// enabling the features requested by the package depending on it.
 fn resolve_package_from_toml(
     toml_path: &Path,
+    requested_features: &[String],
     processed: &mut Vec<String>,
 ) -> Result<Package, ManifestError> {
     // Checks for cyclic dependencies
@@ -457,7 +512,7 @@ fn resolve_package_from_toml(
 
     let result = match nargo_toml.config {
         Config::Package { package_config } => {
-            package_config.resolve_to_package(&nargo_toml.root_dir, processed)
+            package_config.resolve_to_package(&nargo_toml.root_dir, requested_features, processed)
         }
         Config::Workspace { .. } => {
             Err(ManifestError::UnexpectedWorkspace(toml_path.to_path_buf()))
@@ -559,3 +614,37 @@ fn parse_package_expression_width_toml() {
     assert!(Config::try_from(String::from(src)).is_ok());
     assert!(Config::try_from(src).is_ok());
 }
+
+#[test]
+fn parse_package_features_toml() {
+    let src = r#"
+    [package]
+    name = "test"
+    type = "lib"
+    authors = [""]
+
+    [features]
+    default = ["checks"]
+    checks = []
+    debug_checks = ["checks"]
+
+    [dependencies]
+    hello = { path = "./noir_driver", features = ["debug_checks"] }
+    rand = { tag = "next", git = "https://github.com/rust-lang-nursery/rand", features = ["std"] }
+    "#;
+
+    let Config::Package { package_config } = Config::try_from(src).unwrap() else {
+        panic!("Expected a package config");
+    };
+    assert_eq!(package_config.features.len(), 3);
+
+    let enabled_features = package_config
+        .resolve_enabled_features(Path::new("."), &["debug_checks".to_string()])
+        .unwrap();
+    assert_eq!(
+        enabled_features.into_iter().collect::<Vec<_>>(),
+        vec!["checks".to_string(), "debug_checks".to_string(), "default".to_string()]
+    );
+
+    assert!(package_config.resolve_enabled_features(Path::new("."), &["foo".to_string()]).is_err());
+}
@@ -87,7 +87,11 @@ fn strip_build_meta_data(version: &Version) -> String {
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        path::PathBuf,
+        str::FromStr,
+    };
 
     use nargo::package::PackageType;
     use noirc_frontend::graph::CrateName;
@@ -105,6 +109,7 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            enabled_features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -137,6 +142,7 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            enabled_features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -148,6 +154,7 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("good_dependency").unwrap(),
             dependencies: BTreeMap::new(),
+            enabled_features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -158,6 +165,7 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("bad_dependency").unwrap(),
             dependencies: BTreeMap::new(),
+            enabled_features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -199,6 +207,7 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            enabled_features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -219,6 +228,7 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            enabled_features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -239,6 +249,7 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            enabled_features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -25,8 +25,8 @@ fn ensure_unique_strings<'a>(iter: impl Iterator<Item = &'a mut String>) {
 
 proptest::prop_compose! {
     pub(super) fn arb_field_from_integer(bit_size: u32)(value: u128)-> FieldElement {
-        let width = (bit_size % 128).clamp(1, 127);
-        let max_value = 2u128.pow(width) - 1;
+        let width = bit_size.clamp(1, 128);
+        let max_value = u128::MAX >> (128 - width);
         FieldElement::from(value.clamp(0, max_value))
     }
 }
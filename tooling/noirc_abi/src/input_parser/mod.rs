@@ -362,7 +362,7 @@ mod test {
     use acvm::{AcirField, FieldElement};
     use num_bigint::BigUint;
 
-    use super::parse_str_to_field;
+    use super::{parse_str_to_field, parse_str_to_signed};
 
     fn big_uint_from_field(field: FieldElement) -> BigUint {
         BigUint::from_bytes_be(&field.to_be_bytes())
@@ -400,4 +400,18 @@ mod test {
         let noncanonical_field = FieldElement::modulus().to_string();
         assert!(parse_str_to_field(&noncanonical_field).is_err());
     }
+
+    #[test]
+    fn parse_i128_from_strings() {
+        let values = vec![
+            ("0", FieldElement::zero()),
+            ("-1", FieldElement::from(u128::MAX)),
+            ("170141183460469231731687303715884105727", FieldElement::from(i128::MAX)),
+            ("-170141183460469231731687303715884105728", FieldElement::from(1_u128 << 127)),
+        ];
+
+        for (value, expected) in values {
+            assert_eq!(parse_str_to_signed(value, 128).unwrap(), expected);
+        }
+    }
 }